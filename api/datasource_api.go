@@ -3,8 +3,9 @@ package api
 import (
 	"datatrace/common"
 	"datatrace/services"
-	"datatrace/utils"
+	"errors"
 	"fmt"
+	"strconv"
 
 	"github.com/gin-gonic/gin"
 )
@@ -90,6 +91,23 @@ func (api *DataSourceAPI) Delete(c *gin.Context) {
 	common.SuccessWithMessage(c, "删除成功", nil)
 }
 
+// GetUsage 查询引用数据源的任务
+func (api *DataSourceAPI) GetUsage(c *gin.Context) {
+	id := c.Param("id")
+	if _, err := api.service.GetByID(id); err != nil {
+		common.NotFound(c, "数据源不存在")
+		return
+	}
+
+	usages, err := api.service.GetUsage(id)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	common.Success(c, usages)
+}
+
 // TestConnection 测试数据源连接
 func (api *DataSourceAPI) TestConnection(c *gin.Context) {
 	var req services.TestConnectionRequest
@@ -98,7 +116,7 @@ func (api *DataSourceAPI) TestConnection(c *gin.Context) {
 		return
 	}
 
-	result, err := api.service.TestConnection(&req)
+	result, err := api.service.TestConnection(c.Request.Context(), &req)
 	if err != nil {
 		common.Error(c, 500, err.Error())
 		return
@@ -115,7 +133,7 @@ func (api *DataSourceAPI) TestConnection(c *gin.Context) {
 func (api *DataSourceAPI) TestConnectionByID(c *gin.Context) {
 	id := c.Param("id")
 
-	result, err := api.service.TestConnectionByID(id)
+	result, err := api.service.TestConnectionByID(c.Request.Context(), id)
 	if err != nil {
 		common.Error(c, 500, err.Error())
 		return
@@ -128,6 +146,34 @@ func (api *DataSourceAPI) TestConnectionByID(c *gin.Context) {
 	}
 }
 
+// TestConnectionWithQueryRequest 探测查询请求
+type TestConnectionWithQueryRequest struct {
+	Query string `json:"query" binding:"required"`
+}
+
+// TestConnectionWithQuery 测试连接并执行只读探测查询（仅MySQL）
+func (api *DataSourceAPI) TestConnectionWithQuery(c *gin.Context) {
+	id := c.Param("id")
+
+	var req TestConnectionWithQueryRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		common.BadRequest(c, "参数错误: "+err.Error())
+		return
+	}
+
+	result, err := api.service.TestConnectionWithQuery(c.Request.Context(), id, req.Query)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	if result.Success {
+		common.Success(c, result)
+	} else {
+		common.Error(c, 500, result.Message)
+	}
+}
+
 // GetDatabases 获取数据源的数据库列表（仅MySQL）
 func (api *DataSourceAPI) GetDatabases(c *gin.Context) {
 	id := c.Param("id")
@@ -144,37 +190,21 @@ func (api *DataSourceAPI) GetDatabases(c *gin.Context) {
 		return
 	}
 
-	var username, password string
-	crypto := utils.NewCryptoService()
-
-	// 如果使用凭据，从凭据获取账号密码
-	if ds.CredentialID != nil && *ds.CredentialID != "" {
-		credService := services.NewCredentialService()
-		credential, err := credService.GetByID(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据不存在")
-			return
-		}
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
 
-		username = credential.Username
-		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据密码解密失败")
-			return
-		}
-	} else {
-		// 使用数据源自己的账号密码
-		username = ds.Username
-		password, err = crypto.Decrypt(ds.Password)
-		if err != nil {
-			common.Error(c, 500, "密码解密失败")
-			return
-		}
+	filter, err := databaseFilter(c, ds.ExcludeDatabases)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
 	}
 
 	// 获取数据库列表
-	mysqlService := services.NewMySQLMetadataService()
-	databases, err := mysqlService.GetDatabases(ds.Host, ds.Port, username, password)
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+	databases, err := mysqlService.GetDatabases(ds.MySQLHost(), ds.Port, username, password, filter)
 	if err != nil {
 		common.Error(c, 500, err.Error())
 		return
@@ -184,6 +214,7 @@ func (api *DataSourceAPI) GetDatabases(c *gin.Context) {
 }
 
 // GetTables 获取指定数据库的表列表（仅MySQL）
+// 可选参数 page/page_size/search 启用分页和表名搜索，with_stats=true 返回近似行数和大小
 func (api *DataSourceAPI) GetTables(c *gin.Context) {
 	id := c.Param("id")
 	database := c.Query("database")
@@ -205,37 +236,47 @@ func (api *DataSourceAPI) GetTables(c *gin.Context) {
 		return
 	}
 
-	var username, password string
-	crypto := utils.NewCryptoService()
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
 
-	// 如果使用凭据，从凭据获取账号密码
-	if ds.CredentialID != nil && *ds.CredentialID != "" {
-		credService := services.NewCredentialService()
-		credential, err := credService.GetByID(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据不存在")
-			return
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+
+	// 传入 page/page_size/search 时分页查询（大库避免一次返回全部表）
+	if c.Query("page") != "" || c.Query("page_size") != "" || c.Query("search") != "" {
+		page, _ := strconv.Atoi(c.DefaultQuery("page", "1"))
+		pageSize, _ := strconv.Atoi(c.DefaultQuery("page_size", "50"))
+		if page < 1 {
+			page = 1
+		}
+		if pageSize < 1 || pageSize > 500 {
+			pageSize = 50
 		}
 
-		username = credential.Username
-		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
+		tables, total, err := mysqlService.GetTablesWithPagination(ds.MySQLHost(), ds.Port, username, password, database, c.Query("search"), page, pageSize)
 		if err != nil {
-			common.Error(c, 500, "凭据密码解密失败")
+			common.Error(c, 500, err.Error())
 			return
 		}
-	} else {
-		// 使用数据源自己的账号密码
-		username = ds.Username
-		password, err = crypto.Decrypt(ds.Password)
+		common.PageSuccess(c, tables, total, page, pageSize)
+		return
+	}
+
+	// with_stats=true 时附带近似行数和大小
+	if c.Query("with_stats") == "true" {
+		tables, err := mysqlService.GetTablesWithStats(ds.MySQLHost(), ds.Port, username, password, database)
 		if err != nil {
-			common.Error(c, 500, "密码解密失败")
+			common.Error(c, 500, err.Error())
 			return
 		}
+		common.Success(c, tables)
+		return
 	}
 
 	// 获取表列表
-	mysqlService := services.NewMySQLMetadataService()
-	tables, err := mysqlService.GetTables(ds.Host, ds.Port, username, password, database)
+	tables, err := mysqlService.GetTables(ds.MySQLHost(), ds.Port, username, password, database)
 	if err != nil {
 		common.Error(c, 500, err.Error())
 		return
@@ -244,6 +285,27 @@ func (api *DataSourceAPI) GetTables(c *gin.Context) {
 	common.Success(c, tables)
 }
 
+// databaseFilter 解析数据库列表过滤条件：?exclude=a,b* 覆盖数据源的排除规则（?exclude= 表示不排除），
+// ?include=app_* 只保留匹配的库，?show_system=true 显示系统库
+func databaseFilter(c *gin.Context, dsExcludes *string) (services.DatabaseFilter, error) {
+	var override *string
+	if raw, ok := c.GetQuery("exclude"); ok {
+		if err := services.ValidateDatabaseExcludes(raw); err != nil {
+			return services.DatabaseFilter{}, err
+		}
+		override = &raw
+	}
+	includes, err := services.ParseDatabaseIncludes(c.Query("include"))
+	if err != nil {
+		return services.DatabaseFilter{}, err
+	}
+	return services.DatabaseFilter{
+		Excludes:   services.ResolveDatabaseExcludes(dsExcludes, override),
+		Includes:   includes,
+		ShowSystem: c.Query("show_system") == "true",
+	}, nil
+}
+
 // GetDatabasesWithTables 获取数据源的所有数据库及其表列表（树形结构，仅MySQL）
 func (api *DataSourceAPI) GetDatabasesWithTables(c *gin.Context) {
 	id := c.Param("id")
@@ -260,37 +322,21 @@ func (api *DataSourceAPI) GetDatabasesWithTables(c *gin.Context) {
 		return
 	}
 
-	var username, password string
-	crypto := utils.NewCryptoService()
-
-	// 如果使用凭据，从凭据获取账号密码
-	if ds.CredentialID != nil && *ds.CredentialID != "" {
-		credService := services.NewCredentialService()
-		credential, err := credService.GetByID(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据不存在")
-			return
-		}
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
 
-		username = credential.Username
-		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据密码解密失败")
-			return
-		}
-	} else {
-		// 使用数据源自己的账号密码
-		username = ds.Username
-		password, err = crypto.Decrypt(ds.Password)
-		if err != nil {
-			common.Error(c, 500, "密码解密失败")
-			return
-		}
+	filter, err := databaseFilter(c, ds.ExcludeDatabases)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
 	}
 
 	// 获取数据库和表的树形结构
-	mysqlService := services.NewMySQLMetadataService()
-	result, err := mysqlService.GetDatabasesWithTables(ds.Host, ds.Port, username, password)
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+	result, err := mysqlService.GetDatabasesWithTables(ds.MySQLHost(), ds.Port, username, password, filter)
 	if err != nil {
 		common.Error(c, 500, err.Error())
 		return
@@ -322,41 +368,117 @@ func (api *DataSourceAPI) GetTableColumns(c *gin.Context) {
 		return
 	}
 
-	var username, password string
-	crypto := utils.NewCryptoService()
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
 
-	// 如果使用凭据，从凭据获取账号密码
-	if ds.CredentialID != nil && *ds.CredentialID != "" {
-		credService := services.NewCredentialService()
-		credential, err := credService.GetByID(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据不存在")
-			return
+	// 直接查询字段列表
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+	columns, err := mysqlService.GetTableColumns(ds.MySQLHost(), ds.Port, username, password, database, table)
+	if err != nil {
+		common.Error(c, 500, fmt.Sprintf("获取字段列表失败: %v", err))
+		return
+	}
+
+	// 传入 page/page_size 时分页返回（按字段顺序）
+	if c.Query("page") != "" || c.Query("page_size") != "" {
+		page, _ := strconv.Atoi(c.DefaultQuery("page", "1"))
+		pageSize, _ := strconv.Atoi(c.DefaultQuery("page_size", "100"))
+		if page < 1 {
+			page = 1
+		}
+		if pageSize < 1 || pageSize > 500 {
+			pageSize = 100
 		}
 
-		username = credential.Username
-		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
-		if err != nil {
-			common.Error(c, 500, "凭据密码解密失败")
-			return
+		total := len(columns)
+		start := (page - 1) * pageSize
+		if start > total {
+			start = total
 		}
-	} else {
-		// 使用数据源自己的账号密码
-		username = ds.Username
-		password, err = crypto.Decrypt(ds.Password)
-		if err != nil {
-			common.Error(c, 500, "密码解密失败")
-			return
+		end := start + pageSize
+		if end > total {
+			end = total
 		}
+		common.PageSuccess(c, columns[start:end], int64(total), page, pageSize)
+		return
 	}
 
-	// 直接查询字段列表
-	mysqlService := services.NewMySQLMetadataService()
-	columns, err := mysqlService.GetTableColumns(ds.Host, ds.Port, username, password, database, table)
+	common.Success(c, columns)
+}
+
+// PreviewTableData 预览表数据（前 N 行，仅MySQL）
+func (api *DataSourceAPI) PreviewTableData(c *gin.Context) {
+	id := c.Param("id")
+	database := c.Param("database")
+	table := c.Param("table")
+
+	limit, _ := strconv.Atoi(c.DefaultQuery("limit", strconv.Itoa(services.DefaultPreviewLimit)))
+
+	// 获取数据源
+	ds, err := api.service.GetByID(id)
 	if err != nil {
-		common.Error(c, 500, fmt.Sprintf("获取字段列表失败: %v", err))
+		common.NotFound(c, "数据源不存在")
 		return
 	}
 
-	common.Success(c, columns)
+	if ds.Type != "mysql" {
+		common.BadRequest(c, "只有MySQL数据源支持此操作")
+		return
+	}
+
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+	preview, err := mysqlService.PreviewTableData(ds.MySQLHost(), ds.Port, username, password, database, table, limit)
+	if err != nil {
+		common.Error(c, 500, fmt.Sprintf("预览数据失败: %v", err))
+		return
+	}
+
+	common.Success(c, preview)
+}
+
+// GetTableSchema 获取表结构（字段类型、可空、默认值、主键和建表语句）
+func (api *DataSourceAPI) GetTableSchema(c *gin.Context) {
+	id := c.Param("id")
+	database := c.Param("database")
+	table := c.Param("table")
+
+	// 获取数据源
+	ds, err := api.service.GetByID(id)
+	if err != nil {
+		common.NotFound(c, "数据源不存在")
+		return
+	}
+
+	if ds.Type != "mysql" {
+		common.BadRequest(c, "只有MySQL数据源支持此操作")
+		return
+	}
+
+	username, password, err := api.service.ResolveCredentials(ds)
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	mysqlService := services.NewMySQLMetadataService().WithContext(c.Request.Context())
+	schema, err := mysqlService.GetTableSchema(ds.MySQLHost(), ds.Port, username, password, database, table)
+	if err != nil {
+		if errors.Is(err, services.ErrTableNotFound) {
+			common.NotFound(c, err.Error())
+			return
+		}
+		common.Error(c, 500, fmt.Sprintf("获取表结构失败: %v", err))
+		return
+	}
+
+	common.Success(c, schema)
 }
@@ -1,6 +1,7 @@
 package api
 
 import (
+	"datatrace/common"
 	"datatrace/services"
 	"io"
 	"net/http"
@@ -72,3 +73,25 @@ func (api *DataSourceSSEAPI) StreamTestResults(c *gin.Context) {
 		}
 	}
 }
+
+// GetHealth 获取所有数据源的健康状态（后台健康检查结果）
+func (api *DataSourceSSEAPI) GetHealth(c *gin.Context) {
+	list, err := api.sseService.GetHealthList()
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	common.Success(c, list)
+}
+
+// TestAll 立即测试所有数据源，结果实时推送并写入健康状态表
+func (api *DataSourceSSEAPI) TestAll(c *gin.Context) {
+	results, err := api.sseService.TestAll()
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	common.Success(c, results)
+}
@@ -0,0 +1,59 @@
+package api
+
+import (
+	"datatrace/common"
+	"datatrace/services"
+	"encoding/json"
+
+	"github.com/gin-gonic/gin"
+)
+
+// SettingsAPI 全局设置API控制器
+type SettingsAPI struct {
+	service *services.SettingsService
+}
+
+// NewSettingsAPI 创建全局设置API控制器
+func NewSettingsAPI() *SettingsAPI {
+	return &SettingsAPI{
+		service: services.NewSettingsService(),
+	}
+}
+
+// Get 获取全局设置
+func (api *SettingsAPI) Get(c *gin.Context) {
+	common.Success(c, api.service.Get())
+}
+
+// Update 更新全局设置（只修改请求中出现的键）
+func (api *SettingsAPI) Update(c *gin.Context) {
+	var changes map[string]json.RawMessage
+	if err := c.ShouldBindJSON(&changes); err != nil {
+		common.BadRequest(c, "参数错误: "+err.Error())
+		return
+	}
+
+	settings, err := api.service.Update(changes)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, "设置已保存", settings)
+}
+
+// CompactStorage 压缩系统元数据库（有任务运行时拒绝执行），返回压缩前后的大小
+func (api *SettingsAPI) CompactStorage(c *gin.Context) {
+	result, err := services.CompactDatabase()
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, "压缩完成", result)
+}
+
+// Capabilities 获取后端版本、支持的数据源类型、同步方向和启用的功能
+func (api *SettingsAPI) Capabilities(c *gin.Context) {
+	common.Success(c, services.GetCapabilities())
+}
@@ -3,6 +3,10 @@ package api
 import (
 	"datatrace/common"
 	"datatrace/services"
+	"fmt"
+	"strconv"
+	"strings"
+	"time"
 
 	"github.com/gin-gonic/gin"
 )
@@ -87,3 +91,154 @@ func (api *TaskAPI) Delete(c *gin.Context) {
 
 	common.SuccessWithMessage(c, "删除成功", nil)
 }
+
+// Duplicate 以新名称复制任务（配置一并复制，执行历史不复制）
+func (api *TaskAPI) Duplicate(c *gin.Context) {
+	id := c.Param("id")
+	var req services.DuplicateTaskRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		common.BadRequest(c, "参数错误: "+err.Error())
+		return
+	}
+
+	task, err := api.service.Duplicate(id, &req)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, "复制成功", task)
+}
+
+// History 获取任务单元执行历史（开始/结束时间与耗时，?error_code= 按错误分类筛选）
+func (api *TaskAPI) History(c *gin.Context) {
+	id := c.Param("id")
+	errorCode := c.Query("error_code")
+	if errorCode != "" && !services.IsValidErrorCode(errorCode) {
+		common.BadRequest(c, fmt.Sprintf("未知的错误分类: %s", errorCode))
+		return
+	}
+
+	history, err := services.NewTaskHistoryService().GetTaskHistory(id, errorCode)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, history)
+}
+
+// Estimate 预估任务的表数量、总行数和耗时（不启动同步）
+func (api *TaskAPI) Estimate(c *gin.Context) {
+	id := c.Param("id")
+	estimate, err := services.NewTaskEstimateService().EstimateTask(id)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, estimate)
+}
+
+// ConfigDebug 任务配置诊断（只读）：存储的原始配置、Redis 缓存、内存进度、最近执行历史及单元差异
+func (api *TaskAPI) ConfigDebug(c *gin.Context) {
+	debug, err := api.service.GetConfigDebug(c.Param("id"))
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, debug)
+}
+
+// Logs 分页查询任务日志
+// ?category=all/initialize/complete/verify&level=error,warning&keyword=xxx&since=HH:MM:SS&until=HH:MM:SS&limit=100&offset=0
+func (api *TaskAPI) Logs(c *gin.Context) {
+	id := c.Param("id")
+	if _, err := api.service.GetByID(id); err != nil {
+		common.NotFound(c, "任务不存在")
+		return
+	}
+
+	limit, _ := strconv.Atoi(c.Query("limit"))
+	offset, _ := strconv.Atoi(c.Query("offset"))
+	query := services.LogQuery{
+		Category: c.Query("category"),
+		Keyword:  c.Query("keyword"),
+		Since:    c.Query("since"),
+		Until:    c.Query("until"),
+		Limit:    limit,
+		Offset:   offset,
+	}
+	for _, level := range strings.Split(c.Query("level"), ",") {
+		if level = strings.TrimSpace(level); level != "" {
+			query.Levels = append(query.Levels, level)
+		}
+	}
+
+	result, err := services.NewTaskLogService().QueryTaskLogs(id, query)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+	common.Success(c, result)
+}
+
+// ExportLogs 以 NDJSON 文件下载任务的全部日志，任务没有日志时返回空文件
+func (api *TaskAPI) ExportLogs(c *gin.Context) {
+	id := c.Param("id")
+	if _, err := api.service.GetByID(id); err != nil {
+		common.NotFound(c, "任务不存在")
+		return
+	}
+
+	c.Header("Content-Type", "application/x-ndjson; charset=utf-8")
+	c.Header("Content-Disposition", fmt.Sprintf("attachment; filename=task-%s-logs.ndjson", id))
+	// 导出行数在写完后才知道，通过 trailer 返回
+	c.Header("Trailer", "X-Exported-Lines")
+	c.Status(200)
+
+	lines, err := services.NewTaskLogService().ExportTaskLogs(id, c.Writer)
+	if err != nil {
+		// 响应头已发送，只能中断输出并记录
+		c.Error(err)
+		return
+	}
+	c.Writer.Header().Set("X-Exported-Lines", fmt.Sprintf("%d", lines))
+}
+
+// DashboardStats 首页汇总统计（?window=today/7d/30d，默认 today）
+func (api *TaskAPI) DashboardStats(c *gin.Context) {
+	stats, err := services.NewDashboardStatsService().GetStats(c.Query("window"))
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, stats)
+}
+
+// ClearHistory 清除任务单元执行历史（?before=RFC3339 时只清除该时间之前的记录）
+func (api *TaskAPI) ClearHistory(c *gin.Context) {
+	id := c.Param("id")
+	historyService := services.NewTaskHistoryService()
+
+	var deleted int64
+	var err error
+	if before := c.Query("before"); before != "" {
+		beforeTime, parseErr := time.Parse(time.RFC3339, before)
+		if parseErr != nil {
+			common.BadRequest(c, "before 参数格式错误，应为 RFC3339，例如 2024-01-02T15:04:05+08:00")
+			return
+		}
+		deleted, err = historyService.ClearHistoriesBefore(id, beforeTime)
+	} else {
+		deleted, err = historyService.ClearAllHistories(id)
+	}
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, fmt.Sprintf("已清除 %d 条执行历史", deleted), gin.H{"deleted": deleted})
+}
@@ -54,3 +54,60 @@ func (api *TaskControlAPI) Stop(c *gin.Context) {
 
 	common.SuccessWithMessage(c, "任务停止成功", nil)
 }
+
+// Restart 清除运行时状态后从头启动任务（?clear_history=true 同时清除执行历史）
+func (api *TaskControlAPI) Restart(c *gin.Context) {
+	taskID := c.Param("id")
+	clearHistory := c.Query("clear_history") == "true"
+
+	if err := api.service.RestartTask(taskID, clearHistory); err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, "任务重新启动成功", nil)
+}
+
+// Verify 重新校验已完成单元的行数（?checksum=true 同时对比校验和），不同步数据
+func (api *TaskControlAPI) Verify(c *gin.Context) {
+	report, err := api.service.VerifyTask(c.Request.Context(), c.Param("id"), c.Query("checksum") == "true")
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, report)
+}
+
+// EngineStatus 获取引擎状态（运行中/暂停中的任务）
+func (api *TaskControlAPI) EngineStatus(c *gin.Context) {
+	status, err := api.service.GetEngineStatus()
+	if err != nil {
+		common.Error(c, 500, "获取引擎状态失败: "+err.Error())
+		return
+	}
+
+	common.Success(c, status)
+}
+
+// PauseAll 暂停所有运行中的任务（返回每个任务的处理结果）
+func (api *TaskControlAPI) PauseAll(c *gin.Context) {
+	results, err := api.service.PauseAllTasks()
+	if err != nil {
+		common.Error(c, 500, "批量暂停失败: "+err.Error())
+		return
+	}
+
+	common.Success(c, results)
+}
+
+// ResumeAll 重新启动所有暂停中的任务（返回每个任务的处理结果）
+func (api *TaskControlAPI) ResumeAll(c *gin.Context) {
+	results, err := api.service.ResumeAllTasks()
+	if err != nil {
+		common.Error(c, 500, "批量恢复失败: "+err.Error())
+		return
+	}
+
+	common.Success(c, results)
+}
@@ -0,0 +1,77 @@
+package api
+
+import (
+	"datatrace/common"
+	"datatrace/services"
+
+	"github.com/gin-gonic/gin"
+)
+
+// TaskTemplateAPI 任务模板API控制器
+type TaskTemplateAPI struct {
+	service *services.TaskTemplateService
+}
+
+// NewTaskTemplateAPI 创建任务模板API控制器
+func NewTaskTemplateAPI() *TaskTemplateAPI {
+	return &TaskTemplateAPI{
+		service: services.NewTaskTemplateService(),
+	}
+}
+
+// Create 将任务配置保存为模板
+func (api *TaskTemplateAPI) Create(c *gin.Context) {
+	var req services.CreateTemplateRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		common.BadRequest(c, "参数错误: "+err.Error())
+		return
+	}
+
+	template, err := api.service.Create(&req)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, template)
+}
+
+// List 获取模板列表
+func (api *TaskTemplateAPI) List(c *gin.Context) {
+	list, err := api.service.List()
+	if err != nil {
+		common.Error(c, 500, err.Error())
+		return
+	}
+
+	common.Success(c, list)
+}
+
+// Delete 删除模板
+func (api *TaskTemplateAPI) Delete(c *gin.Context) {
+	id := c.Param("id")
+	if err := api.service.Delete(id); err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.SuccessWithMessage(c, "删除成功", nil)
+}
+
+// CreateTask 从模板创建任务（指定源和目标）
+func (api *TaskTemplateAPI) CreateTask(c *gin.Context) {
+	id := c.Param("id")
+	var req services.CreateTaskFromTemplateRequest
+	if err := c.ShouldBindJSON(&req); err != nil {
+		common.BadRequest(c, "参数错误: "+err.Error())
+		return
+	}
+
+	task, err := api.service.CreateTask(id, &req)
+	if err != nil {
+		common.BadRequest(c, err.Error())
+		return
+	}
+
+	common.Success(c, task)
+}
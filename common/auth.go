@@ -0,0 +1,23 @@
+package common
+
+import (
+	"crypto/subtle"
+	"strings"
+
+	"github.com/gin-gonic/gin"
+)
+
+// BearerAuth Bearer Token 认证中间件（请求头 Authorization: Bearer <token>）
+func BearerAuth(token string) gin.HandlerFunc {
+	expected := []byte(token)
+	return func(c *gin.Context) {
+		header := c.GetHeader("Authorization")
+		provided, ok := strings.CutPrefix(header, "Bearer ")
+		if !ok || subtle.ConstantTimeCompare([]byte(strings.TrimSpace(provided)), expected) != 1 {
+			Unauthorized(c, "未授权：缺少或无效的访问令牌")
+			c.Abort()
+			return
+		}
+		c.Next()
+	}
+}
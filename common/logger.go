@@ -1,6 +1,7 @@
 package common
 
 import (
+	"datatrace/utils"
 	"log"
 	"time"
 
@@ -25,8 +26,8 @@ func Logger() gin.HandlerFunc {
 		// 请求方式
 		reqMethod := c.Request.Method
 
-		// 请求路由
-		reqUri := c.Request.RequestURI
+		// 请求路由（查询参数中可能带有密码/令牌，脱敏后再输出）
+		reqUri := utils.RedactSecrets(c.Request.RequestURI)
 
 		// 状态码
 		statusCode := c.Writer.Status()
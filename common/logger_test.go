@@ -0,0 +1,35 @@
+package common
+
+import (
+	"bytes"
+	"log"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"strings"
+	"testing"
+
+	"github.com/gin-gonic/gin"
+)
+
+func TestLoggerRedactsQuerySecrets(t *testing.T) {
+	gin.SetMode(gin.TestMode)
+	var buf bytes.Buffer
+	log.SetOutput(&buf)
+	defer log.SetOutput(os.Stderr)
+
+	r := gin.New()
+	r.Use(Logger())
+	r.POST("/api/v1/datasources/test", func(c *gin.Context) { c.Status(http.StatusOK) })
+
+	req := httptest.NewRequest(http.MethodPost, "/api/v1/datasources/test?host=db&password=Sup3r-S3cret&token=abc123", nil)
+	r.ServeHTTP(httptest.NewRecorder(), req)
+
+	out := buf.String()
+	if strings.Contains(out, "Sup3r-S3cret") || strings.Contains(out, "abc123") {
+		t.Errorf("request log contains secrets: %s", out)
+	}
+	if !strings.Contains(out, "/api/v1/datasources/test?host=db&password=****&token=****") {
+		t.Errorf("request log missing redacted URI: %s", out)
+	}
+}
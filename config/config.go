@@ -7,10 +7,12 @@ import (
 
 // Config 全局配置结构
 type Config struct {
-	Database DatabaseConfig `mapstructure:"database"`
-	Server   ServerConfig   `mapstructure:"server"`
-	Security SecurityConfig `mapstructure:"security"`
-	Redis    RedisConfig    `mapstructure:"redis"`
+	Database    DatabaseConfig    `mapstructure:"database"`
+	Server      ServerConfig      `mapstructure:"server"`
+	Security    SecurityConfig    `mapstructure:"security"`
+	Redis       RedisConfig       `mapstructure:"redis"`
+	HealthCheck HealthCheckConfig `mapstructure:"health_check"`
+	HeadlessAPI HeadlessAPIConfig `mapstructure:"headless_api"`
 }
 
 // DatabaseConfig 数据库配置
@@ -45,6 +47,21 @@ type RedisConfig struct {
 	PoolSize int    `mapstructure:"pool_size"`
 }
 
+// HealthCheckConfig 数据源健康检查配置
+type HealthCheckConfig struct {
+	Enabled  bool `mapstructure:"enabled"`  // 是否启用后台健康检查
+	Interval int  `mapstructure:"interval"` // 检查间隔（秒）
+	Timeout  int  `mapstructure:"timeout"`  // 单个数据源检查超时（秒）
+}
+
+// HeadlessAPIConfig 无界面 API 配置（独立端口，仅提供 /api/v1 接口，使用 Bearer Token 认证）
+type HeadlessAPIConfig struct {
+	Enabled bool   `mapstructure:"enabled"` // 是否启用
+	Host    string `mapstructure:"host"`    // 监听地址，默认只监听本机
+	Port    int    `mapstructure:"port"`    // 监听端口
+	Token   string `mapstructure:"token"`   // 访问令牌（启用时必填）
+}
+
 var GlobalConfig *Config
 
 // LoadConfig 加载配置文件
@@ -52,6 +69,14 @@ func LoadConfig(configPath string) error {
 	viper.SetConfigFile(configPath)
 	viper.SetConfigType("yaml")
 
+	// 默认值
+	viper.SetDefault("health_check.enabled", true)
+	viper.SetDefault("health_check.interval", 10)
+	viper.SetDefault("health_check.timeout", 8)
+	viper.SetDefault("headless_api.enabled", false)
+	viper.SetDefault("headless_api.host", "127.0.0.1")
+	viper.SetDefault("headless_api.port", 8787)
+
 	// 读取配置文件
 	if err := viper.ReadInConfig(); err != nil {
 		return fmt.Errorf("读取配置文件失败: %w", err)
@@ -87,6 +112,14 @@ func validateConfig() error {
 	if len(GlobalConfig.Security.EncryptionKey) != 32 {
 		return fmt.Errorf("加密密钥必须是32字节")
 	}
+	if GlobalConfig.HeadlessAPI.Enabled {
+		if len(GlobalConfig.HeadlessAPI.Token) < 16 {
+			return fmt.Errorf("启用无界面 API 时必须设置至少16个字符的 headless_api.token")
+		}
+		if GlobalConfig.HeadlessAPI.Port <= 0 || GlobalConfig.HeadlessAPI.Port > 65535 {
+			return fmt.Errorf("headless_api.port 无效")
+		}
+	}
 	return nil
 }
 
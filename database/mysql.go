@@ -58,6 +58,11 @@ func autoMigrate() error {
 		&models.Credential{},
 		&models.DataSource{},
 		&models.SyncTask{},
+		&models.DataSourceHealth{},
+		&models.TaskUnitHistory{},
+		&models.Setting{},
+		&models.TaskTemplate{},
+		&models.BidirectionalWatermark{},
 	)
 
 	if err != nil {
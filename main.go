@@ -1,12 +1,19 @@
 package main
 
 import (
+	"context"
 	"datatrace/config"
 	"datatrace/database"
 	"datatrace/routers"
 	"datatrace/services"
+	"datatrace/utils"
 	"fmt"
 	"log"
+	"net/http"
+	"os"
+	"os/signal"
+	"syscall"
+	"time"
 
 	"github.com/gin-gonic/gin"
 )
@@ -19,13 +26,25 @@ func main() {
 
 	// 2. 初始化数据库
 	if err := database.InitDB(); err != nil {
-		log.Fatalf("❌ 初始化数据库失败: %v", err)
+		log.Fatalf("❌ 初始化数据库失败: %s", utils.RedactSecrets(err.Error()))
 	}
 	defer database.CloseDB()
 
+	// 加载全局设置（首次启动写入默认值）
+	if err := services.NewSettingsService().Load(); err != nil {
+		log.Printf("⚠️  加载全局设置失败，使用默认值: %v", err)
+	}
+
+	// 修正上次异常退出遗留的运行状态
+	if n, err := services.RecoverInterruptedTasks(); err != nil {
+		log.Printf("⚠️  修正任务运行状态失败: %v", err)
+	} else if n > 0 {
+		log.Printf("⚠️  %d 个任务上次未正常结束，已标记为已中断", n)
+	}
+
 	// 3. 初始化 Redis
 	if err := database.InitRedis(); err != nil {
-		log.Printf("⚠️  初始化 Redis 失败: %v", err)
+		log.Printf("⚠️  初始化 Redis 失败: %s", utils.RedactSecrets(err.Error()))
 		// Redis 失败不阻止程序启动，增量统计功能将不可用
 	} else {
 		log.Println("✅ Redis 连接成功")
@@ -42,9 +61,19 @@ func main() {
 	gin.SetMode(config.GlobalConfig.Server.Mode)
 
 	// 6. 启动数据源健康检查
-	dsSSE := services.NewDataSourceSSEService()
-	dsSSE.StartHealthCheck()
-	log.Println("✅ 数据源健康检查已启动")
+	if config.GlobalConfig.HealthCheck.Enabled {
+		dsSSE := services.NewDataSourceSSEService()
+		dsSSE.StartHealthCheck()
+		log.Printf("✅ 数据源健康检查已启动（间隔 %d 秒）", config.GlobalConfig.HealthCheck.Interval)
+	} else {
+		log.Println("⚠️  数据源健康检查已禁用")
+	}
+
+	// 定时压缩元数据库（间隔见全局设置 compact_interval_hours）
+	services.StartCompactScheduler()
+
+	// 禁止运行时段检查（任务配置 sync_config.blackout）
+	services.StartBlackoutScheduler()
 
 	// 7. 设置路由
 	r := routers.SetupRouter()
@@ -59,7 +88,45 @@ func main() {
 	log.Printf("🏥 健康检查: http://localhost%s/health", addr)
 	log.Println("========================================")
 
-	if err := r.Run(addr); err != nil {
-		log.Fatalf("❌ 启动服务器失败: %v", err)
+	srv := &http.Server{Addr: addr, Handler: r}
+	go func() {
+		if err := srv.ListenAndServe(); err != nil && err != http.ErrServerClosed {
+			log.Fatalf("❌ 启动服务器失败: %v", err)
+		}
+	}()
+
+	// 无界面 API（独立端口，Bearer Token 认证，默认只监听本机）
+	var headlessSrv *http.Server
+	if headless := config.GlobalConfig.HeadlessAPI; headless.Enabled {
+		headlessAddr := fmt.Sprintf("%s:%d", headless.Host, headless.Port)
+		headlessSrv = &http.Server{Addr: headlessAddr, Handler: routers.SetupHeadlessRouter(headless.Token)}
+		go func() {
+			if err := headlessSrv.ListenAndServe(); err != nil && err != http.ErrServerClosed {
+				log.Fatalf("❌ 启动无界面 API 失败: %v", err)
+			}
+		}()
+		log.Printf("🔑 无界面 API: http://%s/api/v1（需要 Authorization: Bearer <token>）", headlessAddr)
+	}
+
+	// 8. 等待退出信号，中断任务并保存进度后再关闭
+	quit := make(chan os.Signal, 1)
+	signal.Notify(quit, syscall.SIGINT, syscall.SIGTERM)
+	<-quit
+
+	log.Println("🛑 收到退出信号，正在保存任务进度...")
+	if n := services.NewTaskControlService().ShutdownTasks(services.DefaultShutdownTimeout); n > 0 {
+		log.Printf("✅ 已中断 %d 个运行中的任务", n)
+	}
+
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+	if err := srv.Shutdown(ctx); err != nil {
+		log.Printf("⚠️  关闭服务器超时: %v", err)
+	}
+	if headlessSrv != nil {
+		if err := headlessSrv.Shutdown(ctx); err != nil {
+			log.Printf("⚠️  关闭无界面 API 超时: %v", err)
+		}
 	}
+	log.Println("👋 服务已退出")
 }
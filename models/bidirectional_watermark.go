@@ -0,0 +1,21 @@
+package models
+
+import (
+	"time"
+)
+
+// BidirectionalWatermark 双向同步每张表两端已同步到的位置（水位值 + 主键），暂停、停止或服务重启后从该位置继续
+type BidirectionalWatermark struct {
+	ID              uint      `gorm:"primaryKey;autoIncrement" json:"id"`
+	TaskID          string    `gorm:"size:36;not null;uniqueIndex:idx_bi_watermark_table" json:"task_id"`
+	TableKey        string    `gorm:"size:255;not null;uniqueIndex:idx_bi_watermark_table" json:"table_key"` // 源库.源表<->目标库.目标表
+	WatermarkColumn string    `gorm:"size:64;not null" json:"watermark_column"`                              // 水位列变化后原位置作废
+	SourceMark      string    `gorm:"type:text" json:"source_mark"`                                          // JSON：水位值和主键
+	TargetMark      string    `gorm:"type:text" json:"target_mark"`
+	UpdatedAt       time.Time `json:"updated_at"`
+}
+
+// TableName 指定表名
+func (BidirectionalWatermark) TableName() string {
+	return "bidirectional_watermarks"
+}
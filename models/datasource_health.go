@@ -0,0 +1,21 @@
+package models
+
+import (
+	"time"
+)
+
+// DataSourceHealth 数据源健康状态（后台健康检查和手动全部测试写入）
+type DataSourceHealth struct {
+	DataSourceID  string     `gorm:"primaryKey;size:36" json:"datasource_id"`
+	Status        string     `gorm:"size:20;not null" json:"status"` // success/failed
+	Message       string     `gorm:"size:500" json:"message"`        // 错误信息或版本信息
+	LatencyMs     int64      `json:"latency_ms"`                     // 本次检查耗时（毫秒）
+	LastCheckedAt time.Time  `json:"last_checked_at"`                // 最近检查时间
+	LastSuccessAt *time.Time `json:"last_success_at"`                // 最近一次成功时间
+	Steps         string     `gorm:"type:text" json:"steps"`         // 最近一次分步测试结果（JSON 数组：step/name/status/message）
+}
+
+// TableName 指定表名
+func (DataSourceHealth) TableName() string {
+	return "datasource_health"
+}
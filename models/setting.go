@@ -0,0 +1,17 @@
+package models
+
+import (
+	"time"
+)
+
+// Setting 全局设置（键值对，值为 JSON）
+type Setting struct {
+	Key       string    `gorm:"primaryKey;size:64" json:"key"`
+	Value     string    `gorm:"type:text;not null" json:"value"`
+	UpdatedAt time.Time `json:"updated_at"`
+}
+
+// TableName 指定表名
+func (Setting) TableName() string {
+	return "settings"
+}
@@ -0,0 +1,22 @@
+package models
+
+import (
+	"time"
+)
+
+// TaskTemplate 任务模板（保存任务配置，不含源和目标）
+type TaskTemplate struct {
+	ID          string    `gorm:"primaryKey;size:36" json:"id"`
+	Name        string    `gorm:"size:100;not null;uniqueIndex" json:"name"` // 模板名称，唯一
+	Description string    `gorm:"size:255" json:"description"`               // 描述
+	SourceType  string    `gorm:"size:20;not null" json:"source_type"`       // mysql/elasticsearch
+	TargetType  string    `gorm:"size:20;not null" json:"target_type"`       // mysql/elasticsearch
+	Config      string    `gorm:"type:text;not null" json:"config"`          // JSON格式配置（选库选表 + 同步参数）
+	CreatedAt   time.Time `json:"created_at"`
+	UpdatedAt   time.Time `json:"updated_at"`
+}
+
+// TableName 指定表名
+func (TaskTemplate) TableName() string {
+	return "task_templates"
+}
@@ -0,0 +1,26 @@
+package models
+
+import (
+	"time"
+)
+
+// TaskUnitHistory 任务单元执行历史（单元完成或失败时写入）
+type TaskUnitHistory struct {
+	ID               uint       `gorm:"primaryKey;autoIncrement" json:"id"`
+	TaskID           string     `gorm:"size:36;not null;index" json:"task_id"`
+	UnitName         string     `gorm:"size:255;not null" json:"unit_name"` // 目标库.目标表
+	Status           string     `gorm:"size:20;not null" json:"status"`     // completed/failed
+	TotalRecords     int64      `json:"total_records"`                      // 总记录数
+	ProcessedRecords int64      `json:"processed_records"`                  // 已处理记录数
+	ErrorMessage     string     `gorm:"type:text" json:"error_message,omitempty"`
+	ErrorCode        string     `gorm:"size:32;index" json:"error_code,omitempty"` // 错误分类：connection/auth/schema/read/write/network/timeout/duplicate_key/unknown
+	StartedAt        *time.Time `json:"started_at"`                                // 单元开始时间
+	CompletedAt      *time.Time `gorm:"index" json:"completed_at"`                 // 单元结束时间（有索引，首页按时间窗口汇总）
+	DurationMs       int64      `json:"duration_ms"`                               // 耗时（毫秒）
+	CreatedAt        time.Time  `json:"created_at"`
+}
+
+// TableName 指定表名
+func (TaskUnitHistory) TableName() string {
+	return "task_unit_histories"
+}
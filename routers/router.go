@@ -13,8 +13,8 @@ func SetupRouter() *gin.Engine {
 
 	// 使用中间件
 	r.Use(gin.Recovery())
-	// 使用 Gin 自带的日志中间件
-	r.Use(gin.Logger())
+	// 请求日志（查询参数中的密码/令牌脱敏后输出）
+	r.Use(common.Logger())
 
 	// 加载 HTML 模板
 	r.LoadHTMLGlob("templates/**/*.html")
@@ -41,63 +41,124 @@ func SetupRouter() *gin.Engine {
 	})
 
 	// API 路由组
+	registerAPIRoutes(r.Group("/api/v1"))
+
+	return r
+}
+
+// SetupHeadlessRouter 设置无界面 API 路由（只提供 /api/v1 接口和健康检查，接口需要 Bearer Token）
+func SetupHeadlessRouter(token string) *gin.Engine {
+	r := gin.New()
+	r.Use(gin.Recovery())
+	r.Use(common.Logger()) // 请求 URI 脱敏后输出
+
+	r.GET("/health", func(c *gin.Context) {
+		common.Success(c, gin.H{
+			"status": "ok",
+		})
+	})
+
 	apiGroup := r.Group("/api/v1")
+	apiGroup.Use(common.BearerAuth(token))
+	registerAPIRoutes(apiGroup)
+
+	return r
+}
+
+// registerAPIRoutes 注册 /api/v1 接口（界面服务和无界面 API 共用）
+func registerAPIRoutes(apiGroup *gin.RouterGroup) {
+	// 凭据管理
+	credAPI := api.NewCredentialAPI()
+	credentials := apiGroup.Group("/credentials")
 	{
-		// 凭据管理
-		credAPI := api.NewCredentialAPI()
-		credentials := apiGroup.Group("/credentials")
-		{
-			credentials.GET("", credAPI.List)
-			credentials.POST("", credAPI.Create)
-			credentials.GET("/:id", credAPI.GetByID)
-			credentials.PUT("/:id", credAPI.Update)
-			credentials.DELETE("/:id", credAPI.Delete)
-		}
-
-		// 数据源管理
-		dsAPI := api.NewDataSourceAPI()
-		dsSSEAPI := api.NewDataSourceSSEAPI()
-		datasources := apiGroup.Group("/datasources")
-		{
-			datasources.GET("", dsAPI.List)
-			datasources.POST("", dsAPI.Create)
-			datasources.POST("/test", dsAPI.TestConnection)
-			datasources.GET("/:id", dsAPI.GetByID)
-			datasources.POST("/:id/test", dsAPI.TestConnectionByID)
-			datasources.GET("/:id/databases", dsAPI.GetDatabases)
-			datasources.GET("/:id/tables", dsAPI.GetTables)
-			datasources.GET("/:id/database-tables", dsAPI.GetDatabasesWithTables)          // 新增：获取完整树形结构
-			datasources.GET("/:id/tables/:database/:table/columns", dsAPI.GetTableColumns) // 新增：获取表字段列表
-			datasources.PUT("/:id", dsAPI.Update)
-			datasources.DELETE("/:id", dsAPI.Delete)
-
-			// SSE流式推送测试结果
-			datasources.GET("/test/stream", dsSSEAPI.StreamTestResults)
-		}
-
-		// 任务管理
-		taskAPI := api.NewTaskAPI()
-		taskControlAPI := api.NewTaskControlAPI()
-		taskSSEAPI := api.NewTaskSSEAPI()
-		tasks := apiGroup.Group("/tasks")
-		{
-			tasks.GET("", taskAPI.List)
-			tasks.POST("", taskAPI.Create)
-			tasks.GET("/:id", taskAPI.GetByID)
-			tasks.PUT("/:id/config", taskAPI.UpdateConfig)
-			tasks.DELETE("/:id", taskAPI.Delete)
-
-			// 任务控制
-			tasks.POST("/:id/start", taskControlAPI.Start)
-			tasks.POST("/:id/pause", taskControlAPI.Pause)
-			tasks.POST("/:id/stop", taskControlAPI.Stop)
-
-			// SSE流式推送（只保留3个SSE接口）
-			tasks.GET("/:id/stream/detail", taskSSEAPI.StreamTaskDetail) // 任务详情SSE
-			tasks.GET("/:id/stream/progress", taskSSEAPI.StreamProgress) // 统一进度SSE（支持 ?database=xxx 参数）
-			tasks.GET("/:id/stream/logs", taskSSEAPI.StreamLogs)         // 日志SSE
-		}
+		credentials.GET("", credAPI.List)
+		credentials.POST("", credAPI.Create)
+		credentials.GET("/:id", credAPI.GetByID)
+		credentials.PUT("/:id", credAPI.Update)
+		credentials.DELETE("/:id", credAPI.Delete)
 	}
 
-	return r
+	// 数据源管理
+	dsAPI := api.NewDataSourceAPI()
+	dsSSEAPI := api.NewDataSourceSSEAPI()
+	datasources := apiGroup.Group("/datasources")
+	{
+		datasources.GET("", dsAPI.List)
+		datasources.POST("", dsAPI.Create)
+		datasources.POST("/test", dsAPI.TestConnection)
+		datasources.GET("/:id", dsAPI.GetByID)
+		datasources.GET("/:id/usage", dsAPI.GetUsage) // 引用该数据源的任务
+		datasources.POST("/:id/test", dsAPI.TestConnectionByID)
+		datasources.POST("/:id/test-query", dsAPI.TestConnectionWithQuery) // 连接测试 + 只读探测查询
+		datasources.GET("/:id/databases", dsAPI.GetDatabases)
+		datasources.GET("/:id/tables", dsAPI.GetTables)
+		datasources.GET("/:id/database-tables", dsAPI.GetDatabasesWithTables)           // 新增：获取完整树形结构
+		datasources.GET("/:id/tables/:database/:table/columns", dsAPI.GetTableColumns)  // 新增：获取表字段列表
+		datasources.GET("/:id/tables/:database/:table/preview", dsAPI.PreviewTableData) // 预览表数据（?limit=20，最大100）
+		datasources.GET("/:id/tables/:database/:table/schema", dsAPI.GetTableSchema)    // 表结构（类型、可空、默认值、建表语句）
+		datasources.PUT("/:id", dsAPI.Update)
+		datasources.DELETE("/:id", dsAPI.Delete)
+
+		// SSE流式推送测试结果
+		datasources.GET("/test/stream", dsSSEAPI.StreamTestResults)
+		datasources.GET("/health", dsSSEAPI.GetHealth)  // 最近一次健康检查结果（含分步结果）
+		datasources.POST("/test-all", dsSSEAPI.TestAll) // 立即测试全部数据源
+	}
+
+	// 任务管理
+	taskAPI := api.NewTaskAPI()
+	taskControlAPI := api.NewTaskControlAPI()
+	taskSSEAPI := api.NewTaskSSEAPI()
+	tasks := apiGroup.Group("/tasks")
+	{
+		tasks.GET("", taskAPI.List)
+		tasks.POST("", taskAPI.Create)
+		tasks.GET("/:id", taskAPI.GetByID)
+		tasks.PUT("/:id/config", taskAPI.UpdateConfig)
+		tasks.DELETE("/:id", taskAPI.Delete)
+		tasks.POST("/:id/duplicate", taskAPI.Duplicate)     // 复制任务（body: name）
+		tasks.GET("/:id/history", taskAPI.History)          // 单元执行历史（含耗时）
+		tasks.DELETE("/:id/history", taskAPI.ClearHistory)  // 清除执行历史（?before=RFC3339）
+		tasks.GET("/:id/estimate", taskAPI.Estimate)        // 预估行数和耗时（不启动）
+		tasks.GET("/:id/logs", taskAPI.Logs)                // 分页查询日志（level/category/keyword/since/until/limit/offset）
+		tasks.GET("/:id/logs/export", taskAPI.ExportLogs)   // 导出全部日志（NDJSON 文件）
+		tasks.GET("/:id/config/debug", taskAPI.ConfigDebug) // 配置诊断（原始配置、缓存、运行时状态对照，只读）
+
+		// 任务控制
+		tasks.POST("/:id/start", taskControlAPI.Start)
+		tasks.POST("/:id/pause", taskControlAPI.Pause)
+		tasks.POST("/:id/stop", taskControlAPI.Stop)
+		tasks.POST("/:id/restart", taskControlAPI.Restart) // 清除进度后从头运行（?clear_history=true 同时清除执行历史）
+		tasks.POST("/:id/verify", taskControlAPI.Verify)   // 只校验不同步（?checksum=true 同时对比校验和）
+
+		// SSE流式推送（只保留3个SSE接口）
+		tasks.GET("/:id/stream/detail", taskSSEAPI.StreamTaskDetail) // 任务详情SSE
+		tasks.GET("/:id/stream/progress", taskSSEAPI.StreamProgress) // 统一进度SSE（支持 ?database=xxx 参数）
+		tasks.GET("/:id/stream/logs", taskSSEAPI.StreamLogs)         // 日志SSE
+	}
+
+	// 任务模板
+	templateAPI := api.NewTaskTemplateAPI()
+	templates := apiGroup.Group("/task-templates")
+	{
+		templates.GET("", templateAPI.List)
+		templates.POST("", templateAPI.Create) // 将任务配置保存为模板（body: name, description, task_id）
+		templates.DELETE("/:id", templateAPI.Delete)
+		templates.POST("/:id/tasks", templateAPI.CreateTask) // 从模板创建任务（body: name, source_id, target_ids）
+	}
+
+	// 首页汇总统计
+	apiGroup.GET("/dashboard/stats", taskAPI.DashboardStats) // 任务数量、窗口内同步行数和单元成功率（?window=today/7d/30d）
+
+	// 引擎状态
+	apiGroup.GET("/engine/status", taskControlAPI.EngineStatus)
+	apiGroup.POST("/engine/pause-all", taskControlAPI.PauseAll)   // 暂停所有运行中的任务
+	apiGroup.POST("/engine/resume-all", taskControlAPI.ResumeAll) // 重新启动所有暂停中的任务
+
+	// 全局设置
+	settingsAPI := api.NewSettingsAPI()
+	apiGroup.GET("/settings", settingsAPI.Get)
+	apiGroup.PUT("/settings", settingsAPI.Update)
+	apiGroup.POST("/settings/compact", settingsAPI.CompactStorage) // 压缩元数据库（OPTIMIZE TABLE，有任务运行时拒绝）
+	apiGroup.GET("/capabilities", settingsAPI.Capabilities)        // 版本和能力（数据源类型、同步方向、启用的功能）
 }
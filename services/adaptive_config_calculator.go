@@ -5,6 +5,17 @@ import (
 	"fmt"
 )
 
+// MaxReadThreads 同时同步的表数上限
+const MaxReadThreads = 64
+
+// ValidateReadThreads 校验读取线程数（同时同步的表数），0 表示使用自适应线程数
+func ValidateReadThreads(n int) error {
+	if n < 0 || n > MaxReadThreads {
+		return fmt.Errorf("read_threads 必须在 0~%d 之间", MaxReadThreads)
+	}
+	return nil
+}
+
 // AdaptiveConfigCalculator 自适应配置计算器
 type AdaptiveConfigCalculator struct {
 	resourceDetector *SystemResourceDetector
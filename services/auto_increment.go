@@ -0,0 +1,76 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"time"
+)
+
+// 自增列处理方式
+const (
+	AutoIncrementPreserve   = "preserve"   // 原样写入源端的自增值，同步完成后把计数器设为 MAX(id)+1（默认）
+	AutoIncrementRegenerate = "regenerate" // 不写入自增列，由目标表重新生成
+)
+
+// ValidateAutoIncrementMode 校验自增列处理方式，空值表示默认的 preserve
+// regenerate 只支持全量同步：增量同步按主键应用更新和删除，目标端重新生成的自增值无法与源端对应
+func ValidateAutoIncrementMode(mode, syncMode string) error {
+	switch mode {
+	case "", AutoIncrementPreserve:
+		return nil
+	case AutoIncrementRegenerate:
+		if syncMode != "full" {
+			return fmt.Errorf("auto_increment_mode=%s 只支持全量同步", AutoIncrementRegenerate)
+		}
+		return nil
+	}
+	return fmt.Errorf("auto_increment_mode 只支持 %s/%s", AutoIncrementPreserve, AutoIncrementRegenerate)
+}
+
+// getAutoIncrementColumn 查询表的自增列，没有自增列时返回空字符串
+func getAutoIncrementColumn(db *sql.DB, database, table string) (string, error) {
+	var column string
+	err := db.QueryRow(
+		"SELECT COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND EXTRA LIKE '%auto_increment%' LIMIT 1",
+		database, table).Scan(&column)
+	if err == sql.ErrNoRows {
+		return "", nil
+	}
+	return column, err
+}
+
+// resetAutoIncrement 把表的自增计数器设为 MAX(column)+1（空表为 1），返回设置的值
+// 目标表由 SHOW CREATE TABLE 复制而来时会带上源表的 AUTO_INCREMENT 选项，这里按实际数据校正
+func resetAutoIncrement(db *sql.DB, database, table, column string) (int64, error) {
+	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
+
+	var maxValue int64
+	query := fmt.Sprintf("SELECT COALESCE(MAX(`%s`), 0) FROM `%s`.`%s`", column, database, table)
+	if err := db.QueryRowContext(ctx, query).Scan(&maxValue); err != nil {
+		return 0, fmt.Errorf("查询自增列最大值失败: %w", err)
+	}
+
+	next, alter := autoIncrementResetSQL(database, table, maxValue)
+	if _, err := db.ExecContext(ctx, alter); err != nil {
+		return 0, fmt.Errorf("设置自增计数器失败: %w", err)
+	}
+	return next, nil
+}
+
+// autoIncrementResetSQL 按自增列当前最大值（空表为 0）计算下一个自增值和设置计数器的语句
+func autoIncrementResetSQL(database, table string, maxValue int64) (int64, string) {
+	next := maxValue + 1
+	return next, fmt.Sprintf("ALTER TABLE `%s`.`%s` AUTO_INCREMENT = %d", database, table, next)
+}
+
+// syncAutoIncrementCounter 校正目标表自增计数器（preserve 在数据写入后执行，regenerate 在写入前执行），失败只记录警告
+func (e *SyncEngine) syncAutoIncrementCounter(taskID, targetName, unitName string, db *sql.DB, database, table, column string) {
+	next, err := resetAutoIncrement(db, database, table, column)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s %v", targetName, unitName, err))
+		return
+	}
+	e.logService.Info(taskID, fmt.Sprintf("目标 %s 表 %s 自增计数器设为 %d", targetName, unitName, next))
+}
@@ -0,0 +1,54 @@
+package services
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestValidateAutoIncrementMode(t *testing.T) {
+	cases := []struct {
+		mode     string
+		syncMode string
+		wantErr  bool
+	}{
+		{"", "incremental", false},
+		{AutoIncrementPreserve, "incremental", false},
+		{AutoIncrementRegenerate, "full", false},
+		{AutoIncrementRegenerate, "incremental", true},
+		{AutoIncrementRegenerate, "bidirectional", true},
+		{"renumber", "full", true},
+	}
+	for _, c := range cases {
+		err := ValidateAutoIncrementMode(c.mode, c.syncMode)
+		if (err != nil) != c.wantErr {
+			t.Errorf("ValidateAutoIncrementMode(%q, %q) error = %v, wantErr %v", c.mode, c.syncMode, err, c.wantErr)
+		}
+	}
+}
+
+func TestAutoIncrementResetSQL(t *testing.T) {
+	cases := []struct {
+		maxValue int64
+		wantNext int64
+		wantSQL  string
+	}{
+		{0, 1, "ALTER TABLE `db`.`orders` AUTO_INCREMENT = 1"},
+		{41, 42, "ALTER TABLE `db`.`orders` AUTO_INCREMENT = 42"},
+	}
+	for _, c := range cases {
+		next, sql := autoIncrementResetSQL("db", "orders", c.maxValue)
+		if next != c.wantNext || sql != c.wantSQL {
+			t.Errorf("autoIncrementResetSQL(%d) = %d, %q, want %d, %q", c.maxValue, next, sql, c.wantNext, c.wantSQL)
+		}
+	}
+}
+
+func TestChecksumColumnsExcludesAutoIncrement(t *testing.T) {
+	columns := []string{"id", "name", "amount"}
+	if got := checksumColumns(columns, []string{"id"}); !reflect.DeepEqual(got, []string{"name", "amount"}) {
+		t.Errorf("checksumColumns excluding id = %v", got)
+	}
+	if got := checksumColumns(columns, nil); !reflect.DeepEqual(got, columns) {
+		t.Errorf("checksumColumns without exclusions = %v", got)
+	}
+}
@@ -0,0 +1,128 @@
+package services
+
+import (
+	"fmt"
+	"strings"
+	"sync/atomic"
+)
+
+// 写入前按主键去除批次内重复行的方式
+const (
+	BatchDedupFirst = "first" // 保留第一次出现的行
+	BatchDedupLast  = "last"  // 保留最后一次出现的行的值（位置不变）
+)
+
+// ValidateBatchDedup 校验批次内去重方式，空值表示不去重
+func ValidateBatchDedup(mode string) error {
+	switch mode {
+	case "", BatchDedupFirst, BatchDedupLast:
+		return nil
+	}
+	return fmt.Errorf("batch_dedup 只支持 %s/%s", BatchDedupFirst, BatchDedupLast)
+}
+
+// batchDedupPolicy 批次内去重策略，单元同一目标的多个 Writer（含分片 Writer）共享；nil 表示不去重
+// 配置后 Writer 不走流式分块写入，每次 WriteBatch 收到的是管道的整个批次，去重在按占位符限制拆分之前进行
+type batchDedupPolicy struct {
+	keys      []string         // 目标表主键列
+	mode      string           // first/last
+	dropped   int64            // 累计去除的行数
+	onDropped func(rows int64) // 每个批次去除重复行后调用
+}
+
+// apply 按主键去除批次内的重复行，返回去重后的记录（不修改原切片）
+func (p *batchDedupPolicy) apply(records []map[string]interface{}) []map[string]interface{} {
+	if p == nil || len(records) < 2 {
+		return records
+	}
+
+	out := make([]map[string]interface{}, 0, len(records))
+	seen := make(map[string]int, len(records)) // 主键 -> 在 out 中的位置
+	for _, record := range records {
+		key := p.keyOf(record)
+		if idx, ok := seen[key]; ok {
+			if p.mode == BatchDedupLast {
+				out[idx] = record
+			}
+			continue
+		}
+		seen[key] = len(out)
+		out = append(out, record)
+	}
+
+	if dropped := int64(len(records) - len(out)); dropped > 0 {
+		atomic.AddInt64(&p.dropped, dropped)
+		if p.onDropped != nil {
+			p.onDropped(dropped)
+		}
+	}
+	return out
+}
+
+// keyOf 主键值拼接成去重用的键：每列按 类型:长度:值 编码，NULL 与字符串 "<nil>"、
+// 含分隔符的值都不会相互混淆（[]byte 与 string 按内容比较，视为同一类型）
+func (p *batchDedupPolicy) keyOf(record map[string]interface{}) string {
+	var b strings.Builder
+	for _, col := range p.keys {
+		var kind, value string
+		switch v := record[col].(type) {
+		case nil:
+			kind = "null"
+		case []byte:
+			kind, value = "string", string(v)
+		case string:
+			kind, value = "string", v
+		default:
+			kind, value = fmt.Sprintf("%T", v), fmt.Sprint(v)
+		}
+		fmt.Fprintf(&b, "%s:%d:%s", kind, len(value), value)
+	}
+	return b.String()
+}
+
+// droppedRows 累计去除的行数（nil 策略为 0）
+func (p *batchDedupPolicy) droppedRows() int64 {
+	if p == nil {
+		return 0
+	}
+	return atomic.LoadInt64(&p.dropped)
+}
+
+// keepName 保留方式的说明
+func (p *batchDedupPolicy) keepName() string {
+	if p.mode == BatchDedupLast {
+		return "保留最后一行"
+	}
+	return "保留第一行"
+}
+
+// newBatchDedup 创建同步单元使用的批次内去重策略，按目标表主键去重；未配置或目标表没有主键时返回 nil
+func (e *SyncEngine) newBatchDedup(taskID, unitName, targetName, mode string, writer *MySQLWriter, targetDB, targetTable string) *batchDedupPolicy {
+	if mode == "" {
+		return nil
+	}
+	keys, err := getPrimaryKeyColumns(writer.GetDB(), targetDB, targetTable)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 查询主键失败，batch_dedup 不生效: %v", targetName, unitName, err))
+		return nil
+	}
+	if len(keys) == 0 {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 没有主键，batch_dedup 不生效", targetName, unitName))
+		return nil
+	}
+
+	policy := &batchDedupPolicy{keys: keys, mode: mode}
+	policy.onDropped = func(rows int64) {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 批次去除 %d 行重复主键（%s，累计 %d 行）",
+			targetName, unitName, rows, policy.keepName(), atomic.LoadInt64(&policy.dropped)), "sync")
+	}
+	return policy
+}
+
+// logBatchDedup 单元同步结束后汇总去除的重复行数
+func (e *SyncEngine) logBatchDedup(taskID, targetName, unitName string, policy *batchDedupPolicy) {
+	if total := policy.droppedRows(); total > 0 {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 共去除 %d 行批次内重复主键（batch_dedup=%s，主键 %s）",
+			targetName, unitName, total, policy.mode, strings.Join(policy.keys, ", ")))
+	}
+}
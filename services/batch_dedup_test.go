@@ -0,0 +1,111 @@
+package services
+
+import (
+	"context"
+	"testing"
+)
+
+func TestBatchDedupKeyOfDistinguishesValues(t *testing.T) {
+	policy := &batchDedupPolicy{keys: []string{"a", "b"}, mode: BatchDedupFirst}
+	tests := []struct {
+		name string
+		x, y DataRecord
+		same bool
+	}{
+		{"null vs <nil> string", DataRecord{"a": nil, "b": 1}, DataRecord{"a": "<nil>", "b": 1}, false},
+		{"separator inside value", DataRecord{"a": "x\x00", "b": "y"}, DataRecord{"a": "x", "b": "\x00y"}, false},
+		{"bytes with separator", DataRecord{"a": []byte("x\x00"), "b": []byte("y")}, DataRecord{"a": []byte("x"), "b": []byte("\x00y")}, false},
+		{"int vs string", DataRecord{"a": int64(1), "b": "k"}, DataRecord{"a": "1", "b": "k"}, false},
+		{"bytes vs string", DataRecord{"a": []byte("x"), "b": int64(2)}, DataRecord{"a": "x", "b": int64(2)}, true},
+		{"same values", DataRecord{"a": int64(1), "b": nil}, DataRecord{"a": int64(1), "b": nil}, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := policy.keyOf(tt.x) == policy.keyOf(tt.y); got != tt.same {
+				t.Errorf("keyOf(%v) == keyOf(%v) is %v, want %v", tt.x, tt.y, got, tt.same)
+			}
+		})
+	}
+}
+
+func TestBatchDedupApply(t *testing.T) {
+	records := []DataRecord{
+		{"id": int64(1), "v": "a"},
+		{"id": int64(2), "v": "b"},
+		{"id": int64(1), "v": "c"},
+		{"id": int64(3), "v": "d"},
+		{"id": int64(2), "v": "e"},
+	}
+	tests := []struct {
+		mode string
+		want []string
+	}{
+		{BatchDedupFirst, []string{"a", "b", "d"}},
+		{BatchDedupLast, []string{"c", "e", "d"}},
+	}
+	for _, tt := range tests {
+		t.Run(tt.mode, func(t *testing.T) {
+			var reported int64
+			policy := &batchDedupPolicy{keys: []string{"id"}, mode: tt.mode, onDropped: func(rows int64) { reported += rows }}
+			got := policy.apply(records)
+			if len(got) != len(tt.want) {
+				t.Fatalf("apply() returned %d rows, want %d", len(got), len(tt.want))
+			}
+			for i, want := range tt.want {
+				if got[i]["v"] != want {
+					t.Errorf("row %d v = %v, want %s", i, got[i]["v"], want)
+				}
+			}
+			if policy.droppedRows() != 2 || reported != 2 {
+				t.Errorf("dropped = %d, reported = %d, want 2", policy.droppedRows(), reported)
+			}
+			if len(records) != 5 || records[0]["v"] != "a" {
+				t.Errorf("apply() modified its input")
+			}
+		})
+	}
+}
+
+// dedupTxWriter 与 MySQLWriter 一样在 WriteBatch 中去重的批次事务写入器
+type dedupTxWriter struct {
+	stubTxWriter
+	dedup *batchDedupPolicy
+}
+
+func (w *dedupTxWriter) WriteBatch(records []DataRecord) error {
+	return w.stubTxWriter.WriteBatch(w.dedup.apply(records))
+}
+
+func (w *dedupTxWriter) WholeBatch() bool { return w.dedup != nil }
+
+func TestBatchDedupAcrossStreamChunks(t *testing.T) {
+	// 一个批次 10 行，第 2 行和第 9 行主键相同；分块为 3 行时两者在不同分块中
+	rows := makeRows(10)
+	rows[8] = DataRecord{"id": int64(2)}
+	reader := &stubStreamReader{stubReader{rows: rows, batchSize: 10}}
+	writer := &dedupTxWriter{dedup: &batchDedupPolicy{keys: []string{"id"}, mode: BatchDedupFirst}}
+
+	pipeline := NewSyncPipeline(reader, writer, nil, PipelineHooks{}).WithStreaming(3)
+	if pipeline.streamChunk != 0 {
+		t.Fatalf("streaming enabled for a writer that needs whole batches")
+	}
+	if _, err := pipeline.Run(context.Background()); err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+	if len(writer.written) != 9 || writer.dedup.droppedRows() != 1 {
+		t.Errorf("written = %d, dropped = %d, want 9 and 1", len(writer.written), writer.dedup.droppedRows())
+	}
+	if writer.calls != 1 {
+		t.Errorf("WriteBatch called %d times, want once for the whole batch", writer.calls)
+	}
+
+	// MySQLWriter 配置去重后同样不启用流式读取
+	mysqlWriter := &MySQLWriter{}
+	if NewSyncPipeline(reader, mysqlWriter, nil, PipelineHooks{}).WithStreaming(3).streamChunk != 3 {
+		t.Errorf("streaming disabled for a MySQLWriter without batch_dedup")
+	}
+	mysqlWriter.SetBatchDedup(&batchDedupPolicy{keys: []string{"id"}, mode: BatchDedupFirst})
+	if NewSyncPipeline(reader, mysqlWriter, nil, PipelineHooks{}).WithStreaming(3).streamChunk != 0 {
+		t.Errorf("streaming enabled for a MySQLWriter with batch_dedup")
+	}
+}
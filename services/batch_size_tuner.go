@@ -0,0 +1,111 @@
+package services
+
+import (
+	"time"
+)
+
+const (
+	// 自动调优默认值
+	defaultTunerMinBatchSize   = 100
+	defaultTunerMaxBatchSize   = 100000
+	defaultTunerTargetLatency  = 1000 * time.Millisecond
+	defaultTunerHysteresis     = 3 // 连续 N 个批次满足条件才调整，防止来回抖动
+	tunerGrowFactor            = 1.25
+	tunerShrinkFactor          = 0.75
+	tunerFastLatencyRatio      = 0.8 // 低于目标的 80% 视为偏快
+	tunerSlowLatencyRatio      = 1.2 // 高于目标的 120% 视为偏慢
+	tunerTimeoutLatencyRatio   = 3.0 // 超过目标 3 倍视为超时，立即减半
+	tunerFailureShrinkFraction = 0.5
+)
+
+// BatchSizeTuner 批次大小自动调优器
+// 根据每个批次的耗时在 [min, max] 之间调整批次大小：
+//   - 连续偏快 -> 增大 25%
+//   - 连续偏慢 -> 减小 25%
+//   - 失败或严重超时 -> 立即减半
+//
+// 非并发安全，每个管道使用独立实例
+type BatchSizeTuner struct {
+	current       int
+	min           int
+	max           int
+	targetLatency time.Duration
+	hysteresis    int
+
+	fastStreak int
+	slowStreak int
+}
+
+// NewBatchSizeTuner 创建批次大小调优器，参数 <= 0 时使用默认值
+func NewBatchSizeTuner(initial, min, max int, targetLatency time.Duration) *BatchSizeTuner {
+	if min <= 0 {
+		min = defaultTunerMinBatchSize
+	}
+	if max <= 0 {
+		max = defaultTunerMaxBatchSize
+	}
+	if max < min {
+		max = min
+	}
+	if targetLatency <= 0 {
+		targetLatency = defaultTunerTargetLatency
+	}
+
+	return &BatchSizeTuner{
+		current:       clampInt(initial, min, max),
+		min:           min,
+		max:           max,
+		targetLatency: targetLatency,
+		hysteresis:    defaultTunerHysteresis,
+	}
+}
+
+// Current 当前批次大小
+func (t *BatchSizeTuner) Current() int {
+	return t.current
+}
+
+// Observe 记录一个批次的耗时和结果，返回新的批次大小以及是否发生变化
+func (t *BatchSizeTuner) Observe(latency time.Duration, failed bool) (int, bool) {
+	previous := t.current
+
+	switch {
+	case failed || latency > time.Duration(float64(t.targetLatency)*tunerTimeoutLatencyRatio):
+		// 失败或严重超时：立即减半
+		t.fastStreak, t.slowStreak = 0, 0
+		t.current = clampInt(int(float64(t.current)*tunerFailureShrinkFraction), t.min, t.max)
+
+	case latency < time.Duration(float64(t.targetLatency)*tunerFastLatencyRatio):
+		t.slowStreak = 0
+		t.fastStreak++
+		if t.fastStreak >= t.hysteresis {
+			t.fastStreak = 0
+			t.current = clampInt(int(float64(t.current)*tunerGrowFactor), t.min, t.max)
+		}
+
+	case latency > time.Duration(float64(t.targetLatency)*tunerSlowLatencyRatio):
+		t.fastStreak = 0
+		t.slowStreak++
+		if t.slowStreak >= t.hysteresis {
+			t.slowStreak = 0
+			t.current = clampInt(int(float64(t.current)*tunerShrinkFactor), t.min, t.max)
+		}
+
+	default:
+		// 在目标区间内，保持不变
+		t.fastStreak, t.slowStreak = 0, 0
+	}
+
+	return t.current, t.current != previous
+}
+
+// clampInt 限制取值范围
+func clampInt(v, lo, hi int) int {
+	if v < lo {
+		return lo
+	}
+	if v > hi {
+		return hi
+	}
+	return v
+}
@@ -0,0 +1,119 @@
+package services
+
+import (
+	"context"
+	"testing"
+	"time"
+)
+
+const testTargetLatency = 100 * time.Millisecond
+
+func TestBatchSizeTunerGrowsAfterConsecutiveFastBatches(t *testing.T) {
+	tuner := NewBatchSizeTuner(1000, 100, 10000, testTargetLatency)
+	for i := 1; i < defaultTunerHysteresis; i++ {
+		if size, changed := tuner.Observe(10*time.Millisecond, false); changed || size != 1000 {
+			t.Fatalf("fast batch %d: got (%d, %v), want (1000, false)", i, size, changed)
+		}
+	}
+	if size, changed := tuner.Observe(10*time.Millisecond, false); !changed || size != 1250 {
+		t.Fatalf("fast batch %d: got (%d, %v), want (1250, true)", defaultTunerHysteresis, size, changed)
+	}
+}
+
+func TestBatchSizeTunerShrinksAfterConsecutiveSlowBatches(t *testing.T) {
+	tuner := NewBatchSizeTuner(1000, 100, 10000, testTargetLatency)
+	var size int
+	for i := 0; i < defaultTunerHysteresis; i++ {
+		size, _ = tuner.Observe(150*time.Millisecond, false)
+	}
+	if size != 750 {
+		t.Fatalf("size after %d slow batches = %d, want 750", defaultTunerHysteresis, size)
+	}
+}
+
+func TestBatchSizeTunerHalvesOnFailureOrTimeout(t *testing.T) {
+	tuner := NewBatchSizeTuner(1000, 100, 10000, testTargetLatency)
+	if size, changed := tuner.Observe(10*time.Millisecond, true); !changed || size != 500 {
+		t.Fatalf("failed batch: got (%d, %v), want (500, true)", size, changed)
+	}
+	// 超过目标 3 倍视为超时，同样立即减半
+	if size, changed := tuner.Observe(400*time.Millisecond, false); !changed || size != 250 {
+		t.Fatalf("timed out batch: got (%d, %v), want (250, true)", size, changed)
+	}
+}
+
+func TestBatchSizeTunerStreakResets(t *testing.T) {
+	tuner := NewBatchSizeTuner(1000, 100, 10000, testTargetLatency)
+	// 偏快的连续计数被目标区间内的批次打断，不增大
+	tuner.Observe(10*time.Millisecond, false)
+	tuner.Observe(10*time.Millisecond, false)
+	tuner.Observe(testTargetLatency, false)
+	if size, changed := tuner.Observe(10*time.Millisecond, false); changed || size != 1000 {
+		t.Fatalf("got (%d, %v) after an interrupted fast streak, want (1000, false)", size, changed)
+	}
+	// 偏慢打断偏快
+	tuner.Observe(10*time.Millisecond, false)
+	tuner.Observe(150*time.Millisecond, false)
+	if size, changed := tuner.Observe(10*time.Millisecond, false); changed || size != 1000 {
+		t.Fatalf("got (%d, %v) after a slow batch broke the fast streak, want (1000, false)", size, changed)
+	}
+}
+
+func TestBatchSizeTunerStaysWithinBounds(t *testing.T) {
+	tuner := NewBatchSizeTuner(1000, 800, 1100, testTargetLatency)
+	for i := 0; i < 10*defaultTunerHysteresis; i++ {
+		tuner.Observe(time.Millisecond, false)
+	}
+	if got := tuner.Current(); got != 1100 {
+		t.Errorf("size after growing = %d, want max 1100", got)
+	}
+	for i := 0; i < 10; i++ {
+		tuner.Observe(0, true)
+	}
+	if got := tuner.Current(); got != 800 {
+		t.Errorf("size after failures = %d, want min 800", got)
+	}
+	// 已在下限时不算变化
+	if _, changed := tuner.Observe(0, true); changed {
+		t.Error("reported a change while already at the minimum")
+	}
+}
+
+func TestNewBatchSizeTunerDefaults(t *testing.T) {
+	tuner := NewBatchSizeTuner(50, 0, 0, 0)
+	if tuner.min != defaultTunerMinBatchSize || tuner.max != defaultTunerMaxBatchSize || tuner.targetLatency != defaultTunerTargetLatency {
+		t.Errorf("defaults = (%d, %d, %v)", tuner.min, tuner.max, tuner.targetLatency)
+	}
+	// 初始值被限制在范围内
+	if tuner.Current() != defaultTunerMinBatchSize {
+		t.Errorf("Current() = %d, want %d", tuner.Current(), defaultTunerMinBatchSize)
+	}
+	if tuner := NewBatchSizeTuner(1000, 500, 200, 0); tuner.max != 500 || tuner.Current() != 500 {
+		t.Errorf("max < min: max = %d, current = %d, want 500 and 500", tuner.max, tuner.Current())
+	}
+}
+
+func TestPipelineAppliesTunedBatchSize(t *testing.T) {
+	// 第一批失败后批次大小减半，后续批次按新的大小读取
+	reader := &stubReader{rows: makeRows(2000), batchSize: 1000}
+	writer := &stubWriter{failOn: map[int]bool{1: true}}
+	var failed []int
+	var changes [][2]int
+	hooks := skipBatchErrors(&failed)
+	hooks.OnBatchSizeChange = func(oldSize, newSize int) {
+		changes = append(changes, [2]int{oldSize, newSize})
+	}
+
+	result, err := NewSyncPipeline(reader, writer, nil, hooks).
+		WithBatchTuner(NewBatchSizeTuner(1000, 100, 10000, time.Hour)).
+		Run(context.Background())
+	if err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+	if len(changes) != 1 || changes[0] != [2]int{1000, 500} {
+		t.Fatalf("batch size changes = %v, want [[1000 500]]", changes)
+	}
+	if result.Batches != 3 || result.Processed != 1000 {
+		t.Errorf("Batches = %d, Processed = %d, want 3 and 1000", result.Batches, result.Processed)
+	}
+}
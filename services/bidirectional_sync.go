@@ -0,0 +1,730 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"math"
+	"math/big"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// 冲突策略
+const (
+	ConflictSourceWins          = "source_wins"           // 源端优先
+	ConflictTargetWins          = "target_wins"           // 目标端优先
+	ConflictLatestTimestampWins = "latest_timestamp_wins" // 水位列较新的一方优先（相同时源端优先）
+)
+
+// BidirectionalConfig 双向同步配置
+// 要求：两端表结构一致、有相同的稳定主键，并且都有水位列（如 updated_at，每次修改都会更新）
+type BidirectionalConfig struct {
+	WatermarkColumn string `json:"watermark_column"` // 水位列
+	ConflictPolicy  string `json:"conflict_policy"`  // source_wins/target_wins/latest_timestamp_wins
+	IntervalSeconds int    `json:"interval_seconds"` // 轮询间隔（秒），默认 5
+}
+
+// ValidateBidirectionalConfig 校验双向同步配置
+func ValidateBidirectionalConfig(cfg *BidirectionalConfig, targetCount int) error {
+	if cfg == nil {
+		return fmt.Errorf("双向同步需要配置 bidirectional")
+	}
+	if strings.TrimSpace(cfg.WatermarkColumn) == "" {
+		return fmt.Errorf("双向同步需要指定水位列 watermark_column")
+	}
+	switch cfg.ConflictPolicy {
+	case ConflictSourceWins, ConflictTargetWins, ConflictLatestTimestampWins:
+	default:
+		return fmt.Errorf("冲突策略无效: %s（可选 source_wins/target_wins/latest_timestamp_wins）", cfg.ConflictPolicy)
+	}
+	if targetCount != 1 {
+		return fmt.Errorf("双向同步只支持一个目标源")
+	}
+	return nil
+}
+
+// bidirectionalTable 双向同步的表对
+type bidirectionalTable struct {
+	SourceDB    string
+	SourceTable string
+	TargetDB    string
+	TargetTable string
+	PKColumns   []string
+	Key         string // 持久化水位的键：源库.源表<->目标库.目标表
+
+	// 两端各自已读取到的位置，只由本端读到的记录推进
+	SourceMark watermarkPosition
+	TargetMark watermarkPosition
+}
+
+// bidirectionalPageSize 每次读取变更的条数
+const bidirectionalPageSize = 1000
+
+// bidirectionalSide 表对的一端
+type bidirectionalSide struct {
+	db       *sql.DB
+	database string
+	table    string
+	mark     *watermarkPosition
+}
+
+// BidirectionalSync 双向同步引擎（基于水位列轮询）
+type BidirectionalSync struct {
+	taskID     string
+	task       *models.SyncTask
+	config     *TaskConfig
+	biConfig   *BidirectionalConfig
+	logService *TaskLogService
+	dsService  *DataSourceService
+
+	sourceDB *sql.DB
+	targetDB *sql.DB
+	tables   []*bidirectionalTable
+}
+
+// NewBidirectionalSync 创建双向同步引擎
+func NewBidirectionalSync(taskID string) (*BidirectionalSync, error) {
+	var task models.SyncTask
+	if err := database.DB.Preload("SourceConn").Preload("TargetConn").
+		First(&task, "id = ?", taskID).Error; err != nil {
+		return nil, fmt.Errorf("查询任务失败: %v", err)
+	}
+
+	configCache := NewConfigCacheService()
+	config, err := configCache.GetTaskConfigWithFallback(taskID)
+	if err != nil {
+		return nil, fmt.Errorf("获取配置失败: %v", err)
+	}
+
+	if err := ValidateBidirectionalConfig(config.SyncConfig.Bidirectional, 1); err != nil {
+		return nil, err
+	}
+
+	return &BidirectionalSync{
+		taskID:     taskID,
+		task:       &task,
+		config:     config,
+		biConfig:   config.SyncConfig.Bidirectional,
+		logService: NewTaskLogService(),
+		dsService:  NewDataSourceService(),
+	}, nil
+}
+
+// Run 运行双向同步，直到 ctx 被取消
+func (s *BidirectionalSync) Run(ctx context.Context) error {
+	s.logService.Info(s.taskID, "========== 双向同步开始 ==========")
+
+	// 先注册关闭，初始化中途失败时已打开的连接同样会被关闭
+	defer s.closeDatabaseConnections()
+	if err := s.initDatabaseConnections(); err != nil {
+		return fmt.Errorf("初始化数据库连接失败: %v", err)
+	}
+
+	if err := s.prepareTables(); err != nil {
+		return err
+	}
+
+	interval := time.Duration(s.biConfig.IntervalSeconds) * time.Second
+	if interval <= 0 {
+		interval = 5 * time.Second
+	}
+
+	s.logService.Info(s.taskID, fmt.Sprintf("双向同步运行中: %d 张表, 水位列 %s, 冲突策略 %s, 间隔 %s",
+		len(s.tables), s.biConfig.WatermarkColumn, s.biConfig.ConflictPolicy, interval))
+
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+
+	for {
+		select {
+		case <-ctx.Done():
+			s.logService.Info(s.taskID, "========== 双向同步结束 ==========")
+			return nil
+		case <-ticker.C:
+			for _, tbl := range s.tables {
+				if ctx.Err() != nil {
+					break
+				}
+				if err := s.syncTable(ctx, tbl); err != nil {
+					s.logService.Error(s.taskID, fmt.Sprintf("表 %s.%s 双向同步失败: %v", tbl.SourceDB, tbl.SourceTable, err))
+					if s.config.SyncConfig.ErrorStrategy == "pause" {
+						return err
+					}
+				}
+			}
+		}
+	}
+}
+
+// initDatabaseConnections 初始化两端连接（不指定具体数据库）
+func (s *BidirectionalSync) initDatabaseConnections() error {
+	sourcePassword, err := s.dsService.ResolveConnection(s.task.SourceConn)
+	if err != nil {
+		return fmt.Errorf("解密源数据库密码失败: %v", err)
+	}
+	targetPassword, err := s.dsService.ResolveConnection(s.task.TargetConn)
+	if err != nil {
+		return fmt.Errorf("解密目标数据库密码失败: %v", err)
+	}
+
+	// 两端使用相同的会话时区，TIMESTAMP 水位和数据不因两边服务器时区不同而偏移
+	timeZone := sessionTimeZone(s.config.SyncConfig.TimestampMode)
+
+	sourceDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
+		s.task.SourceConn.Username, sourcePassword, mysqlNetAddr(s.task.SourceConn.MySQLHost(), s.task.SourceConn.Port)) +
+		timeZoneDSNParam(timeZone)
+	s.sourceDB, err = openPingedDB("mysql", sourceDSN)
+	if err != nil {
+		return fmt.Errorf("连接源数据库失败: %v", err)
+	}
+
+	targetDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
+		s.task.TargetConn.Username, targetPassword, mysqlNetAddr(s.task.TargetConn.MySQLHost(), s.task.TargetConn.Port)) +
+		timeZoneDSNParam(timeZone)
+	s.targetDB, err = openPingedDB("mysql", targetDSN)
+	if err != nil {
+		return fmt.Errorf("连接目标数据库失败: %v", err)
+	}
+
+	return nil
+}
+
+// closeDatabaseConnections 关闭连接
+func (s *BidirectionalSync) closeDatabaseConnections() {
+	if s.sourceDB != nil {
+		s.sourceDB.Close()
+	}
+	if s.targetDB != nil {
+		s.targetDB.Close()
+	}
+}
+
+// prepareTables 校验两端主键和水位列，从已保存的水位继续，没有时以当前最新位置作为起点
+func (s *BidirectionalSync) prepareTables() error {
+	column := s.biConfig.WatermarkColumn
+
+	for _, dbSel := range s.config.SelectedDatabases {
+		sourceDB := dbSel.SourceDatabase
+		if sourceDB == "" {
+			sourceDB = dbSel.Database
+		}
+		for _, tbl := range dbSel.Tables {
+			targetTable := tbl.TargetTable
+			if targetTable == "" {
+				targetTable = tbl.SourceTable
+			}
+			pair := &bidirectionalTable{
+				SourceDB:    sourceDB,
+				SourceTable: tbl.SourceTable,
+				TargetDB:    dbSel.Database,
+				TargetTable: targetTable,
+			}
+			name := fmt.Sprintf("%s.%s <-> %s.%s", pair.SourceDB, pair.SourceTable, pair.TargetDB, pair.TargetTable)
+
+			// 主键：两端必须一致
+			sourcePK, err := getPrimaryKeyColumns(s.sourceDB, pair.SourceDB, pair.SourceTable)
+			if err != nil {
+				return fmt.Errorf("查询 %s 源端主键失败: %v", name, err)
+			}
+			targetPK, err := getPrimaryKeyColumns(s.targetDB, pair.TargetDB, pair.TargetTable)
+			if err != nil {
+				return fmt.Errorf("查询 %s 目标端主键失败: %v", name, err)
+			}
+			if len(sourcePK) == 0 || strings.Join(sourcePK, ",") != strings.Join(targetPK, ",") {
+				return fmt.Errorf("%s 两端必须有相同的主键（源: %v, 目标: %v）", name, sourcePK, targetPK)
+			}
+			pair.PKColumns = sourcePK
+			pair.Key = fmt.Sprintf("%s.%s<->%s.%s", pair.SourceDB, pair.SourceTable, pair.TargetDB, pair.TargetTable)
+
+			// 水位列：两端必须存在
+			sourceLatest, err := s.latestPosition(s.sourceDB, pair.SourceDB, pair.SourceTable, column, pair.PKColumns)
+			if err != nil {
+				return fmt.Errorf("%s 源端水位列 %s 不可用: %v", name, column, err)
+			}
+			targetLatest, err := s.latestPosition(s.targetDB, pair.TargetDB, pair.TargetTable, column, pair.PKColumns)
+			if err != nil {
+				return fmt.Errorf("%s 目标端水位列 %s 不可用: %v", name, column, err)
+			}
+
+			sourceMark, targetMark, saved, err := loadWatermarks(s.taskID, pair.Key, column)
+			if err != nil {
+				return fmt.Errorf("%s 读取已保存的水位失败: %v", name, err)
+			}
+			if saved {
+				pair.SourceMark, pair.TargetMark = sourceMark, targetMark
+			} else {
+				// 首次运行以当前最新位置作为起点，并立即保存，重启后不会跳过期间的修改
+				pair.SourceMark, pair.TargetMark = sourceLatest, targetLatest
+				if err := saveWatermarks(s.taskID, pair.Key, column, pair.SourceMark, pair.TargetMark); err != nil {
+					return fmt.Errorf("%s 保存水位失败: %v", name, err)
+				}
+			}
+
+			s.tables = append(s.tables, pair)
+			if saved {
+				s.logService.Info(s.taskID, fmt.Sprintf("双向同步表: %s, 主键 %v, 从已保存的水位继续", name, pair.PKColumns))
+			} else {
+				s.logService.Info(s.taskID, fmt.Sprintf("双向同步表: %s, 主键 %v", name, pair.PKColumns))
+			}
+		}
+	}
+
+	if len(s.tables) == 0 {
+		return fmt.Errorf("没有待同步的表")
+	}
+	return nil
+}
+
+// latestPosition 查询当前最新位置（水位最大、同水位主键最大的记录），表为空时返回空位置
+func (s *BidirectionalSync) latestPosition(db *sql.DB, database, table, column string, pkColumns []string) (watermarkPosition, error) {
+	query := fmt.Sprintf("SELECT %s FROM `%s`.`%s` WHERE `%s` IS NOT NULL ORDER BY %s LIMIT 1",
+		quoteColumns(append([]string{column}, pkColumns...)), database, table, column,
+		orderColumns(append([]string{column}, pkColumns...), "DESC"))
+	rows, err := db.Query(query)
+	if err != nil {
+		return watermarkPosition{}, err
+	}
+	defer rows.Close()
+
+	// 与读取变更相同，按列类型还原值，首次保存的起点和之后读到的位置类型一致
+	records, err := scanRecords(rows)
+	if err != nil {
+		return watermarkPosition{}, err
+	}
+	if len(records) == 0 {
+		return watermarkPosition{}, nil
+	}
+	return rowPosition(records[0], column, pkColumns), nil
+}
+
+// syncTable 同步一张表一轮：两端交替按页读取各自水位之后的变更写入对端，直到两端都没有更多变更
+func (s *BidirectionalSync) syncTable(ctx context.Context, tbl *bidirectionalTable) error {
+	source := bidirectionalSide{db: s.sourceDB, database: tbl.SourceDB, table: tbl.SourceTable, mark: &tbl.SourceMark}
+	target := bidirectionalSide{db: s.targetDB, database: tbl.TargetDB, table: tbl.TargetTable, mark: &tbl.TargetMark}
+
+	var toTarget, toSource, conflicts int
+	for ctx.Err() == nil {
+		sourceRead, written, conflicted, err := s.syncPage(tbl, source, target, true)
+		if err != nil {
+			return fmt.Errorf("源->目标同步失败: %v", err)
+		}
+		toTarget += written
+		conflicts += conflicted
+
+		targetRead, written, conflicted, err := s.syncPage(tbl, target, source, false)
+		if err != nil {
+			return fmt.Errorf("目标->源同步失败: %v", err)
+		}
+		toSource += written
+		conflicts += conflicted
+
+		if sourceRead < bidirectionalPageSize && targetRead < bidirectionalPageSize {
+			break
+		}
+	}
+
+	if toTarget > 0 || toSource > 0 || conflicts > 0 {
+		s.logService.Info(s.taskID, fmt.Sprintf("表 %s.%s 双向同步: 源->目标 %d 条, 目标->源 %d 条, 冲突 %d 条",
+			tbl.SourceDB, tbl.SourceTable, toTarget, toSource, conflicts))
+	}
+	return nil
+}
+
+// syncPage 读取 from 端水位之后的一页变更写入 to 端，并只用读到的记录推进 from 端水位
+// 对端已是相同数据的记录（包括本引擎写过去又读回来的）不再写入；对端同一主键也有尚未同步的修改时按冲突策略取舍
+func (s *BidirectionalSync) syncPage(tbl *bidirectionalTable, from, to bidirectionalSide, fromSource bool) (read, written, conflicts int, err error) {
+	column := s.biConfig.WatermarkColumn
+
+	rows, err := s.fetchChanges(from.db, from.database, from.table, column, tbl.PKColumns, *from.mark, bidirectionalPageSize)
+	if err != nil {
+		return 0, 0, 0, fmt.Errorf("读取变更失败: %v", err)
+	}
+	if len(rows) == 0 {
+		return 0, 0, 0, nil
+	}
+	counterparts, err := s.fetchByPrimaryKeys(to.db, to.database, to.table, tbl.PKColumns, rows)
+	if err != nil {
+		return 0, 0, 0, fmt.Errorf("读取对端记录失败: %v", err)
+	}
+
+	var toWrite []DataRecord
+	for _, row := range rows {
+		key := primaryKeyString(row, tbl.PKColumns)
+		other, exists := counterparts[key]
+		if exists && sameRow(row, other) {
+			continue
+		}
+		if exists && comparePosition(rowPosition(other, column, tbl.PKColumns), *to.mark) > 0 {
+			// 同一主键两端都有修改
+			conflicts++
+			sourceRow, targetRow := row, other
+			if !fromSource {
+				sourceRow, targetRow = other, row
+			}
+			sourceWins := s.sourceWins(sourceRow, targetRow)
+			if sourceWins {
+				s.logService.Warning(s.taskID, fmt.Sprintf("冲突 %s.%s 主键 [%s]: 按 %s 采用源端数据", tbl.SourceDB, tbl.SourceTable, key, s.biConfig.ConflictPolicy))
+			} else {
+				s.logService.Warning(s.taskID, fmt.Sprintf("冲突 %s.%s 主键 [%s]: 按 %s 采用目标端数据", tbl.SourceDB, tbl.SourceTable, key, s.biConfig.ConflictPolicy))
+			}
+			// 胜出的是对端时不写入，对端的修改在对端读取时写回本端
+			if sourceWins != fromSource {
+				continue
+			}
+		}
+		toWrite = append(toWrite, row)
+	}
+
+	if err := s.upsertRows(to.db, to.database, to.table, toWrite); err != nil {
+		return 0, 0, 0, fmt.Errorf("写入失败: %v", err)
+	}
+
+	// 写入成功后才推进并保存水位；保存失败时下一轮重读，相同数据不会重复写入
+	*from.mark = rowPosition(rows[len(rows)-1], column, tbl.PKColumns)
+	if err := saveWatermarks(s.taskID, tbl.Key, column, tbl.SourceMark, tbl.TargetMark); err != nil {
+		return 0, 0, 0, fmt.Errorf("保存水位失败: %v", err)
+	}
+	return len(rows), len(toWrite), conflicts, nil
+}
+
+// sourceWins 按冲突策略判断是否采用源端数据
+func (s *BidirectionalSync) sourceWins(sourceRow, targetRow DataRecord) bool {
+	switch s.biConfig.ConflictPolicy {
+	case ConflictTargetWins:
+		return false
+	case ConflictLatestTimestampWins:
+		column := s.biConfig.WatermarkColumn
+		return compareWatermark(sourceRow[column], targetRow[column]) >= 0
+	default:
+		return true
+	}
+}
+
+// fetchChanges 按（水位, 主键）顺序读取位置之后的一页变更
+// 水位相同的记录以主键区分，翻页和重启都不会跳过或重复
+func (s *BidirectionalSync) fetchChanges(db *sql.DB, database, table, column string, pkColumns []string,
+	mark watermarkPosition, limit int) ([]DataRecord, error) {
+	where, args := changesCondition(column, pkColumns, mark)
+	query := fmt.Sprintf("SELECT * FROM `%s`.`%s` WHERE %s ORDER BY %s LIMIT %d",
+		database, table, where, orderColumns(append([]string{column}, pkColumns...), "ASC"), limit)
+	rows, err := db.Query(query, args...)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+	return scanRecords(rows)
+}
+
+// changesCondition 生成位置之后的查询条件
+func changesCondition(column string, pkColumns []string, mark watermarkPosition) (string, []interface{}) {
+	if mark.Value == nil {
+		return fmt.Sprintf("`%s` IS NOT NULL", column), nil
+	}
+	if len(mark.PK) != len(pkColumns) {
+		// 主键列已变化，旧位置的主键无法比较，重读同一水位的记录（相同数据不会重复写入）
+		return fmt.Sprintf("`%s` >= ?", column), []interface{}{mark.Value}
+	}
+	placeholders := strings.TrimSuffix(strings.Repeat("?, ", len(pkColumns)), ", ")
+	where := fmt.Sprintf("(`%s` > ? OR (`%s` = ? AND (%s) > (%s)))", column, column, quoteColumns(pkColumns), placeholders)
+	args := append([]interface{}{mark.Value, mark.Value}, mark.PK...)
+	return where, args
+}
+
+// fetchByPrimaryKeys 按主键批量读取对端记录，返回主键字符串到记录的映射
+func (s *BidirectionalSync) fetchByPrimaryKeys(db *sql.DB, database, table string, pkColumns []string, rows []DataRecord) (map[string]DataRecord, error) {
+	tuple := "(" + strings.TrimSuffix(strings.Repeat("?, ", len(pkColumns)), ", ") + ")"
+	tuples := make([]string, 0, len(rows))
+	args := make([]interface{}, 0, len(rows)*len(pkColumns))
+	for _, row := range rows {
+		tuples = append(tuples, tuple)
+		for _, col := range pkColumns {
+			args = append(args, row[col])
+		}
+	}
+	query := fmt.Sprintf("SELECT * FROM `%s`.`%s` WHERE (%s) IN (%s)",
+		database, table, quoteColumns(pkColumns), strings.Join(tuples, ", "))
+	result, err := db.Query(query, args...)
+	if err != nil {
+		return nil, err
+	}
+	defer result.Close()
+
+	records, err := scanRecords(result)
+	if err != nil {
+		return nil, err
+	}
+	byKey := make(map[string]DataRecord, len(records))
+	for _, record := range records {
+		byKey[primaryKeyString(record, pkColumns)] = record
+	}
+	return byKey, nil
+}
+
+// scanRecords 读取结果集的全部记录，各列按列类型还原为固定的 Go 类型
+func scanRecords(rows *sql.Rows) ([]DataRecord, error) {
+	columns, err := rows.Columns()
+	if err != nil {
+		return nil, err
+	}
+	columnTypes, err := rows.ColumnTypes()
+	if err != nil {
+		return nil, err
+	}
+
+	var records []DataRecord
+	for rows.Next() {
+		values := make([]interface{}, len(columns))
+		valuePtrs := make([]interface{}, len(columns))
+		for i := range values {
+			valuePtrs[i] = &values[i]
+		}
+		if err := rows.Scan(valuePtrs...); err != nil {
+			return nil, err
+		}
+
+		record := make(DataRecord, len(columns))
+		for i, col := range columns {
+			record[col] = normalizeColumnValue(values[i], columnTypes[i].DatabaseTypeName())
+		}
+		records = append(records, record)
+	}
+	return records, rows.Err()
+}
+
+// upsertRows 以 INSERT ... ON DUPLICATE KEY UPDATE 写入（单事务）
+func (s *BidirectionalSync) upsertRows(db *sql.DB, database, table string, rows []DataRecord) error {
+	if len(rows) == 0 {
+		return nil
+	}
+
+	// 生成列由数据库计算，不能写入（查询失败时按无生成列处理）
+	generated, _ := getGeneratedColumns(db, database, table)
+
+	tx, err := db.Begin()
+	if err != nil {
+		return err
+	}
+
+	for _, row := range rows {
+		columns := make([]string, 0, len(row))
+		placeholders := make([]string, 0, len(row))
+		updates := make([]string, 0, len(row))
+		values := make([]interface{}, 0, len(row))
+		for col, val := range row {
+			if generated[col] {
+				continue
+			}
+			columns = append(columns, fmt.Sprintf("`%s`", col))
+			placeholders = append(placeholders, "?")
+			updates = append(updates, fmt.Sprintf("`%s`=VALUES(`%s`)", col, col))
+			values = append(values, val)
+		}
+
+		query := fmt.Sprintf("INSERT INTO `%s`.`%s` (%s) VALUES (%s) ON DUPLICATE KEY UPDATE %s",
+			database, table,
+			strings.Join(columns, ", "),
+			strings.Join(placeholders, ", "),
+			strings.Join(updates, ", "))
+
+		if _, err := tx.Exec(query, values...); err != nil {
+			tx.Rollback()
+			return err
+		}
+	}
+
+	return tx.Commit()
+}
+
+// normalizeValue 将驱动返回的 []byte 转为 string，便于比较和重新写入
+func normalizeValue(v interface{}) interface{} {
+	if b, ok := v.([]byte); ok {
+		return string(b)
+	}
+	return v
+}
+
+// decimalValue DECIMAL 列的值，保留原始精度，比较时按数值比较
+type decimalValue string
+
+// normalizeColumnValue 按列类型将驱动返回的值转为固定类型
+// 无参数的查询走文本协议，整数、浮点数也以 []byte 返回；有参数的查询走二进制协议，返回 int64/float32/float64。
+// 统一后同一列在两种查询下类型一致，水位和主键才能正确比较
+func normalizeColumnValue(v interface{}, typeName string) interface{} {
+	if f, ok := v.(float32); ok {
+		return float64(f)
+	}
+	b, ok := v.([]byte)
+	if !ok {
+		return v
+	}
+	s := string(b)
+	switch typeName {
+	case "TINYINT", "SMALLINT", "MEDIUMINT", "INT", "BIGINT", "YEAR":
+		if n, err := strconv.ParseInt(s, 10, 64); err == nil {
+			return n
+		}
+	case "UNSIGNED TINYINT", "UNSIGNED SMALLINT", "UNSIGNED MEDIUMINT", "UNSIGNED INT", "UNSIGNED BIGINT":
+		// 与二进制协议一致：能放进 int64 的无符号整数返回 int64
+		if n, err := strconv.ParseUint(s, 10, 64); err == nil {
+			if n <= math.MaxInt64 {
+				return int64(n)
+			}
+			return n
+		}
+	case "FLOAT":
+		// 按 float32 精度解析，与二进制协议返回的 float32 转换结果一致
+		if f, err := strconv.ParseFloat(s, 32); err == nil {
+			return f
+		}
+	case "DOUBLE":
+		if f, err := strconv.ParseFloat(s, 64); err == nil {
+			return f
+		}
+	case "DECIMAL":
+		return decimalValue(s)
+	}
+	return s
+}
+
+// quoteColumns 生成带反引号、逗号分隔的列名列表
+func quoteColumns(columns []string) string {
+	quoted := make([]string, len(columns))
+	for i, col := range columns {
+		quoted[i] = fmt.Sprintf("`%s`", col)
+	}
+	return strings.Join(quoted, ", ")
+}
+
+// orderColumns 生成按各列同一方向排序的 ORDER BY 列表
+func orderColumns(columns []string, direction string) string {
+	ordered := make([]string, len(columns))
+	for i, col := range columns {
+		ordered[i] = fmt.Sprintf("`%s` %s", col, direction)
+	}
+	return strings.Join(ordered, ", ")
+}
+
+// sameRow 判断两端记录的各列值是否相同
+func sameRow(a, b DataRecord) bool {
+	if len(a) != len(b) {
+		return false
+	}
+	for col, val := range a {
+		other, ok := b[col]
+		if !ok || compareWatermark(val, other) != 0 {
+			return false
+		}
+	}
+	return true
+}
+
+// primaryKeyString 生成主键字符串（用于两端记录匹配）
+func primaryKeyString(row DataRecord, pkColumns []string) string {
+	parts := make([]string, len(pkColumns))
+	for i, col := range pkColumns {
+		parts[i] = fmt.Sprint(row[col])
+	}
+	return strings.Join(parts, ",")
+}
+
+// compareWatermark 比较两个水位值，nil 最小
+func compareWatermark(a, b interface{}) int {
+	if a == nil && b == nil {
+		return 0
+	}
+	if a == nil {
+		return -1
+	}
+	if b == nil {
+		return 1
+	}
+
+	switch av := a.(type) {
+	case time.Time:
+		if bv, ok := b.(time.Time); ok {
+			switch {
+			case av.Before(bv):
+				return -1
+			case av.After(bv):
+				return 1
+			}
+			return 0
+		}
+	case int64:
+		if bv, ok := b.(int64); ok {
+			switch {
+			case av < bv:
+				return -1
+			case av > bv:
+				return 1
+			}
+			return 0
+		}
+	case uint64:
+		if bv, ok := b.(uint64); ok {
+			switch {
+			case av < bv:
+				return -1
+			case av > bv:
+				return 1
+			}
+			return 0
+		}
+	case float64:
+		if bv, ok := b.(float64); ok {
+			switch {
+			case av < bv:
+				return -1
+			case av > bv:
+				return 1
+			}
+			return 0
+		}
+	case decimalValue:
+		if bv, ok := b.(decimalValue); ok && av == bv {
+			return 0
+		}
+	}
+
+	// 数值列两端类型不一致时（如旧版本按字符串保存的水位与整数）按数值比较，而不是按字符串比较
+	if isNumericValue(a) || isNumericValue(b) {
+		if ar, ok := numericRat(a); ok {
+			if br, ok := numericRat(b); ok {
+				return ar.Cmp(br)
+			}
+		}
+	}
+
+	return strings.Compare(fmt.Sprint(a), fmt.Sprint(b))
+}
+
+// isNumericValue 判断是否为数值列的值
+func isNumericValue(v interface{}) bool {
+	switch v.(type) {
+	case int64, uint64, float64, decimalValue:
+		return true
+	}
+	return false
+}
+
+// numericRat 将数值或数字字符串转为有理数，用于跨类型的精确比较
+func numericRat(v interface{}) (*big.Rat, bool) {
+	switch val := v.(type) {
+	case int64:
+		return new(big.Rat).SetInt64(val), true
+	case uint64:
+		return new(big.Rat).SetUint64(val), true
+	case float64:
+		r := new(big.Rat).SetFloat64(val)
+		return r, r != nil
+	case decimalValue:
+		return new(big.Rat).SetString(string(val))
+	case string:
+		return new(big.Rat).SetString(val)
+	}
+	return nil, false
+}
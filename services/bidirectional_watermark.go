@@ -0,0 +1,164 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"encoding/json"
+	"fmt"
+	"strconv"
+	"time"
+)
+
+// watermarkPosition 一端已同步到的位置：水位值相同的记录再按主键排序，避免同一水位的记录被跳过
+type watermarkPosition struct {
+	Value interface{}   // 水位值，nil 表示从头开始
+	PK    []interface{} // 该水位下已处理到的主键
+}
+
+// taggedValue 持久化时带类型的值（重启后按原类型绑定到查询参数）
+type taggedValue struct {
+	Type  string `json:"t"` // null/time/int/uint/float/decimal/string
+	Value string `json:"v,omitempty"`
+}
+
+// storedPosition 持久化的位置
+type storedPosition struct {
+	Value taggedValue   `json:"value"`
+	PK    []taggedValue `json:"pk"`
+}
+
+// rowPosition 记录所在的位置
+func rowPosition(row DataRecord, column string, pkColumns []string) watermarkPosition {
+	pos := watermarkPosition{Value: row[column], PK: make([]interface{}, len(pkColumns))}
+	for i, col := range pkColumns {
+		pos.PK[i] = row[col]
+	}
+	return pos
+}
+
+// comparePosition 比较两个位置：先比较水位值，相同时逐列比较主键
+func comparePosition(a, b watermarkPosition) int {
+	if c := compareWatermark(a.Value, b.Value); c != 0 {
+		return c
+	}
+	for i := 0; i < len(a.PK) && i < len(b.PK); i++ {
+		if c := compareWatermark(a.PK[i], b.PK[i]); c != 0 {
+			return c
+		}
+	}
+	return len(a.PK) - len(b.PK)
+}
+
+// tagValue 按类型编码值
+func tagValue(v interface{}) taggedValue {
+	switch val := v.(type) {
+	case nil:
+		return taggedValue{Type: "null"}
+	case time.Time:
+		return taggedValue{Type: "time", Value: val.Format(time.RFC3339Nano)}
+	case int64:
+		return taggedValue{Type: "int", Value: strconv.FormatInt(val, 10)}
+	case uint64:
+		return taggedValue{Type: "uint", Value: strconv.FormatUint(val, 10)}
+	case float64:
+		return taggedValue{Type: "float", Value: strconv.FormatFloat(val, 'g', -1, 64)}
+	case decimalValue:
+		return taggedValue{Type: "decimal", Value: string(val)}
+	default:
+		return taggedValue{Type: "string", Value: fmt.Sprint(val)}
+	}
+}
+
+// untagValue 还原带类型的值
+func untagValue(t taggedValue) (interface{}, error) {
+	switch t.Type {
+	case "null":
+		return nil, nil
+	case "time":
+		v, err := time.Parse(time.RFC3339Nano, t.Value)
+		if err != nil {
+			return nil, err
+		}
+		return v.In(time.Local), nil
+	case "int":
+		return strconv.ParseInt(t.Value, 10, 64)
+	case "uint":
+		return strconv.ParseUint(t.Value, 10, 64)
+	case "float":
+		return strconv.ParseFloat(t.Value, 64)
+	case "decimal":
+		return decimalValue(t.Value), nil
+	case "string":
+		return t.Value, nil
+	}
+	return nil, fmt.Errorf("未知的值类型: %s", t.Type)
+}
+
+// encodePosition 位置编码为 JSON
+func encodePosition(pos watermarkPosition) (string, error) {
+	stored := storedPosition{Value: tagValue(pos.Value), PK: make([]taggedValue, len(pos.PK))}
+	for i, v := range pos.PK {
+		stored.PK[i] = tagValue(v)
+	}
+	data, err := json.Marshal(stored)
+	return string(data), err
+}
+
+// decodePosition 解析 JSON 位置
+func decodePosition(data string) (watermarkPosition, error) {
+	var stored storedPosition
+	if err := json.Unmarshal([]byte(data), &stored); err != nil {
+		return watermarkPosition{}, err
+	}
+	value, err := untagValue(stored.Value)
+	if err != nil {
+		return watermarkPosition{}, err
+	}
+	pos := watermarkPosition{Value: value, PK: make([]interface{}, len(stored.PK))}
+	for i, t := range stored.PK {
+		if pos.PK[i], err = untagValue(t); err != nil {
+			return watermarkPosition{}, err
+		}
+	}
+	return pos, nil
+}
+
+// loadWatermarks 读取表对持久化的两端位置，没有记录或水位列已变更时 ok 为 false
+func loadWatermarks(taskID, tableKey, column string) (source, target watermarkPosition, ok bool, err error) {
+	var record models.BidirectionalWatermark
+	result := database.DB.Where("task_id = ? AND table_key = ?", taskID, tableKey).Limit(1).Find(&record)
+	if result.Error != nil {
+		return source, target, false, result.Error
+	}
+	if result.RowsAffected == 0 || record.WatermarkColumn != column {
+		return source, target, false, nil
+	}
+	if source, err = decodePosition(record.SourceMark); err != nil {
+		return source, target, false, fmt.Errorf("解析源端水位失败: %w", err)
+	}
+	if target, err = decodePosition(record.TargetMark); err != nil {
+		return source, target, false, fmt.Errorf("解析目标端水位失败: %w", err)
+	}
+	return source, target, true, nil
+}
+
+// saveWatermarks 持久化表对的两端位置
+func saveWatermarks(taskID, tableKey, column string, source, target watermarkPosition) error {
+	sourceMark, err := encodePosition(source)
+	if err != nil {
+		return err
+	}
+	targetMark, err := encodePosition(target)
+	if err != nil {
+		return err
+	}
+	var record models.BidirectionalWatermark
+	return database.DB.Where(models.BidirectionalWatermark{TaskID: taskID, TableKey: tableKey}).
+		Assign(models.BidirectionalWatermark{WatermarkColumn: column, SourceMark: sourceMark, TargetMark: targetMark}).
+		FirstOrCreate(&record).Error
+}
+
+// clearWatermarks 删除任务的全部水位（删除任务时调用）
+func clearWatermarks(taskID string) error {
+	return database.DB.Where("task_id = ?", taskID).Delete(&models.BidirectionalWatermark{}).Error
+}
@@ -0,0 +1,93 @@
+package services
+
+import (
+	"testing"
+	"time"
+)
+
+func TestComparePositionMixedTypes(t *testing.T) {
+	ts := time.Date(2024, 5, 1, 12, 0, 0, 0, time.UTC)
+	tests := []struct {
+		name string
+		a, b watermarkPosition
+		want int
+	}{
+		{"int vs string value", watermarkPosition{Value: int64(123)}, watermarkPosition{Value: "99"}, 1},
+		{"string vs int value", watermarkPosition{Value: "99"}, watermarkPosition{Value: int64(123)}, -1},
+		{"equal int and string", watermarkPosition{Value: int64(123)}, watermarkPosition{Value: "123"}, 0},
+		{"uint vs int", watermarkPosition{Value: uint64(1 << 63)}, watermarkPosition{Value: int64(5)}, 1},
+		{"decimal values", watermarkPosition{Value: decimalValue("9.50")}, watermarkPosition{Value: decimalValue("10.1")}, -1},
+		{"equal decimals", watermarkPosition{Value: decimalValue("1.50")}, watermarkPosition{Value: decimalValue("1.5")}, 0},
+		{"decimal vs stored string", watermarkPosition{Value: decimalValue("100.25")}, watermarkPosition{Value: "99.5"}, 1},
+		{"float vs int", watermarkPosition{Value: 2.5}, watermarkPosition{Value: int64(3)}, -1},
+		{"same time, int pk vs string pk", watermarkPosition{Value: ts, PK: []interface{}{int64(123)}},
+			watermarkPosition{Value: ts, PK: []interface{}{"99"}}, 1},
+		{"same time, string pk vs int pk", watermarkPosition{Value: ts, PK: []interface{}{"99"}},
+			watermarkPosition{Value: ts, PK: []interface{}{int64(123)}}, -1},
+		{"varchar pk keeps string order", watermarkPosition{Value: ts, PK: []interface{}{"99"}},
+			watermarkPosition{Value: ts, PK: []interface{}{"123"}}, 1},
+		{"nil is smallest", watermarkPosition{}, watermarkPosition{Value: int64(0)}, -1},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got := comparePosition(tt.a, tt.b)
+			if sign(got) != tt.want {
+				t.Errorf("comparePosition() = %d, want sign %d", got, tt.want)
+			}
+		})
+	}
+}
+
+func TestNormalizeColumnValue(t *testing.T) {
+	tests := []struct {
+		name     string
+		value    interface{}
+		typeName string
+		want     interface{}
+	}{
+		{"text int", []byte("123"), "INT", int64(123)},
+		{"text bigint", []byte("-99"), "BIGINT", int64(-99)},
+		{"text unsigned fits int64", []byte("42"), "UNSIGNED BIGINT", int64(42)},
+		{"text unsigned above int64", []byte("18446744073709551615"), "UNSIGNED BIGINT", uint64(18446744073709551615)},
+		{"text double", []byte("1.25"), "DOUBLE", 1.25},
+		{"text float matches binary float32", []byte("1.1"), "FLOAT", float64(float32(1.1))},
+		{"binary float32", float32(1.1), "FLOAT", float64(float32(1.1))},
+		{"decimal", []byte("10.50"), "DECIMAL", decimalValue("10.50")},
+		{"varchar", []byte("99"), "VARCHAR", "99"},
+		{"binary int passes through", int64(7), "INT", int64(7)},
+		{"nil", nil, "INT", nil},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := normalizeColumnValue(tt.value, tt.typeName); got != tt.want {
+				t.Errorf("normalizeColumnValue(%v, %s) = %#v, want %#v", tt.value, tt.typeName, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestEncodePositionKeepsDecimal(t *testing.T) {
+	pos := watermarkPosition{Value: decimalValue("12.340"), PK: []interface{}{int64(7)}}
+	data, err := encodePosition(pos)
+	if err != nil {
+		t.Fatalf("encodePosition() error = %v", err)
+	}
+	got, err := decodePosition(data)
+	if err != nil {
+		t.Fatalf("decodePosition() error = %v", err)
+	}
+	if got.Value != pos.Value || len(got.PK) != 1 || got.PK[0] != int64(7) {
+		t.Errorf("decodePosition(%s) = %#v, want %#v", data, got, pos)
+	}
+}
+
+// sign 将比较结果归一为 -1/0/1
+func sign(c int) int {
+	switch {
+	case c < 0:
+		return -1
+	case c > 0:
+		return 1
+	}
+	return 0
+}
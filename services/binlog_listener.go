@@ -4,6 +4,7 @@ import (
 	"context"
 	"database/sql"
 	"fmt"
+	"strings"
 	"time"
 
 	"github.com/go-mysql-org/go-mysql/mysql"
@@ -45,7 +46,7 @@ type BinlogListener struct {
 
 // BinlogListenerConfig 监听器配置
 type BinlogListenerConfig struct {
-	Host      string
+	Host      string // 地址或 unix socket 路径（以 / 开头）
 	Port      int
 	Username  string
 	Password  string
@@ -100,14 +101,23 @@ func NewBinlogListener(config *BinlogListenerConfig) (*BinlogListener, error) {
 	}, nil
 }
 
+// binlogSyncerAddr 同步器的地址和端口：socket 路径时端口为 0，go-mysql 直接以 Host 作为地址并按 unix socket 连接
+func binlogSyncerAddr(host string, port int) (string, uint16) {
+	if strings.HasPrefix(host, "/") {
+		return host, 0
+	}
+	return host, uint16(port)
+}
+
 // Start 启动监听
 func (l *BinlogListener) Start() error {
 	// 创建 Binlog 同步器配置
+	host, port := binlogSyncerAddr(l.host, l.port)
 	cfg := replication.BinlogSyncerConfig{
 		ServerID: l.serverID,
 		Flavor:   "mysql",
-		Host:     l.host,
-		Port:     uint16(l.port),
+		Host:     host,
+		Port:     port,
 		User:     l.username,
 		Password: l.password,
 	}
@@ -0,0 +1,24 @@
+package services
+
+import "testing"
+
+func TestBinlogSyncerAddr(t *testing.T) {
+	cases := []struct {
+		host     string
+		port     int
+		wantHost string
+		wantPort uint16
+	}{
+		{"127.0.0.1", 3306, "127.0.0.1", 3306},
+		{"db.internal", 3307, "db.internal", 3307},
+		// socket 路径：端口置 0，go-mysql 以 Host 为地址按 unix socket 连接
+		{"/var/run/mysqld/mysqld.sock", 3306, "/var/run/mysqld/mysqld.sock", 0},
+		{"/tmp/mysql.sock", 0, "/tmp/mysql.sock", 0},
+	}
+	for _, c := range cases {
+		host, port := binlogSyncerAddr(c.host, c.port)
+		if host != c.wantHost || port != c.wantPort {
+			t.Errorf("binlogSyncerAddr(%q, %d) = %q, %d, want %q, %d", c.host, c.port, host, port, c.wantHost, c.wantPort)
+		}
+	}
+}
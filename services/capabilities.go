@@ -0,0 +1,68 @@
+package services
+
+import (
+	"datatrace/config"
+	"datatrace/database"
+	"runtime"
+	"runtime/debug"
+)
+
+// Version 后端版本号，构建时通过 -ldflags "-X datatrace/services.Version=v1.2.3" 注入
+// 未注入时使用构建信息中的模块版本或代码提交号
+var Version = "dev"
+
+// SyncDirection 支持的同步方向及可用的同步模式
+type SyncDirection struct {
+	Source string   `json:"source"`
+	Target string   `json:"target"`
+	Modes  []string `json:"modes"` // full/incremental/bidirectional
+}
+
+// Capabilities 后端版本和能力，前端据此隐藏不支持的选项
+type Capabilities struct {
+	Version         string          `json:"version"`
+	GoVersion       string          `json:"go_version"`
+	DataSourceTypes []string        `json:"datasource_types"` // 可创建的数据源类型
+	SyncDirections  []SyncDirection `json:"sync_directions"`  // 可执行的同步方向（同步引擎只实现了 MySQL → MySQL）
+	Transformers    []string        `json:"transformers"`     // 已注册的自定义转换器
+	Features        map[string]bool `json:"features"`         // 按配置启用的功能
+}
+
+// GetCapabilities 获取后端版本和能力（只读内存中的配置，不访问数据库）
+func GetCapabilities() *Capabilities {
+	return &Capabilities{
+		Version:         buildVersion(),
+		GoVersion:       runtime.Version(),
+		DataSourceTypes: []string{"mysql", "elasticsearch"},
+		SyncDirections: []SyncDirection{
+			{Source: "mysql", Target: "mysql", Modes: []string{"full", "incremental", "bidirectional"}},
+		},
+		Transformers: ListTransformers(),
+		Features: map[string]bool{
+			"redis":          database.IsRedisEnabled(), // 配置缓存和增量同步统计
+			"health_check":   config.GlobalConfig.HealthCheck.Enabled,
+			"headless_api":   config.GlobalConfig.HeadlessAPI.Enabled,
+			"structure_sync": false, // 结构同步开发中
+		},
+	}
+}
+
+// buildVersion 版本号：优先使用构建时注入的值，其次为模块版本，最后为代码提交号（前 12 位）
+func buildVersion() string {
+	if Version != "dev" {
+		return Version
+	}
+	info, ok := debug.ReadBuildInfo()
+	if !ok {
+		return Version
+	}
+	if v := info.Main.Version; v != "" && v != "(devel)" {
+		return v
+	}
+	for _, setting := range info.Settings {
+		if setting.Key == "vcs.revision" && len(setting.Value) >= 12 {
+			return Version + "-" + setting.Value[:12]
+		}
+	}
+	return Version
+}
@@ -0,0 +1,93 @@
+package services
+
+import (
+	"fmt"
+	"strings"
+)
+
+// 字段值转换操作
+const (
+	ColumnOpUpper    = "upper"    // 转大写
+	ColumnOpLower    = "lower"    // 转小写
+	ColumnOpTrim     = "trim"     // 去除首尾空白
+	ColumnOpCoalesce = "coalesce" // NULL 替换为默认值，格式 coalesce:默认值
+)
+
+// columnOp 单个字段值的转换函数，NULL 以 nil 传入
+type columnOp func(value interface{}) interface{}
+
+// ValidateColumnTransforms 校验字段转换配置
+func ValidateColumnTransforms(transforms map[string]string) error {
+	_, err := NewColumnTransformer(transforms)
+	return err
+}
+
+// NewColumnTransformer 创建字段值转换器
+// transforms 为 源字段名 -> 操作，操作格式 "name" 或 "name:arg"，如 {"country": "upper", "remark": "coalesce:无"}
+// 字符串类操作（upper/lower/trim）只处理字符串值，NULL 和其他类型保持不变
+func NewColumnTransformer(transforms map[string]string) (Transformer, error) {
+	ops := make(map[string]columnOp, len(transforms))
+	for field, spec := range transforms {
+		if strings.TrimSpace(field) == "" {
+			return nil, fmt.Errorf("字段转换的字段名不能为空")
+		}
+		op, err := parseColumnOp(spec)
+		if err != nil {
+			return nil, fmt.Errorf("字段 %s 的转换配置无效: %w", field, err)
+		}
+		ops[field] = op
+	}
+
+	return TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		for field, op := range ops {
+			if value, ok := record[field]; ok {
+				record[field] = op(value)
+			}
+		}
+		return record, nil
+	}), nil
+}
+
+// parseColumnOp 解析单个转换操作
+func parseColumnOp(spec string) (columnOp, error) {
+	name, arg, hasArg := spec, "", false
+	if idx := strings.Index(spec, ":"); idx >= 0 {
+		name, arg, hasArg = spec[:idx], spec[idx+1:], true
+	}
+	name = strings.ToLower(strings.TrimSpace(name))
+
+	switch name {
+	case ColumnOpUpper:
+		return stringColumnOp(strings.ToUpper), nil
+	case ColumnOpLower:
+		return stringColumnOp(strings.ToLower), nil
+	case ColumnOpTrim:
+		return stringColumnOp(strings.TrimSpace), nil
+	case ColumnOpCoalesce:
+		if !hasArg {
+			return nil, fmt.Errorf("coalesce 需要指定默认值，例如 coalesce:0")
+		}
+		return func(value interface{}) interface{} {
+			if value == nil {
+				return arg
+			}
+			return value
+		}, nil
+	default:
+		return nil, fmt.Errorf("未知的转换操作: %s（可选 upper/lower/trim/coalesce）", name)
+	}
+}
+
+// stringColumnOp 包装字符串转换，NULL 和非字符串值原样返回
+func stringColumnOp(fn func(string) string) columnOp {
+	return func(value interface{}) interface{} {
+		switch v := value.(type) {
+		case string:
+			return fn(v)
+		case []byte:
+			return fn(string(v))
+		default:
+			return value
+		}
+	}
+}
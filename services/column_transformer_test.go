@@ -0,0 +1,110 @@
+package services
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestColumnTransformer(t *testing.T) {
+	cases := []struct {
+		name       string
+		transforms map[string]string
+		record     DataRecord
+		want       DataRecord
+	}{
+		{
+			name:       "upper",
+			transforms: map[string]string{"country": "upper"},
+			record:     DataRecord{"country": "cn", "city": "bj"},
+			want:       DataRecord{"country": "CN", "city": "bj"},
+		},
+		{
+			name:       "lower on bytes",
+			transforms: map[string]string{"email": "lower"},
+			record:     DataRecord{"email": []byte("A@B.COM")},
+			want:       DataRecord{"email": "a@b.com"},
+		},
+		{
+			name:       "trim",
+			transforms: map[string]string{"name": "trim"},
+			record:     DataRecord{"name": "  tom \t"},
+			want:       DataRecord{"name": "tom"},
+		},
+		{
+			name:       "op name is case-insensitive",
+			transforms: map[string]string{"name": " UPPER "},
+			record:     DataRecord{"name": "tom"},
+			want:       DataRecord{"name": "TOM"},
+		},
+		{
+			name:       "string ops keep NULL",
+			transforms: map[string]string{"a": "upper", "b": "lower", "c": "trim"},
+			record:     DataRecord{"a": nil, "b": nil, "c": nil},
+			want:       DataRecord{"a": nil, "b": nil, "c": nil},
+		},
+		{
+			name:       "string ops keep non-string values",
+			transforms: map[string]string{"age": "upper"},
+			record:     DataRecord{"age": int64(18)},
+			want:       DataRecord{"age": int64(18)},
+		},
+		{
+			name:       "coalesce replaces NULL",
+			transforms: map[string]string{"remark": "coalesce:无"},
+			record:     DataRecord{"remark": nil},
+			want:       DataRecord{"remark": "无"},
+		},
+		{
+			name:       "coalesce keeps value",
+			transforms: map[string]string{"remark": "coalesce:无"},
+			record:     DataRecord{"remark": "ok"},
+			want:       DataRecord{"remark": "ok"},
+		},
+		{
+			name:       "coalesce with empty default and colon in default",
+			transforms: map[string]string{"a": "coalesce:", "b": "coalesce:00:00"},
+			record:     DataRecord{"a": nil, "b": nil},
+			want:       DataRecord{"a": "", "b": "00:00"},
+		},
+		{
+			name:       "missing field is not added",
+			transforms: map[string]string{"remark": "coalesce:无"},
+			record:     DataRecord{"id": int64(1)},
+			want:       DataRecord{"id": int64(1)},
+		},
+	}
+	for _, c := range cases {
+		transformer, err := NewColumnTransformer(c.transforms)
+		if err != nil {
+			t.Fatalf("%s: NewColumnTransformer() error = %v", c.name, err)
+		}
+		got, err := transformer.Transform(c.record)
+		if err != nil {
+			t.Fatalf("%s: Transform() error = %v", c.name, err)
+		}
+		if !reflect.DeepEqual(got, c.want) {
+			t.Errorf("%s: Transform() = %v, want %v", c.name, got, c.want)
+		}
+	}
+}
+
+func TestValidateColumnTransforms(t *testing.T) {
+	invalid := []map[string]string{
+		{"": "upper"},
+		{" ": "trim"},
+		{"name": "reverse"},
+		{"name": "coalesce"},
+		{"name": ""},
+	}
+	for _, transforms := range invalid {
+		if err := ValidateColumnTransforms(transforms); err == nil {
+			t.Errorf("ValidateColumnTransforms(%v) accepted", transforms)
+		}
+	}
+	if err := ValidateColumnTransforms(map[string]string{"a": "upper", "b": "coalesce:0"}); err != nil {
+		t.Errorf("ValidateColumnTransforms() error = %v", err)
+	}
+	if err := ValidateColumnTransforms(nil); err != nil {
+		t.Errorf("ValidateColumnTransforms(nil) error = %v", err)
+	}
+}
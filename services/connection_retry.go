@@ -0,0 +1,118 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"math/rand"
+	"strings"
+	"time"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// 连接获取重试默认值（连接数打满时退避重试，与批次数据错误的跳过/暂停策略无关）
+const (
+	DefaultConnectRetries       = 3
+	DefaultConnectRetryJitterMs = 500
+
+	connectRetryBaseDelay = 200 * time.Millisecond
+	connectRetryMaxDelay  = 5 * time.Second
+)
+
+// ValidateConnectRetry 校验连接获取重试配置，0 表示使用默认值
+func ValidateConnectRetry(retries, jitterMs int) error {
+	if retries < 0 || retries > 20 {
+		return fmt.Errorf("connect_retries 必须在 0~20 之间")
+	}
+	if jitterMs < 0 || jitterMs > 60000 {
+		return fmt.Errorf("connect_retry_jitter_ms 必须在 0~60000 之间")
+	}
+	return nil
+}
+
+// isPoolPressureError 是否为连接数打满导致的错误（服务端 max_connections / max_user_connections）
+// 这类错误发生在获取连接阶段，SQL 尚未执行，重试不会重复写入
+func isPoolPressureError(err error) bool {
+	if err == nil {
+		return false
+	}
+	var mysqlErr *mysql.MySQLError
+	if errors.As(err, &mysqlErr) {
+		switch mysqlErr.Number {
+		case 1040, // ER_CON_COUNT_ERROR
+			1203, // ER_TOO_MANY_USER_CONNECTIONS
+			1226: // ER_USER_LIMIT_REACHED
+			return true
+		}
+	}
+	return strings.Contains(strings.ToLower(err.Error()), "too many connections")
+}
+
+// connectRetryPolicy 连接获取重试策略：指数退避 + 随机抖动，避免多个 Worker 同时重连
+// nil 策略表示不重试
+type connectRetryPolicy struct {
+	retries int
+	jitter  time.Duration
+	onRetry func(attempt, retries int, wait time.Duration, err error) // 每次重试前调用（记录连接压力）
+}
+
+// newConnectRetryPolicy 创建连接获取重试策略，retries/jitterMs <= 0 时使用默认值
+func newConnectRetryPolicy(retries, jitterMs int, onRetry func(attempt, retries int, wait time.Duration, err error)) *connectRetryPolicy {
+	if retries <= 0 {
+		retries = DefaultConnectRetries
+	}
+	if jitterMs <= 0 {
+		jitterMs = DefaultConnectRetryJitterMs
+	}
+	return &connectRetryPolicy{
+		retries: retries,
+		jitter:  time.Duration(jitterMs) * time.Millisecond,
+		onRetry: onRetry,
+	}
+}
+
+// delay 第 attempt 次重试前的等待时间
+func (p *connectRetryPolicy) delay(attempt int) time.Duration {
+	wait := connectRetryBaseDelay << uint(attempt-1)
+	if wait <= 0 || wait > connectRetryMaxDelay {
+		wait = connectRetryMaxDelay
+	}
+	if p.jitter > 0 {
+		wait += time.Duration(rand.Int63n(int64(p.jitter)))
+	}
+	return wait
+}
+
+// do 执行 fn，遇到连接数打满的错误时退避重试，其他错误直接返回
+// 退避等待期间 ctx 取消（暂停/停止）时立即返回 ctx.Err()，不再重试
+func (p *connectRetryPolicy) do(ctx context.Context, fn func() error) error {
+	err := fn()
+	if p == nil {
+		return err
+	}
+	for attempt := 1; attempt <= p.retries && isPoolPressureError(err); attempt++ {
+		wait := p.delay(attempt)
+		if p.onRetry != nil {
+			p.onRetry(attempt, p.retries, wait, err)
+		}
+		timer := time.NewTimer(wait)
+		select {
+		case <-ctx.Done():
+			timer.Stop()
+			return ctx.Err()
+		case <-timer.C:
+		}
+		err = fn()
+	}
+	return err
+}
+
+// newConnectRetry 创建同步单元使用的连接获取重试策略，重试时记录连接压力警告
+func (e *SyncEngine) newConnectRetry(taskID, unitName string, syncConfig SyncConfigParams) *connectRetryPolicy {
+	return newConnectRetryPolicy(syncConfig.ConnectRetries, syncConfig.ConnectRetryJitterMs,
+		func(attempt, retries int, wait time.Duration, err error) {
+			e.logService.Warning(taskID, fmt.Sprintf("表 %s 数据库连接数已满，%v 后第 %d/%d 次重试（可适当降低并发线程数）: %v",
+				unitName, wait.Round(time.Millisecond), attempt, retries, err))
+		})
+}
@@ -0,0 +1,86 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"testing"
+	"time"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+var errTooManyConnections = &mysql.MySQLError{Number: 1040, Message: "Too many connections"}
+
+func TestConnectRetryPolicyRetriesPoolPressure(t *testing.T) {
+	policy := &connectRetryPolicy{retries: 3}
+	calls := 0
+	err := policy.do(context.Background(), func() error {
+		calls++
+		if calls < 3 {
+			return errTooManyConnections
+		}
+		return nil
+	})
+	if err != nil || calls != 3 {
+		t.Errorf("do() = %v after %d calls, want nil after 3", err, calls)
+	}
+}
+
+func TestConnectRetryPolicyStopsAfterRetries(t *testing.T) {
+	var attempts []int
+	policy := newConnectRetryPolicy(2, 1, func(attempt, retries int, wait time.Duration, err error) {
+		attempts = append(attempts, attempt)
+	})
+	policy.jitter = 0
+	calls := 0
+	err := policy.do(context.Background(), func() error {
+		calls++
+		return errTooManyConnections
+	})
+	if !isPoolPressureError(err) || calls != 3 || len(attempts) != 2 {
+		t.Errorf("do() = %v, calls = %d, retries = %v, want the pool error after 1 call and 2 retries", err, calls, attempts)
+	}
+}
+
+func TestConnectRetryPolicyDoesNotRetryOtherErrors(t *testing.T) {
+	policy := &connectRetryPolicy{retries: 3}
+	denied := &mysql.MySQLError{Number: 1045, Message: "Access denied"}
+	calls := 0
+	err := policy.do(context.Background(), func() error {
+		calls++
+		return denied
+	})
+	if err != denied || calls != 1 {
+		t.Errorf("do() = %v after %d calls, want the original error after 1 call", err, calls)
+	}
+
+	// nil 策略表示不重试
+	var none *connectRetryPolicy
+	calls = 0
+	if err := none.do(context.Background(), func() error { calls++; return errTooManyConnections }); err == nil || calls != 1 {
+		t.Errorf("nil policy: do() = %v after %d calls, want the error after 1 call", err, calls)
+	}
+}
+
+func TestConnectRetryPolicyStopsWaitingWhenCanceled(t *testing.T) {
+	// 第一次退避至少 200ms，取消后应立即返回而不是等满退避时间
+	policy := &connectRetryPolicy{retries: 3}
+	ctx, cancel := context.WithCancel(context.Background())
+	policy.onRetry = func(attempt, retries int, wait time.Duration, err error) { cancel() }
+
+	calls := 0
+	start := time.Now()
+	err := policy.do(ctx, func() error {
+		calls++
+		return errTooManyConnections
+	})
+	if !errors.Is(err, context.Canceled) {
+		t.Fatalf("do() error = %v, want context.Canceled", err)
+	}
+	if calls != 1 {
+		t.Errorf("fn called %d times, want 1 (no retry after cancel)", calls)
+	}
+	if elapsed := time.Since(start); elapsed >= connectRetryBaseDelay {
+		t.Errorf("do() returned after %v, want before the %v backoff elapsed", elapsed, connectRetryBaseDelay)
+	}
+}
@@ -0,0 +1,111 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"time"
+)
+
+// DashboardStats 首页汇总统计
+type DashboardStats struct {
+	Window         string           `json:"window"` // today/7d/30d
+	Since          time.Time        `json:"since"`  // 统计窗口起点
+	TotalTasks     int64            `json:"total_tasks"`
+	RunningTasks   int64            `json:"running_tasks"`
+	TasksByStatus  map[string]int64 `json:"tasks_by_status"` // 配置状态 idle/configured -> 任务数
+	RowsSynced     int64            `json:"rows_synced"`     // 窗口内结束的单元已处理的行数
+	UnitsCompleted int64            `json:"units_completed"`
+	UnitsFailed    int64            `json:"units_failed"`
+	SuccessRate    float64          `json:"success_rate"` // 单元成功率（百分比），窗口内没有单元时为 0
+}
+
+// DashboardStatsService 首页汇总统计服务
+type DashboardStatsService struct{}
+
+// NewDashboardStatsService 创建首页汇总统计服务
+func NewDashboardStatsService() *DashboardStatsService {
+	return &DashboardStatsService{}
+}
+
+// dashboardWindowStart 统计窗口起点：today 为本地当天零点，7d/30d 为当前时间往前推
+func dashboardWindowStart(window string, now time.Time) (time.Time, error) {
+	switch window {
+	case "today":
+		return time.Date(now.Year(), now.Month(), now.Day(), 0, 0, 0, 0, now.Location()), nil
+	case "7d":
+		return now.AddDate(0, 0, -7), nil
+	case "30d":
+		return now.AddDate(0, 0, -30), nil
+	}
+	return time.Time{}, fmt.Errorf("window 只支持 today/7d/30d")
+}
+
+// GetStats 汇总任务数量和窗口内的单元执行情况，全部使用 SQL 聚合，不加载明细
+func (s *DashboardStatsService) GetStats(window string) (*DashboardStats, error) {
+	if window == "" {
+		window = "today"
+	}
+	since, err := dashboardWindowStart(window, time.Now())
+	if err != nil {
+		return nil, err
+	}
+
+	stats := &DashboardStats{
+		Window:        window,
+		Since:         since,
+		TasksByStatus: make(map[string]int64),
+	}
+
+	// 任务数量（按配置状态分组）
+	var statusCounts []struct {
+		Status string
+		Count  int64
+	}
+	if err := database.DB.Model(&models.SyncTask{}).
+		Select("status, COUNT(*) AS count").
+		Group("status").
+		Scan(&statusCounts).Error; err != nil {
+		return nil, fmt.Errorf("统计任务数量失败: %w", err)
+	}
+	for _, row := range statusCounts {
+		stats.TasksByStatus[row.Status] = row.Count
+		stats.TotalTasks += row.Count
+	}
+
+	if err := database.DB.Model(&models.SyncTask{}).
+		Where("is_running = ?", true).
+		Count(&stats.RunningTasks).Error; err != nil {
+		return nil, fmt.Errorf("统计运行中任务失败: %w", err)
+	}
+
+	// 窗口内结束的单元（按结果分组）
+	var unitCounts []struct {
+		Status        string
+		Count         int64
+		ProcessedRows int64
+	}
+	if err := database.DB.Model(&models.TaskUnitHistory{}).
+		Select("status, COUNT(*) AS count, COALESCE(SUM(processed_records), 0) AS processed_rows").
+		Where("completed_at >= ?", since).
+		Group("status").
+		Scan(&unitCounts).Error; err != nil {
+		return nil, fmt.Errorf("统计单元执行历史失败: %w", err)
+	}
+	for _, row := range unitCounts {
+		stats.RowsSynced += row.ProcessedRows
+		switch row.Status {
+		case "completed":
+			stats.UnitsCompleted += row.Count
+		case "failed":
+			stats.UnitsFailed += row.Count
+		}
+	}
+
+	if finished := stats.UnitsCompleted + stats.UnitsFailed; finished > 0 {
+		rate := float64(stats.UnitsCompleted) * 100 / float64(finished)
+		stats.SuccessRate = float64(int64(rate*10+0.5)) / 10
+	}
+
+	return stats, nil
+}
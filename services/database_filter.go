@@ -0,0 +1,91 @@
+package services
+
+import (
+	"fmt"
+	"path"
+	"strings"
+)
+
+// DefaultExcludedDatabases 默认排除的系统数据库
+var DefaultExcludedDatabases = []string{"information_schema", "mysql", "performance_schema", "sys"}
+
+// ResolveDatabaseExcludes 解析数据库排除规则
+// override 优先于数据源配置；两者都为 nil 时使用默认系统库；空字符串表示不排除任何库
+func ResolveDatabaseExcludes(dsExcludes, override *string) []string {
+	raw := dsExcludes
+	if override != nil {
+		raw = override
+	}
+	if raw == nil {
+		return DefaultExcludedDatabases
+	}
+	return parseGlobList(*raw)
+}
+
+// ValidateDatabaseExcludes 校验排除规则（逗号分隔的 glob 模式）
+func ValidateDatabaseExcludes(raw string) error {
+	for _, pattern := range parseGlobList(raw) {
+		if _, err := path.Match(pattern, ""); err != nil {
+			return fmt.Errorf("排除规则 %s 格式无效: %v", pattern, err)
+		}
+	}
+	return nil
+}
+
+// ParseDatabaseIncludes 解析包含规则（逗号分隔的 glob 模式），空字符串表示不限制
+func ParseDatabaseIncludes(raw string) ([]string, error) {
+	patterns := parseGlobList(raw)
+	for _, pattern := range patterns {
+		if _, err := path.Match(pattern, ""); err != nil {
+			return nil, fmt.Errorf("包含规则 %s 格式无效: %v", pattern, err)
+		}
+	}
+	return patterns, nil
+}
+
+// DatabaseFilter 数据库列表过滤条件
+type DatabaseFilter struct {
+	Excludes   []string // 排除的 glob 模式，见 ResolveDatabaseExcludes
+	Includes   []string // 只保留匹配的库（glob 模式），为空表示不限制
+	ShowSystem bool     // 显示系统库（不受排除规则影响）
+}
+
+// Allows 判断数据库是否出现在列表中：先按排除规则过滤（ShowSystem 时系统库不排除），再按包含规则过滤
+func (f DatabaseFilter) Allows(name string) bool {
+	if !(f.ShowSystem && isSystemDatabase(name)) && isDatabaseExcluded(name, f.Excludes) {
+		return false
+	}
+	return len(f.Includes) == 0 || isDatabaseExcluded(name, f.Includes)
+}
+
+// isSystemDatabase 是否为 MySQL 系统库
+func isSystemDatabase(name string) bool {
+	for _, system := range DefaultExcludedDatabases {
+		if strings.EqualFold(name, system) {
+			return true
+		}
+	}
+	return false
+}
+
+// isDatabaseExcluded 判断数据库是否命中排除规则
+func isDatabaseExcluded(name string, patterns []string) bool {
+	for _, pattern := range patterns {
+		if matched, _ := path.Match(pattern, name); matched {
+			return true
+		}
+	}
+	return false
+}
+
+// parseGlobList 解析逗号分隔的 glob 列表
+func parseGlobList(raw string) []string {
+	patterns := []string{}
+	for _, item := range strings.Split(raw, ",") {
+		item = strings.TrimSpace(item)
+		if item != "" {
+			patterns = append(patterns, item)
+		}
+	}
+	return patterns
+}
@@ -4,7 +4,9 @@ import (
 	"datatrace/database"
 	"datatrace/models"
 	"datatrace/utils"
+	"encoding/json"
 	"fmt"
+	"strings"
 
 	_ "github.com/go-sql-driver/mysql" // MySQL 驱动
 	"github.com/google/uuid"
@@ -24,14 +26,16 @@ func NewDataSourceService() *DataSourceService {
 
 // CreateDataSourceRequest 创建数据源请求
 type CreateDataSourceRequest struct {
-	Name         string  `json:"name" binding:"required"`
-	Type         string  `json:"type" binding:"required"`
-	Host         string  `json:"host" binding:"required"`
-	Port         int     `json:"port" binding:"required"`
-	CredentialID *string `json:"credential_id"` // 凭据ID（可选）
-	Username     string  `json:"username"`      // 用户名（凭据为空时必填）
-	Password     string  `json:"password"`      // 密码（凭据为空时必填）
-	DatabaseName string  `json:"database_name"`
+	Name             string  `json:"name" binding:"required"`
+	Type             string  `json:"type" binding:"required"`
+	Host             string  `json:"host"`          // 配置 socket_path 时可不填
+	Port             int     `json:"port"`          // 配置 socket_path 时可不填
+	SocketPath       string  `json:"socket_path"`   // MySQL unix socket 路径（可选）
+	CredentialID     *string `json:"credential_id"` // 凭据ID（可选）
+	Username         string  `json:"username"`      // 用户名（凭据为空时必填）
+	Password         string  `json:"password"`      // 密码（凭据为空时必填）
+	DatabaseName     string  `json:"database_name"`
+	ExcludeDatabases *string `json:"exclude_databases"` // 列表时排除的数据库 glob（逗号分隔），不传则排除系统库
 }
 
 // Create 创建数据源
@@ -50,13 +54,15 @@ func (s *DataSourceService) Create(req *CreateDataSourceRequest) (*models.DataSo
 
 	// 创建数据源
 	ds := &models.DataSource{
-		ID:           uuid.New().String(),
-		Name:         req.Name,
-		Type:         req.Type,
-		Host:         req.Host,
-		Port:         req.Port,
-		CredentialID: req.CredentialID,
-		DatabaseName: req.DatabaseName,
+		ID:               uuid.New().String(),
+		Name:             req.Name,
+		Type:             req.Type,
+		Host:             req.Host,
+		Port:             req.Port,
+		SocketPath:       req.SocketPath,
+		CredentialID:     req.CredentialID,
+		DatabaseName:     req.DatabaseName,
+		ExcludeDatabases: req.ExcludeDatabases,
 	}
 
 	// 如果使用凭据，验证凭据是否存在
@@ -130,8 +136,10 @@ func (s *DataSourceService) Update(id string, req *CreateDataSourceRequest) (*mo
 	ds.Type = req.Type
 	ds.Host = req.Host
 	ds.Port = req.Port
+	ds.SocketPath = req.SocketPath
 	ds.CredentialID = req.CredentialID
 	ds.DatabaseName = req.DatabaseName
+	ds.ExcludeDatabases = req.ExcludeDatabases
 
 	// 如果使用凭据，验证凭据是否存在
 	if req.CredentialID != nil && *req.CredentialID != "" {
@@ -161,24 +169,164 @@ func (s *DataSourceService) Update(id string, req *CreateDataSourceRequest) (*mo
 	return ds, nil
 }
 
-// Delete 删除数据源
+// DataSourceUsage 引用数据源的任务
+type DataSourceUsage struct {
+	TaskID    string `json:"task_id"`
+	TaskName  string `json:"task_name"`
+	Role      string `json:"role"` // source/target
+	IsRunning bool   `json:"is_running"`
+}
+
+// GetUsage 查询引用该数据源的任务（源端、兼容字段 target_id 以及配置中的多目标 target_ids）
+func (s *DataSourceService) GetUsage(id string) ([]DataSourceUsage, error) {
+	// config 是 JSON 文本，先用 LIKE 粗筛，再解析配置确认
+	var tasks []models.SyncTask
+	if err := database.DB.
+		Select("id", "name", "source_id", "target_id", "config", "is_running").
+		Where("source_id = ? OR target_id = ? OR config LIKE ?", id, id, "%"+id+"%").
+		Order("created_at ASC").
+		Find(&tasks).Error; err != nil {
+		return nil, fmt.Errorf("查询任务失败: %w", err)
+	}
+
+	usages := make([]DataSourceUsage, 0, len(tasks))
+	for _, task := range tasks {
+		role := taskDataSourceRole(task, id)
+		if role == "" {
+			continue
+		}
+		usages = append(usages, DataSourceUsage{
+			TaskID:    task.ID,
+			TaskName:  task.Name,
+			Role:      role,
+			IsRunning: task.IsRunning,
+		})
+	}
+	return usages, nil
+}
+
+// taskDataSourceRole 数据源在任务中的角色（source/target），未引用时返回空
+// 只按 ID 完全相等判断：LIKE 粗筛会命中配置中恰好包含该 ID 子串的任务（如其他数据源 ID、表名、查询文本）
+func taskDataSourceRole(task models.SyncTask, id string) string {
+	if id == "" {
+		return ""
+	}
+	if task.SourceID == id {
+		return "source"
+	}
+	if task.TargetID == id {
+		return "target"
+	}
+	var config TaskConfig
+	if err := json.Unmarshal([]byte(task.Config), &config); err != nil {
+		return ""
+	}
+	if config.SourceID == id {
+		return "source"
+	}
+	if config.TargetID == id {
+		return "target"
+	}
+	for _, targetID := range config.TargetIDs {
+		if targetID == id {
+			return "target"
+		}
+	}
+	return ""
+}
+
+// Delete 删除数据源（被任务引用时拒绝删除，错误信息中列出引用的任务）
 func (s *DataSourceService) Delete(id string) error {
-	// 检查是否被任务使用
-	var count int64
-	database.DB.Model(&models.SyncTask{}).
-		Where("source_id = ? OR target_id = ?", id, id).
-		Count(&count)
-	if count > 0 {
-		return fmt.Errorf("数据源正在被 %d 个任务使用，无法删除", count)
+	usages, err := s.GetUsage(id)
+	if err != nil {
+		return err
+	}
+	if len(usages) > 0 {
+		names := make([]string, 0, len(usages))
+		for _, usage := range usages {
+			names = append(names, usage.TaskName)
+		}
+		return fmt.Errorf("数据源正在被 %d 个任务使用，无法删除: %s", len(usages), strings.Join(names, ", "))
 	}
 
 	if err := database.DB.Delete(&models.DataSource{}, "id = ?", id).Error; err != nil {
 		return fmt.Errorf("删除失败: %w", err)
 	}
 
+	// 清理健康检查记录
+	database.DB.Delete(&models.DataSourceHealth{}, "data_source_id = ?", id)
+
+	return nil
+}
+
+// ResolveEnv 解析数据源 host/socket_path/database_name/username 中的 ${VAR} 环境变量占位符
+// 只修改内存中的对象，数据库中保留占位符；变量未设置时返回错误
+func (s *DataSourceService) ResolveEnv(ds *models.DataSource) error {
+	fields := []struct {
+		name  string
+		value *string
+	}{
+		{"host", &ds.Host},
+		{"socket_path", &ds.SocketPath},
+		{"database_name", &ds.DatabaseName},
+		{"username", &ds.Username},
+	}
+	for _, field := range fields {
+		resolved, err := utils.InterpolateEnv(*field.value)
+		if err != nil {
+			return fmt.Errorf("数据源 %s 的 %s 解析失败: %w", ds.Name, field.name, err)
+		}
+		*field.value = resolved
+	}
 	return nil
 }
 
+// ResolveConnection 准备数据源连接信息：解析 ${VAR} 占位符并返回解密后的密码（密码同样支持占位符）
+func (s *DataSourceService) ResolveConnection(ds *models.DataSource) (string, error) {
+	if err := s.ResolveEnv(ds); err != nil {
+		return "", err
+	}
+	password, err := s.crypto.Decrypt(ds.Password)
+	if err != nil {
+		return "", err
+	}
+	password, err = utils.InterpolateEnv(password)
+	if err != nil {
+		return "", fmt.Errorf("数据源 %s 的 password 解析失败: %w", ds.Name, err)
+	}
+	return password, nil
+}
+
+// ResolveCredentials 解析数据源的账号密码（优先使用凭据，否则使用数据源自己的账号密码）
+// 同时解析数据源中的 ${VAR} 环境变量占位符
+func (s *DataSourceService) ResolveCredentials(ds *models.DataSource) (username, password string, err error) {
+	if err := s.ResolveEnv(ds); err != nil {
+		return "", "", err
+	}
+	if ds.CredentialID != nil && *ds.CredentialID != "" {
+		credService := NewCredentialService()
+		credential, err := credService.GetByID(*ds.CredentialID)
+		if err != nil {
+			return "", "", fmt.Errorf("凭据不存在")
+		}
+		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
+		if err != nil {
+			return "", "", fmt.Errorf("凭据密码解密失败")
+		}
+		return credential.Username, password, nil
+	}
+
+	password, err = s.crypto.Decrypt(ds.Password)
+	if err != nil {
+		return "", "", fmt.Errorf("密码解密失败")
+	}
+	password, err = utils.InterpolateEnv(password)
+	if err != nil {
+		return "", "", fmt.Errorf("数据源 %s 的 password 解析失败: %w", ds.Name, err)
+	}
+	return ds.Username, password, nil
+}
+
 // validate 验证请求
 func (s *DataSourceService) validate(req *CreateDataSourceRequest) error {
 	if req.Name == "" {
@@ -187,12 +335,38 @@ func (s *DataSourceService) validate(req *CreateDataSourceRequest) error {
 	if req.Type != "mysql" && req.Type != "elasticsearch" {
 		return fmt.Errorf("数据源类型无效")
 	}
+	if req.SocketPath != "" {
+		if req.Type != "mysql" {
+			return fmt.Errorf("只有MySQL数据源支持 socket 连接")
+		}
+		if !strings.HasPrefix(req.SocketPath, "/") {
+			return fmt.Errorf("socket 路径必须为绝对路径")
+		}
+		// socket 连接不使用 host/port，未填写时补默认值用于展示
+		if req.Host == "" {
+			req.Host = "localhost"
+		}
+		if req.Port == 0 {
+			req.Port = 3306
+		}
+	}
 	if req.Host == "" {
 		return fmt.Errorf("主机地址不能为空")
 	}
+	if req.Type == "mysql" && strings.Contains(req.Host, ",") {
+		return fmt.Errorf("MySQL数据源只支持单个主机地址")
+	}
+	if req.Type == "elasticsearch" && len(elasticsearchNodes(req.Host, req.Port)) == 0 {
+		return fmt.Errorf("主机地址不能为空")
+	}
 	if req.Port <= 0 || req.Port > 65535 {
 		return fmt.Errorf("端口号无效")
 	}
+	if req.ExcludeDatabases != nil {
+		if err := ValidateDatabaseExcludes(*req.ExcludeDatabases); err != nil {
+			return err
+		}
+	}
 
 	// 如果没有使用凭据，则用户名和密码必填
 	if req.CredentialID == nil || *req.CredentialID == "" {
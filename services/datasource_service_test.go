@@ -0,0 +1,34 @@
+package services
+
+import (
+	"datatrace/models"
+	"testing"
+)
+
+func TestTaskDataSourceRole(t *testing.T) {
+	tests := []struct {
+		name string
+		task models.SyncTask
+		id   string
+		want string
+	}{
+		{"source column", models.SyncTask{SourceID: "ds-1"}, "ds-1", "source"},
+		{"target column", models.SyncTask{SourceID: "ds-2", TargetID: "ds-1"}, "ds-1", "target"},
+		{"config source", models.SyncTask{Config: `{"source_id":"ds-1"}`}, "ds-1", "source"},
+		{"config target", models.SyncTask{Config: `{"target_id":"ds-1"}`}, "ds-1", "target"},
+		{"config target_ids", models.SyncTask{SourceID: "ds-2", Config: `{"target_ids":["ds-3","ds-1"]}`}, "ds-1", "target"},
+		{"id is a substring of other ids", models.SyncTask{SourceID: "ds-10", TargetID: "ds-11",
+			Config: `{"source_id":"ds-10","target_ids":["ds-11","xds-1"]}`}, "ds-1", ""},
+		{"id only inside other config text", models.SyncTask{SourceID: "ds-2",
+			Config: `{"source_id":"ds-2","target_ids":["ds-3"],"sync_config":{"source_query":"SELECT 'ds-1'"}}`}, "ds-1", ""},
+		{"invalid config", models.SyncTask{SourceID: "ds-2", Config: `{"target_ids":["ds-1"]`}, "ds-1", ""},
+		{"empty id never matches", models.SyncTask{}, "", ""},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := taskDataSourceRole(tt.task, tt.id); got != tt.want {
+				t.Errorf("taskDataSourceRole(%q) = %q, want %q", tt.id, got, tt.want)
+			}
+		})
+	}
+}
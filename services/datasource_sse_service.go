@@ -1,6 +1,11 @@
 package services
 
 import (
+	"context"
+	"datatrace/config"
+	"datatrace/database"
+	"datatrace/models"
+	"datatrace/utils"
 	"encoding/json"
 	"fmt"
 	"sync"
@@ -8,8 +13,10 @@ import (
 )
 
 const (
-	// DataSourceCheckInterval 数据源健康检查间隔（秒）
+	// DataSourceCheckInterval 数据源健康检查默认间隔（秒）
 	DataSourceCheckInterval = 10
+	// DataSourceCheckTimeout 单个数据源健康检查默认超时（秒）
+	DataSourceCheckTimeout = 8
 )
 
 // DataSourceSSEService 数据源SSE服务
@@ -25,10 +32,11 @@ type DataSourceSSEService struct {
 
 // DataSourceTestResult 数据源测试结果
 type DataSourceTestResult struct {
-	ID        string    `json:"id"`
-	Status    string    `json:"status"`    // testing/success/failed
-	Message   string    `json:"message"`   // 错误信息或版本信息
-	Timestamp time.Time `json:"timestamp"` // 测试时间
+	ID        string               `json:"id"`
+	Status    string               `json:"status"`          // testing/success/failed
+	Message   string               `json:"message"`         // 错误信息或版本信息
+	Timestamp time.Time            `json:"timestamp"`       // 测试时间
+	Steps     []TestConnectionStep `json:"steps,omitempty"` // 分步测试结果（MySQL）
 }
 
 var (
@@ -65,7 +73,7 @@ func (s *DataSourceSSEService) StartHealthCheck() {
 
 // healthCheckLoop 后台健康检查循环
 func (s *DataSourceSSEService) healthCheckLoop() {
-	ticker := time.NewTicker(DataSourceCheckInterval * time.Second)
+	ticker := time.NewTicker(healthCheckInterval())
 	defer func() {
 		ticker.Stop()
 		s.mu.Lock()
@@ -161,9 +169,18 @@ func (s *DataSourceSSEService) broadcast(results []DataSourceTestResult) {
 	}
 }
 
-// testSingleDataSource 测试单个数据源
+// testSingleDataSource 测试单个数据源（带超时，宕机主机不会拖住整个检查循环）
+// 超时通过 context 传给连接测试，到期后拨号、认证和 HTTP 请求随之中止，不会遗留仍在探测的 goroutine
 func (s *DataSourceSSEService) testSingleDataSource(dsID string) DataSourceTestResult {
-	testResult, err := s.dsService.TestConnectionByID(dsID)
+	start := time.Now()
+	timeout := healthCheckTimeout()
+	ctx, cancel := context.WithTimeout(context.Background(), timeout)
+	defer cancel()
+
+	testResult, err := s.dsService.TestConnectionByID(ctx, dsID)
+	if ctx.Err() == context.DeadlineExceeded {
+		testResult, err = nil, fmt.Errorf("检查超时（%s）", timeout)
+	}
 
 	status := "failed"
 	message := "连接失败"
@@ -180,15 +197,131 @@ func (s *DataSourceSSEService) testSingleDataSource(dsID string) DataSourceTestR
 		message = testResult.Message
 	}
 
-	return DataSourceTestResult{
+	result := DataSourceTestResult{
 		ID:        dsID,
 		Status:    status,
 		Message:   message,
 		Timestamp: time.Now(),
 	}
+	if testResult != nil {
+		result.Steps = testResult.Steps
+	}
+	s.saveHealth(result, time.Since(start))
+
+	return result
+}
+
+// saveHealth 持久化健康检查结果到 datasource_health 表
+func (s *DataSourceSSEService) saveHealth(result DataSourceTestResult, latency time.Duration) {
+	var health models.DataSourceHealth
+	if err := database.DB.First(&health, "data_source_id = ?", result.ID).Error; err != nil {
+		health = models.DataSourceHealth{DataSourceID: result.ID}
+	}
+
+	health.Status = result.Status
+	health.Message = utils.RedactSecrets(result.Message)
+	if runes := []rune(health.Message); len(runes) > 500 {
+		health.Message = string(runes[:500])
+	}
+	health.LatencyMs = latency.Milliseconds()
+	health.LastCheckedAt = result.Timestamp
+	if result.Status == "success" {
+		checkedAt := result.Timestamp
+		health.LastSuccessAt = &checkedAt
+	}
+	health.Steps = ""
+	if len(result.Steps) > 0 {
+		steps := make([]TestConnectionStep, len(result.Steps))
+		for i, step := range result.Steps {
+			step.Message = utils.RedactSecrets(step.Message)
+			steps[i] = step
+		}
+		if data, err := json.Marshal(steps); err == nil {
+			health.Steps = string(data)
+		}
+	}
+
+	database.DB.Save(&health)
+}
+
+// healthToResult 把持久化的健康状态转换为推送给前端的测试结果
+func healthToResult(health models.DataSourceHealth) DataSourceTestResult {
+	result := DataSourceTestResult{
+		ID:        health.DataSourceID,
+		Status:    health.Status,
+		Message:   health.Message,
+		Timestamp: health.LastCheckedAt,
+	}
+	if health.Steps != "" {
+		json.Unmarshal([]byte(health.Steps), &result.Steps)
+	}
+	return result
+}
+
+// TestAll 立即测试所有数据源（手动全部测试）
+// 开始时推送 testing 状态，每个数据源完成后实时推送结果，最终结果写入 datasource_health
+func (s *DataSourceSSEService) TestAll() ([]DataSourceTestResult, error) {
+	dataSources, err := s.dsService.List()
+	if err != nil {
+		return nil, err
+	}
+
+	testing := make([]DataSourceTestResult, 0, len(dataSources))
+	for _, ds := range dataSources {
+		testing = append(testing, DataSourceTestResult{ID: ds.ID, Status: "testing", Timestamp: time.Now()})
+	}
+	if len(testing) > 0 {
+		s.broadcast(testing)
+	}
+
+	results := make([]DataSourceTestResult, len(dataSources))
+	var wg sync.WaitGroup
+	for i, ds := range dataSources {
+		wg.Add(1)
+		go func(i int, dsID string) {
+			defer wg.Done()
+			result := s.testSingleDataSource(dsID)
+
+			s.resultMutex.Lock()
+			s.lastResults[dsID] = result
+			s.resultMutex.Unlock()
+
+			s.broadcast([]DataSourceTestResult{result})
+			results[i] = result
+		}(i, ds.ID)
+	}
+	wg.Wait()
+
+	return results, nil
+}
+
+// GetHealthList 获取所有数据源的健康状态
+func (s *DataSourceSSEService) GetHealthList() ([]models.DataSourceHealth, error) {
+	var list []models.DataSourceHealth
+	if err := database.DB.Order("data_source_id").Find(&list).Error; err != nil {
+		return nil, err
+	}
+	return list, nil
+}
+
+// healthCheckInterval 健康检查间隔
+func healthCheckInterval() time.Duration {
+	if config.GlobalConfig != nil && config.GlobalConfig.HealthCheck.Interval > 0 {
+		return time.Duration(config.GlobalConfig.HealthCheck.Interval) * time.Second
+	}
+	return DataSourceCheckInterval * time.Second
+}
+
+// healthCheckTimeout 单个数据源健康检查超时
+func healthCheckTimeout() time.Duration {
+	if config.GlobalConfig != nil && config.GlobalConfig.HealthCheck.Timeout > 0 {
+		return time.Duration(config.GlobalConfig.HealthCheck.Timeout) * time.Second
+	}
+	return DataSourceCheckTimeout * time.Second
 }
 
 // SendCachedResults 发送缓存的测试结果给新客户端（批量发送）
+// 重启后首轮检查完成前内存缓存为空，此时发送 datasource_health 中持久化的最近结果
 func (s *DataSourceSSEService) SendCachedResults(client chan []DataSourceTestResult) {
 	s.resultMutex.RLock()
 	results := make([]DataSourceTestResult, 0, len(s.lastResults))
@@ -197,6 +330,14 @@ func (s *DataSourceSSEService) SendCachedResults(client chan []DataSourceTestRes
 	}
 	s.resultMutex.RUnlock()
 
+	if len(results) == 0 {
+		if list, err := s.GetHealthList(); err == nil {
+			for _, health := range list {
+				results = append(results, healthToResult(health))
+			}
+		}
+	}
+
 	if len(results) > 0 {
 		go func() {
 			defer func() {
@@ -4,16 +4,23 @@ import (
 	"context"
 	"crypto/tls"
 	"database/sql"
+	"datatrace/utils"
 	"fmt"
+	"net"
 	"net/http"
+	"os"
+	"regexp"
+	"strconv"
+	"strings"
 	"time"
 )
 
 // TestConnectionRequest 测试连接请求
 type TestConnectionRequest struct {
 	Type         string  `json:"type" binding:"required"`
-	Host         string  `json:"host" binding:"required"`
-	Port         int     `json:"port" binding:"required"`
+	Host         string  `json:"host"`          // 配置 socket_path 时可不填
+	Port         int     `json:"port"`          // 配置 socket_path 时可不填
+	SocketPath   string  `json:"socket_path"`   // MySQL unix socket 路径（可选）
 	Username     string  `json:"username"`      // 手动输入时必填
 	Password     string  `json:"password"`      // 手动输入时必填
 	CredentialID *string `json:"credential_id"` // 使用凭据时传此字段，与 username/password 二选一
@@ -23,13 +30,23 @@ type TestConnectionRequest struct {
 
 // TestConnectionResponse 测试连接响应
 type TestConnectionResponse struct {
-	Success bool   `json:"success"`
-	Version string `json:"version"`
-	Message string `json:"message"`
+	Success bool                 `json:"success"`
+	Version string               `json:"version"`
+	Message string               `json:"message"`
+	Steps   []TestConnectionStep `json:"steps,omitempty"` // 分步测试结果（MySQL 分步骤，Elasticsearch 多节点时按节点）
+	Probe   *ProbeQueryResult    `json:"probe,omitempty"` // 探测查询结果（TestConnectionWithQuery）
 }
 
-// TestConnection 测试数据源连接
-func (s *DataSourceService) TestConnection(req *TestConnectionRequest) (*TestConnectionResponse, error) {
+// TestConnectionStep 连接测试步骤结果
+type TestConnectionStep struct {
+	Step    int    `json:"step"`
+	Name    string `json:"name"`    // port/credentials/database，Elasticsearch 为 node
+	Status  string `json:"status"`  // success/failed/skipped
+	Message string `json:"message"` // 结果说明
+}
+
+// TestConnection 测试数据源连接，ctx 取消或超时后各步骤的网络操作随之中止
+func (s *DataSourceService) TestConnection(ctx context.Context, req *TestConnectionRequest) (*TestConnectionResponse, error) {
 	// 若使用凭据，先解析出 username/password
 	if req.CredentialID != nil && *req.CredentialID != "" {
 		credService := NewCredentialService()
@@ -44,96 +61,108 @@ func (s *DataSourceService) TestConnection(req *TestConnectionRequest) (*TestCon
 		req.Username = credential.Username
 		req.Password = password
 	}
+	// 解析 ${VAR} 环境变量占位符
+	for _, value := range []*string{&req.Host, &req.SocketPath, &req.DatabaseName, &req.Username, &req.Password} {
+		resolved, err := utils.InterpolateEnv(*value)
+		if err != nil {
+			return &TestConnectionResponse{Success: false, Message: err.Error()}, nil
+		}
+		*value = resolved
+	}
 	if req.Username == "" || req.Password == "" {
 		return &TestConnectionResponse{Success: false, Message: "请选择凭据或填写用户名和密码"}, nil
 	}
+	if req.SocketPath == "" && (req.Host == "" || req.Port <= 0) {
+		return &TestConnectionResponse{Success: false, Message: "请填写主机地址和端口"}, nil
+	}
 	switch req.Type {
 	case "mysql":
-		return s.testMySQLConnection(req)
+		return s.testMySQLConnection(ctx, req)
 	case "elasticsearch":
-		return s.testElasticsearchConnection(req)
+		return s.testElasticsearchConnection(ctx, req)
 	default:
 		return nil, fmt.Errorf("不支持的数据源类型: %s", req.Type)
 	}
 }
 
 // TestConnectionByID 根据ID测试数据源连接
-func (s *DataSourceService) TestConnectionByID(id string) (*TestConnectionResponse, error) {
+func (s *DataSourceService) TestConnectionByID(ctx context.Context, id string) (*TestConnectionResponse, error) {
 	ds, err := s.GetByID(id)
 	if err != nil {
 		return nil, fmt.Errorf("数据源不存在")
 	}
 
-	var username, password string
-
-	// 如果使用凭据，从凭据获取账号密码
-	if ds.CredentialID != nil && *ds.CredentialID != "" {
-		credService := NewCredentialService()
-		credential, err := credService.GetByID(*ds.CredentialID)
-		if err != nil {
-			return &TestConnectionResponse{
-				Success: false,
-				Message: "凭据不存在",
-			}, nil
-		}
-
-		username = credential.Username
-		password, err = credService.GetDecryptedPassword(*ds.CredentialID)
-		if err != nil {
-			return &TestConnectionResponse{
-				Success: false,
-				Message: "凭据密码解密失败",
-			}, nil
-		}
-	} else {
-		// 使用数据源自己的账号密码
-		username = ds.Username
-		password, err = s.crypto.Decrypt(ds.Password)
-		if err != nil {
-			return &TestConnectionResponse{
-				Success: false,
-				Message: "密码解密失败",
-			}, nil
-		}
+	username, password, err := s.ResolveCredentials(ds)
+	if err != nil {
+		return &TestConnectionResponse{
+			Success: false,
+			Message: err.Error(),
+		}, nil
 	}
 
 	req := &TestConnectionRequest{
 		Type:         ds.Type,
 		Host:         ds.Host,
 		Port:         ds.Port,
+		SocketPath:   ds.SocketPath,
 		Username:     username,
 		Password:     password,
 		DatabaseName: ds.DatabaseName,
 	}
 
-	return s.TestConnection(req)
+	return s.TestConnection(ctx, req)
 }
 
 // testMySQLConnection 测试 MySQL 连接
-func (s *DataSourceService) testMySQLConnection(req *TestConnectionRequest) (*TestConnectionResponse, error) {
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/", req.Username, req.Password, req.Host, req.Port)
-	if req.DatabaseName != "" {
-		dsn += req.DatabaseName
+// 步骤1：端口连通性；步骤2：账号密码；步骤3：数据库是否存在（未配置数据库时跳过）
+func (s *DataSourceService) testMySQLConnection(ctx context.Context, req *TestConnectionRequest) (*TestConnectionResponse, error) {
+	resp := &TestConnectionResponse{}
+
+	fail := func(step int, name, message string) (*TestConnectionResponse, error) {
+		resp.Steps = append(resp.Steps, TestConnectionStep{Step: step, Name: name, Status: "failed", Message: message})
+		resp.Success = false
+		resp.Message = message
+		return resp, nil
 	}
-	dsn += "?charset=utf8mb4&parseTime=True&loc=Local&timeout=5s"
+
+	// 步骤1：端口连通性（socket 连接改为检查 socket 文件）
+	host := req.Host
+	if req.SocketPath != "" {
+		host = req.SocketPath
+		info, err := os.Stat(req.SocketPath)
+		if err != nil {
+			return fail(1, "port", fmt.Sprintf("socket 文件不可用: %v", err))
+		}
+		if info.Mode()&os.ModeSocket == 0 {
+			return fail(1, "port", fmt.Sprintf("%s 不是 socket 文件", req.SocketPath))
+		}
+		resp.Steps = append(resp.Steps, TestConnectionStep{Step: 1, Name: "port", Status: "success", Message: "socket 文件存在"})
+	} else {
+		addr := net.JoinHostPort(req.Host, strconv.Itoa(req.Port))
+		dialer := net.Dialer{Timeout: 5 * time.Second}
+		conn, err := dialer.DialContext(ctx, "tcp", addr)
+		if err != nil {
+			return fail(1, "port", fmt.Sprintf("端口不可达: %v", err))
+		}
+		conn.Close()
+		resp.Steps = append(resp.Steps, TestConnectionStep{Step: 1, Name: "port", Status: "success", Message: "端口可达"})
+	}
+
+	// 步骤2：账号密码（不指定数据库，避免把数据库不存在误报为认证失败）
+	dsn := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local&timeout=5s",
+		req.Username, req.Password, mysqlNetAddr(host, req.Port))
 
 	db, err := sql.Open("mysql", dsn)
 	if err != nil {
-		return &TestConnectionResponse{
-			Success: false,
-			Message: fmt.Sprintf("连接失败: %v", err),
-		}, nil
+		return fail(2, "credentials", fmt.Sprintf("连接失败: %v", err))
 	}
 	defer db.Close()
 
-	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	ctx, cancel := context.WithTimeout(ctx, 5*time.Second)
 	defer cancel()
 
 	if err := db.PingContext(ctx); err != nil {
-		return &TestConnectionResponse{
-			Success: false,
-			Message: fmt.Sprintf("连接失败: %v", err),
-		}, nil
+		return fail(2, "credentials", fmt.Sprintf("连接失败: %v", err))
 	}
 
 	var version string
@@ -141,22 +170,91 @@ func (s *DataSourceService) testMySQLConnection(req *TestConnectionRequest) (*Te
 	if err != nil {
 		version = "未知"
 	}
+	resp.Version = version
+	resp.Steps = append(resp.Steps, TestConnectionStep{Step: 2, Name: "credentials", Status: "success", Message: "认证成功"})
+
+	// 步骤3：数据库是否存在
+	if req.DatabaseName == "" {
+		resp.Steps = append(resp.Steps, TestConnectionStep{Step: 3, Name: "database", Status: "skipped", Message: "未配置数据库，跳过"})
+	} else {
+		var count int
+		err := db.QueryRowContext(ctx, "SELECT COUNT(*) FROM information_schema.SCHEMATA WHERE SCHEMA_NAME = ?", req.DatabaseName).Scan(&count)
+		if err != nil {
+			return fail(3, "database", fmt.Sprintf("检查数据库失败: %v", err))
+		}
+		if count == 0 {
+			return fail(3, "database", fmt.Sprintf("数据库 %s 不存在", req.DatabaseName))
+		}
+		resp.Steps = append(resp.Steps, TestConnectionStep{Step: 3, Name: "database", Status: "success", Message: fmt.Sprintf("数据库 %s 存在", req.DatabaseName)})
+	}
+
+	resp.Success = true
+	resp.Message = "连接成功"
+	return resp, nil
+}
 
-	return &TestConnectionResponse{
-		Success: true,
-		Version: version,
-		Message: "连接成功",
-	}, nil
+// elasticsearchNodes 解析 Elasticsearch 节点列表：host 可填写逗号分隔的多个节点（如 es1,es2:9201），未写端口的节点使用 port
+func elasticsearchNodes(host string, port int) []string {
+	var nodes []string
+	for _, node := range strings.Split(host, ",") {
+		node = strings.TrimSpace(node)
+		if node == "" {
+			continue
+		}
+		if _, _, err := net.SplitHostPort(node); err != nil {
+			node = net.JoinHostPort(node, strconv.Itoa(port))
+		}
+		nodes = append(nodes, node)
+	}
+	return nodes
 }
 
 // testElasticsearchConnection 测试 Elasticsearch 连接
-func (s *DataSourceService) testElasticsearchConnection(req *TestConnectionRequest) (*TestConnectionResponse, error) {
+// 配置多个节点时逐个测试，每个节点的结果作为一个步骤返回，至少一个节点可用即视为连接成功
+func (s *DataSourceService) testElasticsearchConnection(ctx context.Context, req *TestConnectionRequest) (*TestConnectionResponse, error) {
+	nodes := elasticsearchNodes(req.Host, req.Port)
+	if len(nodes) == 1 {
+		if err := pingElasticsearchNode(ctx, nodes[0], req); err != nil {
+			return &TestConnectionResponse{Success: false, Message: err.Error()}, nil
+		}
+		return &TestConnectionResponse{Success: true, Version: "已连接", Message: "连接成功"}, nil
+	}
+
+	resp := &TestConnectionResponse{}
+	var failed []string
+	for i, node := range nodes {
+		step := TestConnectionStep{Step: i + 1, Name: "node", Status: "success", Message: fmt.Sprintf("节点 %s 连接成功", node)}
+		if err := pingElasticsearchNode(ctx, node, req); err != nil {
+			step.Status = "failed"
+			step.Message = fmt.Sprintf("节点 %s %v", node, err)
+			failed = append(failed, node)
+		}
+		resp.Steps = append(resp.Steps, step)
+	}
+
+	available := len(nodes) - len(failed)
+	resp.Success = available > 0
+	switch {
+	case available == 0:
+		resp.Message = fmt.Sprintf("全部 %d 个节点连接失败", len(nodes))
+	case len(failed) > 0:
+		resp.Version = "已连接"
+		resp.Message = fmt.Sprintf("%d/%d 个节点可用，不可用节点: %s", available, len(nodes), strings.Join(failed, ", "))
+	default:
+		resp.Version = "已连接"
+		resp.Message = fmt.Sprintf("全部 %d 个节点连接成功", len(nodes))
+	}
+	return resp, nil
+}
+
+// pingElasticsearchNode 请求单个 Elasticsearch 节点的根路径，返回连接失败的原因
+func pingElasticsearchNode(ctx context.Context, node string, req *TestConnectionRequest) error {
 	// 根据 UseSSL 选择协议
 	scheme := "http"
 	if req.UseSSL {
 		scheme = "https"
 	}
-	url := fmt.Sprintf("%s://%s:%d", scheme, req.Host, req.Port)
+	url := fmt.Sprintf("%s://%s", scheme, node)
 
 	// 创建 HTTP 客户端（支持自签名证书）
 	client := &http.Client{
@@ -166,35 +264,174 @@ func (s *DataSourceService) testElasticsearchConnection(req *TestConnectionReque
 		},
 	}
 
-	httpReq, err := http.NewRequest("GET", url, nil)
+	httpReq, err := http.NewRequestWithContext(ctx, "GET", url, nil)
 	if err != nil {
-		return &TestConnectionResponse{
-			Success: false,
-			Message: fmt.Sprintf("创建请求失败: %v", err),
-		}, nil
+		return fmt.Errorf("创建请求失败: %v", err)
 	}
 
 	httpReq.SetBasicAuth(req.Username, req.Password)
 
 	resp, err := client.Do(httpReq)
 	if err != nil {
-		return &TestConnectionResponse{
-			Success: false,
-			Message: fmt.Sprintf("连接失败: %v", err),
-		}, nil
+		return fmt.Errorf("连接失败: %v", err)
 	}
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
-		return &TestConnectionResponse{
-			Success: false,
-			Message: fmt.Sprintf("连接失败: HTTP %d", resp.StatusCode),
-		}, nil
+		return fmt.Errorf("连接失败: HTTP %d", resp.StatusCode)
+	}
+	return nil
+}
+
+// ProbeQueryResult 探测查询结果
+type ProbeQueryResult struct {
+	Columns   []string                 `json:"columns"`
+	Rows      []map[string]interface{} `json:"rows"`      // 样例行（最多 probeSampleRows 行）
+	RowCount  int                      `json:"row_count"` // 读取到的行数（最多 probeMaxRows）
+	Truncated bool                     `json:"truncated"` // 是否超过 probeMaxRows 被截断
+	ElapsedMs int64                    `json:"elapsed_ms"`
+}
+
+const (
+	probeQueryTimeout = 10 * time.Second
+	probeSampleRows   = 10
+	probeMaxRows      = 1000
+)
+
+var (
+	// 探测查询只允许以这些关键字开头
+	probeAllowedPrefix = regexp.MustCompile(`(?i)^\s*(SELECT|SHOW|DESC|DESCRIBE|EXPLAIN|WITH)\b`)
+	// 探测查询中禁止出现的写操作关键字
+	probeForbiddenKeyword = regexp.MustCompile(`(?i)\b(INSERT|UPDATE|DELETE|DROP|ALTER|CREATE|TRUNCATE|REPLACE|RENAME|GRANT|REVOKE|LOCK|UNLOCK|CALL|LOAD|HANDLER|OUTFILE|DUMPFILE)\b`)
+)
+
+// validateProbeQuery 校验探测查询为单条只读语句
+func validateProbeQuery(query string) (string, error) {
+	query = strings.TrimSpace(query)
+	query = strings.TrimSpace(strings.TrimRight(query, ";"))
+	if query == "" {
+		return "", fmt.Errorf("探测查询不能为空")
+	}
+	if strings.Contains(query, ";") {
+		return "", fmt.Errorf("探测查询只能包含一条语句")
+	}
+	if !probeAllowedPrefix.MatchString(query) {
+		return "", fmt.Errorf("探测查询只允许 SELECT/SHOW/DESC/EXPLAIN 等只读语句")
+	}
+	if kw := probeForbiddenKeyword.FindString(query); kw != "" {
+		return "", fmt.Errorf("探测查询不允许包含 %s", strings.ToUpper(kw))
+	}
+	return query, nil
+}
+
+// TestConnectionWithQuery 测试连接后执行用户提供的只读探测查询（仅MySQL）
+// 用于验证账号对目标库/表是否真正有查询权限，结果作为第 4 步返回
+func (s *DataSourceService) TestConnectionWithQuery(ctx context.Context, id, query string) (*TestConnectionResponse, error) {
+	query, err := validateProbeQuery(query)
+	if err != nil {
+		return nil, err
+	}
+
+	ds, err := s.GetByID(id)
+	if err != nil {
+		return nil, fmt.Errorf("数据源不存在")
+	}
+	if ds.Type != "mysql" {
+		return nil, fmt.Errorf("只有MySQL数据源支持探测查询")
+	}
+
+	// 先执行标准测试步骤
+	resp, err := s.TestConnectionByID(ctx, id)
+	if err != nil || !resp.Success {
+		return resp, err
+	}
+
+	username, password, err := s.ResolveCredentials(ds)
+	if err != nil {
+		return nil, err
+	}
+
+	step := TestConnectionStep{Step: len(resp.Steps) + 1, Name: "probe_query"}
+	probe, err := runProbeQuery(ctx, ds.MySQLHost(), ds.Port, username, password, ds.DatabaseName, query)
+	if err != nil {
+		step.Status = "failed"
+		step.Message = fmt.Sprintf("探测查询失败: %v", err)
+		resp.Steps = append(resp.Steps, step)
+		resp.Success = false
+		resp.Message = step.Message
+		return resp, nil
+	}
+
+	step.Status = "success"
+	step.Message = fmt.Sprintf("探测查询成功: %d 列, %d 行", len(probe.Columns), probe.RowCount)
+	if probe.Truncated {
+		step.Message += "（已截断）"
+	}
+	resp.Steps = append(resp.Steps, step)
+	resp.Probe = probe
+	return resp, nil
+}
+
+// runProbeQuery 在只读事务中执行探测查询（带超时）
+func runProbeQuery(ctx context.Context, host string, port int, username, password, database, query string) (*ProbeQueryResult, error) {
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=5s",
+		username, password, mysqlNetAddr(host, port), database)
+
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		return nil, err
+	}
+	defer db.Close()
+
+	ctx, cancel := context.WithTimeout(ctx, probeQueryTimeout)
+	defer cancel()
+
+	start := time.Now()
+	tx, err := db.BeginTx(ctx, &sql.TxOptions{ReadOnly: true})
+	if err != nil {
+		return nil, err
+	}
+	defer tx.Rollback()
+
+	rows, err := tx.QueryContext(ctx, query)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	columns, err := rows.Columns()
+	if err != nil {
+		return nil, err
+	}
+
+	result := &ProbeQueryResult{Columns: columns, Rows: make([]map[string]interface{}, 0, probeSampleRows)}
+	for rows.Next() {
+		if result.RowCount >= probeMaxRows {
+			result.Truncated = true
+			break
+		}
+		result.RowCount++
+
+		values := make([]interface{}, len(columns))
+		valuePtrs := make([]interface{}, len(columns))
+		for i := range values {
+			valuePtrs[i] = &values[i]
+		}
+		if err := rows.Scan(valuePtrs...); err != nil {
+			return nil, err
+		}
+		if len(result.Rows) < probeSampleRows {
+			row := make(map[string]interface{}, len(columns))
+			for i, col := range columns {
+				row[col] = normalizeValue(values[i])
+			}
+			result.Rows = append(result.Rows, row)
+		}
+	}
+	if err := rows.Err(); err != nil {
+		return nil, err
 	}
 
-	return &TestConnectionResponse{
-		Success: true,
-		Version: "已连接",
-		Message: "连接成功",
-	}, nil
+	result.ElapsedMs = time.Since(start).Milliseconds()
+	return result, nil
 }
@@ -0,0 +1,94 @@
+package services
+
+import (
+	"context"
+	"net"
+	"net/http"
+	"net/http/httptest"
+	"strconv"
+	"testing"
+	"time"
+)
+
+// probeDeadline 测试使用的探测超时，远小于各步骤自身 5 秒的超时
+const probeDeadline = 100 * time.Millisecond
+
+func TestMySQLConnectionTestHonorsContextDeadline(t *testing.T) {
+	// 接受连接但从不发送握手包的服务端：端口可达，认证步骤会一直等待
+	listener, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatalf("listen: %v", err)
+	}
+	defer listener.Close()
+	go func() {
+		var conns []net.Conn
+		defer func() {
+			for _, conn := range conns {
+				conn.Close()
+			}
+		}()
+		for {
+			conn, err := listener.Accept()
+			if err != nil {
+				return
+			}
+			conns = append(conns, conn)
+		}
+	}()
+	host, port, _ := net.SplitHostPort(listener.Addr().String())
+	portNum, _ := strconv.Atoi(port)
+
+	ctx, cancel := context.WithTimeout(context.Background(), probeDeadline)
+	defer cancel()
+	start := time.Now()
+	resp, err := (&DataSourceService{}).TestConnection(ctx, &TestConnectionRequest{
+		Type: "mysql", Host: host, Port: portNum, Username: "u", Password: "p",
+	})
+	if elapsed := time.Since(start); elapsed > 3*time.Second {
+		t.Fatalf("TestConnection() took %v after the context deadline", elapsed)
+	}
+	if err != nil || resp.Success {
+		t.Fatalf("TestConnection() = %+v, %v, want a failed response", resp, err)
+	}
+	if len(resp.Steps) != 2 || resp.Steps[1].Name != "credentials" || resp.Steps[1].Status != "failed" {
+		t.Errorf("steps = %+v, want port success then credentials failed", resp.Steps)
+	}
+}
+
+func TestMySQLConnectionTestCanceledBeforeDial(t *testing.T) {
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+	resp, err := (&DataSourceService{}).TestConnection(ctx, &TestConnectionRequest{
+		Type: "mysql", Host: "10.255.255.1", Port: 3306, Username: "u", Password: "p",
+	})
+	if err != nil || resp.Success || len(resp.Steps) != 1 || resp.Steps[0].Name != "port" {
+		t.Errorf("TestConnection() = %+v, %v, want the port step to fail", resp, err)
+	}
+}
+
+func TestElasticsearchConnectionTestHonorsContextDeadline(t *testing.T) {
+	release := make(chan struct{})
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		select {
+		case <-release:
+		case <-r.Context().Done():
+		}
+	}))
+	defer server.Close()
+	defer close(release)
+	host, port, _ := net.SplitHostPort(server.Listener.Addr().String())
+	portNum, _ := strconv.Atoi(port)
+
+	ctx, cancel := context.WithTimeout(context.Background(), probeDeadline)
+	defer cancel()
+	start := time.Now()
+	resp, err := (&DataSourceService{}).TestConnection(ctx, &TestConnectionRequest{
+		Type: "elasticsearch", Host: host, Port: portNum, Username: "u", Password: "p",
+	})
+	if elapsed := time.Since(start); elapsed > 3*time.Second {
+		t.Fatalf("TestConnection() took %v after the context deadline", elapsed)
+	}
+	if err != nil || resp.Success {
+		t.Errorf("TestConnection() = %+v, %v, want a failed response", resp, err)
+	}
+}
@@ -0,0 +1,49 @@
+package services
+
+import (
+	"fmt"
+	"sync/atomic"
+)
+
+// 写入时主键/唯一键冲突的处理方式
+const (
+	DuplicateKeyStrict = "strict" // 冲突时整个批次写入失败，按 error_strategy 暂停或跳过该批次（默认）
+	DuplicateKeyIgnore = "ignore" // 只忽略冲突的行（保留目标已有数据），批次中其余行照常写入
+)
+
+// ValidateDuplicateKeyMode 校验主键冲突处理方式，空值表示默认的 strict
+func ValidateDuplicateKeyMode(mode string) error {
+	switch mode {
+	case "", DuplicateKeyStrict, DuplicateKeyIgnore:
+		return nil
+	}
+	return fmt.Errorf("duplicate_key_mode 只支持 %s/%s", DuplicateKeyStrict, DuplicateKeyIgnore)
+}
+
+// duplicateKeyClause ignore 模式下追加到 INSERT 的子句：冲突时把第一列赋值为已有值，不修改目标行
+// 与 INSERT IGNORE 不同，数据截断、类型错误等其他错误仍然报错，只有冲突的行被忽略
+func duplicateKeyClause(column string) string {
+	return fmt.Sprintf(" ON DUPLICATE KEY UPDATE `%s` = `%s`", column, column)
+}
+
+// duplicateKeyStats 单元同一目标忽略的冲突行数（多个分片 Writer 共享）
+type duplicateKeyStats struct {
+	ignored int64
+}
+
+// newDuplicateIgnoredHook 创建忽略冲突行时的回调：每次写入记录忽略的行数，并累加到 stats
+func (e *SyncEngine) newDuplicateIgnoredHook(taskID, targetName, unitName string, stats *duplicateKeyStats) func(rows int64) {
+	return func(rows int64) {
+		total := atomic.AddInt64(&stats.ignored, rows)
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 批次忽略 %d 行主键/唯一键冲突（累计 %d 行）",
+			targetName, unitName, rows, total), "sync")
+	}
+}
+
+// logDuplicateKeys 单元同步结束后汇总忽略的冲突行数
+func (e *SyncEngine) logDuplicateKeys(taskID, targetName, unitName string, stats *duplicateKeyStats) {
+	if total := atomic.LoadInt64(&stats.ignored); total > 0 {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 共忽略 %d 行主键/唯一键冲突（duplicate_key_mode=ignore，目标保留原有数据）",
+			targetName, unitName, total))
+	}
+}
@@ -0,0 +1,74 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/models"
+	"fmt"
+)
+
+// 源表为空时的处理方式
+const (
+	EmptyTableSchema = "schema" // 按表存在策略创建/处理目标表结构，不同步数据（默认）
+	EmptyTableSkip   = "skip"   // 完全跳过：不创建、不清空、不删除目标表
+)
+
+// ValidateEmptyTableMode 校验空表处理方式，空值表示默认的 schema
+func ValidateEmptyTableMode(mode string) error {
+	switch mode {
+	case "", EmptyTableSchema, EmptyTableSkip:
+		return nil
+	}
+	return fmt.Errorf("empty_table_mode 只支持 %s/%s", EmptyTableSchema, EmptyTableSkip)
+}
+
+// skipEmptySourceTables skip 模式下找出源表为空的单元，直接标记为完成，返回仍需初始化的单元（保持原顺序）
+// 用 SELECT 1 ... LIMIT 1 判断是否为空（SHOW TABLE STATUS 的行数是估算值）；检查失败的单元按非空处理
+func (e *SyncEngine) skipEmptySourceTables(ctx context.Context, taskID string, task *models.SyncTask, config *TaskConfig,
+	sourcePassword string, unitNames []string) []string {
+	dsn := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
+		task.SourceConn.Username, sourcePassword, mysqlNetAddr(task.SourceConn.MySQLHost(), task.SourceConn.Port))
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("检查空表失败，按非空表处理: %v", err))
+		return unitNames
+	}
+	defer db.Close()
+
+	progressManager := GetProgressManager()
+	timeout := NewSettingsService().MetadataTimeout()
+	remaining := make([]string, 0, len(unitNames))
+	skipped := 0
+	for _, unitName := range unitNames {
+		sourceDB, sourceTable, _, _, err := e.parseUnitName(unitName, config)
+		// 查询源的结果集是否为空在同步阶段按实际总数判断
+		if err != nil || ctx.Err() != nil || e.getSourceQuery(config, sourceDB, sourceTable) != "" {
+			remaining = append(remaining, unitName)
+			continue
+		}
+
+		queryCtx, cancel := context.WithTimeout(ctx, timeout)
+		var one int
+		err = db.QueryRowContext(queryCtx, fmt.Sprintf("SELECT 1 FROM `%s`.`%s` LIMIT 1", sourceDB, sourceTable)).Scan(&one)
+		cancel()
+		if err != sql.ErrNoRows {
+			if err != nil {
+				e.logService.Warning(taskID, fmt.Sprintf("检查表 %s 是否为空失败，按非空表处理: %v", unitName, err))
+			}
+			remaining = append(remaining, unitName)
+			continue
+		}
+
+		// 近似行数也清零，数据同步阶段按空表直接完成，不会连接目标
+		progressManager.SetApproxRows(taskID, unitName, 0)
+		progressManager.UpdateUnitProgress(taskID, unitName, 0, 0)
+		progressManager.UpdateUnitStatus(taskID, unitName, "completed")
+		e.logService.Info(taskID, fmt.Sprintf("源表 %s.%s 为空，跳过: %s", sourceDB, sourceTable, unitName))
+		skipped++
+	}
+
+	if skipped > 0 {
+		e.logService.Info(taskID, fmt.Sprintf("跳过 %d 个空表（不创建目标表）", skipped))
+	}
+	return remaining
+}
@@ -0,0 +1,115 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"sort"
+	"time"
+)
+
+// EngineTaskStatus 引擎中单个任务的运行状态
+type EngineTaskStatus struct {
+	TaskID       string     `json:"task_id"`
+	Name         string     `json:"name"`
+	SyncMode     string     `json:"sync_mode"`
+	State        string     `json:"state"` // running/paused
+	StartedAt    *time.Time `json:"started_at,omitempty"`
+	CurrentStep  string     `json:"current_step"`
+	CurrentUnits []string   `json:"current_units"`
+	Pausable     bool       `json:"pausable"`    // 是否可暂停（增量同步不支持暂停）
+	Cancellable  bool       `json:"cancellable"` // 是否可停止
+}
+
+// EngineStatus 引擎状态
+type EngineStatus struct {
+	Running []EngineTaskStatus `json:"running"`
+	Paused  []EngineTaskStatus `json:"paused"`
+}
+
+// GetEngineStatus 获取引擎状态：运行中和暂停中的任务
+// 运行中：执行管理器中存在的任务；暂停中：未运行但内存中仍保留进度且未完成的任务
+func (s *TaskControlService) GetEngineStatus() (*EngineStatus, error) {
+	execManager := GetExecutionManager()
+	progressManager := GetProgressManager()
+
+	// 收集运行中的任务及启动时间
+	startTimes := make(map[string]time.Time)
+	for _, exec := range execManager.ListExecutions() {
+		startTimes[exec.TaskID] = exec.StartedAt
+	}
+	for taskID, startedAt := range execManager.ListIncrementalSyncs() {
+		startTimes[taskID] = startedAt
+	}
+
+	// 候选任务ID：运行中 + 内存中有进度的任务
+	candidateIDs := make([]string, 0, len(startTimes))
+	for taskID := range startTimes {
+		candidateIDs = append(candidateIDs, taskID)
+	}
+	for _, taskID := range progressManager.ListTaskIDs() {
+		if _, ok := startTimes[taskID]; !ok {
+			candidateIDs = append(candidateIDs, taskID)
+		}
+	}
+
+	status := &EngineStatus{
+		Running: []EngineTaskStatus{},
+		Paused:  []EngineTaskStatus{},
+	}
+	if len(candidateIDs) == 0 {
+		return status, nil
+	}
+
+	// 一次查询补齐任务名称和模式
+	var tasks []models.SyncTask
+	if err := database.DB.Select("id", "name", "sync_mode", "is_running", "current_step").
+		Where("id IN ?", candidateIDs).Find(&tasks).Error; err != nil {
+		return nil, err
+	}
+
+	for _, task := range tasks {
+		item := EngineTaskStatus{
+			TaskID:       task.ID,
+			Name:         task.Name,
+			SyncMode:     task.SyncMode,
+			CurrentStep:  task.CurrentStep,
+			CurrentUnits: []string{},
+		}
+
+		if step, units, ok := progressManager.GetActiveUnits(task.ID); ok {
+			if step != "" {
+				item.CurrentStep = step
+			}
+			if units != nil {
+				item.CurrentUnits = units
+			}
+			if earliest := progressManager.GetEarliestStartTime(task.ID); earliest != nil {
+				item.StartedAt = earliest
+			}
+		}
+
+		if startedAt, running := startTimes[task.ID]; running {
+			if !startedAt.IsZero() {
+				item.StartedAt = &startedAt
+			}
+			item.State = "running"
+			item.Pausable = task.SyncMode != "incremental"
+			item.Cancellable = true
+			status.Running = append(status.Running, item)
+			continue
+		}
+
+		// 已完成、失败或已停止的任务不算暂停
+		if task.IsRunning || item.CurrentStep == "completed" || item.CurrentStep == "failed" {
+			continue
+		}
+		item.State = "paused"
+		item.Cancellable = true
+		status.Paused = append(status.Paused, item)
+	}
+
+	sort.Slice(status.Running, func(i, j int) bool { return status.Running[i].TaskID < status.Running[j].TaskID })
+	sort.Slice(status.Paused, func(i, j int) bool { return status.Paused[i].TaskID < status.Paused[j].TaskID })
+
+	return status, nil
+}
@@ -0,0 +1,124 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"net"
+	"strings"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// ErrorCode 错误分类码（写入单元进度和执行历史，便于按类别筛选和告警）
+type ErrorCode string
+
+// 错误分类
+const (
+	ErrorCodeConnection   ErrorCode = "connection"    // 连接失败（拒绝连接、主机不可达、库不存在等）
+	ErrorCodeAuth         ErrorCode = "auth"          // 认证/权限失败
+	ErrorCodeSchema       ErrorCode = "schema"        // 表结构问题（表/字段不存在、结构不一致）
+	ErrorCodeRead         ErrorCode = "read"          // 读取源数据失败
+	ErrorCodeWrite        ErrorCode = "write"         // 写入目标失败
+	ErrorCodeNetwork      ErrorCode = "network"       // 网络中断（连接被重置、断开）
+	ErrorCodeTimeout      ErrorCode = "timeout"       // 超时
+	ErrorCodeDuplicateKey ErrorCode = "duplicate_key" // 主键/唯一键冲突
+	ErrorCodeUnknown      ErrorCode = "unknown"       // 无法识别
+)
+
+// ErrorCodes 全部错误分类（用于参数校验）
+var ErrorCodes = []ErrorCode{
+	ErrorCodeConnection, ErrorCodeAuth, ErrorCodeSchema, ErrorCodeRead, ErrorCodeWrite,
+	ErrorCodeNetwork, ErrorCodeTimeout, ErrorCodeDuplicateKey, ErrorCodeUnknown,
+}
+
+// IsValidErrorCode 是否为已知的错误分类
+func IsValidErrorCode(code string) bool {
+	for _, c := range ErrorCodes {
+		if string(c) == code {
+			return true
+		}
+	}
+	return false
+}
+
+// mysqlErrorCodes MySQL 错误号 -> 分类
+var mysqlErrorCodes = map[uint16]ErrorCode{
+	1062: ErrorCodeDuplicateKey, // ER_DUP_ENTRY
+	1586: ErrorCodeDuplicateKey, // ER_DUP_ENTRY_WITH_KEY_NAME
+	1044: ErrorCodeAuth,         // ER_DBACCESS_DENIED_ERROR
+	1045: ErrorCodeAuth,         // ER_ACCESS_DENIED_ERROR
+	1142: ErrorCodeAuth,         // ER_TABLEACCESS_DENIED_ERROR
+	1143: ErrorCodeAuth,         // ER_COLUMNACCESS_DENIED_ERROR
+	1227: ErrorCodeAuth,         // ER_SPECIFIC_ACCESS_DENIED_ERROR
+	1049: ErrorCodeConnection,   // ER_BAD_DB_ERROR
+	1040: ErrorCodeConnection,   // ER_CON_COUNT_ERROR
+	1146: ErrorCodeSchema,       // ER_NO_SUCH_TABLE
+	1054: ErrorCodeSchema,       // ER_BAD_FIELD_ERROR
+	1136: ErrorCodeSchema,       // ER_WRONG_VALUE_COUNT_ON_ROW
+	1364: ErrorCodeSchema,       // ER_NO_DEFAULT_FOR_FIELD
+	1406: ErrorCodeWrite,        // ER_DATA_TOO_LONG
+	1264: ErrorCodeWrite,        // ER_WARN_DATA_OUT_OF_RANGE
+	1366: ErrorCodeWrite,        // ER_TRUNCATED_WRONG_VALUE_FOR_FIELD
+	1452: ErrorCodeWrite,        // ER_NO_REFERENCED_ROW_2
+	1205: ErrorCodeTimeout,      // ER_LOCK_WAIT_TIMEOUT
+	3024: ErrorCodeTimeout,      // ER_QUERY_TIMEOUT
+	2006: ErrorCodeNetwork,      // CR_SERVER_GONE_ERROR
+	2013: ErrorCodeNetwork,      // CR_SERVER_LOST
+}
+
+// errorKeywords 错误信息关键字 -> 分类（按顺序匹配，越具体的越靠前）
+var errorKeywords = []struct {
+	code     ErrorCode
+	keywords []string
+}{
+	{ErrorCodeDuplicateKey, []string{"error 1062", "error 1586", "duplicate entry", "duplicate key"}},
+	{ErrorCodeAuth, []string{"error 1044", "error 1045", "error 1142", "error 1143", "error 1227", "access denied", "authentication", "permission denied"}},
+	{ErrorCodeTimeout, []string{"error 1205", "error 3024", "timeout", "timed out", "deadline exceeded", "超时"}},
+	{ErrorCodeNetwork, []string{"error 2006", "error 2013", "invalid connection", "bad connection", "broken pipe", "connection reset", "unexpected eof", "server has gone away", "lost connection"}},
+	{ErrorCodeConnection, []string{"error 1040", "error 1049", "connection refused", "no such host", "no route to host", "network is unreachable", "unknown database", "too many connections", "连接"}},
+	{ErrorCodeSchema, []string{"error 1146", "error 1054", "error 1136", "error 1364", "doesn't exist", "unknown column", "column count", "表结构"}},
+	{ErrorCodeWrite, []string{"error 1406", "error 1264", "error 1366", "error 1452", "insert", "写入", "writer"}},
+	{ErrorCodeRead, []string{"select", "读取", "reader", "查询"}},
+}
+
+// ClassifyError 根据错误值判断分类：优先识别 MySQL 错误号和超时/网络错误类型，否则按错误信息匹配
+func ClassifyError(err error) ErrorCode {
+	if err == nil {
+		return ""
+	}
+
+	var mysqlErr *mysql.MySQLError
+	if errors.As(err, &mysqlErr) {
+		if code, ok := mysqlErrorCodes[mysqlErr.Number]; ok {
+			return code
+		}
+	}
+	if errors.Is(err, context.DeadlineExceeded) {
+		return ErrorCodeTimeout
+	}
+	if errors.Is(err, mysql.ErrInvalidConn) {
+		return ErrorCodeNetwork
+	}
+	var netErr net.Error
+	if errors.As(err, &netErr) && netErr.Timeout() {
+		return ErrorCodeTimeout
+	}
+
+	return ClassifyErrorMessage(err.Error())
+}
+
+// ClassifyErrorMessage 根据错误信息文本判断分类（单元失败时只保留了文本）
+func ClassifyErrorMessage(msg string) ErrorCode {
+	if msg == "" {
+		return ""
+	}
+	lower := strings.ToLower(msg)
+	for _, group := range errorKeywords {
+		for _, keyword := range group.keywords {
+			if strings.Contains(lower, keyword) {
+				return group.code
+			}
+		}
+	}
+	return ErrorCodeUnknown
+}
@@ -0,0 +1,47 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"testing"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// timeoutError 超时的 net.Error
+type timeoutError struct{}
+
+func (timeoutError) Error() string   { return "i/o" }
+func (timeoutError) Timeout() bool   { return true }
+func (timeoutError) Temporary() bool { return true }
+
+func TestClassifyError(t *testing.T) {
+	cases := []struct {
+		name string
+		err  error
+		want ErrorCode
+	}{
+		{"nil", nil, ""},
+		{"duplicate entry", &mysql.MySQLError{Number: 1062, Message: "Duplicate entry '1' for key 'PRIMARY'"}, ErrorCodeDuplicateKey},
+		{"access denied", &mysql.MySQLError{Number: 1045, Message: "Access denied for user"}, ErrorCodeAuth},
+		{"table access denied", &mysql.MySQLError{Number: 1142, Message: "INSERT command denied"}, ErrorCodeAuth},
+		{"unknown database", &mysql.MySQLError{Number: 1049, Message: "Unknown database 'db'"}, ErrorCodeConnection},
+		{"no such table", &mysql.MySQLError{Number: 1146, Message: "Table 'db.t' doesn't exist"}, ErrorCodeSchema},
+		{"data too long", &mysql.MySQLError{Number: 1406, Message: "Data too long for column"}, ErrorCodeWrite},
+		{"lock wait timeout", &mysql.MySQLError{Number: 1205, Message: "Lock wait timeout exceeded"}, ErrorCodeTimeout},
+		// 错误号优先于外层包装文本中的关键字（"写入" 会被匹配为 write）
+		{"wrapped mysql error", fmt.Errorf("目标 t1 写入数据失败: %w", &mysql.MySQLError{Number: 1062, Message: "Duplicate entry"}), ErrorCodeDuplicateKey},
+		{"deadline exceeded", fmt.Errorf("读取数据失败: %w", context.DeadlineExceeded), ErrorCodeTimeout},
+		{"invalid connection", fmt.Errorf("写入数据失败: %w", mysql.ErrInvalidConn), ErrorCodeNetwork},
+		{"net timeout", fmt.Errorf("连接失败: %w", timeoutError{}), ErrorCodeTimeout},
+		{"unmapped mysql number falls back to message", &mysql.MySQLError{Number: 1213, Message: "Deadlock found when trying to get lock"}, ErrorCodeUnknown},
+		{"message only", errors.New("dial tcp: connection refused"), ErrorCodeConnection},
+		{"unknown", errors.New("something odd"), ErrorCodeUnknown},
+	}
+	for _, c := range cases {
+		if got := ClassifyError(c.err); got != c.want {
+			t.Errorf("%s: ClassifyError() = %q, want %q", c.name, got, c.want)
+		}
+	}
+}
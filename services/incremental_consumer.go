@@ -27,6 +27,16 @@ type IncrementalConsumer struct {
 	errorStrategy string // pause/skip
 	saveInterval  time.Duration
 	config        *TaskConfig // 任务配置，用于数据库和表名映射
+
+	// 脱敏转换器缓存（key: 目标库.目标表），只在消费循环中访问
+	maskers map[string]Transformer
+
+	// 目标表生成列缓存（key: 目标库.目标表），只在消费循环中访问
+	generatedColumns map[string]map[string]bool
+
+	// 字段值转换器（按配置懒加载），只在消费循环中访问
+	columnTransformer      Transformer
+	columnTransformerReady bool
 }
 
 // IncrementalConsumerConfig 消费者配置
@@ -64,6 +74,9 @@ func NewIncrementalConsumer(config *IncrementalConsumerConfig) *IncrementalConsu
 		saveInterval:  config.SaveInterval,
 		lastSaveTime:  time.Now(),
 		config:        config.TaskConfig,
+		maskers:       make(map[string]Transformer),
+
+		generatedColumns: make(map[string]map[string]bool),
 	}
 }
 
@@ -96,8 +109,9 @@ func (c *IncrementalConsumer) Start() error {
 	return nil
 }
 
-// consumeLoop 消费循环
+// consumeLoop 消费循环，退出（停止、pause 策略出错）时关闭目标连接
 func (c *IncrementalConsumer) consumeLoop() {
+	defer c.targetDB.Close()
 	defer func() {
 		if r := recover(); r != nil {
 			c.logService.Error(c.taskID, fmt.Sprintf("增量消费引擎 panic: %v", r))
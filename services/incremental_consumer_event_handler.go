@@ -27,8 +27,13 @@ func (c *IncrementalConsumer) applyInsert(event *BinlogEvent) error {
 		return nil
 	}
 
-	// 过滤字段
+	// 过滤字段（生成列由目标库计算，不写入）
 	c.filterEventFields(event)
+	c.dropGeneratedColumns(event, targetDB, targetTable)
+
+	// 字段脱敏
+	c.maskEventFields(event, targetDB, targetTable)
+	c.transformEventColumns(event)
 
 	// 验证数据是否为空
 	if len(event.Data) == 0 {
@@ -82,8 +87,13 @@ func (c *IncrementalConsumer) applyUpdate(event *BinlogEvent) error {
 		return nil
 	}
 
-	// 过滤字段
+	// 过滤字段（生成列由目标库计算，不写入）
 	c.filterEventFields(event)
+	c.dropGeneratedColumns(event, targetDB, targetTable)
+
+	// 字段脱敏
+	c.maskEventFields(event, targetDB, targetTable)
+	c.transformEventColumns(event)
 
 	// 验证数据是否为空
 	if len(event.Data) == 0 {
@@ -169,8 +179,13 @@ func (c *IncrementalConsumer) applyDelete(event *BinlogEvent) error {
 		return nil
 	}
 
-	// 过滤字段
+	// 过滤字段（生成列由目标库计算，不写入）
 	c.filterEventFields(event)
+	c.dropGeneratedColumns(event, targetDB, targetTable)
+
+	// 字段脱敏
+	c.maskEventFields(event, targetDB, targetTable)
+	c.transformEventColumns(event)
 
 	// 验证数据是否为空
 	if len(event.Data) == 0 {
@@ -1,5 +1,9 @@
 package services
 
+import (
+	"fmt"
+)
+
 // mapDatabaseAndTable 映射源数据库和表名到目标数据库和表名
 // 返回: targetDB, targetTable, skip
 // skip=true 表示该表不在同步范围内，应该跳过
@@ -119,3 +123,77 @@ func (c *IncrementalConsumer) filterEventFields(event *BinlogEvent) {
 		event.OldData = filteredOldData
 	}
 }
+
+// dropGeneratedColumns 去掉事件中目标表的生成列（VIRTUAL/STORED GENERATED 列不能写入）
+func (c *IncrementalConsumer) dropGeneratedColumns(event *BinlogEvent, targetDB, targetTable string) {
+	key := targetDB + "." + targetTable
+	generated, ok := c.generatedColumns[key]
+	if !ok {
+		var err error
+		generated, err = getGeneratedColumns(c.targetDB, targetDB, targetTable)
+		if err != nil {
+			c.logService.Warning(c.taskID, fmt.Sprintf("表 %s 查询生成列失败: %v", key, err))
+		}
+		c.generatedColumns[key] = generated
+	}
+
+	for column := range generated {
+		delete(event.Data, column)
+		delete(event.OldData, column)
+	}
+}
+
+// maskEventFields 根据配置对事件中的字段脱敏（Data 和 OldData 使用相同规则，保证 WHERE 条件能匹配目标端的脱敏值）
+func (c *IncrementalConsumer) maskEventFields(event *BinlogEvent, targetDB, targetTable string) {
+	if c.config == nil || len(c.config.SyncConfig.Mask) == 0 {
+		return
+	}
+
+	key := targetDB + "." + targetTable
+	masker, ok := c.maskers[key]
+	if !ok {
+		pkColumns, err := getPrimaryKeyColumns(c.targetDB, targetDB, targetTable)
+		if err != nil {
+			c.logService.Warning(c.taskID, fmt.Sprintf("表 %s 查询主键失败，无法校验脱敏规则: %v", key, err))
+		}
+		var skipped []string
+		masker, skipped = NewMaskTransformer(c.config.SyncConfig.Mask, pkColumns)
+		if len(skipped) > 0 {
+			c.logService.Warning(c.taskID, fmt.Sprintf("表 %s 主键字段不允许脱敏，已忽略: %s", key, joinStrings(skipped, ", ")))
+		}
+		c.maskers[key] = masker
+	}
+
+	if event.Data != nil {
+		masker.Transform(event.Data)
+	}
+	if event.OldData != nil {
+		masker.Transform(event.OldData)
+	}
+}
+
+// transformEventColumns 根据配置转换事件中的字段值（Data 和 OldData 使用相同规则，与全量同步结果一致）
+func (c *IncrementalConsumer) transformEventColumns(event *BinlogEvent) {
+	if c.config == nil || len(c.config.SyncConfig.ColumnTransforms) == 0 {
+		return
+	}
+
+	if !c.columnTransformerReady {
+		c.columnTransformerReady = true
+		transformer, err := NewColumnTransformer(c.config.SyncConfig.ColumnTransforms)
+		if err != nil {
+			c.logService.Warning(c.taskID, fmt.Sprintf("字段转换配置无效，已忽略: %v", err))
+		}
+		c.columnTransformer = transformer
+	}
+	if c.columnTransformer == nil {
+		return
+	}
+
+	if event.Data != nil {
+		c.columnTransformer.Transform(event.Data)
+	}
+	if event.OldData != nil {
+		c.columnTransformer.Transform(event.OldData)
+	}
+}
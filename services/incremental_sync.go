@@ -75,11 +75,11 @@ func (s *IncrementalSync) StartWithContext(ctx context.Context) error {
 
 	s.logService.Info(s.taskID, "========== 增量同步开始 ==========")
 
-	// 1. 初始化数据库连接
+	// 1. 初始化数据库连接（先注册关闭，初始化中途失败时已打开的连接同样会被关闭）
+	defer s.closeDatabaseConnections()
 	if err := s.initDatabaseConnections(); err != nil {
 		return fmt.Errorf("初始化数据库连接失败: %v", err)
 	}
-	defer s.closeDatabaseConnections()
 
 	// 2. 检查源库 Binlog 配置
 	if err := s.checkBinlogConfig(); err != nil {
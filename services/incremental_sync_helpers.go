@@ -1,7 +1,6 @@
 package services
 
 import (
-	"database/sql"
 	"datatrace/database"
 	"datatrace/models"
 	"fmt"
@@ -14,44 +13,39 @@ import (
 // initDatabaseConnections 初始化数据库连接
 func (s *IncrementalSync) initDatabaseConnections() error {
 	// 解密密码
-	sourcePassword, err := s.dsService.crypto.Decrypt(s.task.SourceConn.Password)
+	sourcePassword, err := s.dsService.ResolveConnection(s.task.SourceConn)
 	if err != nil {
 		return fmt.Errorf("解密源数据库密码失败: %v", err)
 	}
 
-	targetPassword, err := s.dsService.crypto.Decrypt(s.task.TargetConn.Password)
+	targetPassword, err := s.dsService.ResolveConnection(s.task.TargetConn)
 	if err != nil {
 		return fmt.Errorf("解密目标数据库密码失败: %v", err)
 	}
 
+	// 源和目标使用与全量同步相同的会话时区，全量阶段和增量阶段写入的 TIMESTAMP 一致
+	timeZone := sessionTimeZone(s.config.SyncConfig.TimestampMode)
+
 	// 连接源数据库（不指定具体数据库）
-	sourceDSN := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
+	sourceDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
 		s.task.SourceConn.Username, sourcePassword,
-		s.task.SourceConn.Host, s.task.SourceConn.Port)
+		mysqlNetAddr(s.task.SourceConn.MySQLHost(), s.task.SourceConn.Port)) + timeZoneDSNParam(timeZone)
 
-	s.sourceDB, err = sql.Open("mysql", sourceDSN)
+	s.sourceDB, err = openPingedDB("mysql", sourceDSN)
 	if err != nil {
 		return fmt.Errorf("连接源数据库失败: %v", err)
 	}
 
-	if err := s.sourceDB.Ping(); err != nil {
-		return fmt.Errorf("源数据库连接测试失败: %v", err)
-	}
-
 	// 连接目标数据库（不指定具体数据库）
-	targetDSN := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
+	targetDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
 		s.task.TargetConn.Username, targetPassword,
-		s.task.TargetConn.Host, s.task.TargetConn.Port)
+		mysqlNetAddr(s.task.TargetConn.MySQLHost(), s.task.TargetConn.Port)) + timeZoneDSNParam(timeZone)
 
-	s.targetDB, err = sql.Open("mysql", targetDSN)
+	s.targetDB, err = openPingedDB("mysql", targetDSN)
 	if err != nil {
 		return fmt.Errorf("连接目标数据库失败: %v", err)
 	}
 
-	if err := s.targetDB.Ping(); err != nil {
-		return fmt.Errorf("目标数据库连接测试失败: %v", err)
-	}
-
 	s.logService.Info(s.taskID, "数据库连接初始化成功")
 	return nil
 }
@@ -150,7 +144,7 @@ func (s *IncrementalSync) startBinlogListener(snapshot *BinlogSnapshot) error {
 	s.logService.Info(s.taskID, "启动 Binlog 监听器...")
 
 	// 解密密码
-	sourcePassword, err := s.dsService.crypto.Decrypt(s.task.SourceConn.Password)
+	sourcePassword, err := s.dsService.ResolveConnection(s.task.SourceConn)
 	if err != nil {
 		return fmt.Errorf("解密源数据库密码失败: %v", err)
 	}
@@ -169,7 +163,7 @@ func (s *IncrementalSync) startBinlogListener(snapshot *BinlogSnapshot) error {
 
 	// 创建监听器配置
 	config := &BinlogListenerConfig{
-		Host:      s.task.SourceConn.Host,
+		Host:      s.task.SourceConn.MySQLHost(),
 		Port:      s.task.SourceConn.Port,
 		Username:  s.task.SourceConn.Username,
 		Password:  sourcePassword,
@@ -377,26 +371,22 @@ func (s *IncrementalSync) startIncrementalConsumer() error {
 	s.logService.Info(s.taskID, "启动增量消费...")
 
 	// 解密密码
-	targetPassword, err := s.dsService.crypto.Decrypt(s.task.TargetConn.Password)
+	targetPassword, err := s.dsService.ResolveConnection(s.task.TargetConn)
 	if err != nil {
 		return fmt.Errorf("解密目标数据库密码失败: %v", err)
 	}
 
-	// 连接目标数据库（不指定具体数据库）
-	targetDSN := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
+	// 连接目标数据库（不指定具体数据库），会话时区与全量阶段一致
+	targetDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
 		s.task.TargetConn.Username, targetPassword,
-		s.task.TargetConn.Host, s.task.TargetConn.Port)
+		mysqlNetAddr(s.task.TargetConn.MySQLHost(), s.task.TargetConn.Port)) + timeZoneDSNParam(sessionTimeZone(s.config.SyncConfig.TimestampMode))
 
-	targetDB, err := sql.Open("mysql", targetDSN)
+	targetDB, err := openPingedDB("mysql", targetDSN)
 	if err != nil {
 		return fmt.Errorf("连接目标数据库失败: %v", err)
 	}
 
-	if err := targetDB.Ping(); err != nil {
-		return fmt.Errorf("目标数据库连接测试失败: %v", err)
-	}
-
-	// 创建消费者配置
+	// 创建消费者配置（连接交给消费者，消费循环退出时关闭）
 	consumerConfig := &IncrementalConsumerConfig{
 		TaskID:        s.taskID,
 		Queue:         s.queue,
@@ -0,0 +1,240 @@
+package services
+
+import (
+	"bytes"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"strconv"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// loadDataSeq LOAD DATA 数据流的注册名序号（驱动按名称查找 Reader，需全局唯一）
+var loadDataSeq uint64
+
+// ValidateLoadDataThreshold 校验 LOAD DATA 写入阈值，0 表示不使用 LOAD DATA
+// 阈值按单次写入的行数判断，流式读取时每次写入一个分块，超过分块大小的阈值永远不会触发
+func ValidateLoadDataThreshold(threshold int) error {
+	if threshold < 0 {
+		return fmt.Errorf("load_data_threshold 不能为负数")
+	}
+	if threshold > DefaultStreamChunkSize {
+		return fmt.Errorf("load_data_threshold 不能超过单次写入的分块大小 %d", DefaultStreamChunkSize)
+	}
+	return nil
+}
+
+// isLoadDataDisabledError 是否为服务端或客户端禁用了 LOAD DATA LOCAL 导致的错误（语句被拒绝，没有写入任何数据）
+func isLoadDataDisabledError(err error) bool {
+	var mysqlErr *mysql.MySQLError
+	if errors.As(err, &mysqlErr) {
+		switch mysqlErr.Number {
+		case 1148, // ER_NOT_ALLOWED_COMMAND
+			3948, // ER_CLIENT_LOCAL_FILES_DISABLED
+			3950: // ER_LOAD_DATA_LOCAL_INFILE_REJECTED
+			return true
+		}
+	}
+	return false
+}
+
+// loadDataPolicy LOAD DATA LOCAL INFILE 写入策略：批次行数达到阈值时使用，目标不支持时回退为 INSERT
+// 同一单元同一目标的多个 Writer（含分片 Writer）共享，回退只检测和记录一次；nil 策略表示不使用
+type loadDataPolicy struct {
+	threshold  int
+	checkOnce  sync.Once
+	disabled   atomic.Bool
+	onFallback func(err error) // 回退为 INSERT 时调用（记录原因）
+}
+
+// newLoadDataPolicy 创建 LOAD DATA 写入策略，threshold <= 0 时返回 nil（不使用）
+func newLoadDataPolicy(threshold int, onFallback func(err error)) *loadDataPolicy {
+	if threshold <= 0 {
+		return nil
+	}
+	return &loadDataPolicy{threshold: threshold, onFallback: onFallback}
+}
+
+// use 批次是否使用 LOAD DATA：首次使用时检查目标的 local_infile 设置
+func (p *loadDataPolicy) use(w *MySQLWriter, rows int) bool {
+	if p == nil || rows < p.threshold || p.disabled.Load() {
+		return false
+	}
+	p.checkOnce.Do(func() {
+		var enabled int
+		if err := w.db.QueryRow("SELECT @@GLOBAL.local_infile").Scan(&enabled); err != nil {
+			p.fallback(fmt.Errorf("查询 local_infile 失败: %w", err))
+		} else if enabled == 0 {
+			p.fallback(fmt.Errorf("目标未开启 local_infile"))
+		}
+	})
+	return !p.disabled.Load()
+}
+
+// fallback 停止使用 LOAD DATA，后续批次改用 INSERT
+func (p *loadDataPolicy) fallback(err error) {
+	if p.disabled.CompareAndSwap(false, true) && p.onFallback != nil {
+		p.onFallback(err)
+	}
+}
+
+// newLoadData 创建同步单元使用的 LOAD DATA 写入策略，回退时记录警告
+func (e *SyncEngine) newLoadData(taskID, unitName, targetName string, syncConfig SyncConfigParams) *loadDataPolicy {
+	return newLoadDataPolicy(syncConfig.LoadDataThreshold, func(err error) {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 无法使用 LOAD DATA LOCAL INFILE，改用 INSERT 写入: %v",
+			targetName, unitName, err))
+	})
+}
+
+// loadDataBatch 以 LOAD DATA LOCAL INFILE 写入一个批次，数据按制表符分隔的文本流发送
+// 返回 false 表示目标不支持 LOAD DATA（没有写入数据），调用方改用 INSERT
+// LOCAL 模式下数据错误和主键冲突只产生警告，这里按影响行数和警告数检查，与 INSERT 一样报错；
+// 语句在事务中执行（已开启批次事务时使用批次事务），检查不通过时回滚，不会留下部分写入的行
+func (w *MySQLWriter) loadDataBatch(records []map[string]interface{}, columns []string) (bool, error) {
+	var buf bytes.Buffer
+	encodeLoadDataRows(&buf, records, columns)
+
+	name := fmt.Sprintf("datatrace_%d", atomic.AddUint64(&loadDataSeq, 1))
+	mysql.RegisterReaderHandler(name, func() io.Reader { return bytes.NewReader(buf.Bytes()) })
+	defer mysql.DeregisterReaderHandler(name)
+
+	columnNames := make([]string, len(columns))
+	for i, col := range columns {
+		columnNames[i] = fmt.Sprintf("`%s`", col)
+	}
+	query := fmt.Sprintf("LOAD DATA LOCAL INFILE 'Reader::%s' INTO TABLE `%s` CHARACTER SET utf8mb4 "+
+		"FIELDS TERMINATED BY '\\t' ESCAPED BY '\\\\' LINES TERMINATED BY '\\n' (%s)",
+		name, w.tableName, strings.Join(columnNames, ", "))
+
+	// 警告只能在同一连接上查询，事务固定使用一个连接
+	ctx := context.Background()
+	tx := w.batchTx
+	ownTx := tx == nil
+	if ownTx {
+		err := w.retry.do(w.retryCtx, func() error {
+			var err error
+			tx, err = w.db.BeginTx(ctx, nil)
+			return err
+		})
+		if err != nil {
+			return true, fmt.Errorf("LOAD DATA 开启事务失败: %w", err)
+		}
+	}
+	// fail 回滚自己开启的事务；批次事务由调用方回滚
+	fail := func(err error) (bool, error) {
+		if ownTx {
+			tx.Rollback()
+			return true, fmt.Errorf("%w（整批已回滚）", err)
+		}
+		return true, err
+	}
+
+	result, err := tx.ExecContext(ctx, query)
+	if err != nil {
+		if isLoadDataDisabledError(err) {
+			// 语句被拒绝，没有写入数据，事务仍可继续用 INSERT 写入
+			if ownTx {
+				tx.Rollback()
+			}
+			w.loadData.fallback(err)
+			return false, nil
+		}
+		return fail(fmt.Errorf("LOAD DATA 写入失败: %w", err))
+	}
+
+	var warnings int
+	if err := tx.QueryRowContext(ctx, "SELECT @@warning_count").Scan(&warnings); err != nil {
+		return fail(fmt.Errorf("LOAD DATA 查询警告失败: %w", err))
+	}
+	if warnings > 0 {
+		var level, message string
+		var code int
+		if err := tx.QueryRowContext(ctx, "SHOW WARNINGS LIMIT 1").Scan(&level, &code, &message); err != nil {
+			message = err.Error()
+		}
+		return fail(fmt.Errorf("LOAD DATA 写入产生 %d 个警告: %s", warnings, message))
+	}
+	if affected, err := result.RowsAffected(); err == nil && affected != int64(len(records)) {
+		return fail(fmt.Errorf("LOAD DATA 写入 %d 行，与批次行数 %d 不一致（可能存在主键冲突）", affected, len(records)))
+	}
+	if ownTx {
+		if err := tx.Commit(); err != nil {
+			return true, fmt.Errorf("LOAD DATA 提交事务失败: %w", err)
+		}
+	}
+	return true, nil
+}
+
+// encodeLoadDataRows 把批次编码为 LOAD DATA 的文本流：字段以制表符分隔，每行以换行结尾
+func encodeLoadDataRows(buf *bytes.Buffer, records []map[string]interface{}, columns []string) {
+	for _, record := range records {
+		for j, col := range columns {
+			if j > 0 {
+				buf.WriteByte('\t')
+			}
+			writeLoadDataValue(buf, record[col])
+		}
+		buf.WriteByte('\n')
+	}
+}
+
+// writeLoadDataValue 按 LOAD DATA 的默认转义规则写出一个字段值：NULL 写为 \N，
+// 反斜杠、制表符、换行、回车和 NUL 加反斜杠转义；时间按连接时区（Local）格式化，与 INSERT 参数一致
+func writeLoadDataValue(buf *bytes.Buffer, value interface{}) {
+	switch v := value.(type) {
+	case nil:
+		buf.WriteString(`\N`)
+	case string:
+		escapeLoadDataBytes(buf, []byte(v))
+	case []byte:
+		escapeLoadDataBytes(buf, v)
+	case bool:
+		if v {
+			buf.WriteByte('1')
+		} else {
+			buf.WriteByte('0')
+		}
+	case int64:
+		buf.WriteString(strconv.FormatInt(v, 10))
+	case uint64:
+		buf.WriteString(strconv.FormatUint(v, 10))
+	case float64:
+		buf.WriteString(strconv.FormatFloat(v, 'g', -1, 64))
+	case float32:
+		buf.WriteString(strconv.FormatFloat(float64(v), 'g', -1, 32))
+	case time.Time:
+		if v.IsZero() {
+			buf.WriteString("0000-00-00 00:00:00")
+		} else {
+			buf.WriteString(v.In(time.Local).Format("2006-01-02 15:04:05.999999"))
+		}
+	default:
+		escapeLoadDataBytes(buf, []byte(fmt.Sprint(v)))
+	}
+}
+
+// escapeLoadDataBytes 转义 LOAD DATA 字段内容中的特殊字符
+func escapeLoadDataBytes(buf *bytes.Buffer, data []byte) {
+	for _, b := range data {
+		switch b {
+		case '\\':
+			buf.WriteString(`\\`)
+		case '\t':
+			buf.WriteString(`\t`)
+		case '\n':
+			buf.WriteString(`\n`)
+		case '\r':
+			buf.WriteString(`\r`)
+		case 0:
+			buf.WriteString(`\0`)
+		default:
+			buf.WriteByte(b)
+		}
+	}
+}
@@ -0,0 +1,96 @@
+package services
+
+import (
+	"crypto/sha256"
+	"encoding/hex"
+	"fmt"
+	"strings"
+)
+
+// 脱敏策略
+const (
+	MaskStrategyFull    = "full"    // 全部替换为 ******
+	MaskStrategyPartial = "partial" // 只保留末尾 4 位
+	MaskStrategySHA256  = "sha256"  // 替换为 SHA-256 十六进制摘要
+)
+
+// MaskRule 字段脱敏规则
+type MaskRule struct {
+	Field    string `json:"field"`    // 源字段名
+	Strategy string `json:"strategy"` // full/partial/sha256
+}
+
+// ValidateMaskRules 校验脱敏规则
+func ValidateMaskRules(rules []MaskRule) error {
+	for _, rule := range rules {
+		if strings.TrimSpace(rule.Field) == "" {
+			return fmt.Errorf("脱敏字段名不能为空")
+		}
+		switch rule.Strategy {
+		case MaskStrategyFull, MaskStrategyPartial, MaskStrategySHA256:
+		default:
+			return fmt.Errorf("字段 %s 的脱敏策略无效: %s（可选 full/partial/sha256）", rule.Field, rule.Strategy)
+		}
+	}
+	return nil
+}
+
+// NewMaskTransformer 创建脱敏转换器
+// protectedFields 为主键字段，主键用于写入/增量 UPSERT 定位记录，不允许脱敏，命中的规则会被忽略并返回
+func NewMaskTransformer(rules []MaskRule, protectedFields []string) (Transformer, []string) {
+	protected := make(map[string]bool, len(protectedFields))
+	for _, f := range protectedFields {
+		protected[strings.ToLower(f)] = true
+	}
+
+	strategies := make(map[string]string, len(rules))
+	var skipped []string
+	for _, rule := range rules {
+		if protected[strings.ToLower(rule.Field)] {
+			skipped = append(skipped, rule.Field)
+			continue
+		}
+		strategies[rule.Field] = rule.Strategy
+	}
+
+	return TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		for field, strategy := range strategies {
+			if value, ok := record[field]; ok {
+				record[field] = maskValue(value, strategy)
+			}
+		}
+		return record, nil
+	}), skipped
+}
+
+// maskValue 按策略脱敏单个值，NULL 保持不变
+// 同一输入总是得到同一输出，保证全量与增量写入的结果一致
+func maskValue(value interface{}, strategy string) interface{} {
+	if value == nil {
+		return nil
+	}
+
+	var str string
+	switch v := value.(type) {
+	case string:
+		str = v
+	case []byte:
+		str = string(v)
+	default:
+		str = fmt.Sprint(v)
+	}
+
+	switch strategy {
+	case MaskStrategySHA256:
+		sum := sha256.Sum256([]byte(str))
+		return hex.EncodeToString(sum[:])
+	case MaskStrategyPartial:
+		runes := []rune(str)
+		if len(runes) <= 4 {
+			return strings.Repeat("*", len(runes))
+		}
+		return strings.Repeat("*", len(runes)-4) + string(runes[len(runes)-4:])
+	default:
+		return "******"
+	}
+}
@@ -1,6 +1,7 @@
 package services
 
 import (
+	"context"
 	"database/sql"
 	"fmt"
 	"time"
@@ -16,26 +17,92 @@ type MySQLReader struct {
 	offset         int64
 	totalCount     int64
 	selectedFields []string // 选中的字段列表，为空表示查询所有字段
+	sourceQuery    string   // 查询源 SQL，非空时读取该查询的结果集（tableName 只作为名称）
+
+	// 主键范围过滤（表内并行分片使用），rangeColumn 为空表示不过滤
+	rangeColumn string
+	rangeLower  int64
+	rangeUpper  int64
+
+	// 行数上限（抽样同步），0 表示不限制；orderColumns 为读取时的排序字段，保证取到确定的前 N 行
+	maxRecords   int64
+	orderColumns []string
+
+	retry    *connectRetryPolicy // 连接数打满时的重试策略，nil 表示不重试
+	retryCtx context.Context     // 重试等待随其取消而中止（同步单元的 ctx）
 }
 
 // NewMySQLReader 创建MySQL读取器
 func NewMySQLReader(host string, port int, username, password, database, tableName string, batchSize int) (*MySQLReader, error) {
-	return NewMySQLReaderWithFields(host, port, username, password, database, tableName, batchSize, nil)
+	return NewMySQLReaderWithFields(host, port, username, password, database, tableName, batchSize, nil, "")
 }
 
-// NewMySQLReaderWithFields 创建MySQL读取器（支持字段选择）
-func NewMySQLReaderWithFields(host string, port int, username, password, database, tableName string, batchSize int, selectedFields []string) (*MySQLReader, error) {
+// NewMySQLReaderWithFields 创建MySQL读取器（支持字段选择），timeZone 为会话时区（空值使用服务器默认时区）
+func NewMySQLReaderWithFields(host string, port int, username, password, database, tableName string, batchSize int, selectedFields []string,
+	timeZone string) (*MySQLReader, error) {
 	// 校验表名
 	if err := ValidateTableName(tableName); err != nil {
 		return nil, fmt.Errorf("表名校验失败: %w", err)
 	}
 
-	// 构建连接字符串,添加超时参数
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s&readTimeout=30s",
-		username, password, host, port, database)
+	db, err := openReaderDB(host, port, username, password, database, timeZone)
+	if err != nil {
+		return nil, err
+	}
+
+	reader := &MySQLReader{
+		db:             db,
+		tableName:      tableName,
+		batchSize:      batchSize,
+		offset:         0,
+		selectedFields: excludeGeneratedColumns(db, database, tableName, selectedFields), // 生成列由目标库计算，不读取
+	}
+
+	// 查询总记录数（每次都重新统计，HasMore 依赖准确的总数）
+	if err := reader.queryTotalCount(); err != nil {
+		db.Close()
+		return nil, err
+	}
+
+	return reader, nil
+}
+
+// NewMySQLQueryReader 创建查询源读取器：按 LIMIT/OFFSET 分页读取 SELECT 结果集，总数为 SELECT COUNT(*) FROM (query)
+// 结果集没有主键，分页的稳定性依赖查询本身（建议查询带 ORDER BY 唯一键）
+func NewMySQLQueryReader(host string, port int, username, password, database, name, query string, batchSize int, timeZone string) (*MySQLReader, error) {
+	if err := ValidateSourceQuery(query); err != nil {
+		return nil, fmt.Errorf("查询源校验失败: %w", err)
+	}
+
+	db, err := openReaderDB(host, port, username, password, database, timeZone)
+	if err != nil {
+		return nil, err
+	}
+
+	reader := &MySQLReader{
+		db:          db,
+		tableName:   name,
+		batchSize:   batchSize,
+		sourceQuery: query,
+	}
+	if err := reader.queryTotalCount(); err != nil {
+		db.Close()
+		return nil, err
+	}
+
+	return reader, nil
+}
+
+// readerDSN 读取器的连接字符串：连接到指定数据库，带超时参数和会话时区
+func readerDSN(host string, port int, username, password, database, timeZone string) string {
+	return fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s&readTimeout=30s",
+		username, password, mysqlNetAddr(host, port), database) + timeZoneDSNParam(timeZone)
+}
 
+// openReaderDB 打开 Reader 使用的源库连接池并测试连接
+func openReaderDB(host string, port int, username, password, database, timeZone string) (*sql.DB, error) {
 	// 连接数据库
-	db, err := sql.Open("mysql", dsn)
+	db, err := sql.Open("mysql", readerDSN(host, port, username, password, database, timeZone))
 	if err != nil {
 		return nil, fmt.Errorf("连接数据库失败: %w", err)
 	}
@@ -52,30 +119,54 @@ func NewMySQLReaderWithFields(host string, port int, username, password, databas
 		return nil, fmt.Errorf("数据库连接测试失败: %w", err)
 	}
 
-	reader := &MySQLReader{
-		db:             db,
-		tableName:      tableName,
-		batchSize:      batchSize,
-		offset:         0,
-		selectedFields: selectedFields,
-	}
+	return db, nil
+}
 
-	// 查询总记录数
-	if err := reader.queryTotalCount(); err != nil {
-		db.Close()
-		return nil, err
+// SetPKRange 限定只读取主键在 [lower, upper] 范围内的数据，并重新统计总记录数
+func (r *MySQLReader) SetPKRange(column string, lower, upper int64) error {
+	r.rangeColumn = column
+	r.rangeLower = lower
+	r.rangeUpper = upper
+	r.offset = 0
+	return r.queryTotalCount()
+}
+
+// SetRecordLimit 限定最多读取 maxRecords 行（总记录数同步截断），orderColumns 非空时按其排序读取
+func (r *MySQLReader) SetRecordLimit(maxRecords int64, orderColumns []string) {
+	r.maxRecords = maxRecords
+	r.orderColumns = orderColumns
+	r.totalCount = capRecords(r.totalCount, maxRecords)
+}
+
+// fromClause 读取的数据来源：源表或包装为派生表的查询源
+func (r *MySQLReader) fromClause() string {
+	if r.sourceQuery != "" {
+		return querySourceFrom(r.sourceQuery)
 	}
+	return fmt.Sprintf("`%s`", r.tableName)
+}
 
-	return reader, nil
+// SourceQuery 查询源 SQL，读取源表时为空
+func (r *MySQLReader) SourceQuery() string {
+	return r.sourceQuery
+}
+
+// whereClause 主键范围过滤条件
+func (r *MySQLReader) whereClause() string {
+	if r.rangeColumn == "" {
+		return ""
+	}
+	return fmt.Sprintf(" WHERE `%s` BETWEEN %d AND %d", r.rangeColumn, r.rangeLower, r.rangeUpper)
 }
 
 // queryTotalCount 查询总记录数
 func (r *MySQLReader) queryTotalCount() error {
-	query := fmt.Sprintf("SELECT COUNT(*) FROM `%s`", r.tableName)
+	query := fmt.Sprintf("SELECT COUNT(*) FROM %s%s", r.fromClause(), r.whereClause())
 	err := r.db.QueryRow(query).Scan(&r.totalCount)
 	if err != nil {
 		return fmt.Errorf("查询总记录数失败: %w", err)
 	}
+	r.totalCount = capRecords(r.totalCount, r.maxRecords)
 	return nil
 }
 
@@ -86,6 +177,87 @@ func (r *MySQLReader) GetTotalCount() int64 {
 
 // ReadBatch 读取一批数据
 func (r *MySQLReader) ReadBatch() ([]map[string]interface{}, error) {
+	rows, columns, err := r.queryBatch()
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	// 读取数据
+	var results []map[string]interface{}
+	for rows.Next() {
+		row, err := scanRowToMap(rows, columns)
+		if err != nil {
+			return nil, err
+		}
+		results = append(results, row)
+	}
+
+	// 更新偏移量
+	r.offset += int64(len(results))
+
+	return results, nil
+}
+
+// StreamBatch 流式读取一批数据：逐行扫描，每攒满 chunkSize 条回调一次 fn
+// 内存占用只与 chunkSize 有关，与批次大小无关
+// fn 返回错误时不再回调，跳过本批次剩余数据并返回该错误；偏移量按本批次实际的行数推进（与整批读取后失败一致），
+// 返回的条数同样是本批次的行数，已回调的分块由调用方的批次事务回滚
+func (r *MySQLReader) StreamBatch(chunkSize int, fn func(chunk []DataRecord) error) (int, error) {
+	if chunkSize <= 0 {
+		chunkSize = r.batchSize
+	}
+
+	rows, columns, err := r.queryBatch()
+	if err != nil {
+		return 0, err
+	}
+	defer rows.Close()
+
+	read := 0
+	chunk := make([]DataRecord, 0, chunkSize)
+	for rows.Next() {
+		row, err := scanRowToMap(rows, columns)
+		if err != nil {
+			return read, err
+		}
+		chunk = append(chunk, row)
+		read++
+
+		if len(chunk) >= chunkSize {
+			if err := fn(chunk); err != nil {
+				// 只计数不扫描剩余的行，得到本批次实际的行数
+				for rows.Next() {
+					read++
+				}
+				if rerr := rows.Err(); rerr != nil {
+					return read, fmt.Errorf("读取数据失败: %w", rerr)
+				}
+				r.offset += int64(read)
+				return read, err
+			}
+			chunk = make([]DataRecord, 0, chunkSize)
+		}
+	}
+	if err := rows.Err(); err != nil {
+		return read, fmt.Errorf("读取数据失败: %w", err)
+	}
+
+	if len(chunk) > 0 {
+		if err := fn(chunk); err != nil {
+			r.offset += int64(read)
+			return read, err
+		}
+	}
+
+	// 更新偏移量
+	r.offset += int64(read)
+
+	return read, nil
+}
+
+// queryBatch 查询当前批次（LIMIT/OFFSET）
+func (r *MySQLReader) queryBatch() (*sql.Rows, []string, error) {
 	// 构建字段列表
 	fieldList := "*"
 	if len(r.selectedFields) > 0 {
@@ -98,55 +270,73 @@ func (r *MySQLReader) ReadBatch() ([]map[string]interface{}, error) {
 	}
 
 	// 构建查询语句
-	query := fmt.Sprintf("SELECT %s FROM `%s` LIMIT %d OFFSET %d",
-		fieldList, r.tableName, r.batchSize, r.offset)
+	// 分片读取时按主键排序，保证 OFFSET 分页稳定
+	orderBy := ""
+	if r.rangeColumn != "" {
+		orderBy = fmt.Sprintf(" ORDER BY `%s`", r.rangeColumn)
+	} else if len(r.orderColumns) > 0 {
+		quotedOrder := make([]string, len(r.orderColumns))
+		for i, col := range r.orderColumns {
+			quotedOrder[i] = fmt.Sprintf("`%s`", col)
+		}
+		orderBy = " ORDER BY " + joinStrings(quotedOrder, ", ")
+	}
+
+	// 设置了行数上限时，最后一批只读到上限为止
+	limit := int64(r.batchSize)
+	if r.maxRecords > 0 && r.maxRecords-r.offset < limit {
+		limit = r.maxRecords - r.offset
+	}
+	query := fmt.Sprintf("SELECT %s FROM %s%s%s LIMIT %d OFFSET %d",
+		fieldList, r.fromClause(), r.whereClause(), orderBy, limit, r.offset)
 
-	// 执行查询
-	rows, err := r.db.Query(query)
+	// 执行查询（失败时偏移量不变，可以安全重试）
+	var rows *sql.Rows
+	err := r.retry.do(r.retryCtx, func() error {
+		var err error
+		rows, err = r.db.Query(query)
+		return err
+	})
 	if err != nil {
-		return nil, fmt.Errorf("查询数据失败: %w", err)
+		return nil, nil, fmt.Errorf("查询数据失败: %w", err)
 	}
-	defer rows.Close()
 
 	// 获取列名
 	columns, err := rows.Columns()
 	if err != nil {
-		return nil, fmt.Errorf("获取列名失败: %w", err)
+		rows.Close()
+		return nil, nil, fmt.Errorf("获取列名失败: %w", err)
 	}
 
-	// 读取数据
-	var results []map[string]interface{}
-	for rows.Next() {
-		// 创建列值容器
-		values := make([]interface{}, len(columns))
-		valuePtrs := make([]interface{}, len(columns))
-		for i := range values {
-			valuePtrs[i] = &values[i]
-		}
-
-		// 扫描行数据
-		if err := rows.Scan(valuePtrs...); err != nil {
-			return nil, fmt.Errorf("扫描行数据失败: %w", err)
-		}
+	return rows, columns, nil
+}
 
-		// 构建结果map
-		row := make(map[string]interface{})
-		for i, col := range columns {
-			val := values[i]
-			// 处理[]byte类型
-			if b, ok := val.([]byte); ok {
-				row[col] = string(b)
-			} else {
-				row[col] = val
-			}
-		}
-		results = append(results, row)
+// scanRowToMap 扫描当前行为 map（[]byte 转为 string）
+func scanRowToMap(rows *sql.Rows, columns []string) (map[string]interface{}, error) {
+	// 创建列值容器
+	values := make([]interface{}, len(columns))
+	valuePtrs := make([]interface{}, len(columns))
+	for i := range values {
+		valuePtrs[i] = &values[i]
 	}
 
-	// 更新偏移量
-	r.offset += int64(len(results))
+	// 扫描行数据
+	if err := rows.Scan(valuePtrs...); err != nil {
+		return nil, fmt.Errorf("扫描行数据失败: %w", err)
+	}
 
-	return results, nil
+	// 构建结果map
+	row := make(map[string]interface{}, len(columns))
+	for i, col := range columns {
+		val := values[i]
+		// 处理[]byte类型
+		if b, ok := val.([]byte); ok {
+			row[col] = string(b)
+		} else {
+			row[col] = val
+		}
+	}
+	return row, nil
 }
 
 // HasMore 是否还有更多数据
@@ -154,6 +344,19 @@ func (r *MySQLReader) HasMore() bool {
 	return r.offset < r.totalCount
 }
 
+// SetBatchSize 调整后续批次大小（批次大小自动调优使用）
+func (r *MySQLReader) SetBatchSize(batchSize int) {
+	if batchSize > 0 {
+		r.batchSize = batchSize
+	}
+}
+
+// SetConnectRetry 设置获取连接的重试策略，ctx 取消时停止退避等待
+func (r *MySQLReader) SetConnectRetry(ctx context.Context, policy *connectRetryPolicy) {
+	r.retry = policy
+	r.retryCtx = ctx
+}
+
 // GetDB 获取数据库连接（用于创建表结构）
 func (r *MySQLReader) GetDB() *sql.DB {
 	return r.db
@@ -1,18 +1,31 @@
 package services
 
 import (
+	"context"
 	"database/sql"
+	"errors"
 	"fmt"
+	"net"
+	"strconv"
+	"strings"
 
 	_ "github.com/go-sql-driver/mysql"
 )
 
 // MySQLMetadataService MySQL 元数据查询服务
-type MySQLMetadataService struct{}
+type MySQLMetadataService struct {
+	ctx context.Context // 调用方的上下文，取消后正在进行的连接和查询立即中止
+}
 
 // NewMySQLMetadataService 创建 MySQL 元数据服务
 func NewMySQLMetadataService() *MySQLMetadataService {
-	return &MySQLMetadataService{}
+	return &MySQLMetadataService{ctx: context.Background()}
+}
+
+// WithContext 返回使用指定上下文的元数据服务（如 HTTP 请求的上下文：前端切换数据源或关闭页面时请求被取消，
+// 正在建立的连接和执行中的查询随之中止，连接池由各方法的 defer 关闭）
+func (s *MySQLMetadataService) WithContext(ctx context.Context) *MySQLMetadataService {
+	return &MySQLMetadataService{ctx: ctx}
 }
 
 // DatabaseInfo 数据库信息
@@ -27,17 +40,68 @@ type TableInfo struct {
 	Comment string `json:"comment"`
 }
 
+// TableStatsInfo 表信息（含近似行数和大小）
+type TableStatsInfo struct {
+	Name       string `json:"name"`
+	Comment    string `json:"comment"`
+	ApproxRows int64  `json:"approx_rows"` // 近似行数（information_schema.TABLES.TABLE_ROWS）
+	DataSize   int64  `json:"data_size"`   // 数据大小（字节）
+	IndexSize  int64  `json:"index_size"`  // 索引大小（字节）
+}
+
 // DatabaseWithTables 数据库及其表列表
 type DatabaseWithTables struct {
 	Database string   `json:"database"`
 	Tables   []string `json:"tables"`
 }
 
-// GetDatabases 获取数据库列表
-func (s *MySQLMetadataService) GetDatabases(host string, port int, username, password string) ([]DatabaseInfo, error) {
+// metadataContext 元数据查询的超时上下文（时长见全局设置 metadata_timeout_seconds），调用方取消时同样结束
+func (s *MySQLMetadataService) metadataContext() (context.Context, context.CancelFunc) {
+	parent := s.ctx
+	if parent == nil {
+		parent = context.Background()
+	}
+	return context.WithTimeout(parent, NewSettingsService().MetadataTimeout())
+}
+
+// metadataError 包装元数据查询错误，超时和被取消时返回明确提示
+func metadataError(ctx context.Context, msg string, err error) error {
+	if errors.Is(ctx.Err(), context.Canceled) {
+		return fmt.Errorf("元数据查询已取消")
+	}
+	if errors.Is(ctx.Err(), context.DeadlineExceeded) {
+		return fmt.Errorf("元数据查询超时（%s），请检查数据库是否可用或调大全局设置 metadata_timeout_seconds",
+			NewSettingsService().MetadataTimeout())
+	}
+	return fmt.Errorf("%s: %w", msg, err)
+}
+
+// mysqlNetAddr 构建 DSN 中的网络地址：host 为绝对路径时使用 unix socket（忽略端口），否则使用 tcp(host:port)
+func mysqlNetAddr(host string, port int) string {
+	if strings.HasPrefix(host, "/") {
+		return fmt.Sprintf("unix(%s)", host)
+	}
+	return fmt.Sprintf("tcp(%s)", net.JoinHostPort(host, strconv.Itoa(port)))
+}
+
+// openPingedDB 打开连接池并测试连接；测试失败时先关闭连接池再返回错误，失败路径不遗留空闲连接
+func openPingedDB(driverName, dsn string) (*sql.DB, error) {
+	db, err := sql.Open(driverName, dsn)
+	if err != nil {
+		return nil, err
+	}
+	if err := db.Ping(); err != nil {
+		db.Close()
+		return nil, err
+	}
+	return db, nil
+}
+
+// GetDatabases 获取数据库列表（按 filter 过滤，见 DatabaseFilter）
+func (s *MySQLMetadataService) GetDatabases(host string, port int, username, password string, filter DatabaseFilter) ([]DatabaseInfo, error) {
 	// 构建连接字符串
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
-		username, password, host, port)
+	dsn := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port))
 
 	// 连接数据库
 	db, err := sql.Open("mysql", dsn)
@@ -46,20 +110,22 @@ func (s *MySQLMetadataService) GetDatabases(host string, port int, username, pas
 	}
 	defer db.Close()
 
-	// 查询数据库列表（排除系统数据库）
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	// 查询数据库列表（排除/包含规则在下面按 glob 过滤）
 	query := `
 		SELECT 
 			SCHEMA_NAME as name,
 			(SELECT COUNT(*) FROM information_schema.TABLES 
 			 WHERE TABLE_SCHEMA = SCHEMA_NAME AND TABLE_TYPE = 'BASE TABLE') as table_count
 		FROM information_schema.SCHEMATA
-		WHERE SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
 		ORDER BY SCHEMA_NAME
 	`
 
-	rows, err := db.Query(query)
+	rows, err := db.QueryContext(ctx, query)
 	if err != nil {
-		return nil, fmt.Errorf("查询失败: %w", err)
+		return nil, metadataError(ctx, "查询失败", err)
 	}
 	defer rows.Close()
 
@@ -69,8 +135,14 @@ func (s *MySQLMetadataService) GetDatabases(host string, port int, username, pas
 		if err := rows.Scan(&db.Name, &db.TableCount); err != nil {
 			return nil, err
 		}
+		if !filter.Allows(db.Name) {
+			continue
+		}
 		databases = append(databases, db)
 	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询失败", err)
+	}
 
 	return databases, nil
 }
@@ -78,8 +150,8 @@ func (s *MySQLMetadataService) GetDatabases(host string, port int, username, pas
 // GetTables 获取指定数据库的表列表
 func (s *MySQLMetadataService) GetTables(host string, port int, username, password, database string) ([]TableInfo, error) {
 	// 构建连接字符串
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local",
-		username, password, host, port, database)
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port), database)
 
 	// 连接数据库
 	db, err := sql.Open("mysql", dsn)
@@ -88,12 +160,15 @@ func (s *MySQLMetadataService) GetTables(host string, port int, username, passwo
 	}
 	defer db.Close()
 
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
 	// 查询表列表（只查询表名，不查询行数，提高速度）
 	query := "SELECT TABLE_NAME as name, IFNULL(TABLE_COMMENT, '') as comment FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE' ORDER BY TABLE_NAME"
 
-	rows, err := db.Query(query, database)
+	rows, err := db.QueryContext(ctx, query, database)
 	if err != nil {
-		return nil, fmt.Errorf("查询失败: %w", err)
+		return nil, metadataError(ctx, "查询失败", err)
 	}
 	defer rows.Close()
 
@@ -105,15 +180,19 @@ func (s *MySQLMetadataService) GetTables(host string, port int, username, passwo
 		}
 		tables = append(tables, table)
 	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询失败", err)
+	}
 
 	return tables, nil
 }
 
-// GetDatabasesWithTables 获取所有数据库及其表列表（树形结构）
-func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, username, password string) ([]DatabaseWithTables, error) {
+// GetTablesWithStats 获取指定数据库的表列表及近似行数和大小
+// 使用 information_schema 的统计值，不对每张表执行 COUNT(*)，大库下也能快速返回
+func (s *MySQLMetadataService) GetTablesWithStats(host string, port int, username, password, database string) ([]TableStatsInfo, error) {
 	// 构建连接字符串
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
-		username, password, host, port)
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port), database)
 
 	// 连接数据库
 	db, err := sql.Open("mysql", dsn)
@@ -122,17 +201,133 @@ func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, use
 	}
 	defer db.Close()
 
-	// 1. 获取数据库列表（排除系统数据库）
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	query := `
+		SELECT 
+			TABLE_NAME,
+			IFNULL(TABLE_COMMENT, ''),
+			IFNULL(TABLE_ROWS, 0),
+			IFNULL(DATA_LENGTH, 0),
+			IFNULL(INDEX_LENGTH, 0)
+		FROM information_schema.TABLES
+		WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'
+		ORDER BY TABLE_NAME
+	`
+
+	rows, err := db.QueryContext(ctx, query, database)
+	if err != nil {
+		return nil, metadataError(ctx, "查询失败", err)
+	}
+	defer rows.Close()
+
+	var tables []TableStatsInfo
+	for rows.Next() {
+		var table TableStatsInfo
+		if err := rows.Scan(&table.Name, &table.Comment, &table.ApproxRows, &table.DataSize, &table.IndexSize); err != nil {
+			return nil, err
+		}
+		tables = append(tables, table)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询失败", err)
+	}
+
+	return tables, nil
+}
+
+// GetTablesWithPagination 分页获取指定数据库的表列表（支持按表名模糊搜索）
+// 过滤和分页都在 SQL 中完成，避免大库一次加载全部表名
+func (s *MySQLMetadataService) GetTablesWithPagination(host string, port int, username, password, database, search string, page, pageSize int) ([]TableInfo, int64, error) {
+	// 构建连接字符串
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port), database)
+
+	// 连接数据库
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		return nil, 0, fmt.Errorf("连接失败: %w", err)
+	}
+	defer db.Close()
+
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	where := "TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'"
+	args := []interface{}{database}
+	if search != "" {
+		where += " AND TABLE_NAME LIKE ?"
+		args = append(args, "%"+escapeLikePattern(search)+"%")
+	}
+
+	// 查询总数
+	var total int64
+	countQuery := "SELECT COUNT(*) FROM information_schema.TABLES WHERE " + where
+	if err := db.QueryRowContext(ctx, countQuery, args...).Scan(&total); err != nil {
+		return nil, 0, metadataError(ctx, "查询失败", err)
+	}
+
+	// 查询当前页
+	query := "SELECT TABLE_NAME as name, IFNULL(TABLE_COMMENT, '') as comment FROM information_schema.TABLES WHERE " +
+		where + " ORDER BY TABLE_NAME LIMIT ? OFFSET ?"
+	pageArgs := append(args, pageSize, (page-1)*pageSize)
+
+	rows, err := db.QueryContext(ctx, query, pageArgs...)
+	if err != nil {
+		return nil, 0, metadataError(ctx, "查询失败", err)
+	}
+	defer rows.Close()
+
+	tables := make([]TableInfo, 0, pageSize)
+	for rows.Next() {
+		var table TableInfo
+		if err := rows.Scan(&table.Name, &table.Comment); err != nil {
+			return nil, 0, err
+		}
+		tables = append(tables, table)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, 0, metadataError(ctx, "查询失败", err)
+	}
+
+	return tables, total, nil
+}
+
+// escapeLikePattern 转义 LIKE 通配符
+func escapeLikePattern(s string) string {
+	s = strings.ReplaceAll(s, "\\", "\\\\")
+	s = strings.ReplaceAll(s, "%", "\\%")
+	s = strings.ReplaceAll(s, "_", "\\_")
+	return s
+}
+
+// GetDatabasesWithTables 获取所有数据库及其表列表（树形结构，filter 同 GetDatabases）
+func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, username, password string, filter DatabaseFilter) ([]DatabaseWithTables, error) {
+	// 构建连接字符串
+	dsn := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port))
+
+	// 连接数据库
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		return nil, fmt.Errorf("连接失败: %w", err)
+	}
+	defer db.Close()
+
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	// 1. 获取数据库列表（按排除/包含规则过滤）
 	dbQuery := `
 		SELECT SCHEMA_NAME
 		FROM information_schema.SCHEMATA
-		WHERE SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
 		ORDER BY SCHEMA_NAME
 	`
 
-	dbRows, err := db.Query(dbQuery)
+	dbRows, err := db.QueryContext(ctx, dbQuery)
 	if err != nil {
-		return nil, fmt.Errorf("查询数据库列表失败: %w", err)
+		return nil, metadataError(ctx, "查询数据库列表失败", err)
 	}
 	defer dbRows.Close()
 
@@ -142,21 +337,26 @@ func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, use
 		if err := dbRows.Scan(&dbName); err != nil {
 			return nil, err
 		}
+		if !filter.Allows(dbName) {
+			continue
+		}
 		databaseNames = append(databaseNames, dbName)
 	}
+	if err := dbRows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询数据库列表失败", err)
+	}
 
-	// 2. 批量查询所有数据库的表
+	// 2. 批量查询所有数据库的表（被排除的库在构建结果时跳过）
 	tableQuery := `
 		SELECT TABLE_SCHEMA, TABLE_NAME
 		FROM information_schema.TABLES
-		WHERE TABLE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
-		  AND TABLE_TYPE = 'BASE TABLE'
+		WHERE TABLE_TYPE = 'BASE TABLE'
 		ORDER BY TABLE_SCHEMA, TABLE_NAME
 	`
 
-	tableRows, err := db.Query(tableQuery)
+	tableRows, err := db.QueryContext(ctx, tableQuery)
 	if err != nil {
-		return nil, fmt.Errorf("查询表列表失败: %w", err)
+		return nil, metadataError(ctx, "查询表列表失败", err)
 	}
 	defer tableRows.Close()
 
@@ -169,6 +369,9 @@ func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, use
 		}
 		dbTablesMap[dbName] = append(dbTablesMap[dbName], tableName)
 	}
+	if err := tableRows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询表列表失败", err)
+	}
 
 	// 4. 构建返回结果
 	var result []DatabaseWithTables
@@ -186,11 +389,12 @@ func (s *MySQLMetadataService) GetDatabasesWithTables(host string, port int, use
 	return result, nil
 }
 
-// GetTableColumns 获取指定表的字段列表（仅MySQL）
+// GetTableColumns 获取指定表的字段列表（仅MySQL），按字段顺序（ORDINAL_POSITION）排列
+// 每个字段包含 name/type/nullable/is_primary/position，供任务配置时勾选同步字段
 func (s *MySQLMetadataService) GetTableColumns(host string, port int, username, password, database, table string) ([]map[string]interface{}, error) {
 	// 构建连接字符串
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local",
-		username, password, host, port, database)
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port), database)
 
 	// 连接数据库
 	db, err := sql.Open("mysql", dsn)
@@ -199,34 +403,283 @@ func (s *MySQLMetadataService) GetTableColumns(host string, port int, username,
 	}
 	defer db.Close()
 
-	// 查询字段列表（包括是否主键）
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	// 查询字段列表（包括类型、是否可空、是否主键）
 	query := `
 		SELECT 
 			COLUMN_NAME,
-			COLUMN_KEY
+			COLUMN_TYPE,
+			IS_NULLABLE,
+			COLUMN_KEY,
+			ORDINAL_POSITION
 		FROM information_schema.COLUMNS
 		WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
 		ORDER BY ORDINAL_POSITION
 	`
 
-	rows, err := db.Query(query, database, table)
+	rows, err := db.QueryContext(ctx, query, database, table)
 	if err != nil {
-		return nil, fmt.Errorf("查询字段列表失败: %w", err)
+		return nil, metadataError(ctx, "查询字段列表失败", err)
 	}
 	defer rows.Close()
 
 	var columns []map[string]interface{}
 	for rows.Next() {
-		var columnName, columnKey string
-		if err := rows.Scan(&columnName, &columnKey); err != nil {
+		var columnName, columnType, isNullable, columnKey string
+		var position int
+		if err := rows.Scan(&columnName, &columnType, &isNullable, &columnKey, &position); err != nil {
 			return nil, err
 		}
 
 		columns = append(columns, map[string]interface{}{
 			"name":       columnName,
+			"type":       columnType,
+			"nullable":   isNullable == "YES",
 			"is_primary": columnKey == "PRI",
+			"position":   position,
 		})
 	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询字段列表失败", err)
+	}
 
 	return columns, nil
 }
+
+// ErrTableNotFound 表不存在
+var ErrTableNotFound = errors.New("表不存在")
+
+// ColumnSchema 字段结构
+type ColumnSchema struct {
+	Name       string  `json:"name"`
+	Type       string  `json:"type"`                 // 完整类型，如 varchar(255)、int unsigned
+	DataType   string  `json:"data_type"`            // 基础类型，如 varchar、int
+	Nullable   bool    `json:"nullable"`             // 是否允许 NULL
+	Default    *string `json:"default"`              // 默认值，NULL 表示无默认值
+	Key        string  `json:"key"`                  // PRI/UNI/MUL
+	Extra      string  `json:"extra"`                // auto_increment、VIRTUAL GENERATED 等
+	Comment    string  `json:"comment"`              // 字段注释
+	Generation string  `json:"generation,omitempty"` // 生成列表达式
+}
+
+// TableSchema 表结构
+type TableSchema struct {
+	Database    string         `json:"database"`
+	Table       string         `json:"table"`
+	Columns     []ColumnSchema `json:"columns"`
+	PrimaryKeys []string       `json:"primary_keys"`
+	CreateSQL   string         `json:"create_sql"`
+}
+
+// GetTableSchema 获取表的完整结构（字段类型、可空、默认值、主键和建表语句）
+func (s *MySQLMetadataService) GetTableSchema(host string, port int, username, password, database, table string) (*TableSchema, error) {
+	if err := ValidateDatabaseName(database); err != nil {
+		return nil, err
+	}
+	if err := ValidateTableName(table); err != nil {
+		return nil, err
+	}
+
+	// 构建连接字符串
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port), database)
+
+	// 连接数据库
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		return nil, fmt.Errorf("连接失败: %w", err)
+	}
+	defer db.Close()
+
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	// GENERATION_EXPRESSION 在 5.7 之前不存在，这里只读 information_schema 的通用列
+	query := `
+		SELECT
+			COLUMN_NAME,
+			COLUMN_TYPE,
+			DATA_TYPE,
+			IS_NULLABLE,
+			COLUMN_DEFAULT,
+			COLUMN_KEY,
+			EXTRA,
+			IFNULL(COLUMN_COMMENT, '')
+		FROM information_schema.COLUMNS
+		WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+		ORDER BY ORDINAL_POSITION
+	`
+
+	rows, err := db.QueryContext(ctx, query, database, table)
+	if err != nil {
+		return nil, metadataError(ctx, "查询字段结构失败", err)
+	}
+	defer rows.Close()
+
+	schema := &TableSchema{
+		Database:    database,
+		Table:       table,
+		Columns:     []ColumnSchema{},
+		PrimaryKeys: []string{},
+	}
+	for rows.Next() {
+		var column ColumnSchema
+		var nullable string
+		var defaultValue sql.NullString
+		if err := rows.Scan(&column.Name, &column.Type, &column.DataType, &nullable, &defaultValue,
+			&column.Key, &column.Extra, &column.Comment); err != nil {
+			return nil, metadataError(ctx, "扫描字段结构失败", err)
+		}
+		column.Nullable = nullable == "YES"
+		if defaultValue.Valid {
+			column.Default = &defaultValue.String
+		}
+		if column.Key == "PRI" {
+			schema.PrimaryKeys = append(schema.PrimaryKeys, column.Name)
+		}
+		schema.Columns = append(schema.Columns, column)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询字段结构失败", err)
+	}
+	if len(schema.Columns) == 0 {
+		return nil, fmt.Errorf("%w: %s.%s", ErrTableNotFound, database, table)
+	}
+
+	// 建表语句（包含索引、生成列表达式等完整定义）
+	var tableName string
+	if err := db.QueryRowContext(ctx, fmt.Sprintf("SHOW CREATE TABLE `%s`", table)).Scan(&tableName, &schema.CreateSQL); err != nil {
+		return nil, metadataError(ctx, "获取建表语句失败", err)
+	}
+
+	// 从建表语句中补充生成列表达式
+	if structure, err := NewTableStructureParser().Parse(schema.CreateSQL); err == nil {
+		definitions := make(map[string]string, len(structure.Fields))
+		for _, field := range structure.Fields {
+			definitions[field.Name] = field.Definition
+		}
+		for i := range schema.Columns {
+			if strings.Contains(schema.Columns[i].Extra, "GENERATED") && !strings.Contains(schema.Columns[i].Extra, "DEFAULT_GENERATED") {
+				schema.Columns[i].Generation = generationExpression(definitions[schema.Columns[i].Name])
+			}
+		}
+	}
+
+	return schema, nil
+}
+
+// generationExpression 从字段定义中提取 AS (...) 生成表达式
+func generationExpression(definition string) string {
+	idx := strings.Index(strings.ToUpper(definition), " AS (")
+	if idx < 0 {
+		return ""
+	}
+	start := idx + len(" AS (")
+	depth := 1
+	for i := start; i < len(definition); i++ {
+		switch definition[i] {
+		case '(':
+			depth++
+		case ')':
+			depth--
+			if depth == 0 {
+				return definition[start:i]
+			}
+		}
+	}
+	return ""
+}
+
+const (
+	// DefaultPreviewLimit 数据预览默认行数
+	DefaultPreviewLimit = 20
+	// MaxPreviewLimit 数据预览最大行数
+	MaxPreviewLimit = 100
+	// maxPreviewValueLength 预览中单个字段值的最大长度（超出截断，避免大字段撑爆响应）
+	maxPreviewValueLength = 1024
+)
+
+// TablePreview 表数据预览
+type TablePreview struct {
+	Columns []string                 `json:"columns"`
+	Rows    []map[string]interface{} `json:"rows"`
+	Limit   int                      `json:"limit"`
+}
+
+// PreviewTableData 预览表的前 limit 行数据（limit 上限 MaxPreviewLimit）
+func (s *MySQLMetadataService) PreviewTableData(host string, port int, username, password, database, table string, limit int) (*TablePreview, error) {
+	if err := ValidateDatabaseName(database); err != nil {
+		return nil, err
+	}
+	if err := ValidateTableName(table); err != nil {
+		return nil, err
+	}
+	if limit <= 0 {
+		limit = DefaultPreviewLimit
+	}
+	if limit > MaxPreviewLimit {
+		limit = MaxPreviewLimit
+	}
+
+	// 构建连接字符串
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s&readTimeout=30s",
+		username, password, mysqlNetAddr(host, port), database)
+
+	// 连接数据库
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		return nil, fmt.Errorf("连接失败: %w", err)
+	}
+	defer db.Close()
+
+	ctx, cancel := s.metadataContext()
+	defer cancel()
+
+	rows, err := db.QueryContext(ctx, fmt.Sprintf("SELECT * FROM `%s` LIMIT %d", table, limit))
+	if err != nil {
+		return nil, metadataError(ctx, "查询数据失败", err)
+	}
+	defer rows.Close()
+
+	columns, err := rows.Columns()
+	if err != nil {
+		return nil, metadataError(ctx, "获取列名失败", err)
+	}
+
+	preview := &TablePreview{
+		Columns: columns,
+		Rows:    []map[string]interface{}{},
+		Limit:   limit,
+	}
+	for rows.Next() {
+		values := make([]interface{}, len(columns))
+		valuePtrs := make([]interface{}, len(columns))
+		for i := range values {
+			valuePtrs[i] = &values[i]
+		}
+		if err := rows.Scan(valuePtrs...); err != nil {
+			return nil, metadataError(ctx, "扫描行数据失败", err)
+		}
+
+		row := make(map[string]interface{}, len(columns))
+		for i, col := range columns {
+			if b, ok := values[i].([]byte); ok {
+				text := string(b)
+				if runes := []rune(text); len(runes) > maxPreviewValueLength {
+					text = string(runes[:maxPreviewValueLength]) + "..."
+				}
+				row[col] = text
+			} else {
+				row[col] = values[i]
+			}
+		}
+		preview.Rows = append(preview.Rows, row)
+	}
+	if err := rows.Err(); err != nil {
+		return nil, metadataError(ctx, "查询数据失败", err)
+	}
+
+	return preview, nil
+}
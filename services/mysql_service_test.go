@@ -0,0 +1,104 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"database/sql/driver"
+	"errors"
+	"sync"
+	"testing"
+)
+
+// closeTrackingDriver 测试用驱动：dsn 为 "fail" 时连接失败；记录各 dsn 的连接池是否已关闭
+type closeTrackingDriver struct{}
+
+var (
+	closedPoolsMu sync.Mutex
+	closedPools   = map[string]bool{}
+)
+
+func init() {
+	sql.Register("closetrack", closeTrackingDriver{})
+}
+
+func (closeTrackingDriver) Open(dsn string) (driver.Conn, error) {
+	return (&closeTrackingConnector{dsn: dsn}).Connect(context.Background())
+}
+
+func (closeTrackingDriver) OpenConnector(dsn string) (driver.Connector, error) {
+	return &closeTrackingConnector{dsn: dsn}, nil
+}
+
+// closeTrackingConnector 连接池关闭时 database/sql 会调用 Connector 的 Close
+type closeTrackingConnector struct {
+	dsn string
+}
+
+func (c *closeTrackingConnector) Connect(context.Context) (driver.Conn, error) {
+	if c.dsn == "fail" {
+		return nil, errors.New("connection refused")
+	}
+	return stubConn{}, nil
+}
+
+func (c *closeTrackingConnector) Driver() driver.Driver { return closeTrackingDriver{} }
+
+func (c *closeTrackingConnector) Close() error {
+	closedPoolsMu.Lock()
+	defer closedPoolsMu.Unlock()
+	closedPools[c.dsn] = true
+	return nil
+}
+
+// stubConn 不执行任何语句的连接
+type stubConn struct{}
+
+func (stubConn) Prepare(string) (driver.Stmt, error) { return nil, errors.New("not supported") }
+func (stubConn) Close() error                        { return nil }
+func (stubConn) Begin() (driver.Tx, error)           { return nil, errors.New("not supported") }
+
+func poolClosed(dsn string) bool {
+	closedPoolsMu.Lock()
+	defer closedPoolsMu.Unlock()
+	return closedPools[dsn]
+}
+
+func TestOpenPingedDBClosesPoolWhenPingFails(t *testing.T) {
+	db, err := openPingedDB("closetrack", "fail")
+	if err == nil {
+		t.Fatal("openPingedDB() succeeded, want the ping error")
+	}
+	if db != nil {
+		t.Error("openPingedDB() returned a pool on failure")
+	}
+	if !poolClosed("fail") {
+		t.Error("pool was not closed after the ping failed")
+	}
+}
+
+func TestOpenPingedDBKeepsPoolOpenOnSuccess(t *testing.T) {
+	db, err := openPingedDB("closetrack", "ok")
+	if err != nil {
+		t.Fatalf("openPingedDB() error = %v", err)
+	}
+	if poolClosed("ok") {
+		t.Fatal("pool closed although the ping succeeded")
+	}
+	db.Close()
+	if !poolClosed("ok") {
+		t.Error("pool was not closed by Close()")
+	}
+}
+
+func TestIncrementalSyncClosesOpenedPools(t *testing.T) {
+	// 初始化中途失败时只打开了源连接，StartWithContext 注册的关闭仍会关闭它
+	source, err := openPingedDB("closetrack", "incremental-source")
+	if err != nil {
+		t.Fatalf("openPingedDB() error = %v", err)
+	}
+	s := &IncrementalSync{sourceDB: source}
+	s.closeDatabaseConnections()
+	if !poolClosed("incremental-source") {
+		t.Error("source pool was not closed")
+	}
+}
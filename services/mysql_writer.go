@@ -5,6 +5,7 @@ import (
 	"database/sql"
 	"fmt"
 	"strings"
+	"sync"
 	"time"
 
 	_ "github.com/go-sql-driver/mysql"
@@ -12,24 +13,37 @@ import (
 
 // MySQLWriter MySQL数据写入器
 type MySQLWriter struct {
-	db        *sql.DB
-	tableName string
+	db          *sql.DB
+	tableName   string
+	skipColumns map[string]bool     // 写入时忽略的列（如由目标重新生成的自增列）
+	charset     string              // 建表时覆盖的表默认字符集，空表示沿用源表
+	collation   string              // 建表时覆盖的表默认排序规则
+	retry       *connectRetryPolicy // 连接数打满时的重试策略，nil 表示不重试
+	retryCtx    context.Context     // 重试等待随其取消而中止（同步单元的 ctx）
+
+	nullPolicies map[string]string   // 字段 NULL 值处理策略（字段名 -> fail/default/nullable）
+	nullFills    map[string]nullFill // default 策略下需要替换 NULL 的 NOT NULL 字段，首次写入时加载
+	nullFillsMu  sync.Mutex          // 并行写入时保护 nullFills 的加载
+	nullStats    *nullCoercionStats  // NULL 值替换统计，nil 表示不统计
+	loadData     *loadDataPolicy     // 大批次使用 LOAD DATA LOCAL INFILE 写入，nil 表示只用 INSERT
+	onDuplicate  func(rows int64)    // 非 nil 时忽略主键/唯一键冲突的行，每次写入后回调忽略的行数
+	dedup        *batchDedupPolicy   // 非 nil 时写入前按主键去除批次内重复的行
+
+	// 批次事务（BeginBatch 开启）：期间的写入都在该事务中执行，统计暂存到提交后再上报
+	batchTx      *sql.Tx
+	batchCoerced map[string]int64
+	batchIgnored int64
 }
 
 // NewMySQLWriter 创建MySQL写入器
-func NewMySQLWriter(host string, port int, username, password, database, tableName string) (*MySQLWriter, error) {
+func NewMySQLWriter(host string, port int, username, password, database, tableName, timeZone string) (*MySQLWriter, error) {
 	// 校验表名
 	if err := ValidateTableName(tableName); err != nil {
 		return nil, fmt.Errorf("表名校验失败: %w", err)
 	}
 
-	// 构建连接字符串（连接到指定数据库）
-	// 添加超时参数
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s&readTimeout=30s&writeTimeout=30s",
-		username, password, host, port, database)
-
 	// 连接数据库
-	db, err := sql.Open("mysql", dsn)
+	db, err := sql.Open("mysql", writerDSN(host, port, username, password, database, timeZone))
 	if err != nil {
 		return nil, fmt.Errorf("连接数据库失败: %w", err)
 	}
@@ -66,6 +80,12 @@ func NewMySQLWriter(host string, port int, username, password, database, tableNa
 	return writer, nil
 }
 
+// writerDSN 写入器的连接字符串：连接到指定数据库，带超时参数和会话时区
+func writerDSN(host string, port int, username, password, database, timeZone string) string {
+	return fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s&readTimeout=30s&writeTimeout=30s",
+		username, password, mysqlNetAddr(host, port), database) + timeZoneDSNParam(timeZone)
+}
+
 // CreateDatabaseIfNotExists 创建数据库（如果不存在）
 func CreateDatabaseIfNotExists(host string, port int, username, password, database, sourceCharset, sourceCollation string) (bool, error) {
 	// 校验数据库名
@@ -74,8 +94,8 @@ func CreateDatabaseIfNotExists(host string, port int, username, password, databa
 	}
 
 	// 连接到MySQL服务器（不指定数据库）
-	dsnWithoutDB := fmt.Sprintf("%s:%s@tcp(%s:%d)/?charset=utf8mb4&parseTime=True&loc=Local",
-		username, password, host, port)
+	dsnWithoutDB := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local",
+		username, password, mysqlNetAddr(host, port))
 
 	db, err := sql.Open("mysql", dsnWithoutDB)
 	if err != nil {
@@ -101,9 +121,11 @@ func CreateDatabaseIfNotExists(host string, port int, username, password, databa
 		return false, nil
 	}
 
-	// 使用源数据库的字符集和排序规则创建目标数据库
-	createDBQuery := fmt.Sprintf("CREATE DATABASE `%s` CHARACTER SET %s COLLATE %s",
-		database, sourceCharset, sourceCollation)
+	// 使用源数据库的字符集和排序规则创建目标数据库（排序规则为空时使用字符集默认值）
+	createDBQuery := fmt.Sprintf("CREATE DATABASE `%s` CHARACTER SET %s", database, sourceCharset)
+	if sourceCollation != "" {
+		createDBQuery += " COLLATE " + sourceCollation
+	}
 
 	_, err = db.Exec(createDBQuery)
 	if err != nil {
@@ -115,6 +137,7 @@ func CreateDatabaseIfNotExists(host string, port int, username, password, databa
 
 // WriteBatch 批量写入数据
 func (w *MySQLWriter) WriteBatch(records []map[string]interface{}) error {
+	records = w.dedup.apply(records)
 	if len(records) == 0 {
 		return nil
 	}
@@ -122,8 +145,27 @@ func (w *MySQLWriter) WriteBatch(records []map[string]interface{}) error {
 	// 获取列名（从第一条记录）
 	var columns []string
 	for col := range records[0] {
+		if w.skipColumns[col] {
+			continue
+		}
 		columns = append(columns, col)
 	}
+	if len(columns) == 0 {
+		return fmt.Errorf("没有可写入的列")
+	}
+
+	// default 策略需要目标字段的可空性和默认值，首次写入时加载（此时目标表已创建）
+	nullFills, err := w.ensureNullFills()
+	if err != nil {
+		return err
+	}
+
+	// 行数达到阈值的批次优先使用 LOAD DATA；default 策略需要逐行写入 DEFAULT、忽略冲突需要统计冲突行数，仍使用 INSERT
+	if len(nullFills) == 0 && w.onDuplicate == nil && w.loadData.use(w, len(records)) {
+		if loaded, err := w.loadDataBatch(records, columns); loaded {
+			return err
+		}
+	}
 
 	columnCount := len(columns)
 
@@ -140,38 +182,175 @@ func (w *MySQLWriter) WriteBatch(records []map[string]interface{}) error {
 		return fmt.Errorf("表列数 %d 超过 MySQL 占位符限制 %d", columnCount, maxPlaceholders)
 	}
 
-	// 分批插入
-	for i := 0; i < len(records); i += maxRecordsPerBatch {
-		end := i + maxRecordsPerBatch
-		if end > len(records) {
-			end = len(records)
+	// 单条 INSERT 本身是原子的；超过占位符限制需要拆成多条时放在同一个事务中执行，
+	// 任一条失败整批回滚，不会留下部分写入的行；已开启批次事务时直接在批次事务中执行
+	if len(records) <= maxRecordsPerBatch {
+		var exec sqlExecer = w.db
+		if w.batchTx != nil {
+			exec = w.batchTx
+		}
+		coerced, ignored, err := w.writeBatchInternal(exec, records, columns)
+		if err != nil {
+			return err
+		}
+		w.reportBatchStats(coerced, ignored)
+		return nil
+	}
+	return w.writeBatchInTx(records, columns, maxRecordsPerBatch)
+}
+
+// ensureNullFills 返回 default 策略需要替换 NULL 的字段，首次调用时加载；并行写入时只加载一次
+func (w *MySQLWriter) ensureNullFills() (map[string]nullFill, error) {
+	w.nullFillsMu.Lock()
+	defer w.nullFillsMu.Unlock()
+	if w.nullFills == nil && hasNullPolicy(w.nullPolicies, NullPolicyDefault) {
+		fills, err := loadNullFills(w.db, w.tableName, w.nullPolicies)
+		if err != nil {
+			return nil, err
+		}
+		if fills == nil {
+			fills = map[string]nullFill{}
 		}
-		if err := w.writeBatchInternal(records[i:end], columns); err != nil {
+		w.nullFills = fills
+	}
+	return w.nullFills, nil
+}
+
+// sqlExecer 执行写入语句的连接池或事务
+type sqlExecer interface {
+	Exec(query string, args ...interface{}) (sql.Result, error)
+}
+
+// writeBatchInTx 在一个事务中依次执行批次拆分出的多条 INSERT，全部成功才提交，统计在提交后上报
+// 已开启批次事务时在批次事务中执行，由 CommitBatch/RollbackBatch 决定提交或回滚
+func (w *MySQLWriter) writeBatchInTx(records []map[string]interface{}, columns []string, chunkSize int) error {
+	tx := w.batchTx
+	ownTx := tx == nil
+	if ownTx {
+		err := w.retry.do(w.retryCtx, func() error {
+			var err error
+			tx, err = w.db.Begin()
 			return err
+		})
+		if err != nil {
+			return fmt.Errorf("开启事务失败: %w", err)
 		}
 	}
 
+	coerced := make(map[string]int64)
+	var ignored int64
+	for i := 0; i < len(records); i += chunkSize {
+		end := min(i+chunkSize, len(records))
+		chunkCoerced, chunkIgnored, err := w.writeBatchInternal(tx, records[i:end], columns)
+		if err != nil {
+			if !ownTx {
+				return err
+			}
+			tx.Rollback()
+			return fmt.Errorf("%w（整批已回滚）", err)
+		}
+		for col, n := range chunkCoerced {
+			coerced[col] += n
+		}
+		ignored += chunkIgnored
+	}
+
+	if ownTx {
+		if err := tx.Commit(); err != nil {
+			return fmt.Errorf("提交事务失败: %w", err)
+		}
+	}
+	w.reportBatchStats(coerced, ignored)
+	return nil
+}
+
+// BeginBatch 开启批次事务：之后的 WriteBatch（包括 LOAD DATA）都在同一个事务中执行，直到 CommitBatch 或 RollbackBatch
+// 流式读取时一个批次分多块写入，借此保证批次整体写入或整体回滚；不能与并行写入同时使用
+func (w *MySQLWriter) BeginBatch() error {
+	if w.batchTx != nil {
+		return fmt.Errorf("批次事务已开启")
+	}
+	var tx *sql.Tx
+	err := w.retry.do(w.retryCtx, func() error {
+		var err error
+		tx, err = w.db.Begin()
+		return err
+	})
+	if err != nil {
+		return fmt.Errorf("开启事务失败: %w", err)
+	}
+	w.batchTx = tx
+	w.batchCoerced = make(map[string]int64)
+	w.batchIgnored = 0
+	return nil
+}
+
+// CommitBatch 提交批次事务，提交成功后才上报批次内的统计
+func (w *MySQLWriter) CommitBatch() error {
+	tx := w.batchTx
+	if tx == nil {
+		return nil
+	}
+	w.batchTx = nil
+	if err := tx.Commit(); err != nil {
+		return fmt.Errorf("提交事务失败: %w", err)
+	}
+	w.reportBatchStats(w.batchCoerced, w.batchIgnored)
 	return nil
 }
 
-// writeBatchInternal 内部批量写入方法
-func (w *MySQLWriter) writeBatchInternal(records []map[string]interface{}, columns []string) error {
+// RollbackBatch 回滚批次事务，批次内的统计一并丢弃
+func (w *MySQLWriter) RollbackBatch() error {
+	tx := w.batchTx
+	if tx == nil {
+		return nil
+	}
+	w.batchTx = nil
+	return tx.Rollback()
+}
+
+// reportBatchStats 批次写入成功后上报 NULL 值替换个数和忽略的冲突行数；批次事务中暂存到提交后上报
+func (w *MySQLWriter) reportBatchStats(coerced map[string]int64, ignored int64) {
+	if w.batchTx != nil {
+		for col, n := range coerced {
+			w.batchCoerced[col] += n
+		}
+		w.batchIgnored += ignored
+		return
+	}
+	w.nullStats.add(coerced)
+	if ignored > 0 && w.onDuplicate != nil {
+		w.onDuplicate(ignored)
+	}
+}
+
+// writeBatchInternal 执行一条多行 INSERT，返回按 default 策略替换的 NULL 个数和忽略的冲突行数
+func (w *MySQLWriter) writeBatchInternal(exec sqlExecer, records []map[string]interface{}, columns []string) (map[string]int64, int64, error) {
 	// 构建INSERT语句
 	placeholders := make([]string, len(records))
 	values := make([]interface{}, 0, len(records)*len(columns))
+	var coerced map[string]int64 // 本批次按 default 策略替换的 NULL 个数
 
 	for i, record := range records {
-		// 构建单行占位符 (?, ?, ?)
+		// 构建单行占位符 (?, ?, ?)，按 default 策略替换的 NULL 使用 DEFAULT 或类型零值
 		rowPlaceholders := make([]string, len(columns))
-		for j := range columns {
+		for j, col := range columns {
+			value := record[col]
+			if fill, ok := w.nullFills[col]; ok && value == nil {
+				if coerced == nil {
+					coerced = make(map[string]int64)
+				}
+				coerced[col]++
+				if fill.useDefault {
+					rowPlaceholders[j] = "DEFAULT"
+					continue
+				}
+				value = fill.zero
+			}
 			rowPlaceholders[j] = "?"
+			values = append(values, value)
 		}
 		placeholders[i] = fmt.Sprintf("(%s)", strings.Join(rowPlaceholders, ", "))
-
-		// 收集值
-		for _, col := range columns {
-			values = append(values, record[col])
-		}
 	}
 
 	// 构建完整SQL
@@ -184,14 +363,85 @@ func (w *MySQLWriter) writeBatchInternal(records []map[string]interface{}, colum
 		w.tableName,
 		strings.Join(columnNames, ", "),
 		strings.Join(placeholders, ", "))
+	if w.onDuplicate != nil {
+		query += duplicateKeyClause(columns[0])
+	}
 
-	// 执行插入
-	_, err := w.db.Exec(query, values...)
+	// 执行插入（连接数打满时获取连接失败，语句未执行，可以安全重试）
+	var result sql.Result
+	err := w.retry.do(w.retryCtx, func() error {
+		var err error
+		result, err = exec.Exec(query, values...)
+		return err
+	})
 	if err != nil {
-		return fmt.Errorf("批量插入失败: %w", err)
+		return nil, 0, fmt.Errorf("批量插入失败: %w", err)
 	}
 
-	return nil
+	// 冲突的行不修改目标，影响行数为 0，其余行各为 1
+	var ignored int64
+	if w.onDuplicate != nil {
+		if affected, err := result.RowsAffected(); err == nil && affected < int64(len(records)) {
+			ignored = int64(len(records)) - affected
+		}
+	}
+
+	return coerced, ignored, nil
+}
+
+// SetSkipColumns 设置写入时忽略的列
+func (w *MySQLWriter) SetSkipColumns(columns ...string) {
+	w.skipColumns = make(map[string]bool, len(columns))
+	for _, col := range columns {
+		w.skipColumns[col] = true
+	}
+}
+
+// SetConnectRetry 设置获取连接的重试策略，ctx 取消时停止退避等待
+func (w *MySQLWriter) SetConnectRetry(ctx context.Context, policy *connectRetryPolicy) {
+	w.retry = policy
+	w.retryCtx = ctx
+}
+
+// SetNullPolicies 设置字段 NULL 值处理策略（建表时应用 nullable，写入时应用 default），stats 为 NULL 值替换统计
+func (w *MySQLWriter) SetNullPolicies(policies map[string]string, stats *nullCoercionStats) {
+	w.nullPolicies = policies
+	w.nullFills = nil
+	w.nullStats = stats
+}
+
+// SetLoadData 设置 LOAD DATA 写入策略
+func (w *MySQLWriter) SetLoadData(policy *loadDataPolicy) {
+	w.loadData = policy
+}
+
+// SetDuplicateKeyIgnore 设置忽略主键/唯一键冲突的行，onIgnored 为每次写入后忽略的行数回调，nil 表示冲突时批次失败
+func (w *MySQLWriter) SetDuplicateKeyIgnore(onIgnored func(rows int64)) {
+	w.onDuplicate = onIgnored
+}
+
+// SetBatchDedup 设置批次内去重策略（nil 表示不去重）
+func (w *MySQLWriter) SetBatchDedup(policy *batchDedupPolicy) {
+	w.dedup = policy
+}
+
+// WholeBatch 配置了批次内去重时需要一次收到整个批次，管道不再按分块流式写入
+func (w *MySQLWriter) WholeBatch() bool {
+	return w.dedup != nil
+}
+
+// SetWriteThreads 并行写入时按写入 goroutine 数扩大连接池（每个 goroutine 一个连接，另留一个给元数据查询）
+func (w *MySQLWriter) SetWriteThreads(threads int) {
+	if threads > 1 {
+		w.db.SetMaxOpenConns(threads + 1)
+		w.db.SetMaxIdleConns(threads)
+	}
+}
+
+// SetTableCharset 设置建表时使用的表默认字符集/排序规则（覆盖源表），charset 为空表示沿用源表
+func (w *MySQLWriter) SetTableCharset(charset, collation string) {
+	w.charset = charset
+	w.collation = collation
 }
 
 // TruncateTable 清空表
@@ -260,6 +510,8 @@ func (w *MySQLWriter) CreateTableLikeWithFields(sourceDB *sql.DB, sourceTable st
 		newTableDef := fmt.Sprintf("CREATE TABLE `%s`", w.tableName)
 		createSQL = strings.Replace(createSQL, oldTableDef, newTableDef, 1)
 	}
+	createSQL = applyTableCharset(createSQL, w.charset, w.collation)
+	createSQL = applyNullablePolicies(createSQL, w.nullPolicies)
 
 	// 执行创建表
 	ctx2, cancel2 := context.WithTimeout(context.Background(), 10*time.Second)
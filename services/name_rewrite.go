@@ -0,0 +1,69 @@
+package services
+
+import (
+	"fmt"
+	"regexp"
+)
+
+// NameRewriteRule 名称正则改写规则，replacement 支持 $1 / ${name} 捕获组引用
+// 例如 {"pattern": "^prod_(.*)$", "replacement": "staging_$1"} 将 prod_order 映射为 staging_order
+type NameRewriteRule struct {
+	Pattern     string `json:"pattern"`
+	Replacement string `json:"replacement"`
+}
+
+// compile 编译规则，pattern 为空时返回错误
+func (r *NameRewriteRule) compile() (*regexp.Regexp, error) {
+	if r.Pattern == "" {
+		return nil, fmt.Errorf("正则表达式不能为空")
+	}
+	re, err := regexp.Compile(r.Pattern)
+	if err != nil {
+		return nil, fmt.Errorf("正则表达式无效: %w", err)
+	}
+	return re, nil
+}
+
+// ValidateNameRewriteRule 校验改写规则，nil 表示未配置
+func ValidateNameRewriteRule(rule *NameRewriteRule) error {
+	if rule == nil {
+		return nil
+	}
+	_, err := rule.compile()
+	return err
+}
+
+// Rewrite 改写名称，不匹配时返回原名称
+func (r *NameRewriteRule) Rewrite(name string) (string, error) {
+	re, err := r.compile()
+	if err != nil {
+		return "", err
+	}
+	if !re.MatchString(name) {
+		return name, nil
+	}
+	return re.ReplaceAllString(name, r.Replacement), nil
+}
+
+// applyDatabaseNameRule 按规则生成目标库名（只处理未手动修改过库名的选择项）
+func applyDatabaseNameRule(selections []DatabaseSelection, rule *NameRewriteRule) error {
+	if rule == nil {
+		return nil
+	}
+
+	for i := range selections {
+		sel := &selections[i]
+		if sel.IsDatabaseModified || sel.SourceDatabase == "" {
+			continue
+		}
+		target, err := rule.Rewrite(sel.SourceDatabase)
+		if err != nil {
+			return err
+		}
+		if err := ValidateDatabaseName(target); err != nil {
+			return fmt.Errorf("库 %s 改写后的名称无效: %w", sel.SourceDatabase, err)
+		}
+		sel.Database = target
+	}
+	return nil
+}
@@ -0,0 +1,67 @@
+package services
+
+import "testing"
+
+func TestNameRewriteRuleRewrite(t *testing.T) {
+	cases := []struct {
+		rule NameRewriteRule
+		name string
+		want string
+	}{
+		{NameRewriteRule{Pattern: "^prod_(.*)$", Replacement: "staging_$1"}, "prod_order", "staging_order"},
+		{NameRewriteRule{Pattern: `^(\w+)_(\d+)$`, Replacement: "${2}_$1"}, "shard_07", "07_shard"},
+		{NameRewriteRule{Pattern: "^app_(?P<env>[a-z]+)$", Replacement: "${env}_app"}, "app_test", "test_app"},
+		// ${1}x 与 $1x 不同：$1x 引用名为 1x 的捕获组，不存在时为空
+		{NameRewriteRule{Pattern: "^(db)$", Replacement: "${1}x"}, "db", "dbx"},
+		{NameRewriteRule{Pattern: "^(db)$", Replacement: "$1x"}, "db", ""},
+		// 不匹配时返回原名称
+		{NameRewriteRule{Pattern: "^prod_(.*)$", Replacement: "staging_$1"}, "dev_order", "dev_order"},
+		{NameRewriteRule{Pattern: "^prod_(.*)$", Replacement: "staging_$1"}, "", ""},
+		// 未锚定的规则替换所有匹配
+		{NameRewriteRule{Pattern: "-", Replacement: "_"}, "a-b-c", "a_b_c"},
+	}
+	for _, c := range cases {
+		got, err := c.rule.Rewrite(c.name)
+		if err != nil {
+			t.Fatalf("%q.Rewrite(%q) error = %v", c.rule.Pattern, c.name, err)
+		}
+		if got != c.want {
+			t.Errorf("%q -> %q: Rewrite(%q) = %q, want %q", c.rule.Pattern, c.rule.Replacement, c.name, got, c.want)
+		}
+	}
+}
+
+func TestValidateNameRewriteRule(t *testing.T) {
+	if err := ValidateNameRewriteRule(nil); err != nil {
+		t.Errorf("ValidateNameRewriteRule(nil) error = %v", err)
+	}
+	for _, pattern := range []string{"", "(", "[a-"} {
+		if err := ValidateNameRewriteRule(&NameRewriteRule{Pattern: pattern}); err == nil {
+			t.Errorf("ValidateNameRewriteRule(%q) accepted", pattern)
+		}
+	}
+}
+
+func TestApplyDatabaseNameRule(t *testing.T) {
+	rule := &NameRewriteRule{Pattern: "^prod_(.*)$", Replacement: "staging_$1"}
+	selections := []DatabaseSelection{
+		{SourceDatabase: "prod_order", Database: "prod_order"},
+		{SourceDatabase: "prod_user", Database: "custom", IsDatabaseModified: true},
+		{SourceDatabase: "logs", Database: "logs"},
+	}
+	if err := applyDatabaseNameRule(selections, rule); err != nil {
+		t.Fatalf("applyDatabaseNameRule() error = %v", err)
+	}
+	want := []string{"staging_order", "custom", "logs"}
+	for i, sel := range selections {
+		if sel.Database != want[i] {
+			t.Errorf("selection %d: Database = %q, want %q", i, sel.Database, want[i])
+		}
+	}
+
+	// 改写结果不是合法库名时报错
+	invalid := []DatabaseSelection{{SourceDatabase: "prod_order"}}
+	if err := applyDatabaseNameRule(invalid, &NameRewriteRule{Pattern: "^prod_", Replacement: "new."}); err == nil {
+		t.Error("applyDatabaseNameRule() accepted an invalid rewritten name")
+	}
+}
@@ -0,0 +1,246 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/models"
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// isSameMySQLInstance 判断源和目标是否为同一个 MySQL 实例
+func isSameMySQLInstance(source, target *models.DataSource) bool {
+	return strings.EqualFold(strings.TrimSpace(source.MySQLHost()), strings.TrimSpace(target.MySQLHost())) &&
+		source.Port == target.Port
+}
+
+// integerTypes MySQL 整数类型
+var integerTypes = map[string]bool{
+	"tinyint": true, "smallint": true, "mediumint": true, "int": true, "integer": true, "bigint": true,
+}
+
+// NativeCopier 同实例表复制：在服务端执行 INSERT ... SELECT，数据不经过应用
+// 按单列主键分段执行，每段结束后上报进度并检查取消信号
+type NativeCopier struct {
+	db          *sql.DB // 目标连接（同实例，可跨库访问源表）
+	sourceDB    string
+	sourceTable string
+	targetDB    string
+	targetTable string
+	columns     []string
+	pkColumn    string
+	pkIsInteger bool
+	chunkSize   int
+}
+
+// NewNativeCopier 创建同实例复制器
+// metaDB 用于查询源表元数据；源表必须有单列主键，否则返回错误（调用方回退到流式同步）
+func NewNativeCopier(db, metaDB *sql.DB, sourceDB, sourceTable, targetDB, targetTable string, selectedFields []string, chunkSize int) (*NativeCopier, error) {
+	pkColumns, err := getPrimaryKeyColumns(metaDB, sourceDB, sourceTable)
+	if err != nil {
+		return nil, fmt.Errorf("查询主键失败: %w", err)
+	}
+	if len(pkColumns) != 1 {
+		return nil, fmt.Errorf("源表不是单列主键，无法分段复制")
+	}
+
+	var dataType string
+	if err := metaDB.QueryRow(
+		"SELECT DATA_TYPE FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = ?",
+		sourceDB, sourceTable, pkColumns[0]).Scan(&dataType); err != nil {
+		return nil, fmt.Errorf("查询主键类型失败: %w", err)
+	}
+
+	columns := selectedFields
+	if len(columns) == 0 {
+		columns, err = getTableColumnNames(metaDB, sourceDB, sourceTable)
+		if err != nil {
+			return nil, fmt.Errorf("查询字段列表失败: %w", err)
+		}
+	}
+	columns = excludeGeneratedColumns(metaDB, sourceDB, sourceTable, columns) // 生成列不能写入
+
+	if chunkSize <= 0 {
+		chunkSize = 10000
+	}
+
+	return &NativeCopier{
+		db:          db,
+		sourceDB:    sourceDB,
+		sourceTable: sourceTable,
+		targetDB:    targetDB,
+		targetTable: targetTable,
+		columns:     columns,
+		pkColumn:    pkColumns[0],
+		pkIsInteger: integerTypes[strings.ToLower(dataType)],
+		chunkSize:   chunkSize,
+	}, nil
+}
+
+// Run 执行复制，onChunk 在每段完成后回调累计复制行数
+func (c *NativeCopier) Run(ctx context.Context, onChunk func(copied int64)) (int64, error) {
+	quoted := make([]string, len(c.columns))
+	for i, col := range c.columns {
+		quoted[i] = fmt.Sprintf("`%s`", col)
+	}
+	columnList := strings.Join(quoted, ", ")
+	source := fmt.Sprintf("`%s`.`%s`", c.sourceDB, c.sourceTable)
+	target := fmt.Sprintf("`%s`.`%s`", c.targetDB, c.targetTable)
+	pk := fmt.Sprintf("`%s`", c.pkColumn)
+
+	var copied int64
+	var lower interface{}
+
+	for {
+		select {
+		case <-ctx.Done():
+			return copied, ErrPipelinePaused
+		default:
+		}
+
+		// 1. 计算本段主键上界
+		boundaryQuery := fmt.Sprintf("SELECT MAX(%s) FROM (SELECT %s FROM %s", pk, pk, source)
+		var args []interface{}
+		if lower != nil {
+			boundaryQuery += fmt.Sprintf(" WHERE %s > ?", pk)
+			args = append(args, lower)
+		}
+		boundaryQuery += fmt.Sprintf(" ORDER BY %s LIMIT %d) t", pk, c.chunkSize)
+
+		var upperRaw sql.NullString
+		if err := c.db.QueryRowContext(ctx, boundaryQuery, args...).Scan(&upperRaw); err != nil {
+			return copied, fmt.Errorf("计算分段边界失败: %w", err)
+		}
+		if !upperRaw.Valid {
+			return copied, nil // 没有更多数据
+		}
+		upper, err := c.parsePK(upperRaw.String)
+		if err != nil {
+			return copied, err
+		}
+
+		// 2. 服务端复制本段
+		insertQuery := fmt.Sprintf("INSERT INTO %s (%s) SELECT %s FROM %s WHERE %s <= ?",
+			target, columnList, columnList, source, pk)
+		insertArgs := []interface{}{upper}
+		if lower != nil {
+			insertQuery += fmt.Sprintf(" AND %s > ?", pk)
+			insertArgs = append(insertArgs, lower)
+		}
+
+		result, err := c.db.ExecContext(ctx, insertQuery, insertArgs...)
+		if err != nil {
+			if ctx.Err() != nil {
+				return copied, ErrPipelinePaused
+			}
+			return copied, fmt.Errorf("INSERT ... SELECT 失败: %w", err)
+		}
+		affected, _ := result.RowsAffected()
+		copied += affected
+		lower = upper
+
+		if onChunk != nil {
+			onChunk(copied)
+		}
+	}
+}
+
+// parsePK 解析主键边界值，整数主键按数值传参，避免按字符串比较
+func (c *NativeCopier) parsePK(raw string) (interface{}, error) {
+	if !c.pkIsInteger {
+		return raw, nil
+	}
+	if v, err := strconv.ParseInt(raw, 10, 64); err == nil {
+		return v, nil
+	}
+	v, err := strconv.ParseUint(raw, 10, 64)
+	if err != nil {
+		return nil, fmt.Errorf("解析主键值 %s 失败: %w", raw, err)
+	}
+	return v, nil
+}
+
+// getTableColumnNames 查询表的全部字段（按定义顺序）
+func getTableColumnNames(db *sql.DB, database, table string) ([]string, error) {
+	rows, err := db.Query(
+		"SELECT COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+		database, table)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	var columns []string
+	for rows.Next() {
+		var column string
+		if err := rows.Scan(&column); err != nil {
+			return nil, err
+		}
+		columns = append(columns, column)
+	}
+	return columns, rows.Err()
+}
+
+// tryNativeCopy 尝试同实例快速复制
+// 返回 handled=false 表示不满足条件，调用方继续走流式同步；handled=true 时 err 为复制结果
+func (e *SyncEngine) tryNativeCopy(ctx context.Context, taskID, unitName string, sourceConn *models.DataSource, targetConn TargetConnWithPassword,
+	targetSQL, sourceSQL *sql.DB, sourceDB, sourceTable, targetDB, targetTable string, selectedFields []string,
+	chunkSize int, hasTransformers bool, totalRecords int64) (bool, error) {
+	targetName := targetConn.Conn.Name
+
+	if !isSameMySQLInstance(sourceConn, targetConn.Conn) {
+		e.logService.Info(taskID, fmt.Sprintf("目标 %s 与源不在同一实例，表 %s 使用流式同步", targetName, unitName))
+		return false, nil
+	}
+	if hasTransformers {
+		e.logService.Info(taskID, fmt.Sprintf("表 %s 配置了转换器/脱敏，无法使用同实例复制，使用流式同步", unitName))
+		return false, nil
+	}
+
+	copier, err := NewNativeCopier(targetSQL, sourceSQL, sourceDB, sourceTable, targetDB, targetTable, selectedFields, chunkSize)
+	if err != nil {
+		e.logService.Info(taskID, fmt.Sprintf("表 %s 无法使用同实例复制（%v），使用流式同步", unitName, err))
+		return false, nil
+	}
+
+	e.logService.Info(taskID, fmt.Sprintf("目标 %s 表 %s 使用同实例复制（INSERT ... SELECT）", targetName, unitName))
+
+	progressManager := GetProgressManager()
+	chunkNum := 0
+	copied, err := copier.Run(ctx, func(copied int64) {
+		chunkNum++
+		progressManager.SendProgress(ProgressMessage{
+			TaskID:       taskID,
+			TargetID:     targetConn.Conn.ID,
+			TargetName:   targetName,
+			UnitName:     unitName,
+			Status:       "running",
+			TotalRecords: totalRecords,
+			Processed:    copied,
+			IsNew:        false,
+		})
+		logMessage := fmt.Sprintf("目标 %s 表 %s 复制分段 %d: %d/%d (%.1f%%)",
+			targetName, unitName, chunkNum, copied, totalRecords, safePercent(copied, totalRecords))
+		e.logService.AddLog(taskID, "info", logMessage, "sync")
+	})
+	if err != nil {
+		if nativeCopyFallback(copied, err) {
+			e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 同实例复制权限不足（%v），使用流式同步", targetName, unitName, err))
+			return false, nil
+		}
+		if err != ErrPipelinePaused {
+			e.logService.Error(taskID, fmt.Sprintf("目标 %s 表 %s 同实例复制失败（已复制 %d 条）: %v", targetName, unitName, copied, err))
+		}
+		return true, err
+	}
+
+	return true, nil
+}
+
+// nativeCopyFallback 同实例复制失败后能否改用流式同步：复制使用目标账号读取源表，
+// 目标账号缺少源库权限（跨库权限不足）时第一段就会失败，单条 INSERT ... SELECT 失败不会写入任何行；
+// 已复制过数据时改用流式同步会重复写入，不回退
+func nativeCopyFallback(copied int64, err error) bool {
+	return copied == 0 && ClassifyError(err) == ErrorCodeAuth
+}
@@ -0,0 +1,35 @@
+package services
+
+import (
+	"fmt"
+	"testing"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+func TestNativeCopyFallback(t *testing.T) {
+	selectDenied := fmt.Errorf("INSERT ... SELECT 失败: %w",
+		&mysql.MySQLError{Number: 1142, Message: "SELECT command denied to user 'target'@'%' for table 'orders'"})
+	dbDenied := fmt.Errorf("计算分段边界失败: %w",
+		&mysql.MySQLError{Number: 1044, Message: "Access denied for user 'target'@'%' to database 'src'"})
+	duplicate := fmt.Errorf("INSERT ... SELECT 失败: %w",
+		&mysql.MySQLError{Number: 1062, Message: "Duplicate entry '1' for key 'PRIMARY'"})
+
+	cases := []struct {
+		name   string
+		copied int64
+		err    error
+		want   bool
+	}{
+		{"select denied on first chunk", 0, selectDenied, true},
+		{"database access denied on first boundary query", 0, dbDenied, true},
+		{"denied after rows were copied", 10000, selectDenied, false},
+		{"non-permission error", 0, duplicate, false},
+		{"paused", 0, ErrPipelinePaused, false},
+	}
+	for _, c := range cases {
+		if got := nativeCopyFallback(c.copied, c.err); got != c.want {
+			t.Errorf("%s: nativeCopyFallback() = %v, want %v", c.name, got, c.want)
+		}
+	}
+}
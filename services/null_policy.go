@@ -0,0 +1,230 @@
+package services
+
+import (
+	"database/sql"
+	"fmt"
+	"regexp"
+	"sort"
+	"strings"
+	"sync"
+)
+
+// 字段 NULL 值处理策略（按目标字段名配置）
+const (
+	NullPolicyFail     = "fail"     // 原样写入 NULL，NOT NULL 字段写入失败（默认，与之前一致）
+	NullPolicyDefault  = "default"  // 写入 NOT NULL 字段时用字段默认值替换 NULL，没有默认值时使用类型零值
+	NullPolicyNullable = "nullable" // 创建目标表时该字段改为允许 NULL（主键字段除外）
+)
+
+var (
+	// createColumnLineRegex 建表语句中的字段定义行：  `name` type ...
+	createColumnLineRegex = regexp.MustCompile("^\\s+`((?:[^`]|``)+)`\\s")
+	// createPrimaryKeyRegex 建表语句中的主键定义
+	createPrimaryKeyRegex = regexp.MustCompile(`(?i)PRIMARY KEY\s*\(([^)]*)\)`)
+	// notNullRegex 字段定义中的 NOT NULL
+	notNullRegex = regexp.MustCompile(`(?i)\sNOT NULL\b`)
+)
+
+// ValidateNullPolicies 校验字段 NULL 值处理配置
+func ValidateNullPolicies(policies map[string]string) error {
+	for column, policy := range policies {
+		if strings.TrimSpace(column) == "" {
+			return fmt.Errorf("null_policies 的字段名不能为空")
+		}
+		switch policy {
+		case NullPolicyFail, NullPolicyDefault, NullPolicyNullable:
+		default:
+			return fmt.Errorf("字段 %s 的 NULL 值处理方式只支持 %s/%s/%s", column, NullPolicyFail, NullPolicyDefault, NullPolicyNullable)
+		}
+	}
+	return nil
+}
+
+// hasNullPolicy 是否有字段配置了指定策略
+func hasNullPolicy(policies map[string]string, policy string) bool {
+	for _, p := range policies {
+		if p == policy {
+			return true
+		}
+	}
+	return false
+}
+
+// nullPolicyColumns 配置了指定策略的字段（按字段名排序）
+func nullPolicyColumns(policies map[string]string, policy string) []string {
+	var columns []string
+	for column, p := range policies {
+		if p == policy {
+			columns = append(columns, column)
+		}
+	}
+	sort.Strings(columns)
+	return columns
+}
+
+// applyNullablePolicies 把配置为 nullable 的字段在建表语句中改为允许 NULL
+// 主键字段必须 NOT NULL，保持不变；没有 nullable 配置时原样返回
+func applyNullablePolicies(createSQL string, policies map[string]string) string {
+	nullable := make(map[string]bool)
+	for column, policy := range policies {
+		if policy == NullPolicyNullable {
+			nullable[strings.ToLower(column)] = true
+		}
+	}
+	if len(nullable) == 0 {
+		return createSQL
+	}
+
+	if m := createPrimaryKeyRegex.FindStringSubmatch(createSQL); m != nil {
+		for _, part := range strings.Split(m[1], ",") {
+			column := strings.Trim(strings.TrimSpace(part), "`")
+			if i := strings.Index(column, "`("); i != -1 { // 前缀索引 `col`(10)
+				column = column[:i]
+			}
+			delete(nullable, strings.ToLower(column))
+		}
+	}
+
+	lines := strings.Split(createSQL, "\n")
+	for i, line := range lines {
+		m := createColumnLineRegex.FindStringSubmatch(line)
+		if m == nil || !nullable[strings.ToLower(strings.ReplaceAll(m[1], "``", "`"))] {
+			continue
+		}
+		if loc := notNullRegex.FindStringIndex(line); loc != nil {
+			lines[i] = line[:loc[0]] + " NULL" + line[loc[1]:]
+		}
+	}
+	return strings.Join(lines, "\n")
+}
+
+// nullFill NOT NULL 字段遇到 NULL 时的替换方式：有默认值时写入 DEFAULT，否则写入类型零值
+type nullFill struct {
+	useDefault bool
+	zero       interface{}
+}
+
+// nullZeroValue 字段类型的零值（日期时间取所有时区都合法的最小值）
+// ENUM 取第一个成员（严格模式下 '' 不是合法的 ENUM 值）；SET 和二进制、字符串类型写入 '' 都合法
+func nullZeroValue(dataType, columnType string) interface{} {
+	switch strings.ToLower(dataType) {
+	case "enum":
+		if member, ok := firstEnumMember(columnType); ok {
+			return member
+		}
+	case "tinyint", "smallint", "mediumint", "int", "integer", "bigint", "decimal", "numeric", "float", "double", "bit", "year":
+		return 0
+	case "date":
+		return "1970-01-02"
+	case "datetime", "timestamp":
+		return "1970-01-02 00:00:00"
+	case "time":
+		return "00:00:00"
+	case "json":
+		return "null"
+	}
+	return ""
+}
+
+// firstEnumMember ENUM 字段类型定义（COLUMN_TYPE，如 enum('a','it''s')）中的第一个成员
+func firstEnumMember(columnType string) (string, bool) {
+	start := strings.Index(columnType, "('")
+	if start == -1 {
+		return "", false
+	}
+	var b strings.Builder
+	for i := start + 2; i < len(columnType); i++ {
+		if columnType[i] != '\'' {
+			b.WriteByte(columnType[i])
+			continue
+		}
+		// 成员中的单引号写作两个单引号
+		if i+1 < len(columnType) && columnType[i+1] == '\'' {
+			b.WriteByte('\'')
+			i++
+			continue
+		}
+		return b.String(), true
+	}
+	return "", false
+}
+
+// loadNullFills 查询目标表中配置为 default 策略且 NOT NULL 的字段，计算替换方式
+func loadNullFills(db *sql.DB, table string, policies map[string]string) (map[string]nullFill, error) {
+	rows, err := db.Query(`SELECT COLUMN_NAME, IS_NULLABLE, COLUMN_DEFAULT, DATA_TYPE, COLUMN_TYPE, EXTRA
+		FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?`, table)
+	if err != nil {
+		return nil, fmt.Errorf("查询目标字段信息失败: %w", err)
+	}
+	defer rows.Close()
+
+	fills := make(map[string]nullFill)
+	for rows.Next() {
+		var name, isNullable, dataType, columnType, extra string
+		var columnDefault sql.NullString
+		if err := rows.Scan(&name, &isNullable, &columnDefault, &dataType, &columnType, &extra); err != nil {
+			return nil, fmt.Errorf("查询目标字段信息失败: %w", err)
+		}
+		if policies[name] != NullPolicyDefault || isNullable == "YES" {
+			continue
+		}
+		// 表达式默认值（如 CURRENT_TIMESTAMP）的 COLUMN_DEFAULT 也不为 NULL，都交给 DEFAULT 关键字处理
+		hasDefault := columnDefault.Valid || strings.Contains(strings.ToUpper(extra), "DEFAULT_GENERATED")
+		fills[name] = nullFill{useDefault: hasDefault, zero: nullZeroValue(dataType, columnType)}
+	}
+	return fills, rows.Err()
+}
+
+// nullCoercionStats 按字段统计被替换的 NULL 值个数（同一单元同一目标的多个 Writer 共享）
+type nullCoercionStats struct {
+	mu     sync.Mutex
+	counts map[string]int64
+}
+
+// newNullCoercionStats 创建 NULL 值替换统计
+func newNullCoercionStats() *nullCoercionStats {
+	return &nullCoercionStats{counts: make(map[string]int64)}
+}
+
+// add 累加字段被替换的个数
+func (s *nullCoercionStats) add(counts map[string]int64) {
+	if s == nil || len(counts) == 0 {
+		return
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	for column, n := range counts {
+		s.counts[column] += n
+	}
+}
+
+// summary 替换统计摘要（按字段名排序），没有替换时返回空
+func (s *nullCoercionStats) summary() (int64, string) {
+	if s == nil {
+		return 0, ""
+	}
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	columns := make([]string, 0, len(s.counts))
+	var total int64
+	for column, n := range s.counts {
+		columns = append(columns, column)
+		total += n
+	}
+	sort.Strings(columns)
+
+	parts := make([]string, len(columns))
+	for i, column := range columns {
+		parts[i] = fmt.Sprintf("%s: %d", column, s.counts[column])
+	}
+	return total, strings.Join(parts, ", ")
+}
+
+// logNullCoercions 单元同步结束后把 NULL 值替换情况写入 verify 日志，便于审计
+func (e *SyncEngine) logNullCoercions(taskID, targetName, unitName string, stats *nullCoercionStats) {
+	if total, detail := stats.summary(); total > 0 {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 按 null_policies 将 %d 个 NULL 替换为默认值（%s）",
+			targetName, unitName, total, detail), "verify")
+	}
+}
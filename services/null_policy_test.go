@@ -0,0 +1,168 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"database/sql/driver"
+	"io"
+	"reflect"
+	"testing"
+)
+
+func TestChecksumColumnsExcludeDefaultNullPolicy(t *testing.T) {
+	policies := map[string]string{
+		"status":  NullPolicyDefault,
+		"remark":  NullPolicyNullable,
+		"created": NullPolicyDefault,
+		"name":    NullPolicyFail,
+	}
+	exclude := nullPolicyColumns(policies, NullPolicyDefault)
+	if want := []string{"created", "status"}; !reflect.DeepEqual(exclude, want) {
+		t.Fatalf("nullPolicyColumns() = %v, want %v", exclude, want)
+	}
+
+	columns := []string{"id", "name", "status", "remark", "created"}
+	got := checksumColumns(columns, append(exclude, "id"))
+	if want := []string{"name", "remark"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("checksumColumns() = %v, want %v", got, want)
+	}
+	if got := nullPolicyColumns(nil, NullPolicyDefault); len(got) != 0 {
+		t.Errorf("nullPolicyColumns(nil) = %v, want empty", got)
+	}
+}
+
+func TestApplyNullablePolicies(t *testing.T) {
+	createSQL := "CREATE TABLE `t` (\n" +
+		"  `id` int NOT NULL AUTO_INCREMENT,\n" +
+		"  `name` varchar(20) NOT NULL,\n" +
+		"  `we``ird` int NOT NULL DEFAULT '0',\n" +
+		"  `code` varchar(10) NOT NULL,\n" +
+		"  `kept` int NOT NULL,\n" +
+		"  PRIMARY KEY (`id`,`code`(5))\n" +
+		") ENGINE=InnoDB"
+	policies := map[string]string{
+		"id":     NullPolicyNullable, // 主键字段保持 NOT NULL
+		"Name":   NullPolicyNullable, // 字段名不区分大小写
+		"we`ird": NullPolicyNullable,
+		"code":   NullPolicyNullable, // 前缀索引主键
+		"kept":   NullPolicyDefault,
+	}
+	want := "CREATE TABLE `t` (\n" +
+		"  `id` int NOT NULL AUTO_INCREMENT,\n" +
+		"  `name` varchar(20) NULL,\n" +
+		"  `we``ird` int NULL DEFAULT '0',\n" +
+		"  `code` varchar(10) NOT NULL,\n" +
+		"  `kept` int NOT NULL,\n" +
+		"  PRIMARY KEY (`id`,`code`(5))\n" +
+		") ENGINE=InnoDB"
+	if got := applyNullablePolicies(createSQL, policies); got != want {
+		t.Errorf("applyNullablePolicies() =\n%s\nwant\n%s", got, want)
+	}
+	if got := applyNullablePolicies(createSQL, map[string]string{"name": NullPolicyFail}); got != createSQL {
+		t.Errorf("applyNullablePolicies() changed the table without nullable policies:\n%s", got)
+	}
+}
+
+func TestFirstEnumMember(t *testing.T) {
+	tests := []struct {
+		columnType string
+		want       string
+		ok         bool
+	}{
+		{"enum('draft','published')", "draft", true},
+		{"enum('it''s','b')", "it's", true},
+		{"enum('a,b','c')", "a,b", true},
+		{"enum('','x')", "", true},
+		{"enum(", "", false},
+		{"varchar(10)", "", false},
+	}
+	for _, tt := range tests {
+		got, ok := firstEnumMember(tt.columnType)
+		if got != tt.want || ok != tt.ok {
+			t.Errorf("firstEnumMember(%q) = %q, %v, want %q, %v", tt.columnType, got, ok, tt.want, tt.ok)
+		}
+	}
+}
+
+// columnsDriver 测试用驱动：任何查询都返回 dsn 对应的 information_schema.COLUMNS 行
+// （COLUMN_NAME, IS_NULLABLE, COLUMN_DEFAULT, DATA_TYPE, COLUMN_TYPE, EXTRA）
+type columnsDriver struct{}
+
+var testColumnRows = map[string][][]driver.Value{}
+
+func init() {
+	sql.Register("columns", columnsDriver{})
+}
+
+func (columnsDriver) Open(dsn string) (driver.Conn, error) { return columnsConn{dsn: dsn}, nil }
+
+// columnsConn 只支持查询的连接
+type columnsConn struct {
+	stubConn
+	dsn string
+}
+
+func (c columnsConn) QueryContext(context.Context, string, []driver.NamedValue) (driver.Rows, error) {
+	return &columnsRows{rows: testColumnRows[c.dsn]}, nil
+}
+
+// columnsRows 内存中的结果集
+type columnsRows struct {
+	rows [][]driver.Value
+}
+
+func (r *columnsRows) Columns() []string {
+	return []string{"COLUMN_NAME", "IS_NULLABLE", "COLUMN_DEFAULT", "DATA_TYPE", "COLUMN_TYPE", "EXTRA"}
+}
+
+func (r *columnsRows) Close() error { return nil }
+
+func (r *columnsRows) Next(dest []driver.Value) error {
+	if len(r.rows) == 0 {
+		return io.EOF
+	}
+	copy(dest, r.rows[0])
+	r.rows = r.rows[1:]
+	return nil
+}
+
+func TestLoadNullFills(t *testing.T) {
+	testColumnRows["null-fills"] = [][]driver.Value{
+		{"status", "NO", nil, "enum", "enum('draft','published')", ""},
+		{"tags", "NO", nil, "set", "set('a','b')", ""},
+		{"count", "NO", nil, "int", "int", ""},
+		{"created", "NO", "CURRENT_TIMESTAMP", "datetime", "datetime", "DEFAULT_GENERATED"},
+		{"updated", "NO", nil, "timestamp", "timestamp", "DEFAULT_GENERATED on update CURRENT_TIMESTAMP"},
+		{"note", "YES", nil, "varchar", "varchar(20)", ""},
+		{"name", "NO", nil, "varchar", "varchar(20)", ""},
+	}
+	db, err := sql.Open("columns", "null-fills")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer db.Close()
+
+	policies := map[string]string{
+		"status":  NullPolicyDefault,
+		"tags":    NullPolicyDefault,
+		"count":   NullPolicyDefault,
+		"created": NullPolicyDefault,
+		"updated": NullPolicyDefault,
+		"note":    NullPolicyDefault, // 允许 NULL，不替换
+		"name":    NullPolicyFail,
+	}
+	fills, err := loadNullFills(db, "t", policies)
+	if err != nil {
+		t.Fatalf("loadNullFills() error = %v", err)
+	}
+	want := map[string]nullFill{
+		"status":  {zero: "draft"},
+		"tags":    {zero: ""},
+		"count":   {zero: 0},
+		"created": {useDefault: true, zero: "1970-01-02 00:00:00"},
+		"updated": {useDefault: true, zero: "1970-01-02 00:00:00"},
+	}
+	if !reflect.DeepEqual(fills, want) {
+		t.Errorf("loadNullFills() = %+v, want %+v", fills, want)
+	}
+}
@@ -0,0 +1,60 @@
+package services
+
+import (
+	"sync"
+	"time"
+)
+
+// progressThrottle 进度推送节流：同一任务在间隔内的多次更新合并为一次推送
+// 被合并的更新由定时器在间隔结束时补推一次，推送内容取当时的最新进度，保证最后状态不丢失
+type progressThrottle struct {
+	mu      sync.Mutex
+	last    map[string]time.Time // taskID -> 上次推送时间
+	pending map[string]bool      // taskID -> 是否已安排补推
+	push    func(taskID string)
+}
+
+// newProgressThrottle 创建进度推送节流器
+func newProgressThrottle(push func(taskID string)) *progressThrottle {
+	return &progressThrottle{
+		last:    make(map[string]time.Time),
+		pending: make(map[string]bool),
+		push:    push,
+	}
+}
+
+// Push 推送进度；immediate 为 true（终态）或未配置间隔时立即推送
+func (t *progressThrottle) Push(taskID string, immediate bool) {
+	interval := NewSettingsService().ProgressInterval()
+
+	t.mu.Lock()
+	elapsed := time.Since(t.last[taskID])
+	if immediate || interval <= 0 || elapsed >= interval {
+		t.last[taskID] = time.Now()
+		t.mu.Unlock()
+		t.push(taskID)
+		return
+	}
+	if t.pending[taskID] {
+		// 已安排补推，本次更新合并进去
+		t.mu.Unlock()
+		return
+	}
+	t.pending[taskID] = true
+	t.mu.Unlock()
+
+	time.AfterFunc(interval-elapsed, func() {
+		t.mu.Lock()
+		delete(t.pending, taskID)
+		t.last[taskID] = time.Now()
+		t.mu.Unlock()
+		t.push(taskID)
+	})
+}
+
+// Forget 清除任务的节流状态
+func (t *progressThrottle) Forget(taskID string) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	delete(t.last, taskID)
+}
@@ -0,0 +1,231 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/models"
+	"fmt"
+	"regexp"
+	"strings"
+	"time"
+)
+
+// querySourceAlias 查询源包装为派生表时使用的别名
+const querySourceAlias = "_dt_query"
+
+var (
+	// querySourceStartRegex 查询源必须以 SELECT 或 WITH（CTE）开头
+	querySourceStartRegex = regexp.MustCompile(`(?i)^(SELECT|WITH)\b`)
+	// querySourceForbiddenRegex 会写数据或加锁的子句
+	querySourceForbiddenRegex = regexp.MustCompile(`(?i)\b(INTO\s+(OUTFILE|DUMPFILE|@)|FOR\s+UPDATE|FOR\s+SHARE|LOCK\s+IN\s+SHARE\s+MODE)\b`)
+)
+
+// ValidateSourceQuery 校验查询源 SQL：只允许单条只读 SELECT（可带 WITH），不能写文件、赋值变量或加锁
+func ValidateSourceQuery(query string) error {
+	query = normalizeSourceQuery(query)
+	if query == "" {
+		return fmt.Errorf("查询语句不能为空")
+	}
+	if strings.Contains(query, ";") {
+		return fmt.Errorf("查询语句只能包含一条 SQL")
+	}
+	if !querySourceStartRegex.MatchString(query) {
+		return fmt.Errorf("查询语句必须以 SELECT 或 WITH 开头")
+	}
+	if match := querySourceForbiddenRegex.FindString(query); match != "" {
+		return fmt.Errorf("查询语句不能包含 %s", strings.ToUpper(match))
+	}
+	return nil
+}
+
+// ValidateSourceQueries 校验所有查询源单元，查询源没有 binlog，只支持全量同步
+func ValidateSourceQueries(selections []DatabaseSelection, syncMode string) error {
+	for _, sel := range selections {
+		for _, tbl := range sel.Tables {
+			if tbl.SourceQuery == "" {
+				continue
+			}
+			if syncMode != "full" {
+				return fmt.Errorf("查询源 %s 只支持全量同步", tbl.SourceTable)
+			}
+			if err := ValidateSourceQuery(tbl.SourceQuery); err != nil {
+				return fmt.Errorf("查询源 %s 无效: %w", tbl.SourceTable, err)
+			}
+		}
+	}
+	return nil
+}
+
+// normalizeSourceQuery 去掉首尾空白和末尾分号
+func normalizeSourceQuery(query string) string {
+	return strings.TrimSpace(strings.TrimRight(strings.TrimSpace(query), ";"))
+}
+
+// querySourceFrom 查询源包装为派生表，用于 FROM 子句
+func querySourceFrom(query string) string {
+	return fmt.Sprintf("(%s) AS `%s`", normalizeSourceQuery(query), querySourceAlias)
+}
+
+// getSourceQuery 获取单元配置的查询源 SQL，普通表返回空
+func (e *SyncEngine) getSourceQuery(config *TaskConfig, sourceDB, sourceTable string) string {
+	if config == nil {
+		return ""
+	}
+	for _, dbSel := range config.SelectedDatabases {
+		if dbSel.SourceDatabase == sourceDB {
+			for _, tbl := range dbSel.Tables {
+				if tbl.SourceTable == sourceTable {
+					return tbl.SourceQuery
+				}
+			}
+		}
+	}
+	return ""
+}
+
+// newUnitReader 创建单元的 Reader：查询源读取查询结果集，普通表读取源表（支持字段选择）
+func newUnitReader(source *models.DataSource, password, sourceDB, sourceTable, sourceQuery string, batchSize int, selectedFields []string,
+	timeZone string) (*MySQLReader, error) {
+	if sourceQuery != "" {
+		return NewMySQLQueryReader(source.MySQLHost(), source.Port, source.Username, password, sourceDB, sourceTable, sourceQuery, batchSize, timeZone)
+	}
+	return NewMySQLReaderWithFields(source.MySQLHost(), source.Port, source.Username, password, sourceDB, sourceTable, batchSize, selectedFields, timeZone)
+}
+
+// buildQueryTableSQL 根据查询结果集的列元数据生成建表语句（不执行查询本身，只取 LIMIT 0 的元数据）
+// 结果集没有主键和索引信息，目标表只包含字段定义
+func buildQueryTableSQL(sourceDB *sql.DB, query, targetTable, charset, collation string) (string, error) {
+	ctx, cancel := context.WithTimeout(context.Background(), 30*time.Second)
+	defer cancel()
+
+	rows, err := sourceDB.QueryContext(ctx, fmt.Sprintf("SELECT * FROM %s LIMIT 0", querySourceFrom(query)))
+	if err != nil {
+		return "", fmt.Errorf("获取查询结果集结构失败: %w", err)
+	}
+	defer rows.Close()
+
+	columnTypes, err := rows.ColumnTypes()
+	if err != nil {
+		return "", fmt.Errorf("获取查询结果集结构失败: %w", err)
+	}
+
+	seen := make(map[string]bool, len(columnTypes))
+	defs := make([]string, 0, len(columnTypes))
+	for _, ct := range columnTypes {
+		name := ct.Name()
+		if seen[strings.ToLower(name)] {
+			return "", fmt.Errorf("查询结果集中字段 %s 重复，请使用别名区分", name)
+		}
+		seen[strings.ToLower(name)] = true
+
+		def := fmt.Sprintf("`%s` %s", strings.ReplaceAll(name, "`", "``"), queryColumnType(ct))
+		if nullable, ok := ct.Nullable(); ok && !nullable {
+			def += " NOT NULL"
+		} else {
+			def += " NULL"
+		}
+		defs = append(defs, def)
+	}
+
+	createSQL := fmt.Sprintf("CREATE TABLE `%s` (\n  %s\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4", targetTable, strings.Join(defs, ",\n  "))
+	return applyTableCharset(createSQL, charset, collation), nil
+}
+
+// queryColumnType 按驱动返回的列元数据推断字段类型，长度未知的字符串/二进制类型使用 LONGTEXT/LONGBLOB 避免截断
+func queryColumnType(ct *sql.ColumnType) string {
+	typeName := strings.ToUpper(ct.DatabaseTypeName())
+	unsigned := false
+	if strings.HasPrefix(typeName, "UNSIGNED ") {
+		unsigned = true
+		typeName = strings.TrimPrefix(typeName, "UNSIGNED ")
+	}
+
+	switch typeName {
+	case "TINYINT", "SMALLINT", "MEDIUMINT", "INT", "BIGINT", "FLOAT", "DOUBLE":
+		if unsigned {
+			return typeName + " UNSIGNED"
+		}
+		return typeName
+	case "DECIMAL":
+		if precision, scale, ok := ct.DecimalSize(); ok && precision > 0 {
+			return fmt.Sprintf("DECIMAL(%d,%d)", precision, scale)
+		}
+		return "DECIMAL(65,30)"
+	case "DATETIME", "TIMESTAMP", "TIME":
+		if _, fsp, ok := ct.DecimalSize(); ok && fsp > 0 && fsp <= 6 {
+			return fmt.Sprintf("%s(%d)", typeName, fsp)
+		}
+		return typeName
+	case "DATE", "YEAR", "JSON", "GEOMETRY":
+		return typeName
+	case "BIT":
+		return "BIT(64)"
+	case "CHAR", "VARCHAR":
+		if length, ok := ct.Length(); ok && length > 0 && length <= 16383 {
+			return fmt.Sprintf("VARCHAR(%d)", length)
+		}
+		return "LONGTEXT"
+	case "BINARY", "VARBINARY":
+		if length, ok := ct.Length(); ok && length > 0 && length <= 65535 {
+			return fmt.Sprintf("VARBINARY(%d)", length)
+		}
+		return "LONGBLOB"
+	case "TINYBLOB", "BLOB", "MEDIUMBLOB", "LONGBLOB":
+		return "LONGBLOB"
+	}
+	// TEXT 系列、ENUM/SET（元数据不含可选值）及其他未知类型
+	return "LONGTEXT"
+}
+
+// createQueryTargetTable 按查询结果集结构创建目标表（目标表已存在时不修改结构）
+func (e *SyncEngine) createQueryTargetTable(taskID, unitName, targetName string, reader *MySQLReader, writer *MySQLWriter) error {
+	createSQL, err := buildQueryTableSQL(reader.GetDB(), reader.SourceQuery(), writer.tableName, writer.charset, writer.collation)
+	if err != nil {
+		return err
+	}
+	createSQL = applyNullablePolicies(createSQL, writer.nullPolicies)
+
+	ctx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+	defer cancel()
+	if _, err := writer.GetDB().ExecContext(ctx, createSQL); err != nil {
+		return fmt.Errorf("创建表失败: %w", err)
+	}
+	e.logService.Info(taskID, fmt.Sprintf("目标 %s: 按查询结果集结构创建表 %s", targetName, unitName))
+	return nil
+}
+
+// ensureQueryTargetTable 查询源的目标表不存在时按结果集结构创建
+func (e *SyncEngine) ensureQueryTargetTable(taskID, unitName, targetName string, reader *MySQLReader, writer *MySQLWriter) error {
+	var count int
+	err := writer.GetDB().QueryRow("SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = DATABASE() AND table_name = ?",
+		writer.tableName).Scan(&count)
+	if err == nil && count > 0 {
+		return nil
+	}
+	return e.createQueryTargetTable(taskID, unitName, targetName, reader, writer)
+}
+
+// applyQueryTableStrategy 查询源按表存在策略处理目标表：drop 重建、truncate 清空，其余策略只在表不存在时创建
+// 结果集没有可对比的源表结构，不做 ALTER 对比
+func (e *SyncEngine) applyQueryTableStrategy(taskID, unitName string, config *TaskConfig, reader *MySQLReader, writer *MySQLWriter,
+	targetDS *models.DataSource) error {
+	strategy := config.SyncConfig.TableExistsStrategy
+	if config.SyncConfig.SyncStructureOnly {
+		strategy = ""
+	}
+
+	if strategy == "drop" {
+		if err := writer.DropTable(); err != nil {
+			return fmt.Errorf("目标 %s 删除表失败: %w", targetDS.Name, err)
+		}
+	}
+	if err := e.ensureQueryTargetTable(taskID, unitName, targetDS.Name, reader, writer); err != nil {
+		return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
+	}
+	if strategy == "truncate" {
+		if err := writer.TruncateTable(); err != nil {
+			return fmt.Errorf("目标 %s 清空表失败: %w", targetDS.Name, err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,36 @@
+package services
+
+import "fmt"
+
+// ValidateMaxRecordsPerUnit 校验每单元最大同步行数，0 表示不限制
+// 增量同步会在全量阶段之后继续同步所有变更，上限没有意义，因此只支持全量同步
+func ValidateMaxRecordsPerUnit(maxRecords int64, syncMode string) error {
+	if maxRecords < 0 {
+		return fmt.Errorf("max_records_per_unit 不能为负数")
+	}
+	if maxRecords > 0 && syncMode != "full" {
+		return fmt.Errorf("max_records_per_unit 只支持全量同步")
+	}
+	return nil
+}
+
+// capRecords 按上限截断行数，maxRecords <= 0 表示不限制
+func capRecords(count, maxRecords int64) int64 {
+	if maxRecords > 0 && count > maxRecords {
+		return maxRecords
+	}
+	return count
+}
+
+// applyRecordLimit 为 Reader 设置行数上限：普通表按主键排序读取，保证读到的是确定的前 N 行（校验时按同样顺序取源数据）
+// 没有主键的表和查询源按原始顺序读取
+func applyRecordLimit(reader *MySQLReader, maxRecords int64, sourceDB, sourceTable string) {
+	if maxRecords <= 0 {
+		return
+	}
+	var orderColumns []string
+	if reader.SourceQuery() == "" {
+		orderColumns, _ = getPrimaryKeyColumns(reader.GetDB(), sourceDB, sourceTable)
+	}
+	reader.SetRecordLimit(maxRecords, orderColumns)
+}
@@ -0,0 +1,261 @@
+package services
+
+import (
+	"database/sql"
+	"fmt"
+	"strings"
+)
+
+// 同步前的字段兼容性检查方式（对比源表和已存在的目标表）
+const (
+	SchemaCheckWarn  = "warn"  // 不兼容的字段写入 verify 日志，照常同步
+	SchemaCheckBlock = "block" // 存在不兼容的字段时该单元初始化失败，不清空、不写入目标表
+)
+
+// ValidateSchemaCheck 校验字段兼容性检查方式，空值表示不检查
+func ValidateSchemaCheck(mode string) error {
+	switch mode {
+	case "", SchemaCheckWarn, SchemaCheckBlock:
+		return nil
+	}
+	return fmt.Errorf("schema_check 只支持 %s/%s", SchemaCheckWarn, SchemaCheckBlock)
+}
+
+// columnMeta 字段元数据（information_schema.COLUMNS）
+type columnMeta struct {
+	Name              string
+	DataType          string
+	ColumnType        string
+	Nullable          bool
+	HasDefault        bool
+	Extra             string
+	CharLength        sql.NullInt64
+	NumericPrecision  sql.NullInt64
+	NumericScale      sql.NullInt64
+	DatetimePrecision sql.NullInt64
+}
+
+// unsigned 是否为无符号数值类型
+func (c columnMeta) unsigned() bool {
+	return strings.Contains(strings.ToLower(c.ColumnType), "unsigned")
+}
+
+// loadColumnMeta 查询当前库中指定表的字段元数据（按字段顺序），表不存在时返回空
+func loadColumnMeta(db *sql.DB, table string) ([]columnMeta, error) {
+	rows, err := db.Query(`SELECT COLUMN_NAME, DATA_TYPE, COLUMN_TYPE, IS_NULLABLE, COLUMN_DEFAULT, EXTRA,
+			CHARACTER_MAXIMUM_LENGTH, NUMERIC_PRECISION, NUMERIC_SCALE, DATETIME_PRECISION
+		FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION`, table)
+	if err != nil {
+		return nil, fmt.Errorf("查询字段信息失败: %w", err)
+	}
+	defer rows.Close()
+
+	var columns []columnMeta
+	for rows.Next() {
+		var c columnMeta
+		var isNullable string
+		var columnDefault sql.NullString
+		if err := rows.Scan(&c.Name, &c.DataType, &c.ColumnType, &isNullable, &columnDefault, &c.Extra,
+			&c.CharLength, &c.NumericPrecision, &c.NumericScale, &c.DatetimePrecision); err != nil {
+			return nil, fmt.Errorf("查询字段信息失败: %w", err)
+		}
+		c.DataType = strings.ToLower(c.DataType)
+		c.Nullable = isNullable == "YES"
+		c.HasDefault = columnDefault.Valid || strings.Contains(strings.ToUpper(c.Extra), "DEFAULT_GENERATED")
+		columns = append(columns, c)
+	}
+	return columns, rows.Err()
+}
+
+// 整数类型按取值范围排序
+var integerRank = map[string]int{"tinyint": 1, "smallint": 2, "mediumint": 3, "int": 4, "integer": 4, "bigint": 5}
+
+// integerDigits 整数类型的最大十进制位数
+var integerDigits = map[string]int64{"tinyint": 3, "smallint": 5, "mediumint": 8, "int": 10, "integer": 10, "bigint": 20}
+
+// columnFamily 字段类型所属大类，不同大类之间的写入视为不兼容
+func columnFamily(dataType string) string {
+	switch dataType {
+	case "tinyint", "smallint", "mediumint", "int", "integer", "bigint":
+		return "integer"
+	case "decimal", "numeric", "float", "double", "real":
+		return "decimal"
+	case "char", "varchar", "tinytext", "text", "mediumtext", "longtext", "enum", "set", "json":
+		return "string"
+	case "binary", "varbinary", "tinyblob", "blob", "mediumblob", "longblob":
+		return "binary"
+	case "date", "datetime", "timestamp", "time", "year":
+		return "temporal"
+	}
+	return dataType
+}
+
+// compareColumnTypes 检查源字段写入目标字段是否有数据损失风险，返回风险说明，兼容时返回空
+func compareColumnTypes(src, dst columnMeta) string {
+	if strings.EqualFold(src.ColumnType, dst.ColumnType) {
+		return ""
+	}
+	srcFamily, dstFamily := columnFamily(src.DataType), columnFamily(dst.DataType)
+
+	switch {
+	case srcFamily == "integer" && dstFamily == "integer":
+		if integerRank[dst.DataType] < integerRank[src.DataType] {
+			return "整数范围缩小，超出范围的值写入会失败"
+		}
+		if integerRank[dst.DataType] == integerRank[src.DataType] && src.unsigned() && !dst.unsigned() {
+			return "无符号改为有符号，超过有符号上限的值写入会失败"
+		}
+		if !src.unsigned() && dst.unsigned() {
+			return "有符号改为无符号，负数写入会失败"
+		}
+		return ""
+
+	case srcFamily == "integer" && dstFamily == "decimal":
+		if dst.DataType == "decimal" || dst.DataType == "numeric" {
+			if dst.NumericPrecision.Int64-dst.NumericScale.Int64 < integerDigits[src.DataType] {
+				return "目标小数的整数位不足，较大的值写入会失败"
+			}
+		}
+		return ""
+
+	case srcFamily == "decimal" && dstFamily == "integer":
+		return "小数写入整数字段，小数部分会被舍入"
+
+	case srcFamily == "decimal" && dstFamily == "decimal":
+		if src.DataType == "double" && dst.DataType == "float" {
+			return "DOUBLE 改为 FLOAT，精度降低"
+		}
+		if (src.DataType == "float" || src.DataType == "double") && dst.DataType != "float" && dst.DataType != "double" {
+			return "浮点数写入定点小数，超出精度的部分会被舍入"
+		}
+		if src.NumericPrecision.Valid && dst.NumericPrecision.Valid && dst.DataType != "float" && dst.DataType != "double" {
+			if dst.NumericPrecision.Int64-dst.NumericScale.Int64 < src.NumericPrecision.Int64-src.NumericScale.Int64 {
+				return "整数位减少，较大的值写入会失败"
+			}
+			if dst.NumericScale.Int64 < src.NumericScale.Int64 {
+				return "小数位减少，超出的小数位会被舍入"
+			}
+		}
+		return ""
+
+	case srcFamily == "string" && dstFamily == "string", srcFamily == "binary" && dstFamily == "binary":
+		if dst.DataType == "enum" || dst.DataType == "set" {
+			return "目标 ENUM/SET 的可选值与源字段不同，不在可选值中的值写入会失败"
+		}
+		if src.CharLength.Valid && dst.CharLength.Valid && dst.CharLength.Int64 < src.CharLength.Int64 {
+			return fmt.Sprintf("长度从 %d 缩小到 %d，超长的值写入会失败", src.CharLength.Int64, dst.CharLength.Int64)
+		}
+		return ""
+
+	case (srcFamily == "integer" || srcFamily == "decimal") && dstFamily == "string":
+		if dst.CharLength.Valid && src.DataType != "float" && src.DataType != "double" {
+			digits := integerDigits[src.DataType]
+			if srcFamily == "decimal" {
+				digits = src.NumericPrecision.Int64 + 2 // 符号和小数点
+			}
+			if dst.CharLength.Int64 < digits {
+				return fmt.Sprintf("目标长度 %d 不足以存放数值，较大的值写入会失败", dst.CharLength.Int64)
+			}
+		}
+		return ""
+
+	case srcFamily == "temporal" && dstFamily == "temporal":
+		if src.DataType == dst.DataType || (src.DataType == "datetime" && dst.DataType == "timestamp") ||
+			(src.DataType == "timestamp" && dst.DataType == "datetime") {
+			if src.DataType == "datetime" && dst.DataType == "timestamp" {
+				return "DATETIME 改为 TIMESTAMP，1970~2038 范围外的值写入会失败"
+			}
+			if dst.DatetimePrecision.Int64 < src.DatetimePrecision.Int64 {
+				return "小数秒精度降低，会被舍入"
+			}
+			return ""
+		}
+		if src.DataType == "date" && (dst.DataType == "datetime" || dst.DataType == "timestamp") {
+			return ""
+		}
+		return fmt.Sprintf("%s 改为 %s，部分日期时间信息会丢失", strings.ToUpper(src.DataType), strings.ToUpper(dst.DataType))
+	}
+
+	if srcFamily != dstFamily {
+		return "类型不兼容，写入可能失败或被转换"
+	}
+	return ""
+}
+
+// compareTableColumns 对比源字段和目标字段，返回不兼容项说明（字段名: 说明）
+// selectedFields 为空表示同步所有源字段；nullPolicies 中 default 策略的字段不报告 NULL 写入 NOT NULL 的问题
+func compareTableColumns(source, target []columnMeta, selectedFields []string, nullPolicies map[string]string) []string {
+	targetByName := make(map[string]columnMeta, len(target))
+	for _, c := range target {
+		targetByName[strings.ToLower(c.Name)] = c
+	}
+
+	var issues []string
+	written := make(map[string]bool, len(source))
+	for _, src := range source {
+		if len(selectedFields) > 0 && !contains(selectedFields, src.Name) {
+			continue
+		}
+		written[strings.ToLower(src.Name)] = true
+		dst, ok := targetByName[strings.ToLower(src.Name)]
+		if !ok {
+			issues = append(issues, fmt.Sprintf("%s: 目标表缺少该字段，写入会失败", src.Name))
+			continue
+		}
+		if risk := compareColumnTypes(src, dst); risk != "" {
+			issues = append(issues, fmt.Sprintf("%s: %s → %s，%s", src.Name, src.ColumnType, dst.ColumnType, risk))
+		}
+		if src.Nullable && !dst.Nullable && nullPolicies[src.Name] != NullPolicyDefault {
+			issues = append(issues, fmt.Sprintf("%s: 源字段允许 NULL，目标为 NOT NULL，NULL 值写入会失败", src.Name))
+		}
+	}
+
+	// 不写入的目标字段必须能自动取值
+	for _, dst := range target {
+		if written[strings.ToLower(dst.Name)] || dst.Nullable || dst.HasDefault {
+			continue
+		}
+		extra := strings.ToUpper(dst.Extra)
+		if strings.Contains(extra, "AUTO_INCREMENT") || strings.Contains(extra, "GENERATED") {
+			continue
+		}
+		issues = append(issues, fmt.Sprintf("%s: 源表没有该字段，目标为 NOT NULL 且没有默认值，写入会失败", dst.Name))
+	}
+	return issues
+}
+
+// checkSchemaCompatibility 同步前对比源表和已存在的目标表字段，不兼容项写入 verify 日志；block 模式下有不兼容项时返回错误
+// 目标表不存在（将按源表创建）时不检查
+func (e *SyncEngine) checkSchemaCompatibility(taskID, unitName, targetName, mode string, reader *MySQLReader, writer *MySQLWriter,
+	sourceTable string, selectedFields []string, nullPolicies map[string]string) error {
+	if mode == "" {
+		return nil
+	}
+
+	target, err := loadColumnMeta(writer.GetDB(), writer.tableName)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 字段兼容性检查失败: %v", targetName, unitName, err))
+		return nil
+	}
+	if len(target) == 0 {
+		return nil
+	}
+	source, err := loadColumnMeta(reader.GetDB(), sourceTable)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 字段兼容性检查失败: %v", targetName, unitName, err))
+		return nil
+	}
+
+	issues := compareTableColumns(source, target, selectedFields, nullPolicies)
+	if len(issues) == 0 {
+		e.logService.AddLog(taskID, "info", fmt.Sprintf("目标 %s 表 %s 字段兼容性检查通过", targetName, unitName), "verify")
+		return nil
+	}
+	for _, issue := range issues {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 字段不兼容 %s", targetName, unitName, issue), "verify")
+	}
+	if mode == SchemaCheckBlock {
+		return fmt.Errorf("目标 %s 表 %s 有 %d 个字段与源表不兼容（详见 verify 日志）", targetName, unitName, len(issues))
+	}
+	return nil
+}
@@ -0,0 +1,37 @@
+package services
+
+import (
+	"fmt"
+	"net/url"
+)
+
+// 同步时读写 TIMESTAMP 使用的会话时区
+const (
+	TimestampModeUTC    = "utc"    // 源和目标会话时区统一为 +00:00，TIMESTAMP 按绝对时间复制（默认）
+	TimestampModeServer = "server" // 使用各自服务器的默认时区，TIMESTAMP 按源端显示的本地时间复制
+)
+
+// ValidateTimestampMode 校验 TIMESTAMP 时区处理方式，空值表示 utc
+func ValidateTimestampMode(mode string) error {
+	switch mode {
+	case "", TimestampModeUTC, TimestampModeServer:
+		return nil
+	}
+	return fmt.Errorf("timestamp_mode 只支持 %s/%s", TimestampModeUTC, TimestampModeServer)
+}
+
+// sessionTimeZone 同步读写连接使用的会话时区，空值表示使用服务器默认时区
+func sessionTimeZone(mode string) string {
+	if mode == TimestampModeServer {
+		return ""
+	}
+	return "+00:00"
+}
+
+// timeZoneDSNParam 设置会话时区的 DSN 参数（驱动在每个新连接上执行 SET time_zone），时区为空时不设置
+func timeZoneDSNParam(timeZone string) string {
+	if timeZone == "" {
+		return ""
+	}
+	return "&time_zone=" + url.QueryEscape("'"+timeZone+"'")
+}
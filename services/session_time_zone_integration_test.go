@@ -0,0 +1,150 @@
+//go:build integration
+
+package services
+
+import (
+	"database/sql"
+	"net"
+	"os"
+	"strconv"
+	"testing"
+	"time"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// 集成测试：需要两台默认时区不同的 MySQL（如 default_time_zone='+08:00' 和 '-05:00'），
+// 通过 DATATRACE_IT_SOURCE_DSN / DATATRACE_IT_TARGET_DSN 指定（go-sql-driver 格式，含库名），运行：
+//
+//	go test -tags integration -run TestTimestampAcrossServerTimeZones ./services/
+
+// itServer 集成测试使用的 MySQL 连接参数
+type itServer struct {
+	host     string
+	port     int
+	user     string
+	password string
+	database string
+}
+
+// loadITServer 从环境变量读取连接参数，未设置时跳过测试
+func loadITServer(t *testing.T, env string) itServer {
+	t.Helper()
+	dsn := os.Getenv(env)
+	if dsn == "" {
+		t.Skipf("未设置 %s", env)
+	}
+	cfg, err := mysql.ParseDSN(dsn)
+	if err != nil {
+		t.Fatalf("%s: %v", env, err)
+	}
+	host, portStr, err := net.SplitHostPort(cfg.Addr)
+	if err != nil {
+		t.Fatalf("%s: %v", env, err)
+	}
+	port, err := strconv.Atoi(portStr)
+	if err != nil {
+		t.Fatalf("%s: %v", env, err)
+	}
+	return itServer{host: host, port: port, user: cfg.User, password: cfg.Passwd, database: cfg.DBName}
+}
+
+// openUTC 以 +00:00 会话时区打开连接，用于准备和核对数据
+func (s itServer) openUTC(t *testing.T) *sql.DB {
+	t.Helper()
+	db, err := sql.Open("mysql", readerDSN(s.host, s.port, s.user, s.password, s.database, "+00:00"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { db.Close() })
+	return db
+}
+
+// mustExec 执行语句，失败时测试失败
+func mustExec(t *testing.T, db *sql.DB, query string, args ...interface{}) {
+	t.Helper()
+	if _, err := db.Exec(query, args...); err != nil {
+		t.Fatalf("%s: %v", query, err)
+	}
+}
+
+// checkSessionTimeZone 校验连接的会话时区，返回会话相对 UTC 的偏移（秒）
+func checkSessionTimeZone(t *testing.T, db *sql.DB, mode string) int64 {
+	t.Helper()
+	var session, global string
+	var offset int64
+	if err := db.QueryRow("SELECT @@session.time_zone, @@global.time_zone, TIMESTAMPDIFF(SECOND, UTC_TIMESTAMP(), NOW())").
+		Scan(&session, &global, &offset); err != nil {
+		t.Fatal(err)
+	}
+	want := global
+	if tz := sessionTimeZone(mode); tz != "" {
+		want = tz
+	}
+	if session != want {
+		t.Errorf("mode %q: session time_zone = %q, want %q", mode, session, want)
+	}
+	return offset
+}
+
+// TestTimestampAcrossServerTimeZones 源和目标服务器默认时区不同：utc 模式按绝对时间复制 TIMESTAMP，
+// server 模式按源端会话下的字面值复制，绝对时间相差两台服务器的时区差
+func TestTimestampAcrossServerTimeZones(t *testing.T) {
+	source := loadITServer(t, "DATATRACE_IT_SOURCE_DSN")
+	target := loadITServer(t, "DATATRACE_IT_TARGET_DSN")
+	instant := time.Date(2024, 3, 1, 12, 0, 0, 0, time.UTC)
+
+	sourceAdmin := source.openUTC(t)
+	targetAdmin := target.openUTC(t)
+	mustExec(t, sourceAdmin, "DROP TABLE IF EXISTS tz_it")
+	mustExec(t, sourceAdmin, "CREATE TABLE tz_it (id INT PRIMARY KEY, ts TIMESTAMP NULL)")
+	mustExec(t, sourceAdmin, "INSERT INTO tz_it VALUES (1, ?)", instant.Format(time.DateTime))
+	t.Cleanup(func() { sourceAdmin.Exec("DROP TABLE IF EXISTS tz_it") })
+
+	for _, mode := range []string{TimestampModeUTC, TimestampModeServer} {
+		t.Run(mode, func(t *testing.T) {
+			mustExec(t, targetAdmin, "DROP TABLE IF EXISTS tz_it")
+			mustExec(t, targetAdmin, "CREATE TABLE tz_it (id INT PRIMARY KEY, ts TIMESTAMP NULL)")
+			t.Cleanup(func() { targetAdmin.Exec("DROP TABLE IF EXISTS tz_it") })
+
+			timeZone := sessionTimeZone(mode)
+			reader, err := NewMySQLReaderWithFields(source.host, source.port, source.user, source.password, source.database,
+				"tz_it", 10, nil, timeZone)
+			if err != nil {
+				t.Fatal(err)
+			}
+			defer reader.Close()
+			writer, err := NewMySQLWriter(target.host, target.port, target.user, target.password, target.database, "tz_it", timeZone)
+			if err != nil {
+				t.Fatal(err)
+			}
+			defer writer.Close()
+
+			sourceOffset := checkSessionTimeZone(t, reader.GetDB(), mode)
+			targetOffset := checkSessionTimeZone(t, writer.GetDB(), mode)
+			if mode == TimestampModeServer && sourceOffset == targetOffset {
+				t.Skipf("两台服务器默认时区相同（%d 秒），无法验证跨时区复制", sourceOffset)
+			}
+
+			records, err := reader.ReadBatch()
+			if err != nil {
+				t.Fatal(err)
+			}
+			if err := writer.WriteBatch(records); err != nil {
+				t.Fatal(err)
+			}
+
+			var unix int64
+			if err := targetAdmin.QueryRow("SELECT UNIX_TIMESTAMP(ts) FROM tz_it WHERE id = 1").Scan(&unix); err != nil {
+				t.Fatal(err)
+			}
+			want := instant
+			if mode == TimestampModeServer {
+				want = instant.Add(time.Duration(sourceOffset-targetOffset) * time.Second)
+			}
+			if got := time.Unix(unix, 0).UTC(); !got.Equal(want) {
+				t.Errorf("copied %v, want %v (source offset %ds, target offset %ds)", got, want, sourceOffset, targetOffset)
+			}
+		})
+	}
+}
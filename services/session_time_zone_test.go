@@ -0,0 +1,42 @@
+package services
+
+import (
+	"testing"
+
+	"github.com/go-sql-driver/mysql"
+)
+
+// TestConstructorDSNTimeZone 读取器和写入器的连接字符串按 timestamp_mode 设置会话时区：
+// 驱动在每个新连接上把 DSN 中的 time_zone 参数作为 SET time_zone 执行，未设置时沿用服务器默认时区
+func TestConstructorDSNTimeZone(t *testing.T) {
+	builders := map[string]func(host string, port int, username, password, database, timeZone string) string{
+		"reader": readerDSN,
+		"writer": writerDSN,
+	}
+	modes := []struct {
+		mode string
+		want string // time_zone 参数的值，空表示不设置
+	}{
+		{"", "'+00:00'"},
+		{TimestampModeUTC, "'+00:00'"},
+		{TimestampModeServer, ""},
+	}
+	for name, build := range builders {
+		for _, m := range modes {
+			for _, host := range []string{"127.0.0.1", "/var/run/mysqld/mysqld.sock"} {
+				dsn := build(host, 3306, "user", "pass", "app", sessionTimeZone(m.mode))
+				cfg, err := mysql.ParseDSN(dsn)
+				if err != nil {
+					t.Fatalf("%s mode %q: ParseDSN(%q) error = %v", name, m.mode, dsn, err)
+				}
+				got, ok := cfg.Params["time_zone"]
+				if got != m.want || ok != (m.want != "") {
+					t.Errorf("%s mode %q host %s: time_zone = %q (set %v), want %q", name, m.mode, host, got, ok, m.want)
+				}
+				if cfg.DBName != "app" || !cfg.ParseTime {
+					t.Errorf("%s mode %q: DSN lost the database or parseTime: %q", name, m.mode, dsn)
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,220 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"encoding/json"
+	"fmt"
+	"net/mail"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+)
+
+// AppSettings 全局设置
+type AppSettings struct {
+	MaxConcurrentTasks     int          `json:"max_concurrent_tasks"`     // 同时运行的任务数上限
+	MetadataTimeoutSeconds int          `json:"metadata_timeout_seconds"` // 元数据查询（库/表列表等）的超时时间
+	ProgressIntervalMs     int          `json:"progress_interval_ms"`     // 进度推送最小间隔，间隔内的更新合并推送，0 表示不节流
+	CompactIntervalHours   int          `json:"compact_interval_hours"`   // 定时压缩元数据库的间隔（小时），0 表示不定时压缩
+	SMTP                   SMTPSettings `json:"smtp"`                     // 任务通知邮件发送配置，host 为空表示不发送邮件
+}
+
+// SMTPSettings 邮件服务器配置（账号密码引用凭据，密码不以明文保存在设置中）
+type SMTPSettings struct {
+	Host         string `json:"host"`
+	Port         int    `json:"port"`
+	From         string `json:"from"`          // 发件人地址
+	CredentialID string `json:"credential_id"` // 登录凭据，为空表示不认证
+}
+
+// DefaultSettings 首次启动时使用的默认设置
+func DefaultSettings() AppSettings {
+	return AppSettings{
+		MaxConcurrentTasks:     10,
+		MetadataTimeoutSeconds: 60,
+		ProgressIntervalMs:     200,
+		SMTP:                   SMTPSettings{Port: 25},
+	}
+}
+
+// Validate 校验设置
+func (s AppSettings) Validate() error {
+	if s.MaxConcurrentTasks < 1 {
+		return fmt.Errorf("max_concurrent_tasks 必须大于等于 1")
+	}
+	if s.MetadataTimeoutSeconds < 1 || s.MetadataTimeoutSeconds > 3600 {
+		return fmt.Errorf("metadata_timeout_seconds 必须在 1~3600 之间")
+	}
+	if s.ProgressIntervalMs < 0 || s.ProgressIntervalMs > 10000 {
+		return fmt.Errorf("progress_interval_ms 必须在 0~10000 之间")
+	}
+	if s.CompactIntervalHours < 0 || s.CompactIntervalHours > 8760 {
+		return fmt.Errorf("compact_interval_hours 必须在 0~8760 之间")
+	}
+	if s.SMTP.Host != "" {
+		if s.SMTP.Port < 1 || s.SMTP.Port > 65535 {
+			return fmt.Errorf("smtp.port 必须在 1~65535 之间")
+		}
+		if _, err := mail.ParseAddress(s.SMTP.From); err != nil {
+			return fmt.Errorf("smtp.from 不是有效的邮箱地址")
+		}
+	}
+	return nil
+}
+
+// SettingsService 全局设置服务（数据库持久化，内存缓存）
+type SettingsService struct {
+	mu       sync.RWMutex
+	settings AppSettings
+}
+
+var (
+	settingsInstance *SettingsService
+	settingsOnce     sync.Once
+)
+
+// NewSettingsService 获取设置服务单例（未加载前返回默认设置）
+func NewSettingsService() *SettingsService {
+	settingsOnce.Do(func() {
+		settingsInstance = &SettingsService{settings: DefaultSettings()}
+	})
+	return settingsInstance
+}
+
+// Load 从数据库加载设置，缺失的键写入默认值
+func (s *SettingsService) Load() error {
+	var rows []models.Setting
+	if err := database.DB.Find(&rows).Error; err != nil {
+		return fmt.Errorf("查询设置失败: %w", err)
+	}
+
+	values, err := settingsToMap(DefaultSettings())
+	if err != nil {
+		return err
+	}
+
+	stored := make(map[string]bool, len(rows))
+	for _, row := range rows {
+		if _, known := values[row.Key]; known {
+			values[row.Key] = json.RawMessage(row.Value)
+			stored[row.Key] = true
+		}
+	}
+
+	settings, err := settingsFromMap(values)
+	if err != nil || settings.Validate() != nil {
+		// 数据库中的值已损坏时回退默认值，避免影响启动
+		settings = DefaultSettings()
+		values, _ = settingsToMap(settings)
+		stored = map[string]bool{}
+	}
+
+	for key, value := range values {
+		if !stored[key] {
+			if err := database.DB.Save(&models.Setting{Key: key, Value: string(value)}).Error; err != nil {
+				return fmt.Errorf("写入默认设置失败: %w", err)
+			}
+		}
+	}
+
+	s.mu.Lock()
+	s.settings = settings
+	s.mu.Unlock()
+	return nil
+}
+
+// Get 获取当前设置
+func (s *SettingsService) Get() AppSettings {
+	s.mu.RLock()
+	defer s.mu.RUnlock()
+	return s.settings
+}
+
+// Update 部分更新设置：只修改请求中出现的键，校验通过后持久化
+func (s *SettingsService) Update(changes map[string]json.RawMessage) (AppSettings, error) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+
+	values, err := settingsToMap(s.settings)
+	if err != nil {
+		return s.settings, err
+	}
+
+	var unknown []string
+	for key, value := range changes {
+		if _, known := values[key]; !known {
+			unknown = append(unknown, key)
+			continue
+		}
+		values[key] = value
+	}
+	if len(unknown) > 0 {
+		sort.Strings(unknown)
+		return s.settings, fmt.Errorf("未知的设置项: %s", strings.Join(unknown, ", "))
+	}
+
+	next, err := settingsFromMap(values)
+	if err != nil {
+		return s.settings, fmt.Errorf("设置值类型错误: %w", err)
+	}
+	if err := next.Validate(); err != nil {
+		return s.settings, err
+	}
+
+	// 重新序列化，保证落库的是规范化后的值
+	normalized, _ := settingsToMap(next)
+	for key := range changes {
+		if err := database.DB.Save(&models.Setting{Key: key, Value: string(normalized[key])}).Error; err != nil {
+			return s.settings, fmt.Errorf("保存设置失败: %w", err)
+		}
+	}
+
+	s.settings = next
+	return next, nil
+}
+
+// MaxConcurrentTasks 同时运行的任务数上限
+func (s *SettingsService) MaxConcurrentTasks() int {
+	return s.Get().MaxConcurrentTasks
+}
+
+// MetadataTimeout 元数据查询超时
+func (s *SettingsService) MetadataTimeout() time.Duration {
+	return time.Duration(s.Get().MetadataTimeoutSeconds) * time.Second
+}
+
+// ProgressInterval 进度推送最小间隔
+func (s *SettingsService) ProgressInterval() time.Duration {
+	return time.Duration(s.Get().ProgressIntervalMs) * time.Millisecond
+}
+
+// CompactInterval 定时压缩元数据库的间隔，0 表示不定时压缩
+func (s *SettingsService) CompactInterval() time.Duration {
+	return time.Duration(s.Get().CompactIntervalHours) * time.Hour
+}
+
+// settingsToMap 设置转为 键 -> JSON 值
+func settingsToMap(settings AppSettings) (map[string]json.RawMessage, error) {
+	data, err := json.Marshal(settings)
+	if err != nil {
+		return nil, fmt.Errorf("序列化设置失败: %w", err)
+	}
+	values := make(map[string]json.RawMessage)
+	if err := json.Unmarshal(data, &values); err != nil {
+		return nil, fmt.Errorf("序列化设置失败: %w", err)
+	}
+	return values, nil
+}
+
+// settingsFromMap 键 -> JSON 值 转为设置
+func settingsFromMap(values map[string]json.RawMessage) (AppSettings, error) {
+	var settings AppSettings
+	data, err := json.Marshal(values)
+	if err != nil {
+		return settings, err
+	}
+	err = json.Unmarshal(data, &settings)
+	return settings, err
+}
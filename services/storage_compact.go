@@ -0,0 +1,158 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"log"
+	"strings"
+	"sync"
+	"sync/atomic"
+	"time"
+)
+
+// CompactResult 元数据库压缩结果（大小为 information_schema 中数据、索引和碎片空间之和，字节）
+type CompactResult struct {
+	Tables     []string `json:"tables"`
+	SizeBefore int64    `json:"size_before"`
+	SizeAfter  int64    `json:"size_after"`
+	Reclaimed  int64    `json:"reclaimed"`
+	Duration   string   `json:"duration"`
+}
+
+var (
+	// compactMu 同一时间只允许一次压缩
+	compactMu sync.Mutex
+	// compactRunning 压缩进行中，期间不允许启动任务
+	compactRunning atomic.Bool
+	// compactSchedulerOnce 定时压缩只启动一次
+	compactSchedulerOnce sync.Once
+)
+
+// CompactDatabase 压缩系统元数据库：对所有表执行 OPTIMIZE TABLE，回收任务、日志、执行历史反复增删留下的碎片空间
+// OPTIMIZE 会重建表，只在没有任务运行时执行；已有压缩在进行时直接返回错误
+func CompactDatabase() (*CompactResult, error) {
+	if !compactMu.TryLock() {
+		return nil, fmt.Errorf("压缩正在进行中")
+	}
+	defer compactMu.Unlock()
+
+	compactRunning.Store(true)
+	defer compactRunning.Store(false)
+	if n := runningTaskCount(); n > 0 {
+		return nil, fmt.Errorf("有 %d 个任务正在运行，请在空闲时压缩", n)
+	}
+
+	sqlDB, err := database.DB.DB()
+	if err != nil {
+		return nil, fmt.Errorf("获取数据库实例失败: %w", err)
+	}
+
+	// 统计信息需要实时值（MySQL 8.0 默认缓存 24 小时），固定使用一个连接设置会话变量
+	ctx := context.Background()
+	conn, err := sqlDB.Conn(ctx)
+	if err != nil {
+		return nil, fmt.Errorf("获取数据库连接失败: %w", err)
+	}
+	defer conn.Close()
+	conn.ExecContext(ctx, "SET SESSION information_schema_stats_expiry = 0") // MySQL 5.7 没有该变量，失败时忽略
+
+	sizeQuery := `SELECT IFNULL(SUM(DATA_LENGTH + INDEX_LENGTH + DATA_FREE), 0) FROM information_schema.TABLES
+		WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'`
+	result := &CompactResult{Tables: []string{}}
+	if err := conn.QueryRowContext(ctx, sizeQuery).Scan(&result.SizeBefore); err != nil {
+		return nil, fmt.Errorf("查询数据库大小失败: %w", err)
+	}
+
+	rows, err := conn.QueryContext(ctx, `SELECT TABLE_NAME FROM information_schema.TABLES
+		WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE' ORDER BY TABLE_NAME`)
+	if err != nil {
+		return nil, fmt.Errorf("查询表列表失败: %w", err)
+	}
+	var tables []string
+	for rows.Next() {
+		var table string
+		if err := rows.Scan(&table); err != nil {
+			rows.Close()
+			return nil, fmt.Errorf("查询表列表失败: %w", err)
+		}
+		tables = append(tables, table)
+	}
+	rows.Close()
+
+	start := time.Now()
+	for _, table := range tables {
+		if err := optimizeTable(ctx, conn, table); err != nil {
+			return nil, err
+		}
+		result.Tables = append(result.Tables, table)
+	}
+
+	if err := conn.QueryRowContext(ctx, sizeQuery).Scan(&result.SizeAfter); err != nil {
+		return nil, fmt.Errorf("查询数据库大小失败: %w", err)
+	}
+	result.Reclaimed = result.SizeBefore - result.SizeAfter
+	result.Duration = formatElapsed(time.Since(start))
+	return result, nil
+}
+
+// optimizeTable 执行 OPTIMIZE TABLE 并检查返回的消息（InnoDB 会提示 "recreate + analyze"，属正常信息）
+func optimizeTable(ctx context.Context, conn *sql.Conn, table string) error {
+	rows, err := conn.QueryContext(ctx, fmt.Sprintf("OPTIMIZE TABLE `%s`", table))
+	if err != nil {
+		return fmt.Errorf("压缩表 %s 失败: %w", table, err)
+	}
+	defer rows.Close()
+
+	for rows.Next() {
+		var name, op, msgType, msgText string
+		if err := rows.Scan(&name, &op, &msgType, &msgText); err != nil {
+			return fmt.Errorf("压缩表 %s 失败: %w", table, err)
+		}
+		if strings.EqualFold(msgType, "error") {
+			return fmt.Errorf("压缩表 %s 失败: %s", table, msgText)
+		}
+	}
+	return rows.Err()
+}
+
+// runningTaskCount 正在运行的任务数（执行中的全量/双向同步、增量同步，以及数据库中标记为运行中的任务）
+func runningTaskCount() int64 {
+	execManager := GetExecutionManager()
+	running := int64(len(execManager.ListExecutions()) + len(execManager.ListIncrementalSyncs()))
+	if running > 0 {
+		return running
+	}
+	database.DB.Model(&models.SyncTask{}).Where("is_running = ?", true).Count(&running)
+	return running
+}
+
+// StartCompactScheduler 启动定时压缩：每小时检查一次，距上次压缩超过全局设置 compact_interval_hours 时执行
+// 间隔为 0 时不执行；有任务运行时跳过，下次检查再试
+func StartCompactScheduler() {
+	compactSchedulerOnce.Do(func() {
+		go func() {
+			lastRun := time.Now()
+			ticker := time.NewTicker(time.Hour)
+			defer ticker.Stop()
+			for range ticker.C {
+				interval := NewSettingsService().CompactInterval()
+				if interval <= 0 || time.Since(lastRun) < interval {
+					continue
+				}
+				if runningTaskCount() > 0 {
+					continue
+				}
+				result, err := CompactDatabase()
+				if err != nil {
+					log.Printf("⚠️  定时压缩元数据库失败: %v", err)
+					continue
+				}
+				lastRun = time.Now()
+				log.Printf("✅ 定时压缩元数据库完成: %d -> %d 字节（耗时 %s）", result.SizeBefore, result.SizeAfter, result.Duration)
+			}
+		}()
+	})
+}
@@ -0,0 +1,139 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"strings"
+)
+
+// 校验和校验结果
+const (
+	ChecksumMatch    = "match"    // 行数和校验和一致
+	ChecksumMismatch = "mismatch" // 行数或校验和不一致
+	ChecksumSkipped  = "skipped"  // 配置了转换器或无法确定同步的行，未校验
+	ChecksumError    = "error"    // 查询字段或计算校验和出错
+)
+
+// TableChecksum 表数据校验和
+type TableChecksum struct {
+	Rows     int64
+	Checksum string
+}
+
+// checksumRowExpr 单行校验表达式：CONCAT_WS 会跳过 NULL，因此额外拼接各字段的 ISNULL 标记，区分 NULL 和空串
+func checksumRowExpr(columns []string) string {
+	quoted := make([]string, len(columns))
+	nullFlags := make([]string, len(columns))
+	for i, col := range columns {
+		quoted[i] = fmt.Sprintf("`%s`", col)
+		nullFlags[i] = fmt.Sprintf("ISNULL(`%s`)", col)
+	}
+	return fmt.Sprintf("CRC32(CONCAT_WS('#', %s, CONCAT(%s)))", strings.Join(quoted, ", "), strings.Join(nullFlags, ", "))
+}
+
+// computeTableChecksum 在数据库端计算表的校验和：逐行 CRC32 后求和，与行顺序无关
+// samplePercent 在 1~99 之间时按主键 CRC32 取模抽样，源和目标抽中的是同一批主键
+// limit > 0 时只计算按主键排序的前 limit 行（与设置了行数上限时 Reader 的读取顺序一致）
+func computeTableChecksum(ctx context.Context, db *sql.DB, database, table string, columns, pkColumns []string, samplePercent int, limit int64) (*TableChecksum, error) {
+	quotedPK := make([]string, len(pkColumns))
+	for i, col := range pkColumns {
+		quotedPK[i] = fmt.Sprintf("`%s`", col)
+	}
+
+	from := fmt.Sprintf("`%s`.`%s`", database, table)
+	if limit > 0 {
+		from = fmt.Sprintf("(SELECT * FROM `%s`.`%s` ORDER BY %s LIMIT %d) AS `_dt_limited`", database, table, strings.Join(quotedPK, ", "), limit)
+	}
+
+	query := fmt.Sprintf("SELECT COUNT(*), COALESCE(SUM(%s), 0) FROM %s", checksumRowExpr(columns), from)
+	var args []interface{}
+	if samplePercent > 0 && samplePercent < 100 {
+		query += fmt.Sprintf(" WHERE CRC32(CONCAT_WS('#', %s)) %% 100 < ?", strings.Join(quotedPK, ", "))
+		args = append(args, samplePercent)
+	}
+
+	result := &TableChecksum{}
+	if err := db.QueryRowContext(ctx, query, args...).Scan(&result.Rows, &result.Checksum); err != nil {
+		return nil, err
+	}
+	return result, nil
+}
+
+// checksumColumns 参与校验和的字段：去掉 exclude 中的字段（如 regenerate 模式下目标重新生成的自增列）
+func checksumColumns(columns, exclude []string) []string {
+	if len(exclude) == 0 {
+		return columns
+	}
+	result := make([]string, 0, len(columns))
+	for _, col := range columns {
+		if !contains(exclude, col) {
+			result = append(result, col)
+		}
+	}
+	return result
+}
+
+// verifyTableChecksum 同步完成后对比源和目标的数据校验和（需要再完整扫描一遍数据）
+// 结果写入 verify 日志分类并返回校验结果（ChecksumMatch 等）；校验不一致只记录错误，不影响单元状态
+// excludeColumns 为两端值本就不同、不参与校验的字段（如 regenerate 模式下的自增列、NULL 值 default 策略的字段）
+func (e *SyncEngine) verifyTableChecksum(ctx context.Context, taskID, unitName, targetName string, sourceSQL, targetSQL *sql.DB,
+	sourceDB, sourceTable, targetDB, targetTable string, selectedFields, excludeColumns []string, samplePercent int, hasTransformers bool,
+	maxRecords int64) string {
+	if hasTransformers {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 配置了转换器/脱敏，目标数据与源不同，跳过校验和校验", targetName, unitName), "verify")
+		return ChecksumSkipped
+	}
+
+	columns := selectedFields
+	if len(columns) == 0 {
+		var err error
+		if columns, err = getTableColumnNames(sourceSQL, sourceDB, sourceTable); err != nil {
+			e.logService.AddLog(taskID, "error", fmt.Sprintf("目标 %s 表 %s 校验和校验失败，查询字段列表出错: %v", targetName, unitName, err), "verify")
+			return ChecksumError
+		}
+	}
+	columns = checksumColumns(columns, excludeColumns)
+
+	pkColumns, err := getPrimaryKeyColumns(sourceSQL, sourceDB, sourceTable)
+	// 主键包含不参与校验的字段（目标重新生成了主键）时，两端同一行的主键不同，不能按主键取前 N 行或抽样
+	if err == nil && len(checksumColumns(pkColumns, excludeColumns)) < len(pkColumns) {
+		pkColumns = nil
+	}
+	// 设置了行数上限时源端只取按主键排序的前 N 行，没有主键时无法确定同步的是哪些行
+	if maxRecords > 0 && (err != nil || len(pkColumns) == 0) {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("表 %s 设置了 max_records_per_unit 但没有可对应的主键，无法确定同步的行，跳过校验和校验", unitName), "verify")
+		return ChecksumSkipped
+	}
+	if samplePercent > 0 && samplePercent < 100 && (err != nil || len(pkColumns) == 0) {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("表 %s 没有可对应的主键，无法抽样，改为全量校验", unitName), "verify")
+		samplePercent = 100
+	}
+
+	scope := "全量"
+	if samplePercent > 0 && samplePercent < 100 {
+		scope = fmt.Sprintf("抽样 %d%%", samplePercent)
+	}
+	if maxRecords > 0 {
+		scope += fmt.Sprintf("，源端前 %d 行", maxRecords)
+	}
+
+	sourceSum, err := computeTableChecksum(ctx, sourceSQL, sourceDB, sourceTable, columns, pkColumns, samplePercent, maxRecords)
+	if err != nil {
+		e.logService.AddLog(taskID, "error", fmt.Sprintf("表 %s 计算源校验和失败: %v", unitName, err), "verify")
+		return ChecksumError
+	}
+	targetSum, err := computeTableChecksum(ctx, targetSQL, targetDB, targetTable, columns, pkColumns, samplePercent, 0)
+	if err != nil {
+		e.logService.AddLog(taskID, "error", fmt.Sprintf("目标 %s 表 %s 计算校验和失败: %v", targetName, unitName, err), "verify")
+		return ChecksumError
+	}
+
+	if sourceSum.Rows == targetSum.Rows && sourceSum.Checksum == targetSum.Checksum {
+		e.logService.AddLog(taskID, "success", fmt.Sprintf("目标 %s 表 %s 校验和一致（%s，%d 行）", targetName, unitName, scope, sourceSum.Rows), "verify")
+		return ChecksumMatch
+	}
+	e.logService.AddLog(taskID, "error", fmt.Sprintf("目标 %s 表 %s 校验和不一致（%s）：源 %d 行/%s，目标 %d 行/%s",
+		targetName, unitName, scope, sourceSum.Rows, sourceSum.Checksum, targetSum.Rows, targetSum.Checksum), "verify")
+	return ChecksumMismatch
+}
@@ -0,0 +1,82 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"sync/atomic"
+	"time"
+)
+
+// DefaultMaxConsecutiveFailures skip 策略下默认允许的连续失败批次数
+const DefaultMaxConsecutiveFailures = 10
+
+// ValidateMaxConsecutiveFailures 校验连续失败熔断阈值，0 表示使用默认值
+func ValidateMaxConsecutiveFailures(n int) error {
+	if n < 0 || n > 10000 {
+		return fmt.Errorf("max_consecutive_failures 必须在 0~10000 之间")
+	}
+	return nil
+}
+
+// failureBreaker 连续批次失败熔断器：连续失败达到阈值时熔断，任一批次成功即清零
+// 分片并行时多个管道共用同一个熔断器，计数使用原子操作
+type failureBreaker struct {
+	threshold int32
+	failures  int32
+}
+
+// newFailureBreaker 创建熔断器，threshold <= 0 时使用默认值
+func newFailureBreaker(threshold int) *failureBreaker {
+	if threshold <= 0 {
+		threshold = DefaultMaxConsecutiveFailures
+	}
+	return &failureBreaker{threshold: int32(threshold)}
+}
+
+// Success 批次成功，清零连续失败计数
+func (b *failureBreaker) Success() {
+	atomic.StoreInt32(&b.failures, 0)
+}
+
+// Failure 记录一次批次失败，返回当前连续失败次数以及是否达到熔断阈值
+func (b *failureBreaker) Failure() (int, bool) {
+	n := atomic.AddInt32(&b.failures, 1)
+	return int(n), n >= b.threshold
+}
+
+// tripCircuitBreaker 熔断：记录错误并暂停整个任务（目标不可用时继续跳过只会空跑一遍全表）
+// 暂停需要等待所有 Worker 退出，因此在独立 goroutine 中执行
+func (e *SyncEngine) tripCircuitBreaker(taskID, targetName, unitName string, failures int, err error) error {
+	e.logService.Error(taskID, fmt.Sprintf("目标 %s 表 %s 连续 %d 个批次失败，已熔断并暂停任务，请检查目标配置后重新启动。最后一次错误: %v",
+		targetName, unitName, failures, err))
+	go func() {
+		if pauseErr := NewTaskControlService().PauseTask(taskID); pauseErr != nil {
+			e.logService.Warning(taskID, fmt.Sprintf("熔断后暂停任务失败: %v", pauseErr))
+		}
+	}()
+	e.notifyCircuitBreak(taskID, fmt.Sprintf("目标 %s 表 %s 连续 %d 个批次失败，已熔断并暂停任务: %v", targetName, unitName, failures, err))
+	return fmt.Errorf("连续 %d 个批次失败，已熔断: %w", failures, err)
+}
+
+// notifyCircuitBreak 熔断暂停不会走任务正常结束流程，单独发送失败通知
+func (e *SyncEngine) notifyCircuitBreak(taskID, message string) {
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(taskID)
+	if err != nil || config.SyncConfig.Notify == nil {
+		return
+	}
+	var task models.SyncTask
+	database.DB.Select("id", "name", "started_at").First(&task, "id = ?", taskID)
+
+	summary := TaskRunSummary{
+		TaskID:   taskID,
+		TaskName: task.Name,
+		Status:   "failed",
+		Error:    message,
+	}
+	if task.StartedAt != nil {
+		summary.StartedAt = task.StartedAt.Format("2006-01-02 15:04:05")
+		summary.Duration = formatElapsed(time.Since(*task.StartedAt))
+	}
+	NotifyTaskFinished(config.SyncConfig.Notify, summary)
+}
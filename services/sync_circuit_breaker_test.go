@@ -0,0 +1,87 @@
+package services
+
+import (
+	"sync"
+	"testing"
+)
+
+func TestFailureBreakerTripsAfterConsecutiveFailures(t *testing.T) {
+	b := newFailureBreaker(3)
+	for i := 1; i <= 2; i++ {
+		if n, tripped := b.Failure(); n != i || tripped {
+			t.Fatalf("failure %d: got (%d, %v), want (%d, false)", i, n, tripped, i)
+		}
+	}
+	if n, tripped := b.Failure(); n != 3 || !tripped {
+		t.Fatalf("failure 3: got (%d, %v), want (3, true)", n, tripped)
+	}
+}
+
+func TestFailureBreakerSuccessResetsCount(t *testing.T) {
+	b := newFailureBreaker(3)
+	b.Failure()
+	b.Failure()
+	b.Success()
+	// 成功后重新计数：再失败两次不熔断，第三次才熔断
+	for i := 1; i <= 2; i++ {
+		if _, tripped := b.Failure(); tripped {
+			t.Fatalf("tripped after %d failures following a success", i)
+		}
+	}
+	if _, tripped := b.Failure(); !tripped {
+		t.Fatal("not tripped after 3 consecutive failures")
+	}
+}
+
+func TestFailureBreakerDefaultThreshold(t *testing.T) {
+	for _, threshold := range []int{0, -1} {
+		b := newFailureBreaker(threshold)
+		for i := 1; i < DefaultMaxConsecutiveFailures; i++ {
+			if _, tripped := b.Failure(); tripped {
+				t.Fatalf("threshold %d: tripped after %d failures, want %d", threshold, i, DefaultMaxConsecutiveFailures)
+			}
+		}
+		if _, tripped := b.Failure(); !tripped {
+			t.Fatalf("threshold %d: not tripped after %d failures", threshold, DefaultMaxConsecutiveFailures)
+		}
+	}
+}
+
+func TestFailureBreakerSharedAcrossShards(t *testing.T) {
+	// 分片并行时多个管道共用一个熔断器：恰好一次失败报告达到阈值
+	const shards, perShard = 4, 25
+	b := newFailureBreaker(shards * perShard)
+	var wg sync.WaitGroup
+	var mu sync.Mutex
+	trips := 0
+	for i := 0; i < shards; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for j := 0; j < perShard; j++ {
+				if n, tripped := b.Failure(); tripped && n == shards*perShard {
+					mu.Lock()
+					trips++
+					mu.Unlock()
+				}
+			}
+		}()
+	}
+	wg.Wait()
+	if trips != 1 {
+		t.Errorf("threshold reached %d times, want 1", trips)
+	}
+}
+
+func TestValidateMaxConsecutiveFailures(t *testing.T) {
+	for _, n := range []int{0, 1, 10000} {
+		if err := ValidateMaxConsecutiveFailures(n); err != nil {
+			t.Errorf("ValidateMaxConsecutiveFailures(%d) error = %v", n, err)
+		}
+	}
+	for _, n := range []int{-1, 10001} {
+		if err := ValidateMaxConsecutiveFailures(n); err == nil {
+			t.Errorf("ValidateMaxConsecutiveFailures(%d) accepted", n)
+		}
+	}
+}
@@ -5,6 +5,7 @@ import (
 	"database/sql"
 	"datatrace/database"
 	"datatrace/models"
+	"errors"
 	"fmt"
 	"strings"
 	"sync"
@@ -46,7 +47,7 @@ func (e *SyncEngine) loadTargetConns(targetIDs []string) ([]TargetConnWithPasswo
 			return nil, fmt.Errorf("查询目标源 %s 失败: %w", targetID, err)
 		}
 
-		password, err := e.dsService.crypto.Decrypt(ds.Password)
+		password, err := e.dsService.ResolveConnection(&ds)
 		if err != nil {
 			return nil, fmt.Errorf("解密目标源 %s 密码失败: %w", targetID, err)
 		}
@@ -61,7 +62,8 @@ func (e *SyncEngine) loadTargetConns(targetIDs []string) ([]TargetConnWithPasswo
 }
 
 // ensureTargetTableExists 确保目标表存在，不存在则创建
-func (e *SyncEngine) ensureTargetTableExists(targetDB, sourceDB *sql.DB, sourceDatabase, sourceTable, targetTable string, selectedFields []string, targetDatabase string) error {
+func (e *SyncEngine) ensureTargetTableExists(targetDB, sourceDB *sql.DB, sourceDatabase, sourceTable, targetTable string, selectedFields []string, targetDatabase string,
+	syncConfig SyncConfigParams) error {
 	// 检查目标表是否存在
 	var count int
 	query := "SELECT COUNT(*) FROM information_schema.tables WHERE table_schema = ? AND table_name = ?"
@@ -77,14 +79,15 @@ func (e *SyncEngine) ensureTargetTableExists(targetDB, sourceDB *sql.DB, sourceD
 
 createTable:
 	// 表不存在，创建表结构
-	if err := e.createTableLike(targetDB, sourceDB, sourceDatabase, sourceTable, targetTable, selectedFields); err != nil {
+	if err := e.createTableLike(targetDB, sourceDB, sourceDatabase, sourceTable, targetTable, selectedFields, syncConfig); err != nil {
 		return fmt.Errorf("创建表结构失败: %w", err)
 	}
 	return nil
 }
 
-// createTableLike 根据源表结构创建目标表
-func (e *SyncEngine) createTableLike(targetDB, sourceDB *sql.DB, sourceDatabase, sourceTable, targetTable string, selectedFields []string) error {
+// createTableLike 根据源表结构创建目标表（配置了 target_charset 时覆盖表默认字符集）
+func (e *SyncEngine) createTableLike(targetDB, sourceDB *sql.DB, sourceDatabase, sourceTable, targetTable string, selectedFields []string,
+	syncConfig SyncConfigParams) error {
 	// 获取源表的CREATE TABLE语句
 	var tableName, createSQL string
 	query := fmt.Sprintf("SHOW CREATE TABLE `%s`", sourceTable)
@@ -110,6 +113,8 @@ func (e *SyncEngine) createTableLike(targetDB, sourceDB *sql.DB, sourceDatabase,
 		newTableDef := fmt.Sprintf("CREATE TABLE `%s`", targetTable)
 		createSQL = strings.Replace(createSQL, oldTableDef, newTableDef, 1)
 	}
+	createSQL = applyTableCharset(createSQL, syncConfig.TargetCharset, syncConfig.TargetCollation)
+	createSQL = applyNullablePolicies(createSQL, syncConfig.NullPolicies)
 
 	// 执行创建表
 	ctx2, cancel2 := context.WithTimeout(context.Background(), 10*time.Second)
@@ -173,14 +178,14 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 	// 2. 查询任务配置
 	var task models.SyncTask
 	if err := database.DB.Preload("SourceConn").Preload("TargetConn").First(&task, "id = ?", taskID).Error; err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("查询任务失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("查询任务失败: %w", err))
 	}
 
 	// 3. 从Redis获取配置
 	configCache := NewConfigCacheService()
 	config, err := configCache.GetTaskConfigWithFallback(taskID)
 	if err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("获取配置失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("获取配置失败: %w", err))
 	}
 
 	// 获取多目标源ID列表
@@ -193,47 +198,97 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 	// 4. 解析表名（格式：database.table）
 	sourceDB, sourceTable, targetDB, targetTable, err := e.parseUnitName(unitName, config)
 	if err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("解析表名失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("解析表名失败: %w", err))
 	}
 
 	// 5. 解密源数据库密码
-	sourcePassword, err := e.dsService.crypto.Decrypt(task.SourceConn.Password)
+	sourcePassword, err := e.dsService.ResolveConnection(task.SourceConn)
 	if err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("解密源数据库密码失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("解密源数据库密码失败: %w", err))
 	}
 
-	// 6. 获取字段配置
+	// 6. 获取字段配置（查询源的字段由查询语句决定）
 	selectedFields := e.getSelectedFields(config, sourceDB, sourceTable)
+	sourceQuery := e.getSourceQuery(config, sourceDB, sourceTable)
 
-	// 7. 计算自适应批次大小
-	batchSize := e.calculateAdaptiveBatchSize(task.SourceConn, sourceDB, sourceTable, sourcePassword)
+	// 7. 计算自适应批次大小（查询源没有表统计信息，使用默认批次大小）
+	var batchSize int
+	if sourceQuery != "" {
+		batchSize = NewAdaptiveConfigCalculator().GetDefaultConfig().BatchSize
+	} else {
+		batchSize = e.calculateAdaptiveBatchSize(task.SourceConn, sourceDB, sourceTable, sourcePassword)
+	}
+
+	// 查询源结果集没有主键；设置了行数上限时需要按顺序读取前 N 行，这两种情况都不做表内分片
+	maxRecords := config.SyncConfig.MaxRecordsPerUnit
+	parallelShards := config.SyncConfig.ParallelShards
+	if sourceQuery != "" || maxRecords > 0 {
+		parallelShards = 0
+	}
+
+	// 连接数打满时退避重试（建立连接和每个批次的读写），与批次错误策略相互独立
+	connRetry := e.newConnectRetry(taskID, unitName, config.SyncConfig)
+	// 源和目标的读写连接使用相同的会话时区，TIMESTAMP 不因两边服务器时区不同而偏移
+	timeZone := sessionTimeZone(config.SyncConfig.TimestampMode)
 
 	// 8. 创建Reader（支持字段选择和自适应批次）
-	reader, err := NewMySQLReaderWithFields(
-		task.SourceConn.Host,
-		task.SourceConn.Port,
-		task.SourceConn.Username,
-		sourcePassword,
-		sourceDB,
-		sourceTable,
-		batchSize,
-		selectedFields,
-	)
+	var reader *MySQLReader
+	err = connRetry.do(ctx, func() error {
+		var err error
+		reader, err = newUnitReader(task.SourceConn, sourcePassword, sourceDB, sourceTable, sourceQuery, batchSize, selectedFields, timeZone)
+		return err
+	})
 	if err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("创建Reader失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("创建Reader失败: %w", err))
 	}
 	defer reader.Close()
 
+	// 脱敏（包括 mask 转换器）不修改主键字段，查询失败时按没有主键处理
+	var pkColumns []string
+	if len(config.SyncConfig.Mask) > 0 || len(config.SyncConfig.Transformers) > 0 {
+		pkColumns, err = getPrimaryKeyColumns(reader.GetDB(), sourceDB, sourceTable)
+		if err != nil {
+			e.logService.Warning(taskID, fmt.Sprintf("表 %s 查询主键失败，无法校验脱敏规则: %v", unitName, err))
+		}
+	}
+
+	// 构建转换器链
+	transformers, skippedFields, err := BuildTransformers(config.SyncConfig.Transformers, pkColumns)
+	if err != nil {
+		return e.failUnit(taskID, unitName, fmt.Errorf("创建转换器失败: %w", err))
+	}
+	if len(skippedFields) > 0 {
+		e.logService.Warning(taskID, fmt.Sprintf("表 %s 主键字段不允许脱敏，mask 转换器已忽略: %s", unitName, strings.Join(skippedFields, ", ")))
+	}
+
+	// 字段值转换放在自定义转换器之前（基于源字段名）
+	if len(config.SyncConfig.ColumnTransforms) > 0 {
+		columnTransformer, err := NewColumnTransformer(config.SyncConfig.ColumnTransforms)
+		if err != nil {
+			return e.failUnit(taskID, unitName, fmt.Errorf("创建字段转换器失败: %w", err))
+		}
+		transformers = append([]Transformer{columnTransformer}, transformers...)
+	}
+
+	// 字段脱敏放在转换器链最前面（基于源字段名），主键字段不参与脱敏
+	if len(config.SyncConfig.Mask) > 0 {
+		maskTransformer, skipped := NewMaskTransformer(config.SyncConfig.Mask, pkColumns)
+		if len(skipped) > 0 {
+			e.logService.Warning(taskID, fmt.Sprintf("表 %s 主键字段不允许脱敏，已忽略: %s", unitName, strings.Join(skipped, ", ")))
+		}
+		transformers = append([]Transformer{maskTransformer}, transformers...)
+	}
+
 	// 9. 加载多个目标源连接
 	targetConns, err := e.loadTargetConns(targetIDs)
 	if err != nil {
-		return e.failUnit(taskID, unitName, fmt.Sprintf("加载目标源失败: %v", err))
+		return e.failUnit(taskID, unitName, fmt.Errorf("加载目标源失败: %w", err))
 	}
 
 	// 10. 获取总记录数
 	unit := progressManager.GetUnit(taskID, unitName)
 	if unit == nil {
-		return e.failUnit(taskID, unitName, "获取单元信息失败")
+		return e.failUnit(taskID, unitName, errors.New("获取单元信息失败"))
 	}
 
 	// 检查是否为空表
@@ -241,9 +296,9 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 	approxRows := progressManager.GetApproxRows(taskID, unitName)
 	tableTotalRecords := unit.TotalRecords
 	if tableTotalRecords == 0 && approxRows > 0 {
-		// 使用近似行数作为预估
-		tableTotalRecords = approxRows
-		progressManager.UpdateUnitProgress(taskID, unitName, approxRows, 0)
+		// 使用近似行数作为预估（不超过行数上限）
+		tableTotalRecords = capRecords(approxRows, maxRecords)
+		progressManager.UpdateUnitProgress(taskID, unitName, tableTotalRecords, 0)
 	}
 
 	if tableTotalRecords == 0 {
@@ -278,21 +333,19 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 			defer wg.Done()
 
 			// 为每个目标源创建独立的 reader（MySQLReader 不是线程安全的）
-			targetReader, err := NewMySQLReaderWithFields(
-				task.SourceConn.Host,
-				task.SourceConn.Port,
-				task.SourceConn.Username,
-				sourcePassword,
-				sourceDB,
-				sourceTable,
-				batchSize,
-				selectedFields,
-			)
+			var targetReader *MySQLReader
+			err := connRetry.do(ctx, func() error {
+				var err error
+				targetReader, err = newUnitReader(task.SourceConn, sourcePassword, sourceDB, sourceTable, sourceQuery, batchSize, selectedFields, timeZone)
+				return err
+			})
 			if err != nil {
-				errChan <- fmt.Errorf("目标 %s 创建Reader失败: %v", targetConn.Conn.Name, err)
+				errChan <- fmt.Errorf("目标 %s 创建Reader失败: %w", targetConn.Conn.Name, err)
 				return
 			}
 			defer targetReader.Close()
+			targetReader.SetConnectRetry(ctx, connRetry)
+			applyRecordLimit(targetReader, maxRecords, sourceDB, sourceTable)
 
 			e.logService.Info(taskID, fmt.Sprintf("同步到目标 %d/%d: %s", targetIdx+1, len(targetConns), targetConn.Conn.Name))
 
@@ -320,18 +373,19 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 				IsNew:        false,
 			})
 
-			// 确保目标数据库存在
+			// 确保目标数据库存在（通常已在初始化阶段按源库字符集创建）
+			dbCharset, dbCollation := databaseCharset(config.SyncConfig, "utf8mb4", "utf8mb4_general_ci")
 			created, err := CreateDatabaseIfNotExists(
-				targetConn.Conn.Host,
+				targetConn.Conn.MySQLHost(),
 				targetConn.Conn.Port,
 				targetConn.Conn.Username,
 				targetConn.Password,
 				targetDB,
-				"utf8mb4",
-				"utf8mb4_general_ci",
+				dbCharset,
+				dbCollation,
 			)
 			if err != nil {
-				errChan <- fmt.Errorf("目标 %s 创建数据库失败: %v", targetConn.Conn.Name, err)
+				errChan <- fmt.Errorf("目标 %s 创建数据库失败: %w", targetConn.Conn.Name, err)
 				return
 			}
 			if created {
@@ -339,90 +393,266 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 			}
 
 			// 创建Writer
-			writer, err := NewMySQLWriter(
-				targetConn.Conn.Host,
-				targetConn.Conn.Port,
-				targetConn.Conn.Username,
-				targetConn.Password,
-				targetDB,
-				targetTable,
-			)
+			var writer *MySQLWriter
+			err = connRetry.do(ctx, func() error {
+				var err error
+				writer, err = NewMySQLWriter(
+					targetConn.Conn.MySQLHost(),
+					targetConn.Conn.Port,
+					targetConn.Conn.Username,
+					targetConn.Password,
+					targetDB,
+					targetTable,
+					timeZone,
+				)
+				return err
+			})
 			if err != nil {
-				errChan <- fmt.Errorf("目标 %s 创建Writer失败: %v", targetConn.Conn.Name, err)
+				errChan <- fmt.Errorf("目标 %s 创建Writer失败: %w", targetConn.Conn.Name, err)
 				return
 			}
+			writer.SetConnectRetry(ctx, connRetry)
+			nullStats := newNullCoercionStats()
+			writer.SetNullPolicies(config.SyncConfig.NullPolicies, nullStats)
+			loadData := e.newLoadData(taskID, unitName, targetConn.Conn.Name, config.SyncConfig)
+			writer.SetLoadData(loadData)
+			var onDuplicate func(rows int64)
+			duplicates := &duplicateKeyStats{}
+			if config.SyncConfig.DuplicateKeyMode == DuplicateKeyIgnore {
+				onDuplicate = e.newDuplicateIgnoredHook(taskID, targetConn.Conn.Name, unitName, duplicates)
+				writer.SetDuplicateKeyIgnore(onDuplicate)
+			}
 
-			// 检查目标表是否存在，不存在则创建
-			if err := e.ensureTargetTableExists(writer.GetDB(), targetReader.GetDB(), sourceDB, sourceTable, targetTable, selectedFields, targetDB); err != nil {
+			// 检查目标表是否存在，不存在则创建（查询源按结果集结构创建）
+			if sourceQuery != "" {
+				writer.SetTableCharset(config.SyncConfig.TargetCharset, config.SyncConfig.TargetCollation)
+				err = e.ensureQueryTargetTable(taskID, unitName, targetConn.Conn.Name, targetReader, writer)
+			} else {
+				err = e.ensureTargetTableExists(writer.GetDB(), targetReader.GetDB(), sourceDB, sourceTable, targetTable, selectedFields, targetDB, config.SyncConfig)
+			}
+			if err != nil {
 				writer.Close()
-				errChan <- fmt.Errorf("目标 %s 创建表结构失败: %v", targetConn.Conn.Name, err)
+				errChan <- fmt.Errorf("目标 %s 创建表结构失败: %w", targetConn.Conn.Name, err)
 				return
 			}
 
-			// 批量读取和写入数据
-			batchNum := 0
-			// 当前目标源已处理的记录数（不累加到整体进度）
-			targetProcessed := int64(0)
-			for targetReader.HasMore() {
-				// 检查context是否被取消
-				select {
-				case <-ctx.Done():
-					writer.Close()
-					errChan <- fmt.Errorf("目标 %s 任务被暂停", targetConn.Conn.Name)
-					return
-				default:
-				}
+			targetName := targetConn.Conn.Name
 
-				batchNum++
+			// 自增列：preserve 原样写入源端的值，完成后校正计数器；regenerate 不写入自增列，由目标重新生成
+			autoIncColumn, err := getAutoIncrementColumn(writer.GetDB(), targetDB, targetTable)
+			if err != nil {
+				e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 查询自增列失败，按普通列写入: %v", targetName, unitName, err))
+				autoIncColumn = ""
+			}
+			regenerateIDs := autoIncColumn != "" && config.SyncConfig.AutoIncrementMode == AutoIncrementRegenerate
+			if regenerateIDs {
+				writer.SetSkipColumns(autoIncColumn)
+				e.syncAutoIncrementCounter(taskID, targetName, unitName, writer.GetDB(), targetDB, targetTable, autoIncColumn)
+			}
 
-				// 读取批次
-				records, err := targetReader.ReadBatch()
-				if err != nil {
-					writer.Close()
-					errChan <- fmt.Errorf("目标 %s 读取数据失败: %v", targetConn.Conn.Name, err)
-					return
-				}
+			// 批次内去重：按目标表主键去除同一批次中重复的行（目标表已创建）
+			dedup := e.newBatchDedup(taskID, unitName, targetName, config.SyncConfig.BatchDedup, writer, targetDB, targetTable)
+			writer.SetBatchDedup(dedup)
 
-				if len(records) == 0 {
-					break
+			// 目标端前置 SQL（目标表已创建）：失败时该单元失败，不写入数据
+			if err := e.runTargetSQLHooks(ctx, taskID, targetName, unitName, "pre_sql", writer.GetDB(),
+				config.SyncConfig.PreSQL, targetDB, targetTable); err != nil {
+				writer.Close()
+				errChan <- fmt.Errorf("目标 %s %w", targetName, err)
+				return
+			}
+			// 目标端后置 SQL：同步失败或被停止时也执行（如恢复 DISABLE KEYS），失败只记录警告
+			runPostSQL := func() {
+				if err := e.runTargetSQLHooks(context.Background(), taskID, targetName, unitName, "post_sql", writer.GetDB(),
+					config.SyncConfig.PostSQL, targetDB, targetTable); err != nil {
+					e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s %v", targetName, unitName, err))
 				}
+			}
 
-				// 写入批次
-				if err := writer.WriteBatch(records); err != nil {
+			// 同实例快速路径：服务端 INSERT ... SELECT，数据不经过应用（regenerate 需要跳过自增列，不走该路径）
+			// NULL 值 default 策略需要逐行替换、忽略冲突和批次内去重需要逐批处理，也不走该路径
+			if config.SyncConfig.UseNativeCopy && !regenerateIDs && sourceQuery == "" && maxRecords == 0 &&
+				!hasNullPolicy(config.SyncConfig.NullPolicies, NullPolicyDefault) && onDuplicate == nil && dedup == nil {
+				handled, err := e.tryNativeCopy(ctx, taskID, unitName, task.SourceConn, targetConn, writer.GetDB(), targetReader.GetDB(),
+					sourceDB, sourceTable, targetDB, targetTable, selectedFields, batchSize, len(transformers) > 0, tableTotalRecords)
+				if handled {
+					if err == nil && config.SyncConfig.ChecksumVerify {
+						e.verifyTableChecksum(ctx, taskID, unitName, targetConn.Conn.Name, targetReader.GetDB(), writer.GetDB(),
+							sourceDB, sourceTable, targetDB, targetTable, selectedFields, nil, config.SyncConfig.ChecksumSamplePercent, false, 0)
+					}
+					if err == nil && autoIncColumn != "" && !regenerateIDs {
+						e.syncAutoIncrementCounter(taskID, targetName, unitName, writer.GetDB(), targetDB, targetTable, autoIncColumn)
+					}
+					runPostSQL()
 					writer.Close()
+					if err != nil {
+						errChan <- fmt.Errorf("目标 %s %w", targetName, err)
+						return
+					}
+					progressManager.SendProgress(ProgressMessage{
+						TaskID:       taskID,
+						TargetID:     targetConn.Conn.ID,
+						TargetName:   targetName,
+						UnitName:     unitName,
+						Status:       "completed",
+						TotalRecords: unit.TotalRecords,
+						Processed:    unit.TotalRecords,
+						IsNew:        false,
+					})
+					e.logService.Info(taskID, fmt.Sprintf("目标 %s 同步完成(同实例复制): %s", targetName, unitName))
+					return
+				}
+			}
+
+			// 批量读取和写入数据（Reader -> Transformers -> Writer）
+			breaker := newFailureBreaker(config.SyncConfig.MaxConsecutiveFailures)
+			hooks := PipelineHooks{
+				OnBatch: func(batchNum int, batchSize int, processed int64) {
+					breaker.Success()
+
+					// 发送进度消息给 Process 线程（当前目标源已处理的记录数，不累加到整体进度）
+					progressManager.SendProgress(ProgressMessage{
+						TaskID:       taskID,
+						TargetID:     targetConn.Conn.ID,
+						TargetName:   targetName,
+						UnitName:     unitName,
+						Status:       "running",
+						TotalRecords: unit.TotalRecords,
+						Processed:    processed,
+						IsNew:        false,
+					})
+
+					// 生成同步批次日志
+					logMessage := fmt.Sprintf("目标 %s 表 %s 批次 %d: %d/%d (%.1f%%)",
+						targetName, unitName, batchNum, processed, unit.TotalRecords,
+						safePercent(processed, unit.TotalRecords))
+					e.logService.AddLog(taskID, "info", logMessage, "sync")
+				},
+				OnBatchError: func(batchNum int, err error) error {
 					// 根据错误策略处理
 					if config.SyncConfig.ErrorStrategy == "pause" {
-						errChan <- fmt.Errorf("目标 %s 写入数据失败: %v", targetConn.Conn.Name, err)
-						return
-					} else {
-						e.logService.Error(taskID, fmt.Sprintf("目标 %s 批次 %d 写入失败(跳过): %v", targetConn.Conn.Name, batchNum, err))
-						continue
+						return err
 					}
+					// skip 策略下连续失败达到阈值时熔断，避免目标不可用时跳过所有批次后显示"成功"
+					if failures, open := breaker.Failure(); open {
+						return e.tripCircuitBreaker(taskID, targetName, unitName, failures, err)
+					}
+					e.logService.Error(taskID, fmt.Sprintf("目标 %s 批次 %d 失败(跳过): %v", targetName, batchNum, err))
+					return nil
+				},
+				OnBatchSizeChange: func(oldSize, newSize int) {
+					e.logService.Info(taskID, fmt.Sprintf("目标 %s 表 %s 批次大小自动调整: %d -> %d", targetName, unitName, oldSize, newSize))
+				},
+			}
+
+			newTuner := func() *BatchSizeTuner {
+				if !config.SyncConfig.AutoTuneBatch {
+					return nil
 				}
+				return NewBatchSizeTuner(batchSize,
+					config.SyncConfig.MinBatchSize,
+					config.SyncConfig.MaxBatchSize,
+					time.Duration(config.SyncConfig.TargetBatchLatencyMs)*time.Millisecond)
+			}
 
-				// 更新当前目标源的进度（临时变量，不累加到整体）
-				targetProcessed += int64(len(records))
-
-				// 发送进度消息给 Process 线程
-				progressManager.SendProgress(ProgressMessage{
-					TaskID:       taskID,
-					TargetID:     targetConn.Conn.ID,
-					TargetName:   targetConn.Conn.Name,
-					UnitName:     unitName,
-					Status:       "running",
-					TotalRecords: unit.TotalRecords,
-					Processed:    targetProcessed,
-					IsNew:        false,
-				})
-
-				// 生成同步批次日志
-				logMessage := fmt.Sprintf("目标 %s 表 %s 批次 %d: %d/%d (%.1f%%)",
-					targetConn.Conn.Name, unitName, batchNum, targetProcessed, unit.TotalRecords,
-					safePercent(targetProcessed, unit.TotalRecords))
-				e.logService.AddLog(taskID, "info", logMessage, "sync")
+			// 表内并行分片：按主键范围拆分，多个 Reader/Writer 并发同步同一张表
+			shards := e.planTableShards(taskID, unitName, targetReader.GetDB(), sourceDB, sourceTable,
+				parallelShards, tableTotalRecords, batchSize)
+			var result *PipelineResult
+			if len(shards) > 1 {
+				e.logService.Info(taskID, fmt.Sprintf("目标 %s 表 %s 按主键 %s 拆分为 %d 个分片并行同步", targetName, unitName, shards[0].PKColumn, len(shards)))
+				result, err = e.runShardedPipelines(ctx, shards,
+					func() (*MySQLReader, error) {
+						var shardReader *MySQLReader
+						err := connRetry.do(ctx, func() error {
+							var err error
+							shardReader, err = NewMySQLReaderWithFields(task.SourceConn.MySQLHost(), task.SourceConn.Port, task.SourceConn.Username,
+								sourcePassword, sourceDB, sourceTable, batchSize, selectedFields, timeZone)
+							return err
+						})
+						if err == nil {
+							shardReader.SetConnectRetry(ctx, connRetry)
+						}
+						return shardReader, err
+					},
+					func() (*MySQLWriter, error) {
+						var shardWriter *MySQLWriter
+						err := connRetry.do(ctx, func() error {
+							var err error
+							shardWriter, err = NewMySQLWriter(targetConn.Conn.MySQLHost(), targetConn.Conn.Port, targetConn.Conn.Username,
+								targetConn.Password, targetDB, targetTable, timeZone)
+							return err
+						})
+						if err == nil {
+							shardWriter.SetConnectRetry(ctx, connRetry)
+							shardWriter.SetNullPolicies(config.SyncConfig.NullPolicies, nullStats)
+							shardWriter.SetLoadData(loadData)
+							shardWriter.SetDuplicateKeyIgnore(onDuplicate)
+							shardWriter.SetBatchDedup(dedup)
+							if regenerateIDs {
+								shardWriter.SetSkipColumns(autoIncColumn)
+							}
+						}
+						return shardWriter, err
+					},
+					transformers, hooks, newTuner, config.SyncConfig.ReadAheadBatches)
+			} else {
+				pipeline := NewSyncPipeline(targetReader, writer, transformers, hooks)
+
+				// 流式读取：批次内按分块读写，内存不随批次大小增长
+				pipeline.WithStreaming(DefaultStreamChunkSize)
+				// 预读：读取下一批的同时写入当前批（配置后优先于流式读取）
+				pipeline.WithReadAhead(config.SyncConfig.ReadAheadBatches)
+				// 并行写入：多个 goroutine 并发写入预读的批次（分片时每个分片已有独立的 Writer，不启用）
+				pipeline.WithWriteThreads(config.SyncConfig.WriteThreads)
+				writer.SetWriteThreads(config.SyncConfig.WriteThreads)
+
+				if tuner := newTuner(); tuner != nil {
+					pipeline.WithBatchTuner(tuner)
+				}
+
+				result, err = pipeline.Run(ctx)
 			}
 
+			e.logNullCoercions(taskID, targetName, unitName, nullStats)
+			e.logDuplicateKeys(taskID, targetName, unitName, duplicates)
+			e.logBatchDedup(taskID, targetName, unitName, dedup)
+
+			// 行数校验：对比本次应写入和实际写入目标的行数（不受目标原有数据影响）
+			var counts rowCounts
+			if err == nil {
+				sourceRows, countErr := countSourceRows(targetReader.GetDB(), sourceDB, sourceTable, sourceQuery, maxRecords)
+				if countErr != nil {
+					e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 统计源端行数失败: %v", targetName, unitName, countErr))
+					sourceRows = -1
+				}
+				counts = e.verifyRowCounts(taskID, targetName, unitName, sourceRows, result, duplicates, dedup)
+			}
+
+			// 深度校验：对比源和目标的数据校验和（需要再扫描一遍数据）
+			if err == nil && config.SyncConfig.ChecksumVerify {
+				if sourceQuery != "" {
+					e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 为查询源，跳过校验和校验", targetName, unitName), "verify")
+				} else {
+					// default 策略的字段在目标端可能被替换为默认值，也不参与校验
+					excludeColumns := nullPolicyColumns(config.SyncConfig.NullPolicies, NullPolicyDefault)
+					if regenerateIDs {
+						excludeColumns = append(excludeColumns, autoIncColumn)
+					}
+					e.verifyTableChecksum(ctx, taskID, unitName, targetName, targetReader.GetDB(), writer.GetDB(),
+						sourceDB, sourceTable, targetDB, targetTable, selectedFields, excludeColumns, config.SyncConfig.ChecksumSamplePercent,
+						len(transformers) > 0, maxRecords)
+				}
+			}
+			if err == nil && autoIncColumn != "" && !regenerateIDs {
+				e.syncAutoIncrementCounter(taskID, targetName, unitName, writer.GetDB(), targetDB, targetTable, autoIncColumn)
+			}
+			runPostSQL()
 			writer.Close()
+			if err != nil {
+				errChan <- fmt.Errorf("目标 %s %w", targetName, err)
+				return
+			}
 
 			// 发送完成消息给 Process 线程
 			progressManager.SendProgress(ProgressMessage{
@@ -433,6 +663,8 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 				Status:       "completed",
 				TotalRecords: unit.TotalRecords,
 				Processed:    unit.TotalRecords,
+				Intended:     counts.intended,
+				Written:      counts.written,
 				IsNew:        false,
 			})
 
@@ -447,7 +679,7 @@ func (e *SyncEngine) SyncTable(ctx context.Context, taskID string, unitName stri
 	// 检查是否有错误
 	for err := range errChan {
 		if err != nil {
-			return e.failUnit(taskID, unitName, err.Error())
+			return e.failUnit(taskID, unitName, err)
 		}
 	}
 
@@ -7,12 +7,12 @@ import (
 	"strings"
 )
 
-// failUnit 标记单元失败（内存操作）
-func (e *SyncEngine) failUnit(taskID, unitName, errMsg string) error {
+// failUnit 标记单元失败（内存操作），err 需用 %w 包装原始错误，以便按 MySQL 错误号分类
+func (e *SyncEngine) failUnit(taskID, unitName string, err error) error {
 	progressManager := GetProgressManager()
-	progressManager.UpdateUnitError(taskID, unitName, errMsg)
+	progressManager.UpdateUnitError(taskID, unitName, err)
 	e.sseService.BroadcastProgressUpdate(taskID)
-	return fmt.Errorf(errMsg)
+	return err
 }
 
 // pauseUnit 标记单元暂停（内存操作）
@@ -84,13 +84,107 @@ func (e *SyncEngine) getSelectedFields(config *TaskConfig, sourceDB, sourceTable
 	return nil
 }
 
+// getPrimaryKeyColumns 查询表的主键字段（按主键顺序）
+func getPrimaryKeyColumns(db *sql.DB, database, table string) ([]string, error) {
+	query := `
+		SELECT COLUMN_NAME
+		FROM information_schema.KEY_COLUMN_USAGE
+		WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND CONSTRAINT_NAME = 'PRIMARY'
+		ORDER BY ORDINAL_POSITION
+	`
+	rows, err := db.Query(query, database, table)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	var columns []string
+	for rows.Next() {
+		var column string
+		if err := rows.Scan(&column); err != nil {
+			return nil, err
+		}
+		columns = append(columns, column)
+	}
+	return columns, rows.Err()
+}
+
+// getIntegerPrimaryKey 查询表的整数单列主键，不存在时返回 false
+func getIntegerPrimaryKey(db *sql.DB, database, table string) (string, bool) {
+	pkColumns, err := getPrimaryKeyColumns(db, database, table)
+	if err != nil || len(pkColumns) != 1 {
+		return "", false
+	}
+
+	var dataType string
+	query := "SELECT DATA_TYPE FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = ?"
+	if err := db.QueryRow(query, database, table, pkColumns[0]).Scan(&dataType); err != nil {
+		return "", false
+	}
+	if !integerTypes[strings.ToLower(dataType)] {
+		return "", false
+	}
+	return pkColumns[0], true
+}
+
+// getGeneratedColumns 查询表的生成列（VIRTUAL/STORED GENERATED），值由数据库计算，不能写入
+// 不含 DEFAULT_GENERATED（MySQL 8.0 中带表达式默认值的普通列）
+func getGeneratedColumns(db *sql.DB, database, table string) (map[string]bool, error) {
+	rows, err := db.Query(
+		"SELECT COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND (EXTRA LIKE '%VIRTUAL GENERATED%' OR EXTRA LIKE '%STORED GENERATED%')",
+		database, table)
+	if err != nil {
+		return nil, err
+	}
+	defer rows.Close()
+
+	generated := make(map[string]bool)
+	for rows.Next() {
+		var column string
+		if err := rows.Scan(&column); err != nil {
+			return nil, err
+		}
+		generated[column] = true
+	}
+	return generated, rows.Err()
+}
+
+// excludeGeneratedColumns 从字段列表中去掉生成列；fields 为空表示全部字段，存在生成列时展开为显式列表
+// 查询失败（如不支持生成列的旧版本）时原样返回
+func excludeGeneratedColumns(db *sql.DB, database, table string, fields []string) []string {
+	generated, err := getGeneratedColumns(db, database, table)
+	if err != nil || len(generated) == 0 {
+		return fields
+	}
+
+	if len(fields) == 0 {
+		all, err := getTableColumnNames(db, database, table)
+		if err != nil {
+			return fields
+		}
+		fields = all
+	}
+	return withoutGeneratedColumns(fields, generated)
+}
+
+// withoutGeneratedColumns 去掉字段列表中的生成列（保持原顺序）
+func withoutGeneratedColumns(fields []string, generated map[string]bool) []string {
+	result := make([]string, 0, len(fields))
+	for _, field := range fields {
+		if !generated[field] {
+			result = append(result, field)
+		}
+	}
+	return result
+}
+
 // calculateAdaptiveBatchSize 计算自适应批次大小
 func (e *SyncEngine) calculateAdaptiveBatchSize(sourceConn *models.DataSource, database, table, password string) int {
 	calculator := NewAdaptiveConfigCalculator()
 
 	// 尝试连接数据库获取表统计信息
-	dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local",
-		sourceConn.Username, password, sourceConn.Host, sourceConn.Port, database)
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local",
+		sourceConn.Username, password, mysqlNetAddr(sourceConn.MySQLHost(), sourceConn.Port), database)
 
 	db, err := sql.Open("mysql", dsn)
 	if err != nil {
@@ -0,0 +1,52 @@
+package services
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestWithoutGeneratedColumns(t *testing.T) {
+	// 表结构: id, first_name, last_name, full_name (STORED GENERATED), name_len (VIRTUAL GENERATED)
+	generated := map[string]bool{"full_name": true, "name_len": true}
+	cases := []struct {
+		fields []string
+		want   []string
+	}{
+		{[]string{"id", "first_name", "last_name", "full_name", "name_len"}, []string{"id", "first_name", "last_name"}},
+		{[]string{"full_name", "id"}, []string{"id"}},
+		{[]string{"id", "last_name"}, []string{"id", "last_name"}},
+		{[]string{"full_name"}, []string{}},
+	}
+	for _, c := range cases {
+		if got := withoutGeneratedColumns(c.fields, generated); !reflect.DeepEqual(got, c.want) {
+			t.Errorf("withoutGeneratedColumns(%v) = %v, want %v", c.fields, got, c.want)
+		}
+	}
+}
+
+func TestDropGeneratedColumnsFromBinlogEvent(t *testing.T) {
+	// 生成列已缓存，不查询目标库
+	c := &IncrementalConsumer{generatedColumns: map[string]map[string]bool{
+		"db.users": {"full_name": true},
+		"db.logs":  nil,
+	}}
+
+	event := &BinlogEvent{
+		Type:    "UPDATE",
+		Data:    map[string]interface{}{"id": int64(1), "first_name": "a", "full_name": "a b"},
+		OldData: map[string]interface{}{"id": int64(1), "first_name": "x", "full_name": "x b"},
+	}
+	c.dropGeneratedColumns(event, "db", "users")
+	wantData := map[string]interface{}{"id": int64(1), "first_name": "a"}
+	wantOld := map[string]interface{}{"id": int64(1), "first_name": "x"}
+	if !reflect.DeepEqual(event.Data, wantData) || !reflect.DeepEqual(event.OldData, wantOld) {
+		t.Errorf("after drop: Data = %v, OldData = %v, want %v and %v", event.Data, event.OldData, wantData, wantOld)
+	}
+
+	// 没有生成列的表原样保留
+	event = &BinlogEvent{Type: "INSERT", Data: map[string]interface{}{"id": int64(2), "msg": "hi"}}
+	c.dropGeneratedColumns(event, "db", "logs")
+	if len(event.Data) != 2 {
+		t.Errorf("Data = %v, want unchanged", event.Data)
+	}
+}
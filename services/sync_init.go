@@ -40,12 +40,12 @@ func (e *SyncEngine) InitializeWorker(ctx context.Context, taskID string, unitNa
 	}
 
 	// 3. 解密密码
-	sourcePassword, err := e.dsService.crypto.Decrypt(task.SourceConn.Password)
+	sourcePassword, err := e.dsService.ResolveConnection(task.SourceConn)
 	if err != nil {
 		return fmt.Errorf("解密源数据库密码失败: %v", err)
 	}
 
-	targetPassword, err := e.dsService.crypto.Decrypt(task.TargetConn.Password)
+	targetPassword, err := e.dsService.ResolveConnection(task.TargetConn)
 	if err != nil {
 		return fmt.Errorf("解密目标数据库密码失败: %v", err)
 	}
@@ -101,7 +101,7 @@ func (e *SyncEngine) InitializeWorker(ctx context.Context, taskID string, unitNa
 			continue
 		}
 
-		targetPwd, err := e.dsService.crypto.Decrypt(targetDS.Password)
+		targetPwd, err := e.dsService.ResolveConnection(&targetDS)
 		if err != nil {
 			e.logService.Error(taskID, fmt.Sprintf("解密目标源 %s 密码失败: %v", targetID, err))
 			continue
@@ -119,8 +119,8 @@ func (e *SyncEngine) InitializeWorker(ctx context.Context, taskID string, unitNa
 			}
 
 			// 连接源数据库获取字符集
-			sourceDSN := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
-				task.SourceConn.Username, sourcePassword, task.SourceConn.Host, task.SourceConn.Port, group.sourceDB)
+			sourceDSN := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
+				task.SourceConn.Username, sourcePassword, mysqlNetAddr(task.SourceConn.MySQLHost(), task.SourceConn.Port), group.sourceDB)
 
 			sourceDB, err := sql.Open("mysql", sourceDSN)
 			if err != nil {
@@ -138,10 +138,11 @@ func (e *SyncEngine) InitializeWorker(ctx context.Context, taskID string, unitNa
 				charset = "utf8mb4"
 				collation = "utf8mb4_general_ci"
 			}
+			charset, collation = databaseCharset(config.SyncConfig, charset, collation)
 
 			// 创建目标数据库
 			created, err := CreateDatabaseIfNotExists(
-				targetDS.Host,
+				targetDS.MySQLHost(),
 				targetDS.Port,
 				targetDS.Username,
 				targetPwd,
@@ -169,6 +170,11 @@ func (e *SyncEngine) InitializeWorker(ctx context.Context, taskID string, unitNa
 		e.logService.Warning(taskID, fmt.Sprintf("获取源表数据量失败: %v，将使用实际同步量统计", err))
 	}
 
+	// 源表为空的单元：skip 模式下不处理目标表结构
+	if config.SyncConfig.EmptyTableMode == EmptyTableSkip {
+		unitNames = e.skipEmptySourceTables(ctx, taskID, &task, config, sourcePassword, unitNames)
+	}
+
 	// 7. 按顺序初始化所有表
 	e.logService.Info(taskID, fmt.Sprintf("开始初始化 %d 个表的结构", len(unitNames)))
 
@@ -233,19 +239,13 @@ func (e *SyncEngine) InitializeTable(ctx context.Context, taskID string, unitNam
 	}
 
 	// 2. 创建Reader（用于获取表结构和记录数）
-	reader, err := NewMySQLReader(
-		task.SourceConn.Host,
-		task.SourceConn.Port,
-		task.SourceConn.Username,
-		sourcePassword,
-		sourceDB,
-		sourceTable,
-		config.SyncConfig.BatchSize,
-	)
+	reader, err := newUnitReader(task.SourceConn, sourcePassword, sourceDB, sourceTable,
+		e.getSourceQuery(config, sourceDB, sourceTable), config.SyncConfig.BatchSize, nil, sessionTimeZone(config.SyncConfig.TimestampMode))
 	if err != nil {
 		return fmt.Errorf("创建Reader失败: %v", err)
 	}
 	defer reader.Close()
+	applyRecordLimit(reader, config.SyncConfig.MaxRecordsPerUnit, sourceDB, sourceTable)
 
 	// 获取字段配置
 	selectedFields := e.getSelectedFields(config, sourceDB, sourceTable)
@@ -267,7 +267,7 @@ func (e *SyncEngine) InitializeTable(ctx context.Context, taskID string, unitNam
 			return fmt.Errorf("查询目标源 %s 失败: %w", targetID, err)
 		}
 
-		targetPwd, err := e.dsService.crypto.Decrypt(targetDS.Password)
+		targetPwd, err := e.dsService.ResolveConnection(&targetDS)
 		if err != nil {
 			return fmt.Errorf("解密目标源 %s 密码失败: %w", targetID, err)
 		}
@@ -276,23 +276,126 @@ func (e *SyncEngine) InitializeTable(ctx context.Context, taskID string, unitNam
 
 		// 创建Writer
 		writer, err := NewMySQLWriter(
-			targetDS.Host,
+			targetDS.MySQLHost(),
 			targetDS.Port,
 			targetDS.Username,
 			targetPwd,
 			targetDB,
 			targetTable,
+			sessionTimeZone(config.SyncConfig.TimestampMode),
 		)
 		if err != nil {
 			return fmt.Errorf("目标 %s 创建Writer失败: %w", targetDS.Name, err)
 		}
-		defer writer.Close()
 
-		// 4. 处理表存在策略
-		strategy := config.SyncConfig.TableExistsStrategy
+		// 4. 处理表存在策略（每个目标的连接在本轮结束时立即关闭，不等到所有目标处理完）
+		err = e.applyTableExistsStrategy(taskID, unitName, config, reader, writer, &targetDS, sourceTable, targetTable, selectedFields)
+		writer.Close()
+		if err != nil {
+			return err
+		}
+
+		e.logService.Info(taskID, fmt.Sprintf("目标 %s 初始化完成: %s", targetDS.Name, unitName))
+	}
+
+	// 5. 更新总记录数到内存（为后续同步做准备）
+	// 只有在非"只同步表结构"模式下才更新记录数
+	if !config.SyncConfig.SyncStructureOnly {
+		totalRecords := reader.GetTotalCount()
+		progressManager.UpdateUnitProgress(taskID, unitName, totalRecords, 0)
+	}
+	progressManager.UpdateUnitStatus(taskID, unitName, "initialized")
+
+	// 同时更新所有目标端的状态
+	for _, targetID := range targetIDs {
+		progressManager.UpdateTargetUnitStatus(taskID, targetID, unitName, "initialized")
+	}
+
+	// 每张表初始化后推送 SSE，便于前端与 sse 日志看到初始化进度
+	NewTaskSSEService().BroadcastProgressUpdate(taskID)
+
+	return nil
+}
+
+// applyTableExistsStrategy 按表存在策略（或只同步表结构模式）处理单个目标上的目标表
+func (e *SyncEngine) applyTableExistsStrategy(taskID, unitName string, config *TaskConfig, reader *MySQLReader, writer *MySQLWriter,
+	targetDS *models.DataSource, sourceTable, targetTable string, selectedFields []string) error {
+	writer.SetTableCharset(config.SyncConfig.TargetCharset, config.SyncConfig.TargetCollation)
+	writer.SetNullPolicies(config.SyncConfig.NullPolicies, nil)
+	if reader.SourceQuery() != "" {
+		return e.applyQueryTableStrategy(taskID, unitName, config, reader, writer, targetDS)
+	}
+	strategy := config.SyncConfig.TableExistsStrategy
+
+	// 检查是否是"只同步表结构"模式
+	if config.SyncConfig.SyncStructureOnly {
+		// 使用ALTER对比逻辑
+		alterService := NewTableStructureAlterService()
+		alterResult, err := alterService.CompareAndAlter(
+			reader.GetDB(),
+			writer.GetDB(),
+			sourceTable,
+			targetTable,
+			selectedFields,
+			config.SyncConfig.NullPolicies,
+		)
+		if err != nil {
+			return fmt.Errorf("目标 %s 表结构对比失败: %w", targetDS.Name, err)
+		}
+
+		switch alterResult.Action {
+		case "create":
+			e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 不存在，创建表结构", targetDS.Name, unitName))
+			if err := writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields); err != nil {
+				return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
+			}
+
+		case "update":
+			e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 存在，对比结构差异", targetDS.Name, unitName))
+			if len(alterResult.SQLs) > 0 {
+				// 执行ALTER
+				alterSQL := "ALTER TABLE `" + targetTable + "` " + strings.Join(alterResult.SQLs, ", ")
+				e.logService.Info(taskID, fmt.Sprintf("目标 %s: 执行ALTER: %s", targetDS.Name, alterSQL))
+				if _, err := writer.GetDB().Exec(alterSQL); err != nil {
+					return fmt.Errorf("目标 %s 执行ALTER失败: %w", targetDS.Name, err)
+				}
+				e.logService.Info(taskID, fmt.Sprintf("目标 %s: ALTER完成", targetDS.Name))
+			} else {
+				e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表结构无需修改", targetDS.Name))
+			}
+
+		case "skip":
+			e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 结构一致，跳过", targetDS.Name, unitName))
+		}
+	} else {
+		// 保留目标表结构的策略（drop 重建、append 按源表 ALTER），同步前检查字段兼容性，block 模式下不兼容时不清空目标表
+		if strategy != "drop" && strategy != "append" {
+			if err := e.checkSchemaCompatibility(taskID, unitName, targetDS.Name, config.SyncConfig.SchemaCheck, reader, writer,
+				sourceTable, selectedFields, config.SyncConfig.NullPolicies); err != nil {
+				return err
+			}
+		}
 
-		// 检查是否是"只同步表结构"模式
-		if config.SyncConfig.SyncStructureOnly {
+		// 原有逻辑：全量/增量同步
+		switch strategy {
+		case "drop":
+			// 删除表
+			if err := writer.DropTable(); err != nil {
+				return fmt.Errorf("目标 %s 删除表失败: %w", targetDS.Name, err)
+			}
+
+			// 创建表结构（支持字段过滤）
+			if err := writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields); err != nil {
+				return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
+			}
+
+		case "truncate":
+			// 清空表
+			if err := writer.TruncateTable(); err != nil {
+				return fmt.Errorf("目标 %s 清空表失败: %w", targetDS.Name, err)
+			}
+
+		case "append":
 			// 使用ALTER对比逻辑
 			alterService := NewTableStructureAlterService()
 			alterResult, err := alterService.CompareAndAlter(
@@ -301,6 +404,7 @@ func (e *SyncEngine) InitializeTable(ctx context.Context, taskID string, unitNam
 				sourceTable,
 				targetTable,
 				selectedFields,
+				config.SyncConfig.NullPolicies,
 			)
 			if err != nil {
 				return fmt.Errorf("目标 %s 表结构对比失败: %w", targetDS.Name, err)
@@ -316,96 +420,19 @@ func (e *SyncEngine) InitializeTable(ctx context.Context, taskID string, unitNam
 			case "update":
 				e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 存在，对比结构差异", targetDS.Name, unitName))
 				if len(alterResult.SQLs) > 0 {
-					// 执行ALTER
 					alterSQL := "ALTER TABLE `" + targetTable + "` " + strings.Join(alterResult.SQLs, ", ")
 					e.logService.Info(taskID, fmt.Sprintf("目标 %s: 执行ALTER: %s", targetDS.Name, alterSQL))
 					if _, err := writer.GetDB().Exec(alterSQL); err != nil {
 						return fmt.Errorf("目标 %s 执行ALTER失败: %w", targetDS.Name, err)
 					}
-					e.logService.Info(taskID, fmt.Sprintf("目标 %s: ALTER完成", targetDS.Name))
-				} else {
-					e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表结构无需修改", targetDS.Name))
 				}
 
 			case "skip":
 				e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 结构一致，跳过", targetDS.Name, unitName))
 			}
-		} else {
-			// 原有逻辑：全量/增量同步
-			switch strategy {
-			case "drop":
-				// 删除表
-				if err := writer.DropTable(); err != nil {
-					return fmt.Errorf("目标 %s 删除表失败: %w", targetDS.Name, err)
-				}
-
-				// 创建表结构（支持字段过滤）
-				if err := writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields); err != nil {
-					return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
-				}
-
-			case "truncate":
-				// 清空表
-				if err := writer.TruncateTable(); err != nil {
-					return fmt.Errorf("目标 %s 清空表失败: %w", targetDS.Name, err)
-				}
-
-			case "append":
-				// 使用ALTER对比逻辑
-				alterService := NewTableStructureAlterService()
-				alterResult, err := alterService.CompareAndAlter(
-					reader.GetDB(),
-					writer.GetDB(),
-					sourceTable,
-					targetTable,
-					selectedFields,
-				)
-				if err != nil {
-					return fmt.Errorf("目标 %s 表结构对比失败: %w", targetDS.Name, err)
-				}
-
-				switch alterResult.Action {
-				case "create":
-					e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 不存在，创建表结构", targetDS.Name, unitName))
-					if err := writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields); err != nil {
-						return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
-					}
-
-				case "update":
-					e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 存在，对比结构差异", targetDS.Name, unitName))
-					if len(alterResult.SQLs) > 0 {
-						alterSQL := "ALTER TABLE `" + targetTable + "` " + strings.Join(alterResult.SQLs, ", ")
-						e.logService.Info(taskID, fmt.Sprintf("目标 %s: 执行ALTER: %s", targetDS.Name, alterSQL))
-						if _, err := writer.GetDB().Exec(alterSQL); err != nil {
-							return fmt.Errorf("目标 %s 执行ALTER失败: %w", targetDS.Name, err)
-						}
-					}
-
-				case "skip":
-					e.logService.Info(taskID, fmt.Sprintf("目标 %s: 表 %s 结构一致，跳过", targetDS.Name, unitName))
-				}
-			}
 		}
-
-		e.logService.Info(taskID, fmt.Sprintf("目标 %s 初始化完成: %s", targetDS.Name, unitName))
-	}
-
-	// 5. 更新总记录数到内存（为后续同步做准备）
-	// 只有在非"只同步表结构"模式下才更新记录数
-	if !config.SyncConfig.SyncStructureOnly {
-		totalRecords := reader.GetTotalCount()
-		progressManager.UpdateUnitProgress(taskID, unitName, totalRecords, 0)
-	}
-	progressManager.UpdateUnitStatus(taskID, unitName, "initialized")
-
-	// 同时更新所有目标端的状态
-	for _, targetID := range targetIDs {
-		progressManager.UpdateTargetUnitStatus(taskID, targetID, unitName, "initialized")
 	}
 
-	// 每张表初始化后推送 SSE，便于前端与 sse 日志看到初始化进度
-	NewTaskSSEService().BroadcastProgressUpdate(taskID)
-
 	return nil
 }
 
@@ -427,19 +454,20 @@ func (e *SyncEngine) dropTable(ctx context.Context, taskID string, unitName stri
 			return fmt.Errorf("查询目标源 %s 失败: %w", targetID, err)
 		}
 
-		targetPwd, err := e.dsService.crypto.Decrypt(targetDS.Password)
+		targetPwd, err := e.dsService.ResolveConnection(&targetDS)
 		if err != nil {
 			return fmt.Errorf("解密目标源 %s 密码失败: %w", targetID, err)
 		}
 
 		// 创建Writer
 		writer, err := NewMySQLWriter(
-			targetDS.Host,
+			targetDS.MySQLHost(),
 			targetDS.Port,
 			targetDS.Username,
 			targetPwd,
 			targetDB,
 			targetTable,
+			sessionTimeZone(config.SyncConfig.TimestampMode),
 		)
 		if err != nil {
 			return fmt.Errorf("目标 %s 创建Writer失败: %w", targetDS.Name, err)
@@ -471,19 +499,13 @@ func (e *SyncEngine) createTable(ctx context.Context, taskID string, unitName st
 	}
 
 	// 创建Reader（用于获取表结构和记录数）
-	reader, err := NewMySQLReader(
-		task.SourceConn.Host,
-		task.SourceConn.Port,
-		task.SourceConn.Username,
-		sourcePassword,
-		sourceDB,
-		sourceTable,
-		config.SyncConfig.BatchSize,
-	)
+	reader, err := newUnitReader(task.SourceConn, sourcePassword, sourceDB, sourceTable,
+		e.getSourceQuery(config, sourceDB, sourceTable), config.SyncConfig.BatchSize, nil, sessionTimeZone(config.SyncConfig.TimestampMode))
 	if err != nil {
 		return fmt.Errorf("创建Reader失败: %v", err)
 	}
 	defer reader.Close()
+	applyRecordLimit(reader, config.SyncConfig.MaxRecordsPerUnit, sourceDB, sourceTable)
 
 	// 获取字段配置
 	selectedFields := e.getSelectedFields(config, sourceDB, sourceTable)
@@ -496,26 +518,34 @@ func (e *SyncEngine) createTable(ctx context.Context, taskID string, unitName st
 			return fmt.Errorf("查询目标源 %s 失败: %w", targetID, err)
 		}
 
-		targetPwd, err := e.dsService.crypto.Decrypt(targetDS.Password)
+		targetPwd, err := e.dsService.ResolveConnection(&targetDS)
 		if err != nil {
 			return fmt.Errorf("解密目标源 %s 密码失败: %w", targetID, err)
 		}
 
 		// 创建Writer
 		writer, err := NewMySQLWriter(
-			targetDS.Host,
+			targetDS.MySQLHost(),
 			targetDS.Port,
 			targetDS.Username,
 			targetPwd,
 			targetDB,
 			targetTable,
+			sessionTimeZone(config.SyncConfig.TimestampMode),
 		)
 		if err != nil {
 			return fmt.Errorf("目标 %s 创建Writer失败: %w", targetDS.Name, err)
 		}
 
 		// 创建表结构（支持字段过滤）
-		if err := writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields); err != nil {
+		writer.SetTableCharset(config.SyncConfig.TargetCharset, config.SyncConfig.TargetCollation)
+		writer.SetNullPolicies(config.SyncConfig.NullPolicies, nil)
+		if reader.SourceQuery() != "" {
+			err = e.createQueryTargetTable(taskID, unitName, targetDS.Name, reader, writer)
+		} else {
+			err = writer.CreateTableLikeWithFields(reader.GetDB(), sourceTable, selectedFields)
+		}
+		if err != nil {
 			writer.Close()
 			return fmt.Errorf("目标 %s 创建表结构失败: %w", targetDS.Name, err)
 		}
@@ -553,6 +583,9 @@ func (e *SyncEngine) fetchSourceTableRows(ctx context.Context, taskID string, ta
 			sourceDB = db.Database
 		}
 		for _, tbl := range db.Tables {
+			if tbl.SourceQuery != "" {
+				continue // 查询源没有表状态，按实际统计的总数显示进度
+			}
 			key := db.Database + "." + tbl.TargetTable
 			tableMapping[key] = [2]string{sourceDB, tbl.SourceTable}
 		}
@@ -574,21 +607,20 @@ func (e *SyncEngine) fetchSourceTableRows(ctx context.Context, taskID string, ta
 		default:
 		}
 
-		dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=30s",
-			task.SourceConn.Username, sourcePassword, task.SourceConn.Host, task.SourceConn.Port, dbName)
+		dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=30s",
+			task.SourceConn.Username, sourcePassword, mysqlNetAddr(task.SourceConn.MySQLHost(), task.SourceConn.Port), dbName)
 
 		db, err := sql.Open("mysql", dsn)
 		if err != nil {
 			return fmt.Errorf("连接源数据库 %s 失败: %w", dbName, err)
 		}
-		defer db.Close()
 
 		// 执行 SHOW TABLE STATUS
 		rows, err := db.Query("SHOW TABLE STATUS")
 		if err != nil {
+			db.Close()
 			return fmt.Errorf("获取表状态失败: %w", err)
 		}
-		defer rows.Close()
 
 		// 创建需要过滤的表集合
 		neededTables := make(map[string]bool)
@@ -627,7 +659,9 @@ func (e *SyncEngine) fetchSourceTableRows(ctx context.Context, taskID string, ta
 				}
 			}
 		}
+		// 每个源库的连接处理完立即关闭，避免库多时连接堆积到函数返回
 		rows.Close()
+		db.Close()
 	}
 
 	return nil
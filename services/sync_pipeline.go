@@ -0,0 +1,620 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sync"
+	"sync/atomic"
+	"time"
+)
+
+// DataRecord 标准数据记录（字段名 -> 字段值），Reader/Transformer/Writer 之间的交换格式
+type DataRecord = map[string]interface{}
+
+// DefaultStreamChunkSize 流式读取默认分块大小
+const DefaultStreamChunkSize = 1000
+
+// MaxReadAheadBatches 预读缓冲批次数上限
+const MaxReadAheadBatches = 16
+
+// ValidateReadAheadBatches 校验预读缓冲批次数，0 表示不预读
+func ValidateReadAheadBatches(n int) error {
+	if n < 0 || n > MaxReadAheadBatches {
+		return fmt.Errorf("read_ahead_batches 必须在 0~%d 之间", MaxReadAheadBatches)
+	}
+	return nil
+}
+
+// MaxWriteThreads 每个管道并行写入的 goroutine 数上限
+const MaxWriteThreads = 16
+
+// ValidateWriteThreads 校验并行写入数，0 和 1 表示单个写入
+func ValidateWriteThreads(n int) error {
+	if n < 0 || n > MaxWriteThreads {
+		return fmt.Errorf("write_threads 必须在 0~%d 之间", MaxWriteThreads)
+	}
+	return nil
+}
+
+// ErrPipelinePaused 管道因 context 取消而中止（暂停/停止）
+var ErrPipelinePaused = errors.New("任务被暂停")
+
+// SourceReader 源读取器
+type SourceReader interface {
+	// ReadBatch 读取一个批次，返回空切片表示没有更多数据
+	ReadBatch() ([]DataRecord, error)
+	// HasMore 是否还有更多数据
+	HasMore() bool
+	// GetTotalCount 获取总记录数（用于进度）
+	GetTotalCount() int64
+	// Close 释放资源
+	Close() error
+}
+
+// TargetWriter 目标写入器
+type TargetWriter interface {
+	// WriteBatch 写入一个批次
+	WriteBatch(records []DataRecord) error
+	// Close 释放资源
+	Close() error
+}
+
+// BatchTransactionWriter 支持批次事务的写入器：BeginBatch 之后的多次 WriteBatch 在同一个事务中执行
+// 流式读取时一个批次分多块写入，通过批次事务保证整个批次全部写入或全部回滚
+type BatchTransactionWriter interface {
+	BeginBatch() error
+	CommitBatch() error
+	RollbackBatch() error
+}
+
+// WholeBatchWriter 需要一次收到整个批次的写入器（如批次内去重）：WholeBatch 返回 true 时不启用流式读取，
+// 否则写入器只能看到分块，跨分块的处理无法生效
+type WholeBatchWriter interface {
+	WholeBatch() bool
+}
+
+// StreamingSourceReader 支持流式读取的读取器
+// 逐行读取一个批次，每攒满 chunkSize 条回调一次，单批次内存与批次大小无关
+type StreamingSourceReader interface {
+	// StreamBatch 流式读取一个批次，返回本批次读取的条数；fn 返回错误时停止并原样返回该错误
+	StreamBatch(chunkSize int, fn func(chunk []DataRecord) error) (int, error)
+}
+
+// BatchSizeAdjustable 支持运行时调整批次大小的读取器
+type BatchSizeAdjustable interface {
+	SetBatchSize(batchSize int)
+}
+
+// Transformer 转换器：输入一条记录，返回转换后的记录
+// 返回 nil 记录表示丢弃该条记录；返回 error 时整个批次按批次错误处理
+type Transformer interface {
+	Transform(record DataRecord) (DataRecord, error)
+}
+
+// PipelineHooks 管道回调（进度、错误）
+type PipelineHooks struct {
+	// OnBatch 每个批次写入成功后调用，processed 为累计写入条数
+	OnBatch func(batchNum int, batchSize int, processed int64)
+	// OnBatchError 批次转换或写入失败时调用：返回 nil 表示跳过该批次继续，返回 error 则中止管道
+	// 未设置时默认中止
+	OnBatchError func(batchNum int, err error) error
+	// OnBatchSizeChange 自动调优改变批次大小时调用
+	OnBatchSizeChange func(oldSize, newSize int)
+}
+
+// PipelineResult 管道执行结果
+type PipelineResult struct {
+	Read          int64 // 从 Reader 读取的记录数（转换前）
+	Processed     int64 // 成功写入的记录数
+	Intended      int64 // 应写入的记录数（读取后经转换器过滤，含失败批次的记录）
+	Batches       int   // 已读取的批次数
+	FailedBatches int   // 被跳过的失败批次数
+}
+
+// SyncPipeline 同步管道：Reader -> Transformers -> Writer
+// Reader 和 Writer 的生命周期由调用方管理，管道不负责关闭
+type SyncPipeline struct {
+	reader       SourceReader
+	writer       TargetWriter
+	transformers []Transformer
+	hooks        PipelineHooks
+	tuner        *BatchSizeTuner // 批次大小自动调优（可选）
+	streamChunk  int             // 流式读取分块大小（>0 且 Reader、Writer 都支持时启用）
+	readAhead    int             // 预读缓冲批次数（>0 时读写并行，优先于流式读取）
+	writeThreads int             // 并行写入的 goroutine 数（>1 时启用，Writer 需支持并发写入）
+
+	// 预读模式下调优得到的新批次大小，由读取 goroutine 在下一次读取前应用（Reader 不是线程安全的）
+	pendingBatchSize int64
+}
+
+// NewSyncPipeline 创建同步管道
+func NewSyncPipeline(reader SourceReader, writer TargetWriter, transformers []Transformer, hooks PipelineHooks) *SyncPipeline {
+	return &SyncPipeline{
+		reader:       reader,
+		writer:       writer,
+		transformers: transformers,
+		hooks:        hooks,
+	}
+}
+
+// WithBatchTuner 启用批次大小自动调优（Reader 需实现 BatchSizeAdjustable）
+func (p *SyncPipeline) WithBatchTuner(tuner *BatchSizeTuner) *SyncPipeline {
+	if _, ok := p.reader.(BatchSizeAdjustable); ok {
+		p.tuner = tuner
+	}
+	return p
+}
+
+// WithStreaming 启用流式读取（Reader 需实现 StreamingSourceReader，Writer 需实现 BatchTransactionWriter）
+// 读取和写入按 chunkSize 分块交替进行，大批次不再整批加载到内存；批次仍整体提交或整体回滚，
+// Writer 不支持批次事务时不启用，避免失败批次留下部分写入的分块；Writer 需要整个批次时也不启用
+func (p *SyncPipeline) WithStreaming(chunkSize int) *SyncPipeline {
+	_, streaming := p.reader.(StreamingSourceReader)
+	_, transactional := p.writer.(BatchTransactionWriter)
+	if whole, ok := p.writer.(WholeBatchWriter); ok && whole.WholeBatch() {
+		return p
+	}
+	if streaming && transactional && chunkSize > 0 {
+		p.streamChunk = chunkSize
+	}
+	return p
+}
+
+// WithReadAhead 启用预读：读取在独立 goroutine 中进行，最多缓冲 batches 个批次
+// 写入第 N 批时同时读取第 N+1 批，缓冲区满时读取阻塞（背压），内存最多占用 batches+2 个批次
+func (p *SyncPipeline) WithReadAhead(batches int) *SyncPipeline {
+	if batches > 0 {
+		p.readAhead = batches
+	}
+	return p
+}
+
+// WithWriteThreads 启用并行写入：一个读取 goroutine 预读批次，threads 个写入 goroutine 并发转换和写入
+// 预读缓冲不少于 threads 个批次；threads <= 1 时不启用
+func (p *SyncPipeline) WithWriteThreads(threads int) *SyncPipeline {
+	if threads > 1 {
+		p.writeThreads = threads
+		if p.readAhead < threads {
+			p.readAhead = threads
+		}
+	}
+	return p
+}
+
+// Run 运行管道直到 Reader 读完、ctx 被取消或遇到不可跳过的错误
+func (p *SyncPipeline) Run(ctx context.Context) (*PipelineResult, error) {
+	if p.writeThreads > 1 {
+		return p.runParallelWrites(ctx)
+	}
+	if p.readAhead > 0 {
+		return p.runReadAhead(ctx)
+	}
+	if p.streamChunk > 0 {
+		return p.runStreaming(ctx)
+	}
+
+	result := &PipelineResult{}
+
+	for p.reader.HasMore() {
+		// 每个批次边界检查暂停/停止信号
+		select {
+		case <-ctx.Done():
+			return result, ErrPipelinePaused
+		default:
+		}
+
+		result.Batches++
+		batchNum := result.Batches
+		batchStart := time.Now()
+
+		records, err := p.reader.ReadBatch()
+		if err != nil {
+			return result, fmt.Errorf("读取数据失败: %w", err)
+		}
+		if len(records) == 0 {
+			break
+		}
+
+		read := len(records)
+		result.Read += int64(read)
+		records, err = p.applyTransformers(records)
+		if err != nil {
+			result.Intended += int64(read)
+			if herr := p.handleBatchError(batchNum, fmt.Errorf("转换数据失败: %w", err)); herr != nil {
+				return result, herr
+			}
+			result.FailedBatches++
+			continue
+		}
+		result.Intended += int64(len(records))
+
+		if err := p.writer.WriteBatch(records); err != nil {
+			p.tuneBatchSize(time.Since(batchStart), true)
+			if herr := p.handleBatchError(batchNum, fmt.Errorf("写入数据失败: %w", err)); herr != nil {
+				return result, herr
+			}
+			result.FailedBatches++
+			continue
+		}
+		p.tuneBatchSize(time.Since(batchStart), false)
+
+		result.Processed += int64(len(records))
+		if p.hooks.OnBatch != nil {
+			p.hooks.OnBatch(batchNum, len(records), result.Processed)
+		}
+	}
+
+	return result, nil
+}
+
+// runStreaming 流式运行：批次内按分块读取 -> 转换 -> 写入，整个批次在一个批次事务中提交
+// 批次错误语义与 Run 一致：分块失败时整批回滚、按失败处理，Reader 跳过整个批次
+func (p *SyncPipeline) runStreaming(ctx context.Context) (*PipelineResult, error) {
+	result := &PipelineResult{}
+	streamer := p.reader.(StreamingSourceReader)
+	txWriter := p.writer.(BatchTransactionWriter)
+
+	for p.reader.HasMore() {
+		select {
+		case <-ctx.Done():
+			return result, ErrPipelinePaused
+		default:
+		}
+
+		result.Batches++
+		batchNum := result.Batches
+		batchStart := time.Now()
+
+		if err := txWriter.BeginBatch(); err != nil {
+			return result, fmt.Errorf("写入数据失败: %w", err)
+		}
+
+		var consumed, intended, written int
+		var batchErr error
+		read, err := streamer.StreamBatch(p.streamChunk, func(chunk []DataRecord) error {
+			consumed += len(chunk)
+			rows := len(chunk)
+			chunk, err := p.applyTransformers(chunk)
+			if err != nil {
+				intended += rows
+				batchErr = fmt.Errorf("转换数据失败: %w", err)
+				return batchErr
+			}
+			intended += len(chunk)
+			if err := p.writer.WriteBatch(chunk); err != nil {
+				batchErr = fmt.Errorf("写入数据失败: %w", err)
+				return batchErr
+			}
+			written += len(chunk)
+			return nil
+		})
+
+		// 整批成功才提交；任一分块失败或读取出错时回滚已写入的分块
+		if batchErr == nil && err == nil {
+			if cerr := txWriter.CommitBatch(); cerr != nil {
+				batchErr = fmt.Errorf("写入数据失败: %w", cerr)
+				written = 0
+			}
+		} else {
+			txWriter.RollbackBatch()
+			written = 0
+		}
+		// 失败批次中未回调的行（Reader 已跳过）同样计入应写入的行数
+		if batchErr != nil && read > consumed {
+			intended += read - consumed
+		}
+		result.Read += int64(read)
+		result.Intended += int64(intended)
+
+		if batchErr != nil {
+			p.tuneBatchSize(time.Since(batchStart), true)
+			if herr := p.handleBatchError(batchNum, batchErr); herr != nil {
+				return result, herr
+			}
+			result.FailedBatches++
+			continue
+		}
+		if err != nil {
+			return result, fmt.Errorf("读取数据失败: %w", err)
+		}
+		if read == 0 {
+			break
+		}
+		p.tuneBatchSize(time.Since(batchStart), false)
+
+		result.Processed += int64(written)
+		if p.hooks.OnBatch != nil {
+			p.hooks.OnBatch(batchNum, written, result.Processed)
+		}
+	}
+
+	return result, nil
+}
+
+// readAheadBatch 预读缓冲中的一个批次
+type readAheadBatch struct {
+	batchNum int
+	records  []DataRecord
+}
+
+// runReadAhead 预读运行：读取 goroutine 把批次放入有界缓冲，当前 goroutine 转换并写入
+// 暂停/停止或写入中止时通知读取 goroutine 退出，并等待其退出后再返回，调用方随后关闭 Reader 是安全的
+func (p *SyncPipeline) runReadAhead(ctx context.Context) (*PipelineResult, error) {
+	result := &PipelineResult{}
+
+	batches := make(chan readAheadBatch, p.readAhead)
+	done := make(chan struct{})
+	readerExited := make(chan struct{})
+	var readErr error
+
+	go func() {
+		defer close(readerExited)
+		defer close(batches)
+		batchNum := 0
+		for p.reader.HasMore() {
+			if size := atomic.SwapInt64(&p.pendingBatchSize, 0); size > 0 {
+				p.reader.(BatchSizeAdjustable).SetBatchSize(int(size))
+			}
+
+			records, err := p.reader.ReadBatch()
+			if err != nil {
+				readErr = fmt.Errorf("读取数据失败: %w", err)
+				return
+			}
+			if len(records) == 0 {
+				return
+			}
+
+			batchNum++
+			select {
+			case batches <- readAheadBatch{batchNum: batchNum, records: records}:
+			case <-done:
+				return
+			case <-ctx.Done():
+				return
+			}
+		}
+	}()
+
+	// stop 通知读取 goroutine 退出并等待，缓冲中未写入的批次直接丢弃
+	stop := func() {
+		close(done)
+		for range batches {
+		}
+		<-readerExited
+	}
+
+	for {
+		var batch readAheadBatch
+		var ok bool
+		select {
+		case <-ctx.Done():
+			stop()
+			return result, ErrPipelinePaused
+		case batch, ok = <-batches:
+		}
+		if !ok {
+			break
+		}
+		// 缓冲中已有批次时也要响应暂停/停止
+		if ctx.Err() != nil {
+			stop()
+			return result, ErrPipelinePaused
+		}
+
+		// 读取与写入并行，批次耗时只统计转换和写入
+		result.Batches++
+		result.Read += int64(len(batch.records))
+		batchStart := time.Now()
+		records, err := p.applyTransformers(batch.records)
+		if err != nil {
+			result.Intended += int64(len(batch.records))
+			if herr := p.handleBatchError(batch.batchNum, fmt.Errorf("转换数据失败: %w", err)); herr != nil {
+				stop()
+				return result, herr
+			}
+			result.FailedBatches++
+			continue
+		}
+		result.Intended += int64(len(records))
+
+		if err := p.writer.WriteBatch(records); err != nil {
+			p.tuneBatchSize(time.Since(batchStart), true)
+			if herr := p.handleBatchError(batch.batchNum, fmt.Errorf("写入数据失败: %w", err)); herr != nil {
+				stop()
+				return result, herr
+			}
+			result.FailedBatches++
+			continue
+		}
+		p.tuneBatchSize(time.Since(batchStart), false)
+
+		result.Processed += int64(len(records))
+		if p.hooks.OnBatch != nil {
+			p.hooks.OnBatch(batch.batchNum, len(records), result.Processed)
+		}
+	}
+
+	<-readerExited
+	if readErr != nil {
+		return result, readErr
+	}
+	if ctx.Err() != nil {
+		return result, ErrPipelinePaused
+	}
+	return result, nil
+}
+
+// runParallelWrites 并行写入运行：读取 goroutine 把批次放入有界缓冲，writeThreads 个写入 goroutine 并发转换并写入
+// 批次完成顺序不固定；统计、进度和错误回调在锁内执行。任一批次返回不可跳过的错误或 ctx 取消时，
+// 正在写入的批次写完后停止，缓冲中未写入的批次丢弃
+func (p *SyncPipeline) runParallelWrites(ctx context.Context) (*PipelineResult, error) {
+	result := &PipelineResult{}
+
+	writeCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	batches := make(chan readAheadBatch, p.readAhead)
+	readerExited := make(chan struct{})
+	var readErr error
+
+	go func() {
+		defer close(readerExited)
+		defer close(batches)
+		batchNum := 0
+		for p.reader.HasMore() {
+			if size := atomic.SwapInt64(&p.pendingBatchSize, 0); size > 0 {
+				p.reader.(BatchSizeAdjustable).SetBatchSize(int(size))
+			}
+
+			records, err := p.reader.ReadBatch()
+			if err != nil {
+				readErr = fmt.Errorf("读取数据失败: %w", err)
+				return
+			}
+			if len(records) == 0 {
+				return
+			}
+
+			batchNum++
+			select {
+			case batches <- readAheadBatch{batchNum: batchNum, records: records}:
+			case <-writeCtx.Done():
+				return
+			}
+		}
+	}()
+
+	var mu sync.Mutex
+	var writeErr error
+	var wg sync.WaitGroup
+	for i := 0; i < p.writeThreads; i++ {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for batch := range batches {
+				// 已停止时只排空缓冲，读取 goroutine 随后退出
+				if writeCtx.Err() != nil {
+					continue
+				}
+				if err := p.writeParallelBatch(batch, result, &mu); err != nil {
+					mu.Lock()
+					if writeErr == nil {
+						writeErr = err
+					}
+					mu.Unlock()
+					cancel()
+				}
+			}
+		}()
+	}
+	wg.Wait()
+	<-readerExited
+
+	if writeErr != nil {
+		return result, writeErr
+	}
+	if readErr != nil {
+		return result, readErr
+	}
+	if ctx.Err() != nil {
+		return result, ErrPipelinePaused
+	}
+	return result, nil
+}
+
+// writeParallelBatch 并行写入模式下转换并写入一个批次，转换和写入在锁外进行，统计和回调在锁内进行
+func (p *SyncPipeline) writeParallelBatch(batch readAheadBatch, result *PipelineResult, mu *sync.Mutex) error {
+	batchStart := time.Now()
+	records, transformErr := p.applyTransformers(batch.records)
+	var writeErr error
+	if transformErr == nil {
+		writeErr = p.writer.WriteBatch(records)
+	}
+	latency := time.Since(batchStart)
+
+	mu.Lock()
+	defer mu.Unlock()
+
+	result.Batches++
+	result.Read += int64(len(batch.records))
+	if transformErr != nil {
+		result.Intended += int64(len(batch.records))
+		if herr := p.handleBatchError(batch.batchNum, fmt.Errorf("转换数据失败: %w", transformErr)); herr != nil {
+			return herr
+		}
+		result.FailedBatches++
+		return nil
+	}
+	result.Intended += int64(len(records))
+
+	if writeErr != nil {
+		p.tuneBatchSize(latency, true)
+		if herr := p.handleBatchError(batch.batchNum, fmt.Errorf("写入数据失败: %w", writeErr)); herr != nil {
+			return herr
+		}
+		result.FailedBatches++
+		return nil
+	}
+	p.tuneBatchSize(latency, false)
+
+	result.Processed += int64(len(records))
+	if p.hooks.OnBatch != nil {
+		p.hooks.OnBatch(batch.batchNum, len(records), result.Processed)
+	}
+	return nil
+}
+
+// tuneBatchSize 根据本批次耗时调整后续批次大小
+func (p *SyncPipeline) tuneBatchSize(latency time.Duration, failed bool) {
+	if p.tuner == nil {
+		return
+	}
+	oldSize := p.tuner.Current()
+	newSize, changed := p.tuner.Observe(latency, failed)
+	if !changed {
+		return
+	}
+	if p.readAhead > 0 {
+		atomic.StoreInt64(&p.pendingBatchSize, int64(newSize))
+	} else {
+		p.reader.(BatchSizeAdjustable).SetBatchSize(newSize)
+	}
+	if p.hooks.OnBatchSizeChange != nil {
+		p.hooks.OnBatchSizeChange(oldSize, newSize)
+	}
+}
+
+// applyTransformers 按顺序对批次中的每条记录应用转换器
+func (p *SyncPipeline) applyTransformers(records []DataRecord) ([]DataRecord, error) {
+	if len(p.transformers) == 0 {
+		return records, nil
+	}
+
+	out := make([]DataRecord, 0, len(records))
+	for _, record := range records {
+		var err error
+		for _, t := range p.transformers {
+			record, err = t.Transform(record)
+			if err != nil {
+				return nil, err
+			}
+			if record == nil {
+				break
+			}
+		}
+		if record != nil {
+			out = append(out, record)
+		}
+	}
+	return out, nil
+}
+
+// handleBatchError 交给错误回调决定是否继续
+func (p *SyncPipeline) handleBatchError(batchNum int, err error) error {
+	if p.hooks.OnBatchError == nil {
+		return err
+	}
+	return p.hooks.OnBatchError(batchNum, err)
+}
@@ -0,0 +1,434 @@
+package services
+
+import (
+	"context"
+	"errors"
+	"sync"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+// stubReader 内存中的 SourceReader，按 batchSize 分批返回 rows
+type stubReader struct {
+	rows      []DataRecord
+	batchSize int
+	offset    int
+}
+
+func (r *stubReader) ReadBatch() ([]DataRecord, error) {
+	end := min(r.offset+r.batchSize, len(r.rows))
+	batch := r.rows[r.offset:end]
+	r.offset = end
+	return batch, nil
+}
+
+func (r *stubReader) HasMore() bool        { return r.offset < len(r.rows) }
+func (r *stubReader) GetTotalCount() int64 { return int64(len(r.rows)) }
+func (r *stubReader) Close() error         { return nil }
+func (r *stubReader) SetBatchSize(n int)   { r.batchSize = n }
+
+// stubStreamReader 支持流式读取的 stubReader：一个批次按 chunkSize 分块回调
+type stubStreamReader struct {
+	stubReader
+}
+
+func (r *stubStreamReader) StreamBatch(chunkSize int, fn func(chunk []DataRecord) error) (int, error) {
+	batch, _ := r.ReadBatch()
+	for i := 0; i < len(batch); i += chunkSize {
+		if err := fn(batch[i:min(i+chunkSize, len(batch))]); err != nil {
+			return len(batch), err
+		}
+	}
+	return len(batch), nil
+}
+
+// countingReader 记录 ReadBatch 调用次数的 stubReader（预读模式下在读取 goroutine 中调用）
+type countingReader struct {
+	stubReader
+	reads int32
+}
+
+func (r *countingReader) ReadBatch() ([]DataRecord, error) {
+	atomic.AddInt32(&r.reads, 1)
+	return r.stubReader.ReadBatch()
+}
+
+// stubWriter 内存中的 TargetWriter，failOn 中的第 N 次 WriteBatch 调用（从 1 开始）返回错误
+type stubWriter struct {
+	mu      sync.Mutex
+	written []DataRecord
+	calls   int
+	failOn  map[int]bool
+}
+
+func (w *stubWriter) WriteBatch(records []DataRecord) error {
+	w.mu.Lock()
+	defer w.mu.Unlock()
+	w.calls++
+	if w.failOn[w.calls] {
+		return errors.New("写入失败")
+	}
+	w.written = append(w.written, records...)
+	return nil
+}
+
+func (w *stubWriter) Close() error { return nil }
+
+// stubTxWriter 支持批次事务的 stubWriter：事务中的写入在提交时才计入 written
+type stubTxWriter struct {
+	stubWriter
+	inTx      bool
+	pending   []DataRecord
+	commits   int
+	rollbacks int
+}
+
+func (w *stubTxWriter) WriteBatch(records []DataRecord) error {
+	if !w.inTx {
+		return w.stubWriter.WriteBatch(records)
+	}
+	w.calls++
+	if w.failOn[w.calls] {
+		return errors.New("写入失败")
+	}
+	w.pending = append(w.pending, records...)
+	return nil
+}
+
+func (w *stubTxWriter) BeginBatch() error {
+	w.inTx = true
+	return nil
+}
+
+func (w *stubTxWriter) CommitBatch() error {
+	w.written = append(w.written, w.pending...)
+	w.pending, w.inTx = nil, false
+	w.commits++
+	return nil
+}
+
+func (w *stubTxWriter) RollbackBatch() error {
+	w.pending, w.inTx = nil, false
+	w.rollbacks++
+	return nil
+}
+
+// blockingWriter 每次 WriteBatch 先通知 entered，等 release 放行后才写入
+type blockingWriter struct {
+	stubWriter
+	entered chan struct{}
+	release chan struct{}
+}
+
+func (w *blockingWriter) WriteBatch(records []DataRecord) error {
+	w.entered <- struct{}{}
+	<-w.release
+	return w.stubWriter.WriteBatch(records)
+}
+
+// waitFor 轮询等待条件成立，超时则测试失败
+func waitFor(t *testing.T, what string, cond func() bool) {
+	t.Helper()
+	deadline := time.Now().Add(2 * time.Second)
+	for !cond() {
+		if time.Now().After(deadline) {
+			t.Fatalf("timed out waiting for %s", what)
+		}
+		time.Sleep(time.Millisecond)
+	}
+}
+
+// makeRows 生成 n 条 id 从 1 开始的记录
+func makeRows(n int) []DataRecord {
+	rows := make([]DataRecord, n)
+	for i := range rows {
+		rows[i] = DataRecord{"id": int64(i + 1)}
+	}
+	return rows
+}
+
+// skipBatchErrors 跳过失败批次继续执行
+func skipBatchErrors(failed *[]int) PipelineHooks {
+	return PipelineHooks{OnBatchError: func(batchNum int, err error) error {
+		*failed = append(*failed, batchNum)
+		return nil
+	}}
+}
+
+func TestStreamingRollsBackWholeBatchOnMidBatchFailure(t *testing.T) {
+	// 两个批次各 2500 行，按 1000 行分块写入；第一批的第 2 个分块（第 2 次写入）失败
+	reader := &stubStreamReader{stubReader{rows: makeRows(5000), batchSize: 2500}}
+	writer := &stubTxWriter{stubWriter: stubWriter{failOn: map[int]bool{2: true}}}
+	var failed []int
+
+	result, err := NewSyncPipeline(reader, writer, nil, skipBatchErrors(&failed)).
+		WithStreaming(1000).
+		Run(context.Background())
+	if err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+
+	if len(failed) != 1 || failed[0] != 1 {
+		t.Fatalf("failed batches = %v, want [1]", failed)
+	}
+	if writer.rollbacks != 1 || writer.commits != 1 {
+		t.Errorf("rollbacks = %d, commits = %d, want 1 and 1", writer.rollbacks, writer.commits)
+	}
+	// 第一批已写入的第 1 个分块随整批回滚，只留下第二批
+	if len(writer.written) != 2500 {
+		t.Fatalf("written %d rows, want 2500", len(writer.written))
+	}
+	if first := writer.written[0]["id"]; first != int64(2501) {
+		t.Errorf("first written id = %v, want 2501", first)
+	}
+	if result.Processed != 2500 || result.FailedBatches != 1 {
+		t.Errorf("Processed = %d, FailedBatches = %d, want 2500 and 1", result.Processed, result.FailedBatches)
+	}
+}
+
+func TestStreamingFailedBatchCountsWholeBatchAsIntended(t *testing.T) {
+	// 第一批 2500 行在第 1 个分块就失败，剩余 1500 行未回调，仍计入应写入的行数
+	reader := &stubStreamReader{stubReader{rows: makeRows(5000), batchSize: 2500}}
+	writer := &stubTxWriter{stubWriter: stubWriter{failOn: map[int]bool{1: true}}}
+	var failed []int
+
+	result, err := NewSyncPipeline(reader, writer, nil, skipBatchErrors(&failed)).
+		WithStreaming(1000).
+		Run(context.Background())
+	if err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+	if result.Intended != 5000 || result.Processed != 2500 {
+		t.Errorf("Intended = %d, Processed = %d, want 5000 and 2500", result.Intended, result.Processed)
+	}
+	if reader.offset != 5000 {
+		t.Errorf("reader offset = %d, want 5000", reader.offset)
+	}
+}
+
+func TestStreamingRequiresBatchTransactionWriter(t *testing.T) {
+	reader := &stubStreamReader{stubReader{rows: makeRows(10), batchSize: 10}}
+
+	pipeline := NewSyncPipeline(reader, &stubWriter{}, nil, PipelineHooks{}).WithStreaming(3)
+	if pipeline.streamChunk != 0 {
+		t.Errorf("streaming enabled for a writer without batch transactions")
+	}
+	pipeline = NewSyncPipeline(reader, &stubTxWriter{}, nil, PipelineHooks{}).WithStreaming(3)
+	if pipeline.streamChunk != 3 {
+		t.Errorf("streamChunk = %d, want 3", pipeline.streamChunk)
+	}
+}
+
+func TestReadAheadBackpressure(t *testing.T) {
+	// 写入阻塞时，读取最多领先：正在写入的 1 批 + 缓冲中的 readAhead 批 + 已读出等待放入缓冲的 1 批
+	const readAhead, batches = 2, 10
+	reader := &countingReader{stubReader: stubReader{rows: makeRows(batches * 10), batchSize: 10}}
+	writer := &blockingWriter{entered: make(chan struct{}), release: make(chan struct{})}
+
+	type runResult struct {
+		result *PipelineResult
+		err    error
+	}
+	done := make(chan runResult, 1)
+	go func() {
+		result, err := NewSyncPipeline(reader, writer, nil, PipelineHooks{}).WithReadAhead(readAhead).Run(context.Background())
+		done <- runResult{result, err}
+	}()
+
+	reads := func() int32 { return atomic.LoadInt32(&reader.reads) }
+	for i := 1; i <= batches; i++ {
+		<-writer.entered
+		limit := int32(min(i+readAhead+1, batches))
+		if i == 1 {
+			waitFor(t, "reader to fill the buffer", func() bool { return reads() == limit })
+			// 缓冲已满，读取 goroutine 不再继续读取
+			time.Sleep(20 * time.Millisecond)
+		}
+		if got := reads(); got > limit {
+			t.Fatalf("writing batch %d: reader read %d batches, want at most %d", i, got, limit)
+		}
+		writer.release <- struct{}{}
+	}
+
+	r := <-done
+	if r.err != nil {
+		t.Fatalf("Run() error = %v", r.err)
+	}
+	if r.result.Batches != batches || r.result.Processed != batches*10 {
+		t.Errorf("Batches = %d, Processed = %d, want %d and %d", r.result.Batches, r.result.Processed, batches, batches*10)
+	}
+	for i, record := range writer.written {
+		if record["id"] != int64(i+1) {
+			t.Fatalf("written[%d] id = %v, want %d (batches written out of order)", i, record["id"], i+1)
+		}
+	}
+}
+
+func TestReadAheadPauseStopsReader(t *testing.T) {
+	const readAhead = 2
+	reader := &countingReader{stubReader: stubReader{rows: makeRows(100), batchSize: 10}}
+	writer := &blockingWriter{entered: make(chan struct{}), release: make(chan struct{})}
+	ctx, cancel := context.WithCancel(context.Background())
+
+	done := make(chan error, 1)
+	go func() {
+		_, err := NewSyncPipeline(reader, writer, nil, PipelineHooks{}).WithReadAhead(readAhead).Run(ctx)
+		done <- err
+	}()
+
+	<-writer.entered
+	waitFor(t, "reader to fill the buffer", func() bool { return atomic.LoadInt32(&reader.reads) == readAhead+2 })
+	cancel()
+	writer.release <- struct{}{}
+
+	if err := <-done; !errors.Is(err, ErrPipelinePaused) {
+		t.Fatalf("Run() error = %v, want ErrPipelinePaused", err)
+	}
+	// Run 返回前已等待读取 goroutine 退出，缓冲中的批次被丢弃且不再读取
+	reads := atomic.LoadInt32(&reader.reads)
+	time.Sleep(20 * time.Millisecond)
+	if got := atomic.LoadInt32(&reader.reads); got != reads {
+		t.Errorf("reader kept reading after Run returned: %d -> %d", reads, got)
+	}
+	if len(writer.written) != 10 {
+		t.Errorf("written %d rows, want only the first batch", len(writer.written))
+	}
+}
+
+func TestPipelineRunsAllBatchesInEveryMode(t *testing.T) {
+	modes := []struct {
+		name  string
+		build func(reader *stubStreamReader, writer *stubTxWriter, hooks PipelineHooks) *SyncPipeline
+	}{
+		{"batch", func(r *stubStreamReader, w *stubTxWriter, h PipelineHooks) *SyncPipeline {
+			return NewSyncPipeline(&r.stubReader, w, nil, h)
+		}},
+		{"streaming", func(r *stubStreamReader, w *stubTxWriter, h PipelineHooks) *SyncPipeline {
+			return NewSyncPipeline(r, w, nil, h).WithStreaming(4)
+		}},
+		{"read-ahead", func(r *stubStreamReader, w *stubTxWriter, h PipelineHooks) *SyncPipeline {
+			return NewSyncPipeline(r, w, nil, h).WithReadAhead(2)
+		}},
+	}
+	for _, mode := range modes {
+		reader := &stubStreamReader{stubReader{rows: makeRows(25), batchSize: 10}}
+		writer := &stubTxWriter{}
+		var progress [][3]int64
+		hooks := PipelineHooks{OnBatch: func(batchNum, batchSize int, processed int64) {
+			progress = append(progress, [3]int64{int64(batchNum), int64(batchSize), processed})
+		}}
+
+		result, err := mode.build(reader, writer, hooks).Run(context.Background())
+		if err != nil {
+			t.Fatalf("%s: Run() error = %v", mode.name, err)
+		}
+		if result.Batches != 3 || result.Read != 25 || result.Intended != 25 || result.Processed != 25 {
+			t.Errorf("%s: result = %+v, want 3 batches and 25 rows read/intended/processed", mode.name, *result)
+		}
+		want := [][3]int64{{1, 10, 10}, {2, 10, 20}, {3, 5, 25}}
+		if len(progress) != len(want) {
+			t.Fatalf("%s: OnBatch calls = %v, want %v", mode.name, progress, want)
+		}
+		for i := range want {
+			if progress[i] != want[i] {
+				t.Errorf("%s: OnBatch call %d = %v, want %v", mode.name, i, progress[i], want[i])
+			}
+		}
+		if len(writer.written) != 25 {
+			t.Errorf("%s: written %d rows, want 25", mode.name, len(writer.written))
+		}
+	}
+}
+
+func TestPipelineAppliesTransformersInOrder(t *testing.T) {
+	// 第一个转换器丢弃偶数 id 并计算 double，第二个转换器基于 double 继续计算
+	dropEven := TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		id := record["id"].(int64)
+		if id%2 == 0 {
+			return nil, nil
+		}
+		record["double"] = id * 2
+		return record, nil
+	})
+	plusOne := TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		record["next"] = record["double"].(int64) + 1
+		return record, nil
+	})
+	reader := &stubReader{rows: makeRows(10), batchSize: 4}
+	writer := &stubWriter{}
+
+	result, err := NewSyncPipeline(reader, writer, []Transformer{dropEven, plusOne}, PipelineHooks{}).Run(context.Background())
+	if err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+	if result.Read != 10 || result.Intended != 5 || result.Processed != 5 {
+		t.Errorf("Read = %d, Intended = %d, Processed = %d, want 10, 5 and 5", result.Read, result.Intended, result.Processed)
+	}
+	for _, record := range writer.written {
+		id := record["id"].(int64)
+		if id%2 == 0 || record["next"] != id*2+1 {
+			t.Errorf("written record %v, want odd id with next = id*2+1", record)
+		}
+	}
+}
+
+func TestPipelineSkipsFailedTransformBatch(t *testing.T) {
+	failOnThree := TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		if record["id"] == int64(3) {
+			return nil, errors.New("转换失败")
+		}
+		return record, nil
+	})
+	reader := &stubReader{rows: makeRows(20), batchSize: 10}
+	writer := &stubWriter{}
+	var failed []int
+
+	result, err := NewSyncPipeline(reader, writer, []Transformer{failOnThree}, skipBatchErrors(&failed)).Run(context.Background())
+	if err != nil {
+		t.Fatalf("Run() error = %v", err)
+	}
+	if len(failed) != 1 || failed[0] != 1 {
+		t.Fatalf("failed batches = %v, want [1]", failed)
+	}
+	// 失败批次整批计入应写入的行数，不写入任何一行
+	if result.FailedBatches != 1 || result.Intended != 20 || result.Processed != 10 || len(writer.written) != 10 {
+		t.Errorf("result = %+v, written %d, want 1 failed batch, 20 intended and 10 processed", *result, len(writer.written))
+	}
+}
+
+func TestPipelineAbortsOnWriteErrorWithoutHook(t *testing.T) {
+	reader := &stubReader{rows: makeRows(30), batchSize: 10}
+	writer := &stubWriter{failOn: map[int]bool{2: true}}
+
+	result, err := NewSyncPipeline(reader, writer, nil, PipelineHooks{}).Run(context.Background())
+	if err == nil {
+		t.Fatal("Run() succeeded, want the write error")
+	}
+	if result.Processed != 10 || result.Batches != 2 {
+		t.Errorf("Processed = %d, Batches = %d, want 10 and 2", result.Processed, result.Batches)
+	}
+	if reader.offset != 20 {
+		t.Errorf("reader offset = %d, want 20 (no reads after the abort)", reader.offset)
+	}
+}
+
+func TestPipelinePausesAtBatchBoundary(t *testing.T) {
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	reader := &stubReader{rows: makeRows(30), batchSize: 10}
+	writer := &stubWriter{}
+	hooks := PipelineHooks{OnBatch: func(batchNum, batchSize int, processed int64) {
+		if batchNum == 1 {
+			cancel()
+		}
+	}}
+
+	result, err := NewSyncPipeline(reader, writer, nil, hooks).Run(ctx)
+	if !errors.Is(err, ErrPipelinePaused) {
+		t.Fatalf("Run() error = %v, want ErrPipelinePaused", err)
+	}
+	if result.Processed != 10 || reader.offset != 10 {
+		t.Errorf("Processed = %d, reader offset = %d, want 10 and 10", result.Processed, reader.offset)
+	}
+}
@@ -0,0 +1,141 @@
+package services
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"sync"
+	"time"
+)
+
+// AutoRetryConfig 失败单元自动重试配置
+type AutoRetryConfig struct {
+	Attempts     int `json:"attempts"`      // 最多重试轮数 1~10
+	DelaySeconds int `json:"delay_seconds"` // 每轮重试前等待的秒数 0~3600
+}
+
+// ValidateAutoRetryConfig 校验自动重试配置，nil 表示未开启
+func ValidateAutoRetryConfig(cfg *AutoRetryConfig) error {
+	if cfg == nil {
+		return nil
+	}
+	if cfg.Attempts < 1 || cfg.Attempts > 10 {
+		return fmt.Errorf("attempts 必须在 1~10 之间")
+	}
+	if cfg.DelaySeconds < 0 || cfg.DelaySeconds > 3600 {
+		return fmt.Errorf("delay_seconds 必须在 0~3600 之间")
+	}
+	return nil
+}
+
+// RetryFailedUnits 全量同步主流程结束后自动重试失败的单元
+// 每轮：等待 delay → 重置失败单元进度 → 清空目标表（仅 drop/truncate 策略）→ 用 Worker 池重新同步
+// 没有失败单元、任务被取消或达到重试轮数时结束，返回最终仍失败的单元
+func (e *SyncEngine) RetryFailedUnits(ctx context.Context, taskID string, unitNames []string, cfg *AutoRetryConfig, threadCount int) []string {
+	progressManager := GetProgressManager()
+	failed := progressManager.GetFailedUnitNames(taskID, unitNames)
+	if cfg == nil || len(failed) == 0 {
+		return failed
+	}
+
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(taskID)
+	if err != nil {
+		e.logService.Error(taskID, fmt.Sprintf("自动重试获取配置失败: %v", err))
+		return failed
+	}
+
+	for round := 1; round <= cfg.Attempts && len(failed) > 0; round++ {
+		e.logService.Info(taskID, fmt.Sprintf("========== 自动重试 %d/%d: %d 个失败单元 ==========", round, cfg.Attempts, len(failed)))
+
+		if cfg.DelaySeconds > 0 {
+			select {
+			case <-ctx.Done():
+				e.logService.Info(taskID, "任务被取消，停止自动重试")
+				return failed
+			case <-time.After(time.Duration(cfg.DelaySeconds) * time.Second):
+			}
+		}
+		if ctx.Err() != nil {
+			e.logService.Info(taskID, "任务被取消，停止自动重试")
+			return failed
+		}
+
+		queue := make(chan string, len(failed))
+		for _, unitName := range failed {
+			progressManager.ResetUnit(taskID, unitName)
+			e.clearRetryTargets(taskID, unitName, config)
+			queue <- unitName
+		}
+		close(queue)
+		e.sseService.BroadcastProgressUpdate(taskID)
+
+		workers := threadCount
+		if workers > len(failed) {
+			workers = len(failed)
+		}
+		if workers < 1 {
+			workers = 1
+		}
+
+		var wg sync.WaitGroup
+		for i := 0; i < workers; i++ {
+			wg.Add(1)
+			go func(workerID int) {
+				defer wg.Done()
+				e.Worker(ctx, taskID, queue, workerID)
+			}(i)
+		}
+		wg.Wait()
+
+		retried := len(failed)
+		failed = progressManager.GetFailedUnitNames(taskID, unitNames)
+		e.logService.Info(taskID, fmt.Sprintf("自动重试 %d/%d 完成: 成功 %d 个，仍失败 %d 个", round, cfg.Attempts, retried-len(failed), len(failed)))
+	}
+
+	if len(failed) > 0 {
+		e.logService.Error(taskID, fmt.Sprintf("自动重试结束，仍有 %d 个单元失败: %s", len(failed), strings.Join(failed, ", ")))
+	} else {
+		e.logService.Info(taskID, "自动重试结束，所有失败单元已同步成功")
+	}
+	return failed
+}
+
+// clearRetryTargets 重试前清空各目标源上的目标表，避免与上次写入的部分数据主键冲突
+// 只在 drop/truncate 策略下执行（初始化时目标表本就被清空）；skip/append 策略保留目标原有数据，不清空
+func (e *SyncEngine) clearRetryTargets(taskID, unitName string, config *TaskConfig) {
+	strategy := config.SyncConfig.TableExistsStrategy
+	if strategy != "drop" && strategy != "truncate" {
+		e.logService.Warning(taskID, fmt.Sprintf("表 %s 的目标表存在策略为 %s，重试前不清空目标表，已写入的数据可能导致主键冲突", unitName, strategy))
+		return
+	}
+
+	_, _, targetDB, targetTable, err := e.parseUnitName(unitName, config)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("表 %s 重试前解析表名失败: %v", unitName, err))
+		return
+	}
+
+	targetIDs := config.TargetIDs
+	if len(targetIDs) == 0 {
+		targetIDs = []string{config.TargetID}
+	}
+	targetConns, err := e.loadTargetConns(targetIDs)
+	if err != nil {
+		e.logService.Warning(taskID, fmt.Sprintf("表 %s 重试前加载目标源失败: %v", unitName, err))
+		return
+	}
+
+	for _, targetConn := range targetConns {
+		writer, err := NewMySQLWriter(targetConn.Conn.MySQLHost(), targetConn.Conn.Port, targetConn.Conn.Username,
+			targetConn.Password, targetDB, targetTable, sessionTimeZone(config.SyncConfig.TimestampMode))
+		if err != nil {
+			e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 重试前连接失败: %v", targetConn.Conn.Name, unitName, err))
+			continue
+		}
+		// 目标表不存在时清空会失败，重新同步时会自动建表，这里忽略
+		if err := writer.TruncateTable(); err != nil {
+			e.logService.Warning(taskID, fmt.Sprintf("目标 %s 表 %s 重试前清空失败: %v", targetConn.Conn.Name, unitName, err))
+		}
+		writer.Close()
+	}
+}
@@ -0,0 +1,80 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"strings"
+	"sync/atomic"
+	"time"
+)
+
+// rowCounts 单元同一目标本次应写入和实际写入的行数
+type rowCounts struct {
+	intended int64
+	written  int64
+}
+
+// countSourceRows 在源端独立统计本单元应同步的行数：源表或查询源结果集的 COUNT(*)，按 max_records_per_unit 截断
+// 全量同步总是从第 0 行开始读取（没有断点续读），因此不需要扣除起始偏移
+func countSourceRows(db *sql.DB, database, table, sourceQuery string, maxRecords int64) (int64, error) {
+	from := fmt.Sprintf("`%s`.`%s`", database, table)
+	if sourceQuery != "" {
+		from = querySourceFrom(sourceQuery)
+	}
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Minute)
+	defer cancel()
+
+	var count int64
+	if err := db.QueryRowContext(ctx, fmt.Sprintf("SELECT COUNT(*) FROM %s", from)).Scan(&count); err != nil {
+		return 0, err
+	}
+	return capRecords(count, maxRecords), nil
+}
+
+// verifyRowCounts 对比本次应写入的行数和实际写入目标的行数，结果写入 verify 日志
+// 应写入行数按源端独立统计的行数（sourceRows，统计失败时为 -1）计算，再扣除转换器过滤掉的行；
+// 与管道读取的行数不一致时（读取提前结束，或同步期间源表有变化）会在原因中列出。
+// 实际写入行数为成功写入的记录数减去 duplicate_key_mode=ignore 忽略的冲突行；batch_dedup 去除的重复行两边都不计入；
+// 与目标表 COUNT(*) 不同，不受目标表原有数据（append 策略）的影响
+func (e *SyncEngine) verifyRowCounts(taskID, targetName, unitName string, sourceRows int64, result *PipelineResult,
+	duplicates *duplicateKeyStats, dedup *batchDedupPolicy) rowCounts {
+	if result == nil {
+		return rowCounts{}
+	}
+	deduped := dedup.droppedRows()
+	filtered := result.Read - result.Intended // 转换器过滤掉的行
+	if sourceRows < 0 {
+		e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 源端行数统计失败，按本次读取的 %d 行校验",
+			targetName, unitName, result.Read), "verify")
+		sourceRows = result.Read
+	}
+	counts := rowCounts{
+		intended: sourceRows - filtered - deduped,
+		written:  result.Processed - deduped - atomic.LoadInt64(&duplicates.ignored),
+	}
+
+	if counts.written == counts.intended {
+		e.logService.AddLog(taskID, "success", fmt.Sprintf("目标 %s 表 %s 行数校验通过: 应写入 %d 行，实际写入 %d 行",
+			targetName, unitName, counts.intended, counts.written), "verify")
+		return counts
+	}
+
+	var reasons []string
+	if sourceRows != result.Read {
+		reasons = append(reasons, fmt.Sprintf("源端 %d 行，本次只读取 %d 行（读取提前结束或同步期间源表有变化）", sourceRows, result.Read))
+	}
+	if result.FailedBatches > 0 {
+		reasons = append(reasons, fmt.Sprintf("跳过 %d 个失败批次", result.FailedBatches))
+	}
+	if ignored := atomic.LoadInt64(&duplicates.ignored); ignored > 0 {
+		reasons = append(reasons, fmt.Sprintf("忽略 %d 行主键冲突", ignored))
+	}
+	message := fmt.Sprintf("目标 %s 表 %s 行数不一致: 应写入 %d 行，实际写入 %d 行，相差 %d 行",
+		targetName, unitName, counts.intended, counts.written, counts.intended-counts.written)
+	if len(reasons) > 0 {
+		message += fmt.Sprintf("（%s）", strings.Join(reasons, "，"))
+	}
+	e.logService.AddLog(taskID, "warning", message, "verify")
+	return counts
+}
@@ -0,0 +1,169 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"fmt"
+	"sync"
+)
+
+// TableShard 表内并行分片（主键闭区间 [Lower, Upper]）
+type TableShard struct {
+	PKColumn string
+	Lower    int64
+	Upper    int64
+}
+
+// planTableShards 规划表内并行分片，不满足条件时返回 nil（走单 Reader 串行同步）
+// 条件：配置的分片数 > 1、整数单列主键、数据量足够每个分片至少一个批次；分片数受自适应线程数限制
+func (e *SyncEngine) planTableShards(taskID, unitName string, db *sql.DB, database, table string, requested int, totalRecords int64, batchSize int) []TableShard {
+	if requested <= 1 {
+		return nil
+	}
+
+	shardCount := requested
+	if threadBudget := NewAdaptiveConfigCalculator().GetDefaultConfig().ThreadCount; threadBudget > 0 && shardCount > threadBudget {
+		shardCount = threadBudget
+	}
+	if batchSize > 0 && totalRecords/int64(batchSize) < int64(shardCount) {
+		shardCount = int(totalRecords / int64(batchSize))
+	}
+	if shardCount <= 1 {
+		return nil
+	}
+
+	pkColumn, ok := getIntegerPrimaryKey(db, database, table)
+	if !ok {
+		e.logService.Info(taskID, fmt.Sprintf("表 %s 没有整数单列主键，不进行表内并行分片", unitName))
+		return nil
+	}
+
+	var minPK, maxPK sql.NullInt64
+	query := fmt.Sprintf("SELECT MIN(`%s`), MAX(`%s`) FROM `%s`", pkColumn, pkColumn, table)
+	if err := db.QueryRow(query).Scan(&minPK, &maxPK); err != nil || !minPK.Valid || !maxPK.Valid {
+		e.logService.Warning(taskID, fmt.Sprintf("表 %s 查询主键范围失败，不进行表内并行分片: %v", unitName, err))
+		return nil
+	}
+
+	return splitPKRange(pkColumn, minPK.Int64, maxPK.Int64, shardCount)
+}
+
+// splitPKRange 将 [minPK, maxPK] 均分为 n 段
+func splitPKRange(pkColumn string, minPK, maxPK int64, n int) []TableShard {
+	span := uint64(maxPK - minPK) // 两数之差按无符号计算，避免溢出
+	if span < uint64(n) {
+		n = int(span) + 1
+	}
+	step := span/uint64(n) + 1
+
+	shards := make([]TableShard, 0, n)
+	for i := 0; i < n; i++ {
+		lower := minPK + int64(uint64(i)*step)
+		upper := lower + int64(step) - 1
+		if i == n-1 || upper > maxPK || upper < lower {
+			upper = maxPK
+		}
+		shards = append(shards, TableShard{PKColumn: pkColumn, Lower: lower, Upper: upper})
+		if upper == maxPK {
+			break
+		}
+	}
+	return shards
+}
+
+// runShardedPipelines 并行运行各分片的管道
+// 每个分片使用独立的 Reader/Writer；进度在锁内累加后回调，保证不重复计数且单调递增
+// 返回各分片管道结果之和（分片失败时也包含已完成的部分）
+func (e *SyncEngine) runShardedPipelines(ctx context.Context, shards []TableShard,
+	newReader func() (*MySQLReader, error), newWriter func() (*MySQLWriter, error),
+	transformers []Transformer, hooks PipelineHooks, newTuner func() *BatchSizeTuner, readAhead int) (*PipelineResult, error) {
+	shardCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	var mu sync.Mutex
+	var processed int64
+	total := &PipelineResult{}
+	batchCounter := 0
+
+	shardHooks := PipelineHooks{
+		OnBatch: func(_ int, batchSize int, _ int64) {
+			mu.Lock()
+			defer mu.Unlock()
+			processed += int64(batchSize)
+			batchCounter++
+			if hooks.OnBatch != nil {
+				hooks.OnBatch(batchCounter, batchSize, processed)
+			}
+		},
+		OnBatchError:      hooks.OnBatchError,
+		OnBatchSizeChange: hooks.OnBatchSizeChange,
+	}
+
+	var wg sync.WaitGroup
+	errCh := make(chan error, len(shards))
+
+	for i, shard := range shards {
+		wg.Add(1)
+		go func(shardNum int, shard TableShard) {
+			defer wg.Done()
+
+			fail := func(err error) {
+				errCh <- fmt.Errorf("分片 %d [%d, %d]: %w", shardNum, shard.Lower, shard.Upper, err)
+				cancel() // 一个分片失败，其余分片停止
+			}
+
+			reader, err := newReader()
+			if err != nil {
+				fail(fmt.Errorf("创建Reader失败: %w", err))
+				return
+			}
+			defer reader.Close()
+
+			if err := reader.SetPKRange(shard.PKColumn, shard.Lower, shard.Upper); err != nil {
+				fail(err)
+				return
+			}
+
+			writer, err := newWriter()
+			if err != nil {
+				fail(fmt.Errorf("创建Writer失败: %w", err))
+				return
+			}
+			defer writer.Close()
+
+			pipeline := NewSyncPipeline(reader, writer, transformers, shardHooks).
+				WithStreaming(DefaultStreamChunkSize).
+				WithReadAhead(readAhead)
+			if tuner := newTuner(); tuner != nil {
+				pipeline.WithBatchTuner(tuner)
+			}
+
+			result, err := pipeline.Run(shardCtx)
+			if result != nil {
+				mu.Lock()
+				total.Read += result.Read
+				total.Processed += result.Processed
+				total.Intended += result.Intended
+				total.Batches += result.Batches
+				total.FailedBatches += result.FailedBatches
+				mu.Unlock()
+			}
+			if err != nil {
+				fail(err)
+			}
+		}(i+1, shard)
+	}
+
+	wg.Wait()
+	close(errCh)
+
+	// 优先返回真实错误，其余分片因取消产生的暂停错误只作兜底
+	var firstErr error
+	for err := range errCh {
+		if firstErr == nil || errors.Is(firstErr, ErrPipelinePaused) {
+			firstErr = err
+		}
+	}
+	return total, firstErr
+}
@@ -0,0 +1,104 @@
+package services
+
+import (
+	"fmt"
+	"regexp"
+	"strings"
+)
+
+// 建表语句中无法获取源表字符集时使用的默认值（与之前的固定值一致）
+const (
+	defaultTableCharset   = "utf8mb4"
+	defaultTableCollation = "utf8mb4_unicode_ci"
+)
+
+var (
+	charsetNameRegex  = regexp.MustCompile(`^[A-Za-z0-9_]+$`)
+	tableCharsetRegex = regexp.MustCompile(`(?i)\s*(DEFAULT\s+)?(CHARSET|CHARACTER\s+SET)\s*=\s*(\w+)`)
+	tableCollateRegex = regexp.MustCompile(`(?i)\s*(DEFAULT\s+)?COLLATE\s*=\s*(\w+)`)
+)
+
+// ValidateTableCharset 校验目标字符集/排序规则覆盖配置，两者都为空表示沿用源端
+func ValidateTableCharset(charset, collation string) error {
+	if charset == "" && collation == "" {
+		return nil
+	}
+	if charset == "" {
+		return fmt.Errorf("设置 target_collation 时必须同时设置 target_charset")
+	}
+	if !charsetNameRegex.MatchString(charset) {
+		return fmt.Errorf("target_charset 格式无效: %s", charset)
+	}
+	if collation != "" {
+		if !charsetNameRegex.MatchString(collation) {
+			return fmt.Errorf("target_collation 格式无效: %s", collation)
+		}
+		if !strings.HasPrefix(strings.ToLower(collation), strings.ToLower(charset)+"_") {
+			return fmt.Errorf("排序规则 %s 不属于字符集 %s", collation, charset)
+		}
+	}
+	return nil
+}
+
+// databaseCharset 创建目标库使用的字符集/排序规则：配置了 target_charset 时覆盖，否则使用给定的值
+func databaseCharset(syncConfig SyncConfigParams, charset, collation string) (string, string) {
+	if syncConfig.TargetCharset != "" {
+		return syncConfig.TargetCharset, syncConfig.TargetCollation
+	}
+	return charset, collation
+}
+
+// tableOptionsRange 建表语句中表选项所在行的范围（列定义结束的 ") ENGINE=... DEFAULT CHARSET=..." 行）
+// SHOW CREATE TABLE 中列定义都带缩进，第一个以 ")" 开头的行就是表选项行；找不到时返回 -1
+func tableOptionsRange(createSQL string) (int, int) {
+	start := strings.Index(createSQL, "\n)")
+	if start == -1 {
+		return -1, -1
+	}
+	start++
+	end := strings.Index(createSQL[start:], "\n")
+	if end == -1 {
+		return start, len(createSQL)
+	}
+	return start, start + end
+}
+
+// parseTableCharset 从建表语句的表选项中提取默认字符集和排序规则，没有时返回空字符串
+// MySQL 5.7 在排序规则为字符集默认值时只输出 DEFAULT CHARSET
+func parseTableCharset(createSQL string) (string, string) {
+	start, end := tableOptionsRange(createSQL)
+	if start == -1 {
+		return "", ""
+	}
+	options := createSQL[start:end]
+
+	var charset, collation string
+	if m := tableCharsetRegex.FindStringSubmatch(options); m != nil {
+		charset = m[3]
+	}
+	if m := tableCollateRegex.FindStringSubmatch(options); m != nil {
+		collation = m[2]
+	}
+	return charset, collation
+}
+
+// applyTableCharset 用配置的字符集/排序规则替换建表语句的表级默认值
+// 列上显式声明的字符集/排序规则保持不变，沿用表默认值的列随之改变；charset 为空时原样返回
+func applyTableCharset(createSQL, charset, collation string) string {
+	if charset == "" {
+		return createSQL
+	}
+	start, end := tableOptionsRange(createSQL)
+	if start == -1 {
+		return createSQL
+	}
+
+	options := createSQL[start:end]
+	options = tableCharsetRegex.ReplaceAllString(options, "")
+	options = tableCollateRegex.ReplaceAllString(options, "")
+	options += " DEFAULT CHARSET=" + charset
+	if collation != "" {
+		options += " COLLATE=" + collation
+	}
+	return createSQL[:start] + options + createSQL[end:]
+}
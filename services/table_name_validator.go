@@ -35,3 +35,33 @@ func ValidateDatabaseName(name string) error {
 	}
 	return nil
 }
+
+// ValidateTableMappings 校验表名映射：未配置目标表名时默认与源表同名（会回填 TargetTable），
+// 目标表名必须合法，且同一任务内不允许多张源表写入同一张目标表
+func ValidateTableMappings(selections []DatabaseSelection) error {
+	targets := make(map[string]string) // 目标库.目标表 -> 源库.源表
+	for i := range selections {
+		sel := &selections[i]
+		sourceDB := sel.SourceDatabase
+		if sourceDB == "" {
+			sourceDB = sel.Database
+		}
+		for j := range sel.Tables {
+			tbl := &sel.Tables[j]
+			if tbl.TargetTable == "" {
+				tbl.TargetTable = tbl.SourceTable
+			}
+			if err := ValidateTableName(tbl.TargetTable); err != nil {
+				return fmt.Errorf("源表 %s.%s 的目标表名无效: %w", sourceDB, tbl.SourceTable, err)
+			}
+
+			source := sourceDB + "." + tbl.SourceTable
+			target := sel.Database + "." + tbl.TargetTable
+			if existing, ok := targets[target]; ok {
+				return fmt.Errorf("源表 %s 和 %s 映射到了同一张目标表 %s", existing, source, target)
+			}
+			targets[target] = source
+		}
+	}
+	return nil
+}
@@ -49,6 +49,7 @@ func (s *TableStructureAlterService) CompareAndAlter(
 	sourceTable string,
 	targetTable string,
 	selectedFields []string,
+	nullPolicies map[string]string,
 ) (*AlterResult, error) {
 	result := &AlterResult{
 		TableName: targetTable,
@@ -59,6 +60,8 @@ func (s *TableStructureAlterService) CompareAndAlter(
 	if err != nil {
 		return nil, fmt.Errorf("获取源表结构失败: %w", err)
 	}
+	// 配置为 nullable 的字段按允许 NULL 对比，已存在的 NOT NULL 目标字段会被 MODIFY 为允许 NULL
+	sourceCreateSQL = applyNullablePolicies(sourceCreateSQL, nullPolicies)
 
 	sourceStructure, err := s.parser.Parse(sourceCreateSQL)
 	if err != nil {
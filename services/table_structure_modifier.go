@@ -81,11 +81,13 @@ func (m *TableStructureModifier) FilterFieldsAndRebuild(createSQL string, select
 	}
 
 	// 重建CREATE TABLE语句
-	return m.buildCreateSQL(newTableName, filteredFields, structure.PrimaryKeys, filteredIndexes, filteredForeignKeys), nil
+	return m.buildCreateSQL(newTableName, filteredFields, structure.PrimaryKeys, filteredIndexes, filteredForeignKeys,
+		structure.Charset, structure.Collation), nil
 }
 
-// buildCreateSQL 构建CREATE TABLE语句
-func (m *TableStructureModifier) buildCreateSQL(tableName string, fields []FieldDefinition, primaryKeys []string, indexes []IndexDefinition, foreignKeys []ForeignKeyDefinition) string {
+// buildCreateSQL 构建CREATE TABLE语句，表默认字符集/排序规则沿用源表（源表没有时使用 utf8mb4/utf8mb4_unicode_ci）
+func (m *TableStructureModifier) buildCreateSQL(tableName string, fields []FieldDefinition, primaryKeys []string, indexes []IndexDefinition, foreignKeys []ForeignKeyDefinition,
+	charset, collation string) string {
 	var parts []string
 
 	// 添加字段定义
@@ -123,10 +125,20 @@ func (m *TableStructureModifier) buildCreateSQL(tableName string, fields []Field
 		parts = append(parts, "  "+fk.Definition)
 	}
 
+	// 表默认字符集：排序规则是字符集默认值时源表可能只有 CHARSET
+	if charset == "" {
+		charset, collation = defaultTableCharset, defaultTableCollation
+	}
+	tableOptions := "DEFAULT CHARSET=" + charset
+	if collation != "" {
+		tableOptions += " COLLATE=" + collation
+	}
+
 	// 组装完整的CREATE TABLE语句
-	sql := fmt.Sprintf("CREATE TABLE `%s` (\n%s\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci",
+	sql := fmt.Sprintf("CREATE TABLE `%s` (\n%s\n) ENGINE=InnoDB %s",
 		tableName,
-		strings.Join(parts, ",\n"))
+		strings.Join(parts, ",\n"),
+		tableOptions)
 
 	return sql
 }
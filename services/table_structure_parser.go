@@ -13,6 +13,8 @@ type TableStructure struct {
 	PrimaryKeys []string
 	Indexes     []IndexDefinition
 	ForeignKeys []ForeignKeyDefinition
+	Charset     string // 表默认字符集（建表语句中没有时为空）
+	Collation   string // 表默认排序规则（建表语句中没有时为空）
 	CreateSQL   string
 }
 
@@ -58,6 +60,9 @@ func (p *TableStructureParser) Parse(createSQL string) (*TableStructure, error)
 		structure.TableName = matches[1]
 	}
 
+	// 提取表默认字符集和排序规则
+	structure.Charset, structure.Collation = parseTableCharset(createSQL)
+
 	// 提取字段和约束定义（括号内的内容）
 	startIdx := strings.Index(createSQL, "(")
 	endIdx := strings.LastIndex(createSQL, ")")
@@ -0,0 +1,69 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"fmt"
+	"strings"
+	"time"
+)
+
+// MaxTargetSQLHooks pre_sql/post_sql 各自最多的语句数
+const MaxTargetSQLHooks = 20
+
+// targetSQLHookTimeout 单条钩子语句的超时时间（ANALYZE TABLE 等大表操作可能较慢）
+const targetSQLHookTimeout = 30 * time.Minute
+
+// ValidateTargetSQLHooks 校验目标端前置/后置 SQL，不填表示不执行
+// 只做基本检查，不解析语句：能修改任务配置的用户即可用目标数据源账号的权限执行任意 SQL
+func ValidateTargetSQLHooks(preSQL, postSQL []string) error {
+	for name, statements := range map[string][]string{"pre_sql": preSQL, "post_sql": postSQL} {
+		if len(statements) > MaxTargetSQLHooks {
+			return fmt.Errorf("%s 最多 %d 条语句", name, MaxTargetSQLHooks)
+		}
+		for i, stmt := range statements {
+			if strings.TrimSpace(stmt) == "" {
+				return fmt.Errorf("%s 第 %d 条语句为空", name, i+1)
+			}
+		}
+	}
+	return nil
+}
+
+// expandTargetSQLHook 替换语句中的 {database}/{table} 为目标库名、表名（带反引号）
+func expandTargetSQLHook(stmt, targetDB, targetTable string) string {
+	quote := func(name string) string {
+		return "`" + strings.ReplaceAll(name, "`", "``") + "`"
+	}
+	return strings.NewReplacer("{database}", quote(targetDB), "{table}", quote(targetTable)).Replace(stmt)
+}
+
+// runTargetSQLHooks 在目标库的同一个连接上依次执行钩子语句，结果写入任务日志
+// 遇到错误立即返回，后续语句不再执行；stage 为 pre_sql/post_sql
+func (e *SyncEngine) runTargetSQLHooks(ctx context.Context, taskID, targetName, unitName, stage string, db *sql.DB,
+	statements []string, targetDB, targetTable string) error {
+	if len(statements) == 0 {
+		return nil
+	}
+
+	conn, err := db.Conn(ctx)
+	if err != nil {
+		return fmt.Errorf("%s 获取目标连接失败: %w", stage, err)
+	}
+	defer conn.Close()
+
+	for i, stmt := range statements {
+		stmt = expandTargetSQLHook(stmt, targetDB, targetTable)
+		stmtCtx, cancel := context.WithTimeout(ctx, targetSQLHookTimeout)
+		start := time.Now()
+		res, err := conn.ExecContext(stmtCtx, stmt)
+		cancel()
+		if err != nil {
+			return fmt.Errorf("%s 第 %d 条语句执行失败: %s: %w", stage, i+1, stmt, err)
+		}
+		affected, _ := res.RowsAffected()
+		e.logService.Info(taskID, fmt.Sprintf("目标 %s 表 %s 执行 %s 第 %d 条语句: %s（影响 %d 行，耗时 %v）",
+			targetName, unitName, stage, i+1, stmt, affected, time.Since(start).Round(time.Millisecond)))
+	}
+	return nil
+}
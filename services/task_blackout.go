@@ -0,0 +1,221 @@
+package services
+
+import (
+	"fmt"
+	"log"
+	"strings"
+	"sync"
+	"time"
+)
+
+// BlackoutConfig 禁止运行时段：任务运行中进入时段时自动暂停，离开时段后自动继续
+type BlackoutConfig struct {
+	Timezone string           `json:"timezone,omitempty"` // IANA 时区（如 Asia/Shanghai），不填使用服务器本地时区
+	Windows  []BlackoutWindow `json:"windows"`
+}
+
+// BlackoutWindow 一个禁止运行时段，结束时间早于开始时间表示跨零点（如 22:00-06:00）
+type BlackoutWindow struct {
+	Days  []string `json:"days,omitempty"` // mon/tue/wed/thu/fri/sat/sun，不填表示每天；跨零点的时段按开始那天匹配
+	Start string   `json:"start"`          // HH:MM
+	End   string   `json:"end"`            // HH:MM
+}
+
+// blackoutWeekdays 星期缩写
+var blackoutWeekdays = map[string]time.Weekday{
+	"sun": time.Sunday, "mon": time.Monday, "tue": time.Tuesday, "wed": time.Wednesday,
+	"thu": time.Thursday, "fri": time.Friday, "sat": time.Saturday,
+}
+
+var (
+	// blackoutPaused 因进入禁止运行时段被暂停的任务（离开时段后自动继续）
+	blackoutPaused sync.Map
+	// blackoutSchedulerOnce 时段检查只启动一次
+	blackoutSchedulerOnce sync.Once
+)
+
+// ValidateBlackout 校验禁止运行时段配置，nil 表示不限制
+// 增量同步不支持暂停，不能设置
+func ValidateBlackout(cfg *BlackoutConfig, syncMode string) error {
+	if cfg == nil {
+		return nil
+	}
+	if syncMode == "incremental" {
+		return fmt.Errorf("增量同步不支持暂停，不能设置禁止运行时段")
+	}
+	if cfg.Timezone != "" {
+		if _, err := time.LoadLocation(cfg.Timezone); err != nil {
+			return fmt.Errorf("禁止运行时段时区无效: %s", cfg.Timezone)
+		}
+	}
+	if len(cfg.Windows) == 0 {
+		return fmt.Errorf("禁止运行时段至少需要一个时段")
+	}
+	for i, w := range cfg.Windows {
+		start, err := parseClock(w.Start)
+		if err != nil {
+			return fmt.Errorf("禁止运行时段 %d 开始时间无效: %s（格式 HH:MM）", i+1, w.Start)
+		}
+		end, err := parseClock(w.End)
+		if err != nil {
+			return fmt.Errorf("禁止运行时段 %d 结束时间无效: %s（格式 HH:MM）", i+1, w.End)
+		}
+		if start == end {
+			return fmt.Errorf("禁止运行时段 %d 开始和结束时间相同", i+1)
+		}
+		for _, day := range w.Days {
+			if _, ok := blackoutWeekdays[strings.ToLower(day)]; !ok {
+				return fmt.Errorf("禁止运行时段 %d 星期无效: %s（支持 mon/tue/wed/thu/fri/sat/sun）", i+1, day)
+			}
+		}
+	}
+	return nil
+}
+
+// parseClock 解析 HH:MM，返回当天的分钟数
+func parseClock(s string) (int, error) {
+	t, err := time.Parse("15:04", s)
+	if err != nil {
+		return 0, err
+	}
+	return t.Hour()*60 + t.Minute(), nil
+}
+
+// matchesDay 时段是否适用于指定星期
+func (w BlackoutWindow) matchesDay(day time.Weekday) bool {
+	if len(w.Days) == 0 {
+		return true
+	}
+	for _, d := range w.Days {
+		if blackoutWeekdays[strings.ToLower(d)] == day {
+			return true
+		}
+	}
+	return false
+}
+
+// contains 指定时间（已转换到配置时区）是否在时段内
+func (w BlackoutWindow) contains(t time.Time) bool {
+	start, err := parseClock(w.Start)
+	if err != nil {
+		return false
+	}
+	end, err := parseClock(w.End)
+	if err != nil {
+		return false
+	}
+	minute := t.Hour()*60 + t.Minute()
+	if start < end {
+		return w.matchesDay(t.Weekday()) && minute >= start && minute < end
+	}
+	// 跨零点：开始当天的 [start, 24:00) 和次日的 [00:00, end)
+	yesterday := (t.Weekday() + 6) % 7
+	return (w.matchesDay(t.Weekday()) && minute >= start) || (w.matchesDay(yesterday) && minute < end)
+}
+
+// String 时段描述，如 mon,tue 09:00-18:00
+func (w BlackoutWindow) String() string {
+	if len(w.Days) == 0 {
+		return fmt.Sprintf("%s-%s", w.Start, w.End)
+	}
+	return fmt.Sprintf("%s %s-%s", strings.Join(w.Days, ","), w.Start, w.End)
+}
+
+// activeWindow 返回当前所在的禁止运行时段
+func (cfg *BlackoutConfig) activeWindow(now time.Time) (BlackoutWindow, bool) {
+	if cfg == nil {
+		return BlackoutWindow{}, false
+	}
+	loc := time.Local
+	if cfg.Timezone != "" {
+		if l, err := time.LoadLocation(cfg.Timezone); err == nil {
+			loc = l
+		}
+	}
+	local := now.In(loc)
+	for _, w := range cfg.Windows {
+		if w.contains(local) {
+			return w, true
+		}
+	}
+	return BlackoutWindow{}, false
+}
+
+// activeBlackoutWindow 任务当前所在的禁止运行时段（未配置或读取配置失败时视为不在时段内）
+func activeBlackoutWindow(taskID string, now time.Time) (BlackoutWindow, bool) {
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(taskID)
+	if err != nil {
+		return BlackoutWindow{}, false
+	}
+	return config.SyncConfig.Blackout.activeWindow(now)
+}
+
+// StartBlackoutScheduler 启动禁止运行时段检查：每分钟检查一次，
+// 运行中的任务进入时段时暂停，因时段暂停的任务离开时段后重新启动（从暂停处继续）
+func StartBlackoutScheduler() {
+	blackoutSchedulerOnce.Do(func() {
+		go func() {
+			ticker := time.NewTicker(time.Minute)
+			defer ticker.Stop()
+			for range ticker.C {
+				if err := NewTaskControlService().applyBlackoutWindows(time.Now()); err != nil {
+					log.Printf("⚠️  检查禁止运行时段失败: %v", err)
+				}
+			}
+		}()
+	})
+}
+
+// applyBlackoutWindows 按禁止运行时段暂停或继续任务，切换时写入任务日志
+// 只继续因时段暂停的任务；期间被手动停止、重启的任务不再自动继续
+func (s *TaskControlService) applyBlackoutWindows(now time.Time) error {
+	status, err := s.GetEngineStatus()
+	if err != nil {
+		return err
+	}
+	logService := NewTaskLogService()
+	paused := make(map[string]bool, len(status.Paused))
+
+	for _, item := range status.Running {
+		if !item.Pausable {
+			continue
+		}
+		window, ok := activeBlackoutWindow(item.TaskID, now)
+		if !ok {
+			continue
+		}
+		logService.Warning(item.TaskID, fmt.Sprintf("进入禁止运行时段 %s，暂停任务，时段结束后自动继续", window))
+		if err := s.PauseTask(item.TaskID); err != nil {
+			logService.Error(item.TaskID, fmt.Sprintf("禁止运行时段暂停任务失败: %v", err))
+			continue
+		}
+		blackoutPaused.Store(item.TaskID, true)
+		paused[item.TaskID] = true
+	}
+
+	for _, item := range status.Paused {
+		paused[item.TaskID] = true
+		if _, ok := blackoutPaused.Load(item.TaskID); !ok {
+			continue
+		}
+		if _, ok := activeBlackoutWindow(item.TaskID, now); ok {
+			continue
+		}
+		logService.Info(item.TaskID, "禁止运行时段结束，继续同步")
+		if err := s.StartTask(item.TaskID); err != nil {
+			// 保留标记，下次检查时重试（如并发数已满）
+			logService.Error(item.TaskID, fmt.Sprintf("禁止运行时段结束后继续任务失败: %v", err))
+			continue
+		}
+		blackoutPaused.Delete(item.TaskID)
+	}
+
+	// 不再处于暂停状态的任务（已被手动停止、重启或删除）清除标记
+	blackoutPaused.Range(func(key, _ interface{}) bool {
+		if !paused[key.(string)] {
+			blackoutPaused.Delete(key)
+		}
+		return true
+	})
+	return nil
+}
@@ -0,0 +1,69 @@
+package services
+
+import (
+	"sync"
+)
+
+// BulkControlResult 批量控制中单个任务的结果
+type BulkControlResult struct {
+	TaskID  string `json:"task_id"`
+	Name    string `json:"name"`
+	Result  string `json:"result"` // paused/resumed/skipped/failed
+	Message string `json:"message,omitempty"`
+}
+
+// PauseAllTasks 暂停所有运行中的任务，各任务在当前批次结束后停下
+// 增量同步不支持暂停，记为 skipped；执行期间恰好结束的任务也记为 skipped
+func (s *TaskControlService) PauseAllTasks() ([]BulkControlResult, error) {
+	status, err := s.GetEngineStatus()
+	if err != nil {
+		return nil, err
+	}
+
+	results := make([]BulkControlResult, len(status.Running))
+	var wg sync.WaitGroup
+	for i, item := range status.Running {
+		results[i] = BulkControlResult{TaskID: item.TaskID, Name: item.Name}
+		if !item.Pausable {
+			results[i].Result = "skipped"
+			results[i].Message = "增量同步不支持暂停"
+			continue
+		}
+
+		// 并发暂停，避免逐个等待 Worker 退出
+		wg.Add(1)
+		go func(result *BulkControlResult) {
+			defer wg.Done()
+			if err := s.PauseTask(result.TaskID); err != nil {
+				result.Result = "skipped"
+				result.Message = err.Error()
+				return
+			}
+			result.Result = "paused"
+		}(&results[i])
+	}
+	wg.Wait()
+
+	return results, nil
+}
+
+// ResumeAllTasks 重新启动所有暂停中的任务
+// 受全局并发上限限制，超出上限或启动失败的任务记为 failed
+func (s *TaskControlService) ResumeAllTasks() ([]BulkControlResult, error) {
+	status, err := s.GetEngineStatus()
+	if err != nil {
+		return nil, err
+	}
+
+	results := make([]BulkControlResult, 0, len(status.Paused))
+	for _, item := range status.Paused {
+		result := BulkControlResult{TaskID: item.TaskID, Name: item.Name, Result: "resumed"}
+		if err := s.StartTask(item.TaskID); err != nil {
+			result.Result = "failed"
+			result.Message = err.Error()
+		}
+		results = append(results, result)
+	}
+
+	return results, nil
+}
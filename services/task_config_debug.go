@@ -0,0 +1,178 @@
+package services
+
+import (
+	"bytes"
+	"datatrace/database"
+	"datatrace/models"
+	"encoding/json"
+	"fmt"
+	"sort"
+)
+
+// TaskConfigDebug 任务配置诊断信息（只读）：对照存储的原始配置、Redis 缓存和运行时状态，排查配置漂移
+type TaskConfigDebug struct {
+	TaskID        string                   `json:"task_id"`
+	RawConfig     string                   `json:"raw_config"`             // MySQL 中存储的原始配置 JSON
+	ConfigError   string                   `json:"config_error,omitempty"` // 原始配置解析失败的原因
+	CacheEnabled  bool                     `json:"cache_enabled"`          // 是否启用了 Redis 配置缓存
+	CachedConfig  *TaskConfig              `json:"cached_config"`          // Redis 中缓存的配置（未启用或不存在时为 null）
+	CacheInSync   bool                     `json:"cache_in_sync"`          // 缓存与存储的配置是否一致（未启用缓存时为 true）
+	ConfigUnits   []string                 `json:"config_units"`           // 按存储的配置生成的单元
+	CurrentStep   string                   `json:"current_step"`           // 内存进度中的当前步骤（不在内存中时为空）
+	RuntimeUnits  []*TaskUnit              `json:"runtime_units"`          // 内存进度中的单元
+	LatestHistory []models.TaskUnitHistory `json:"latest_history"`         // 每个单元最近一次执行历史
+	Diff          TaskConfigDiff           `json:"diff"`
+}
+
+// TaskConfigDiff 配置单元与运行时状态的差异
+type TaskConfigDiff struct {
+	NewUnits       []string `json:"new_units"`       // 配置中有，但没有进度也没有执行历史
+	CompletedUnits []string `json:"completed_units"` // 配置中有，进度或最近一次历史为已完成
+	OrphanedUnits  []string `json:"orphaned_units"`  // 进度或执行历史中有，但配置中已没有
+}
+
+// configUnitNames 按配置生成单元名（目标库.目标表），与启动任务时的生成规则一致
+func configUnitNames(config *TaskConfig) []string {
+	var unitNames []string
+	for _, db := range config.SelectedDatabases {
+		for _, tableConfig := range db.Tables {
+			targetTable := tableConfig.TargetTable
+			if targetTable == "" {
+				targetTable = tableConfig.SourceTable
+			}
+			unitNames = append(unitNames, fmt.Sprintf("%s.%s", db.Database, targetTable))
+		}
+	}
+	return unitNames
+}
+
+// GetConfigDebug 汇总任务配置诊断信息，只读取不修改任何状态
+func (s *TaskService) GetConfigDebug(id string) (*TaskConfigDebug, error) {
+	var task models.SyncTask
+	if err := database.DB.First(&task, "id = ?", id).Error; err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+
+	debug := &TaskConfigDebug{
+		TaskID:        id,
+		RawConfig:     task.Config,
+		CacheEnabled:  database.IsRedisEnabled(),
+		CacheInSync:   true,
+		ConfigUnits:   []string{},
+		RuntimeUnits:  []*TaskUnit{},
+		LatestHistory: []models.TaskUnitHistory{},
+	}
+
+	// 存储的配置
+	var config *TaskConfig
+	if task.Config == "" {
+		debug.ConfigError = "任务尚未配置"
+	} else {
+		var parsed TaskConfig
+		if err := json.Unmarshal([]byte(task.Config), &parsed); err != nil {
+			debug.ConfigError = fmt.Sprintf("解析配置失败: %v", err)
+		} else {
+			config = &parsed
+			debug.ConfigUnits = configUnitNames(config)
+		}
+	}
+
+	// Redis 缓存的配置：按缓存写入时的方式序列化后比较
+	if debug.CacheEnabled {
+		if cached, err := NewConfigCacheService().GetTaskConfigFromRedis(id); err == nil {
+			debug.CachedConfig = cached
+		}
+		debug.CacheInSync = sameTaskConfig(config, debug.CachedConfig)
+	}
+
+	// 内存进度
+	progressManager := GetProgressManager()
+	debug.CurrentStep, _, _ = progressManager.GetActiveUnits(id)
+	for _, unit := range progressManager.GetUnits(id) {
+		unitCopy := *unit // 运行中的单元会被并发更新，返回快照
+		debug.RuntimeUnits = append(debug.RuntimeUnits, &unitCopy)
+	}
+	sort.Slice(debug.RuntimeUnits, func(i, j int) bool {
+		return debug.RuntimeUnits[i].UnitName < debug.RuntimeUnits[j].UnitName
+	})
+
+	// 每个单元最近一次执行历史
+	var records []models.TaskUnitHistory
+	if err := database.DB.Where("task_id = ?", id).Order("id DESC").Find(&records).Error; err != nil {
+		return nil, fmt.Errorf("查询执行历史失败: %w", err)
+	}
+	latest := make(map[string]models.TaskUnitHistory)
+	for _, record := range records {
+		if _, ok := latest[record.UnitName]; !ok {
+			latest[record.UnitName] = record
+			debug.LatestHistory = append(debug.LatestHistory, record)
+		}
+	}
+	sort.Slice(debug.LatestHistory, func(i, j int) bool {
+		return debug.LatestHistory[i].UnitName < debug.LatestHistory[j].UnitName
+	})
+
+	debug.Diff = diffConfigUnits(debug.ConfigUnits, debug.RuntimeUnits, latest)
+	return debug, nil
+}
+
+// sameTaskConfig 存储的配置与缓存的配置是否一致，任一方不存在时只有双方都不存在才算一致
+func sameTaskConfig(stored, cached *TaskConfig) bool {
+	if stored == nil || cached == nil {
+		return stored == nil && cached == nil
+	}
+	storedJSON, err1 := json.Marshal(stored)
+	cachedJSON, err2 := json.Marshal(cached)
+	return err1 == nil && err2 == nil && bytes.Equal(storedJSON, cachedJSON)
+}
+
+// diffConfigUnits 对照配置单元、内存进度和执行历史，内存进度优先于执行历史
+func diffConfigUnits(configUnits []string, runtimeUnits []*TaskUnit, latest map[string]models.TaskUnitHistory) TaskConfigDiff {
+	diff := TaskConfigDiff{
+		NewUnits:       []string{},
+		CompletedUnits: []string{},
+		OrphanedUnits:  []string{},
+	}
+
+	runtime := make(map[string]*TaskUnit, len(runtimeUnits))
+	for _, unit := range runtimeUnits {
+		runtime[unit.UnitName] = unit
+	}
+
+	inConfig := make(map[string]bool, len(configUnits))
+	for _, unitName := range configUnits {
+		inConfig[unitName] = true
+		unit, hasRuntime := runtime[unitName]
+		record, hasHistory := latest[unitName]
+		switch {
+		case hasRuntime:
+			if unit.Status == "completed" {
+				diff.CompletedUnits = append(diff.CompletedUnits, unitName)
+			}
+		case hasHistory:
+			if record.Status == "completed" {
+				diff.CompletedUnits = append(diff.CompletedUnits, unitName)
+			}
+		default:
+			diff.NewUnits = append(diff.NewUnits, unitName)
+		}
+	}
+
+	orphaned := make(map[string]bool)
+	for unitName := range runtime {
+		if !inConfig[unitName] {
+			orphaned[unitName] = true
+		}
+	}
+	for unitName := range latest {
+		if !inConfig[unitName] {
+			orphaned[unitName] = true
+		}
+	}
+	for unitName := range orphaned {
+		diff.OrphanedUnits = append(diff.OrphanedUnits, unitName)
+	}
+	sort.Strings(diff.OrphanedUnits)
+
+	return diff
+}
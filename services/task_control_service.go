@@ -41,10 +41,26 @@ func (s *TaskControlService) StartTask(taskID string) error {
 		return fmt.Errorf("任务未配置，无法启动")
 	}
 
+	if compactRunning.Load() {
+		return fmt.Errorf("元数据库正在压缩，请稍后再启动")
+	}
+	if window, ok := activeBlackoutWindow(taskID, time.Now()); ok {
+		return fmt.Errorf("当前处于禁止运行时段 %s，请在时段结束后启动", window)
+	}
+
+	// 检查全局并发上限
+	execManager := GetExecutionManager()
+	running := len(execManager.ListExecutions()) + len(execManager.ListIncrementalSyncs())
+	if limit := NewSettingsService().MaxConcurrentTasks(); running >= limit {
+		return fmt.Errorf("同时运行的任务数已达上限（%d），请等待其他任务结束或调整全局设置", limit)
+	}
+
 	// 3. 根据同步模式选择执行路径
 	switch task.SyncMode {
 	case "incremental":
 		return s.startIncrementalTask(taskID)
+	case "bidirectional":
+		return s.startBidirectionalTask(taskID)
 	case "structure":
 		// 结构同步：对比两边表结构，使用 ALTER 增删改（功能开发中）
 		return fmt.Errorf("结构同步功能开发中，敬请期待")
@@ -0,0 +1,157 @@
+package services
+
+import (
+	"context"
+	"database/sql"
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"time"
+)
+
+// UnitEstimate 单元预估
+type UnitEstimate struct {
+	UnitName    string `json:"unit_name"`    // 目标库.目标表
+	SourceTable string `json:"source_table"` // 源库.源表
+	Rows        int64  `json:"rows"`
+	Error       string `json:"error,omitempty"`
+}
+
+// TaskEstimate 任务工作量预估
+type TaskEstimate struct {
+	TaskID            string         `json:"task_id"`
+	TableCount        int            `json:"table_count"`
+	TotalRows         int64          `json:"total_rows"`
+	RowsPerSecond     float64        `json:"rows_per_second,omitempty"`    // 最近一次执行的平均速率，无历史时为 0
+	EstimatedSeconds  int64          `json:"estimated_seconds,omitempty"`  // 预计耗时（秒），无历史时为 0
+	EstimatedDuration string         `json:"estimated_duration,omitempty"` // 可读预计耗时，例如 6m0s
+	HasHistory        bool           `json:"has_history"`                  // 是否有历史速率可用于预估耗时
+	Summary           string         `json:"summary"`                      // 例如 "12 张表，4100000 行，按最近速率约 6m0s"
+	Units             []UnitEstimate `json:"units"`
+}
+
+// TaskEstimateService 任务预估服务
+type TaskEstimateService struct{}
+
+// NewTaskEstimateService 创建任务预估服务
+func NewTaskEstimateService() *TaskEstimateService {
+	return &TaskEstimateService{}
+}
+
+// EstimateTask 预估任务的表数量、总行数和耗时（不启动同步）
+// 行数为源表 COUNT(*) 精确值，只用于展示，启动任务时 Reader 会重新统计
+// 耗时按最近一次执行的 已完成行数 / 任务耗时 推算，没有历史数据时只返回行数
+func (s *TaskEstimateService) EstimateTask(taskID string) (*TaskEstimate, error) {
+	var task models.SyncTask
+	if err := database.DB.Preload("SourceConn").First(&task, "id = ?", taskID).Error; err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(taskID)
+	if err != nil {
+		return nil, fmt.Errorf("获取任务配置失败: %w", err)
+	}
+
+	sourcePassword, err := NewDataSourceService().ResolveConnection(task.SourceConn)
+	if err != nil {
+		return nil, fmt.Errorf("解密源数据库密码失败: %w", err)
+	}
+
+	estimate := &TaskEstimate{TaskID: taskID}
+	for _, dbSel := range config.SelectedDatabases {
+		sourceDB := dbSel.SourceDatabase
+		if sourceDB == "" {
+			sourceDB = dbSel.Database
+		}
+		if len(dbSel.Tables) == 0 {
+			continue
+		}
+
+		units := s.countDatabaseTables(task.SourceConn, sourcePassword, sourceDB, dbSel)
+		for i := range units {
+			// 设置了行数上限时按上限计算实际同步的行数
+			units[i].Rows = capRecords(units[i].Rows, config.SyncConfig.MaxRecordsPerUnit)
+			estimate.TotalRows += units[i].Rows
+		}
+		estimate.Units = append(estimate.Units, units...)
+	}
+
+	if len(estimate.Units) == 0 {
+		return nil, fmt.Errorf("没有待处理的任务单元")
+	}
+	estimate.TableCount = len(estimate.Units)
+
+	if rate := recentThroughput(&task); rate > 0 {
+		estimate.HasHistory = true
+		estimate.RowsPerSecond = rate
+		estimate.EstimatedSeconds = int64(float64(estimate.TotalRows)/rate + 0.5)
+		estimate.EstimatedDuration = formatElapsed(time.Duration(estimate.EstimatedSeconds) * time.Second)
+		estimate.Summary = fmt.Sprintf("%d 张表，%d 行，按最近速率约 %s", estimate.TableCount, estimate.TotalRows, estimate.EstimatedDuration)
+	} else {
+		estimate.Summary = fmt.Sprintf("%d 张表，%d 行（暂无历史速率，无法预估耗时）", estimate.TableCount, estimate.TotalRows)
+	}
+
+	return estimate, nil
+}
+
+// countDatabaseTables 统计一个源库中选中表的行数，单表失败只记录在该表的 Error 中
+func (s *TaskEstimateService) countDatabaseTables(source *models.DataSource, password, sourceDB string, dbSel DatabaseSelection) []UnitEstimate {
+	units := make([]UnitEstimate, 0, len(dbSel.Tables))
+	for _, tbl := range dbSel.Tables {
+		targetTable := tbl.TargetTable
+		if targetTable == "" {
+			targetTable = tbl.SourceTable
+		}
+		units = append(units, UnitEstimate{
+			UnitName:    fmt.Sprintf("%s.%s", dbSel.Database, targetTable),
+			SourceTable: fmt.Sprintf("%s.%s", sourceDB, tbl.SourceTable),
+		})
+	}
+
+	dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
+		source.Username, password, mysqlNetAddr(source.MySQLHost(), source.Port), sourceDB)
+	db, err := sql.Open("mysql", dsn)
+	if err != nil {
+		for i := range units {
+			units[i].Error = fmt.Sprintf("连接源数据库失败: %v", err)
+		}
+		return units
+	}
+	defer db.Close()
+
+	timeout := NewSettingsService().MetadataTimeout()
+	for i, tbl := range dbSel.Tables {
+		ctx, cancel := context.WithTimeout(context.Background(), timeout)
+		var count int64
+		countSQL := fmt.Sprintf("SELECT COUNT(*) FROM `%s`.`%s`", sourceDB, tbl.SourceTable)
+		if tbl.SourceQuery != "" {
+			countSQL = fmt.Sprintf("SELECT COUNT(*) FROM %s", querySourceFrom(tbl.SourceQuery))
+		}
+		err := db.QueryRowContext(ctx, countSQL).Scan(&count)
+		cancel()
+		if err != nil {
+			units[i].Error = fmt.Sprintf("统计行数失败: %v", err)
+			continue
+		}
+		units[i].Rows = count
+	}
+	return units
+}
+
+// recentThroughput 最近一次执行的平均速率（行/秒）：该次执行中完成单元的行数之和 / 任务总耗时
+// 任务耗时包含多线程并发，因此得到的是整体吞吐而不是单表速率
+func recentThroughput(task *models.SyncTask) float64 {
+	if task.StartedAt == nil || task.DurationSec <= 0 {
+		return 0
+	}
+
+	var processed int64
+	database.DB.Model(&models.TaskUnitHistory{}).
+		Where("task_id = ? AND status = ? AND completed_at >= ?", task.ID, "completed", *task.StartedAt).
+		Select("COALESCE(SUM(processed_records), 0)").
+		Scan(&processed)
+	if processed == 0 {
+		return 0
+	}
+	return float64(processed) / float64(task.DurationSec)
+}
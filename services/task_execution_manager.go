@@ -3,6 +3,7 @@ package services
 import (
 	"context"
 	"sync"
+	"time"
 )
 
 // TaskExecution 任务执行信息
@@ -10,12 +11,14 @@ type TaskExecution struct {
 	TaskID    string
 	Cancel    context.CancelFunc
 	WaitGroup *sync.WaitGroup
+	StartedAt time.Time
 }
 
 // TaskExecutionManager 任务执行管理器
 type TaskExecutionManager struct {
 	executions       sync.Map // map[taskID]*TaskExecution
 	incrementalSyncs sync.Map // map[taskID]*IncrementalSync
+	incrementalStart sync.Map // map[taskID]time.Time
 }
 
 var (
@@ -37,6 +40,7 @@ func (m *TaskExecutionManager) StoreExecution(taskID string, cancel context.Canc
 		TaskID:    taskID,
 		Cancel:    cancel,
 		WaitGroup: wg,
+		StartedAt: time.Now(),
 	}
 	m.executions.Store(taskID, execution)
 }
@@ -55,9 +59,20 @@ func (m *TaskExecutionManager) DeleteExecution(taskID string) {
 	m.executions.Delete(taskID)
 }
 
+// ListExecutions 列出所有执行中的任务（sync.Map 遍历，不加全局锁）
+func (m *TaskExecutionManager) ListExecutions() []*TaskExecution {
+	var list []*TaskExecution
+	m.executions.Range(func(_, value interface{}) bool {
+		list = append(list, value.(*TaskExecution))
+		return true
+	})
+	return list
+}
+
 // StoreIncrementalSync 存储增量同步实例
 func (m *TaskExecutionManager) StoreIncrementalSync(taskID string, sync *IncrementalSync) {
 	m.incrementalSyncs.Store(taskID, sync)
+	m.incrementalStart.Store(taskID, time.Now())
 }
 
 // GetIncrementalSync 获取增量同步实例
@@ -72,4 +87,20 @@ func (m *TaskExecutionManager) GetIncrementalSync(taskID string) (*IncrementalSy
 // DeleteIncrementalSync 删除增量同步实例
 func (m *TaskExecutionManager) DeleteIncrementalSync(taskID string) {
 	m.incrementalSyncs.Delete(taskID)
+	m.incrementalStart.Delete(taskID)
+}
+
+// ListIncrementalSyncs 列出所有运行中的增量同步任务及其启动时间
+func (m *TaskExecutionManager) ListIncrementalSyncs() map[string]time.Time {
+	result := make(map[string]time.Time)
+	m.incrementalSyncs.Range(func(key, _ interface{}) bool {
+		taskID := key.(string)
+		var startedAt time.Time
+		if v, ok := m.incrementalStart.Load(taskID); ok {
+			startedAt = v.(time.Time)
+		}
+		result[taskID] = startedAt
+		return true
+	})
+	return result
 }
@@ -0,0 +1,34 @@
+package services
+
+import "fmt"
+
+// ValidateMaxFailedUnitRatio 校验失败单元比例阈值（0~1），0 表示不限制
+func ValidateMaxFailedUnitRatio(ratio float64) error {
+	if ratio < 0 || ratio > 1 {
+		return fmt.Errorf("max_failed_unit_ratio 必须在 0~1 之间")
+	}
+	return nil
+}
+
+// checkFailedUnitRatio 全量同步结束后（自动重试之后）统计失败单元比例，超过阈值时返回任务失败原因，否则返回空
+func checkFailedUnitRatio(taskID string, unitNames []string, maxRatio float64) string {
+	if maxRatio <= 0 || len(unitNames) == 0 {
+		return ""
+	}
+	failed := len(GetProgressManager().GetFailedUnitNames(taskID, unitNames))
+	return failedUnitRatioReason(failed, len(unitNames), maxRatio)
+}
+
+// failedUnitRatioReason 失败单元比例超过阈值时返回任务失败原因，否则返回空
+// 比例恰好等于阈值时不算失败；maxRatio <= 0 表示不限制
+func failedUnitRatioReason(failed, total int, maxRatio float64) string {
+	if maxRatio <= 0 || total == 0 {
+		return ""
+	}
+	ratio := float64(failed) / float64(total)
+	if ratio <= maxRatio {
+		return ""
+	}
+	return fmt.Sprintf("失败单元 %d/%d（%.1f%%）超过 max_failed_unit_ratio=%.1f%%，任务标记为失败",
+		failed, total, ratio*100, maxRatio*100)
+}
@@ -0,0 +1,42 @@
+package services
+
+import "testing"
+
+func TestFailedUnitRatioReason(t *testing.T) {
+	cases := []struct {
+		name     string
+		failed   int
+		total    int
+		maxRatio float64
+		fail     bool
+	}{
+		{"below threshold", 599, 1000, 0.6, false},
+		{"equal to threshold", 600, 1000, 0.6, false},
+		{"above threshold", 601, 1000, 0.6, true},
+		{"small task equal", 3, 10, 0.3, false},
+		{"small task above", 4, 10, 0.3, true},
+		{"all failed with full ratio", 10, 10, 1, false},
+		{"no limit", 10, 10, 0, false},
+		{"no units", 0, 0, 0.5, false},
+		{"no failures", 0, 10, 0.1, false},
+	}
+	for _, c := range cases {
+		reason := failedUnitRatioReason(c.failed, c.total, c.maxRatio)
+		if (reason != "") != c.fail {
+			t.Errorf("%s: failedUnitRatioReason(%d, %d, %v) = %q, want failure %v", c.name, c.failed, c.total, c.maxRatio, reason, c.fail)
+		}
+	}
+}
+
+func TestValidateMaxFailedUnitRatio(t *testing.T) {
+	for _, ratio := range []float64{0, 0.5, 1} {
+		if err := ValidateMaxFailedUnitRatio(ratio); err != nil {
+			t.Errorf("ValidateMaxFailedUnitRatio(%v) error = %v", ratio, err)
+		}
+	}
+	for _, ratio := range []float64{-0.1, 1.01} {
+		if err := ValidateMaxFailedUnitRatio(ratio); err == nil {
+			t.Errorf("ValidateMaxFailedUnitRatio(%v) accepted", ratio)
+		}
+	}
+}
@@ -4,7 +4,6 @@ import (
 	"database/sql"
 	"datatrace/database"
 	"datatrace/models"
-	"datatrace/utils"
 	"fmt"
 )
 
@@ -36,8 +35,7 @@ func (s *TaskForeignKeySorter) SortUnitsByForeignKeys(task *models.SyncTask, uni
 	}
 
 	// 解密源数据库密码
-	crypto := utils.NewCryptoService()
-	sourcePassword, err := crypto.Decrypt(task.SourceConn.Password)
+	sourcePassword, err := NewDataSourceService().ResolveConnection(task.SourceConn)
 	if err != nil {
 		return nil, false, fkTableSet, fmt.Errorf("解密源数据库密码失败: %w", err)
 	}
@@ -77,8 +75,8 @@ func (s *TaskForeignKeySorter) SortUnitsByForeignKeys(task *models.SyncTask, uni
 		}
 
 		// 连接到源数据库
-		dsn := fmt.Sprintf("%s:%s@tcp(%s:%d)/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
-			task.SourceConn.Username, sourcePassword, task.SourceConn.Host, task.SourceConn.Port, sourceDBName)
+		dsn := fmt.Sprintf("%s:%s@%s/%s?charset=utf8mb4&parseTime=True&loc=Local&timeout=10s",
+			task.SourceConn.Username, sourcePassword, mysqlNetAddr(task.SourceConn.MySQLHost(), task.SourceConn.Port), sourceDBName)
 
 		db, err := sql.Open("mysql", dsn)
 		if err != nil {
@@ -38,8 +38,12 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 	calculator := NewAdaptiveConfigCalculator()
 	adaptiveConfig := calculator.GetDefaultConfig()
 	threadCount := adaptiveConfig.ThreadCount
-
-	logService.Info(taskID, fmt.Sprintf("系统自适应配置: 线程数=%d", threadCount))
+	if config.SyncConfig.ReadThreads > 0 {
+		threadCount = config.SyncConfig.ReadThreads
+		logService.Info(taskID, fmt.Sprintf("读取线程数: %d（read_threads）", threadCount))
+	} else {
+		logService.Info(taskID, fmt.Sprintf("系统自适应配置: 线程数=%d", threadCount))
+	}
 
 	// 生成任务单元列表（从配置中）
 	var unitNames []string
@@ -82,6 +86,7 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 	// 更新任务为运行状态
 	task.IsRunning = true
 	task.CurrentStep = "initialize"
+	startedAt := markTaskStarted(&task)
 	if err := database.DB.Save(&task).Error; err != nil {
 		return fmt.Errorf("更新任务状态失败: %w", err)
 	}
@@ -101,6 +106,7 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 	wg.Add(1)
 	go func() {
 		defer wg.Done()
+		var runErr string // 任务级错误（单元失败由进度管理器记录）
 		defer func() {
 			execManager.DeleteExecution(taskID)
 
@@ -109,9 +115,8 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 			database.DB.First(&currentTask, "id = ?", taskID)
 
 			// 如果任务正常完成（不是被取消或失败），设置为completed步骤
-			updateData := map[string]interface{}{
-				"is_running": false,
-			}
+			updateData := taskFinishedFields(startedAt)
+			updateData["is_running"] = false
 
 			// 如果是正常完成（没有错误），设置completed步骤
 			if currentTask.CurrentStep == "sync_data" {
@@ -126,6 +131,11 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 			// 广播任务详情更新
 			sseService := NewTaskSSEService()
 			sseService.BroadcastTaskDetailUpdate(taskID)
+
+			// 结束通知在状态落库后发送，暂停/停止不通知
+			if ctx.Err() == nil {
+				notifyFullSyncFinished(taskID, task.Name, config, sortedUnitNames, startedAt, runErr)
+			}
 		}()
 
 		engine := NewSyncEngine()
@@ -147,6 +157,7 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 
 		if err := engine.InitializeWorker(ctx, taskID, sortedUnitNames); err != nil {
 			logService.Error(taskID, fmt.Sprintf("初始化阶段失败: %v", err))
+			runErr = fmt.Sprintf("初始化阶段失败: %v", err)
 			return
 		}
 
@@ -199,6 +210,17 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 			normalUnitNames = sortedUnitNames
 		}
 
+		// 按配置调整普通表的调度顺序（外键表必须按依赖顺序，不参与排序），行数取初始化阶段统计的值
+		if config.SyncConfig.UnitOrder != "" || len(config.SyncConfig.UnitPriority) > 0 {
+			normalUnitNames = orderUnits(normalUnitNames, config.SyncConfig.UnitOrder, config.SyncConfig.UnitPriority,
+				func(unitName string) int64 { return unitSize(taskID, unitName) })
+			order := config.SyncConfig.UnitOrder
+			if order == "" {
+				order = "加载顺序"
+			}
+			logService.Info(taskID, fmt.Sprintf("单元执行顺序: %s，优先单元 %d 个", order, len(config.SyncConfig.UnitPriority)))
+		}
+
 		// 创建队列
 		fkQueue := make(chan string, len(fkUnitNames))
 		normalQueue := make(chan string, len(normalUnitNames))
@@ -242,6 +264,25 @@ func (s *TaskControlService) startFullSyncTask(taskID string) error {
 		// 等待所有Worker完成
 		syncWg.Wait()
 
+		// 失败单元自动重试（统计以重试后的结果为准）
+		if config.SyncConfig.AutoRetryFailed != nil && ctx.Err() == nil {
+			engine.RetryFailedUnits(ctx, taskID, sortedUnitNames, config.SyncConfig.AutoRetryFailed, threadCount)
+		}
+
+		// 失败单元比例超过阈值时整个任务标记为失败（而不是完成）
+		if ctx.Err() == nil {
+			if reason := checkFailedUnitRatio(taskID, sortedUnitNames, config.SyncConfig.MaxFailedUnitRatio); reason != "" {
+				logService.Error(taskID, reason)
+				runErr = reason
+				database.DB.Model(&models.SyncTask{}).
+					Where("id = ?", taskID).
+					Update("current_step", "failed")
+				progressManager.UpdateTaskStep(taskID, "failed")
+				logService.Info(taskID, "========== 全量同步失败 ==========")
+				return
+			}
+		}
+
 		logService.Info(taskID, "========== 全量同步完成 ==========")
 	}()
 
@@ -273,6 +314,7 @@ func (s *TaskControlService) startIncrementalTask(taskID string) error {
 
 	// 更新任务状态
 	task.IsRunning = true
+	startedAt := markTaskStarted(&task)
 	if err := database.DB.Save(&task).Error; err != nil {
 		return fmt.Errorf("更新任务状态失败: %w", err)
 	}
@@ -289,11 +331,11 @@ func (s *TaskControlService) startIncrementalTask(taskID string) error {
 	go func() {
 		defer func() {
 			// 更新任务状态
+			updateData := taskFinishedFields(startedAt)
+			updateData["is_running"] = false
 			database.DB.Model(&models.SyncTask{}).
 				Where("id = ?", taskID).
-				Updates(map[string]interface{}{
-					"is_running": false,
-				})
+				Updates(updateData)
 			// 广播任务详情更新
 			sseService := NewTaskSSEService()
 			sseService.BroadcastTaskDetailUpdate(taskID)
@@ -308,3 +350,66 @@ func (s *TaskControlService) startIncrementalTask(taskID string) error {
 
 	return nil
 }
+
+// startBidirectionalTask 启动双向同步任务
+// 使用与全量同步相同的执行管理（cancel + WaitGroup），暂停/停止直接复用
+func (s *TaskControlService) startBidirectionalTask(taskID string) error {
+	// 查询任务
+	var task models.SyncTask
+	if err := database.DB.First(&task, "id = ?", taskID).Error; err != nil {
+		return fmt.Errorf("任务不存在")
+	}
+
+	// 清理之前的日志文件
+	logDir := filepath.Join("logs", taskID)
+	if _, err := os.Stat(logDir); err == nil {
+		os.RemoveAll(logDir)
+	}
+
+	logService := NewTaskLogService()
+
+	// 创建双向同步引擎
+	biSync, err := NewBidirectionalSync(taskID)
+	if err != nil {
+		return fmt.Errorf("创建双向同步引擎失败: %v", err)
+	}
+
+	// 更新任务状态
+	task.IsRunning = true
+	task.CurrentStep = "bidirectional"
+	startedAt := markTaskStarted(&task)
+	if err := database.DB.Save(&task).Error; err != nil {
+		return fmt.Errorf("更新任务状态失败: %w", err)
+	}
+
+	// 广播任务详情更新
+	sseService := NewTaskSSEService()
+	sseService.BroadcastTaskDetailUpdate(taskID)
+
+	ctx, cancel := context.WithCancel(context.Background())
+	wg := &sync.WaitGroup{}
+
+	execManager := GetExecutionManager()
+	execManager.StoreExecution(taskID, cancel, wg)
+
+	wg.Add(1)
+	go func() {
+		defer wg.Done()
+		defer func() {
+			execManager.DeleteExecution(taskID)
+			updateData := taskFinishedFields(startedAt)
+			updateData["is_running"] = false
+			database.DB.Model(&models.SyncTask{}).
+				Where("id = ?", taskID).
+				Updates(updateData)
+			sseService := NewTaskSSEService()
+			sseService.BroadcastTaskDetailUpdate(taskID)
+		}()
+
+		if err := biSync.Run(ctx); err != nil {
+			logService.Error(taskID, fmt.Sprintf("双向同步失败: %v", err))
+		}
+	}()
+
+	return nil
+}
@@ -0,0 +1,144 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"time"
+)
+
+// TaskHistoryService 任务执行历史服务
+type TaskHistoryService struct{}
+
+// NewTaskHistoryService 创建任务执行历史服务
+func NewTaskHistoryService() *TaskHistoryService {
+	return &TaskHistoryService{}
+}
+
+// UnitHistoryItem 单元执行历史（带可读耗时）
+type UnitHistoryItem struct {
+	models.TaskUnitHistory
+	Duration string `json:"duration"` // 可读耗时，例如 3m12s
+}
+
+// TaskHistory 任务执行历史
+type TaskHistory struct {
+	TaskID      string            `json:"task_id"`
+	StartedAt   *time.Time        `json:"started_at"`
+	FinishedAt  *time.Time        `json:"finished_at"`
+	DurationSec int64             `json:"duration_seconds"`
+	Duration    string            `json:"duration"`
+	Units       []UnitHistoryItem `json:"units"`
+}
+
+// GetTaskHistory 获取任务的单元执行历史，errorCode 非空时只返回该错误分类的记录
+func (s *TaskHistoryService) GetTaskHistory(taskID, errorCode string) (*TaskHistory, error) {
+	var task models.SyncTask
+	if err := database.DB.First(&task, "id = ?", taskID).Error; err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+
+	query := database.DB.Where("task_id = ?", taskID)
+	if errorCode != "" {
+		query = query.Where("error_code = ?", errorCode)
+	}
+
+	var records []models.TaskUnitHistory
+	if err := query.Order("id DESC").Find(&records).Error; err != nil {
+		return nil, fmt.Errorf("查询执行历史失败: %w", err)
+	}
+
+	history := &TaskHistory{
+		TaskID:      taskID,
+		StartedAt:   task.StartedAt,
+		FinishedAt:  task.FinishedAt,
+		DurationSec: task.DurationSec,
+		Units:       make([]UnitHistoryItem, 0, len(records)),
+	}
+
+	// 旧记录没有耗时时，根据开始/结束时间回填
+	if history.DurationSec == 0 && task.StartedAt != nil && task.FinishedAt != nil {
+		history.DurationSec = int64(task.FinishedAt.Sub(*task.StartedAt).Seconds())
+	}
+	history.Duration = formatElapsed(time.Duration(history.DurationSec) * time.Second)
+
+	for _, record := range records {
+		if record.DurationMs == 0 && record.StartedAt != nil && record.CompletedAt != nil {
+			record.DurationMs = record.CompletedAt.Sub(*record.StartedAt).Milliseconds()
+		}
+		history.Units = append(history.Units, UnitHistoryItem{
+			TaskUnitHistory: record,
+			Duration:        formatElapsed(time.Duration(record.DurationMs) * time.Millisecond),
+		})
+	}
+
+	return history, nil
+}
+
+// ClearAllHistories 清除任务的全部执行历史，返回删除的记录数
+func (s *TaskHistoryService) ClearAllHistories(taskID string) (int64, error) {
+	result := database.DB.Where("task_id = ?", taskID).Delete(&models.TaskUnitHistory{})
+	if result.Error != nil {
+		return 0, fmt.Errorf("清除执行历史失败: %w", result.Error)
+	}
+	return result.RowsAffected, nil
+}
+
+// ClearHistoriesBefore 清除任务在指定时间之前结束的执行历史，返回删除的记录数
+func (s *TaskHistoryService) ClearHistoriesBefore(taskID string, before time.Time) (int64, error) {
+	result := database.DB.Where("task_id = ? AND created_at < ?", taskID, before).Delete(&models.TaskUnitHistory{})
+	if result.Error != nil {
+		return 0, fmt.Errorf("清除执行历史失败: %w", result.Error)
+	}
+	return result.RowsAffected, nil
+}
+
+// recordUnitHistory 写入单元执行历史
+func recordUnitHistory(taskID string, unit TaskUnit) {
+	if database.DB == nil {
+		return
+	}
+
+	record := models.TaskUnitHistory{
+		TaskID:           taskID,
+		UnitName:         unit.UnitName,
+		Status:           unit.Status,
+		TotalRecords:     unit.TotalRecords,
+		ProcessedRecords: unit.ProcessedRecords,
+		ErrorMessage:     unit.ErrorMessage,
+		ErrorCode:        string(unit.ErrorCode),
+		StartedAt:        unit.StartedAt,
+		CompletedAt:      unit.CompletedAt,
+	}
+	if unit.StartedAt != nil && unit.CompletedAt != nil {
+		record.DurationMs = unit.CompletedAt.Sub(*unit.StartedAt).Milliseconds()
+	}
+
+	database.DB.Create(&record)
+}
+
+// markTaskStarted 记录任务启动时间（清空上一次的结束时间和耗时）
+func markTaskStarted(task *models.SyncTask) time.Time {
+	now := time.Now()
+	task.StartedAt = &now
+	task.FinishedAt = nil
+	task.DurationSec = 0
+	return now
+}
+
+// taskFinishedFields 任务结束时需要更新的时间字段
+func taskFinishedFields(startedAt time.Time) map[string]interface{} {
+	now := time.Now()
+	return map[string]interface{}{
+		"finished_at":  now,
+		"duration_sec": int64(now.Sub(startedAt).Seconds()),
+	}
+}
+
+// formatElapsed 格式化耗时，例如 3m12s / 1h2m3s / 850ms
+func formatElapsed(d time.Duration) string {
+	if d < time.Second {
+		return d.Round(time.Millisecond).String()
+	}
+	return d.Round(time.Second).String()
+}
@@ -1,9 +1,14 @@
 package services
 
 import (
+	"bufio"
+	"datatrace/utils"
+	"encoding/json"
 	"fmt"
+	"io"
 	"os"
 	"path/filepath"
+	"strings"
 	"sync"
 	"time"
 )
@@ -23,7 +28,7 @@ type TaskLog struct {
 	Time     string `json:"time"`     // 时间
 	Level    string `json:"level"`    // 日志级别：info/success/warning/error
 	Message  string `json:"message"`  // 日志消息
-	Category string `json:"category"` // 日志分类：all/initialize/complete
+	Category string `json:"category"` // 日志分类：all/initialize/complete/verify
 }
 
 // getLogFilePath 获取日志文件路径
@@ -78,6 +83,186 @@ func (s *TaskLogService) GetTaskLogs(taskID string, category string, limit int)
 	return logs, nil
 }
 
+// 日志查询每页条数
+const (
+	DefaultLogQueryLimit = 100
+	MaxLogQueryLimit     = 1000
+)
+
+// LogQuery 日志查询条件，字段为空表示不按该条件过滤
+type LogQuery struct {
+	Category string   // all/initialize/complete/verify，默认 all
+	Levels   []string // 日志级别，满足其一即可
+	Keyword  string   // 消息包含的文本（不区分大小写）
+	Since    string   // 起始时间 HH:MM:SS（含）
+	Until    string   // 结束时间 HH:MM:SS（含）
+	Limit    int      // 每页条数，默认 100，最大 1000
+	Offset   int      // 跳过的匹配条数
+}
+
+// LogQueryResult 日志查询结果
+type LogQueryResult struct {
+	Items  []TaskLog `json:"items"`
+	Total  int       `json:"total"` // 匹配的总条数
+	Limit  int       `json:"limit"`
+	Offset int       `json:"offset"`
+}
+
+// Normalize 校验查询条件并填充默认值
+func (q *LogQuery) Normalize() error {
+	if q.Category == "" {
+		q.Category = "all"
+	}
+	if q.Category != "all" && !contains(logCategoryFiles, q.Category) {
+		return fmt.Errorf("日志分类无效: %s（支持 all/%s）", q.Category, strings.Join(logCategoryFiles, "/"))
+	}
+	for _, bound := range []string{q.Since, q.Until} {
+		if bound == "" {
+			continue
+		}
+		if _, err := time.Parse("15:04:05", bound); err != nil {
+			return fmt.Errorf("时间无效: %s（格式 HH:MM:SS）", bound)
+		}
+	}
+	if q.Limit <= 0 {
+		q.Limit = DefaultLogQueryLimit
+	}
+	if q.Limit > MaxLogQueryLimit {
+		q.Limit = MaxLogQueryLimit
+	}
+	if q.Offset < 0 {
+		q.Offset = 0
+	}
+	q.Keyword = strings.ToLower(q.Keyword)
+	return nil
+}
+
+// matches 日志是否满足查询条件（Keyword 已转为小写）
+// 日志只记录时分秒，时间范围按当天时间比较
+func (q *LogQuery) matches(log *TaskLog) bool {
+	if len(q.Levels) > 0 && !contains(q.Levels, log.Level) {
+		return false
+	}
+	if q.Since != "" && log.Time < q.Since {
+		return false
+	}
+	if q.Until != "" && log.Time > q.Until {
+		return false
+	}
+	if q.Keyword != "" && !strings.Contains(strings.ToLower(log.Message), q.Keyword) {
+		return false
+	}
+	return true
+}
+
+// QueryTaskLogs 按级别、分类、关键字、时间范围过滤任务日志并分页（按写入顺序）
+// 逐行扫描日志文件，只保留当前页的记录；与导出一样不持有锁
+func (s *TaskLogService) QueryTaskLogs(taskID string, query LogQuery) (*LogQueryResult, error) {
+	if err := query.Normalize(); err != nil {
+		return nil, err
+	}
+	result := &LogQueryResult{Items: []TaskLog{}, Limit: query.Limit, Offset: query.Offset}
+
+	file, err := os.Open(s.getLogFilePath(taskID, query.Category))
+	if os.IsNotExist(err) {
+		return result, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("打开日志文件失败: %v", err)
+	}
+	defer file.Close()
+
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 64*1024), 4*1024*1024)
+	for scanner.Scan() {
+		log := parseLogLine(scanner.Text())
+		if log == nil || !query.matches(log) {
+			continue
+		}
+		if result.Total >= query.Offset && len(result.Items) < query.Limit {
+			log.Category = query.Category
+			result.Items = append(result.Items, *log)
+		}
+		result.Total++
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("读取日志文件失败: %v", err)
+	}
+	return result, nil
+}
+
+// logCategoryFiles 除 all 以外单独落盘的日志分类
+var logCategoryFiles = []string{"initialize", "complete", "verify"}
+
+// ExportTaskLogs 以 NDJSON（每行一个 JSON 对象：time/level/category/message）流式导出任务的全部日志，返回写入的行数
+// 逐行读取 all.log 并写出，不把整个日志载入内存；没有日志时不写任何内容
+// 导出期间不持有锁，避免大文件导出阻塞同步过程中的日志写入
+func (s *TaskLogService) ExportTaskLogs(taskID string, w io.Writer) (int, error) {
+	file, err := os.Open(s.getLogFilePath(taskID, "all"))
+	if os.IsNotExist(err) {
+		return 0, nil
+	}
+	if err != nil {
+		return 0, fmt.Errorf("打开日志文件失败: %v", err)
+	}
+	defer file.Close()
+
+	// all.log 中不记录分类，按分类文件中的行还原（分类文件只包含少量关键日志）
+	categories := s.loadCategoryLines(taskID)
+
+	buffered := bufio.NewWriter(w)
+	encoder := json.NewEncoder(buffered)
+	encoder.SetEscapeHTML(false)
+
+	scanner := bufio.NewScanner(file)
+	scanner.Buffer(make([]byte, 64*1024), 4*1024*1024)
+
+	written := 0
+	for scanner.Scan() {
+		line := scanner.Text()
+		log := parseLogLine(line)
+		if log == nil {
+			continue
+		}
+		log.Category = "all"
+		for _, category := range logCategoryFiles {
+			if categories[category][line] > 0 {
+				categories[category][line]--
+				log.Category = category
+				break
+			}
+		}
+		if err := encoder.Encode(log); err != nil {
+			return written, fmt.Errorf("写入导出内容失败: %v", err)
+		}
+		written++
+	}
+	if err := scanner.Err(); err != nil {
+		return written, fmt.Errorf("读取日志文件失败: %v", err)
+	}
+	if err := buffered.Flush(); err != nil {
+		return written, fmt.Errorf("写入导出内容失败: %v", err)
+	}
+	return written, nil
+}
+
+// loadCategoryLines 读取各分类日志文件：分类 -> 日志行 -> 出现次数
+func (s *TaskLogService) loadCategoryLines(taskID string) map[string]map[string]int {
+	result := make(map[string]map[string]int, len(logCategoryFiles))
+	for _, category := range logCategoryFiles {
+		lines := make(map[string]int)
+		if content, err := os.ReadFile(s.getLogFilePath(taskID, category)); err == nil {
+			for _, line := range splitLines(string(content)) {
+				if line != "" {
+					lines[line]++
+				}
+			}
+		}
+		result[category] = lines
+	}
+	return result
+}
+
 // AddLog 添加日志（写入文件）
 func (s *TaskLogService) AddLog(taskID string, level string, message string, category string) {
 	s.mu.Lock()
@@ -91,7 +276,7 @@ func (s *TaskLogService) AddLog(taskID string, level string, message string, cat
 	log := TaskLog{
 		Time:     formatLogTime(time.Now()),
 		Level:    level,
-		Message:  message,
+		Message:  utils.RedactSecrets(message), // 写入文件和推送前脱敏，避免连接串/密码落盘
 		Category: category,
 	}
 
@@ -106,6 +291,8 @@ func (s *TaskLogService) AddLog(taskID string, level string, message string, cat
 		s.appendToFile(taskID, "initialize", logLine)
 	} else if category == "complete" {
 		s.appendToFile(taskID, "complete", logLine)
+	} else if category == "verify" {
+		s.appendToFile(taskID, "verify", logLine)
 	}
 
 	// 广播新日志到SSE客户端
@@ -0,0 +1,152 @@
+package services
+
+import (
+	"datatrace/models"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestAddLogRedactsSecrets(t *testing.T) {
+	t.Chdir(t.TempDir())
+	const password = "Sup3r-S3cret!"
+
+	tcpDSN := fmt.Sprintf("%s:%s@%s/?charset=utf8mb4&parseTime=True&loc=Local", "root", password, mysqlNetAddr("db.internal", 3306)) +
+		timeZoneDSNParam(sessionTimeZone(TimestampModeUTC))
+	socketDSN := fmt.Sprintf("%s:%s@%s/app", "root", password, mysqlNetAddr("/var/run/mysqld/mysqld.sock", 0))
+	config, err := json.Marshal(models.DataSource{Name: "prod", Type: "mysql", Host: "db.internal", Port: 3306, Username: "root", Password: password})
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	logService := NewTaskLogService()
+	logService.Error("task-1", fmt.Sprintf("连接源数据库失败: dsn=%s", tcpDSN))
+	logService.Error("task-1", fmt.Sprintf("连接目标数据库失败: dsn=%s", socketDSN))
+	logService.AddLog("task-1", "info", "数据源配置: "+string(config), "initialize")
+	logService.Warning("task-1", fmt.Sprintf("请求 ES 失败: Authorization: Basic %s", password))
+
+	for _, category := range []string{"all", "initialize"} {
+		content, err := os.ReadFile(logService.getLogFilePath("task-1", category))
+		if err != nil {
+			t.Fatalf("读取 %s.log 失败: %v", category, err)
+		}
+		if strings.Contains(string(content), password) {
+			t.Errorf("%s.log contains the password:\n%s", category, content)
+		}
+		if !strings.Contains(string(content), "****") {
+			t.Errorf("%s.log has no redaction mask:\n%s", category, content)
+		}
+	}
+
+	logs, err := logService.GetTaskLogs("task-1", "all", 0)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(logs) != 4 {
+		t.Fatalf("got %d logs, want 4", len(logs))
+	}
+	if !strings.Contains(logs[0].Message, "db.internal:3306") {
+		t.Errorf("redaction removed the host: %s", logs[0].Message)
+	}
+}
+
+// writeTestLogs 在当前目录下写入任务的日志文件
+func writeTestLogs(t *testing.T, taskID, category string, logs []TaskLog) {
+	t.Helper()
+	lines := make([]string, len(logs))
+	for i, log := range logs {
+		lines[i] = formatLogLine(log)
+	}
+	dir := filepath.Join("logs", taskID)
+	if err := os.MkdirAll(dir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, category+".log"), []byte(strings.Join(lines, "\n")+"\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+}
+
+func TestQueryTaskLogsFilters(t *testing.T) {
+	t.Chdir(t.TempDir())
+	writeTestLogs(t, "task-1", "all", []TaskLog{
+		{Time: "08:00:00", Level: "info", Message: "开始同步 Orders"},
+		{Time: "08:30:00", Level: "warning", Message: "表 orders 慢查询"},
+		{Time: "09:00:00", Level: "error", Message: "写入 ORDERS 失败"},
+		{Time: "09:00:00", Level: "info", Message: "批次完成"},
+		{Time: "10:15:00", Level: "success", Message: "同步完成"},
+		{Time: "11:00:00", Level: "error", Message: "连接超时"},
+	})
+	writeTestLogs(t, "task-1", "initialize", []TaskLog{
+		{Time: "08:00:00", Level: "info", Message: "开始同步 Orders"},
+	})
+
+	tests := []struct {
+		name     string
+		query    LogQuery
+		want     []string // 当前页的消息
+		total    int
+		category string
+	}{
+		{"no filter", LogQuery{}, []string{"开始同步 Orders", "表 orders 慢查询", "写入 ORDERS 失败", "批次完成", "同步完成", "连接超时"}, 6, "all"},
+		{"single level", LogQuery{Levels: []string{"error"}}, []string{"写入 ORDERS 失败", "连接超时"}, 2, "all"},
+		{"several levels", LogQuery{Levels: []string{"warning", "error"}}, []string{"表 orders 慢查询", "写入 ORDERS 失败", "连接超时"}, 3, "all"},
+		{"keyword is case-insensitive", LogQuery{Keyword: "ORDers"}, []string{"开始同步 Orders", "表 orders 慢查询", "写入 ORDERS 失败"}, 3, "all"},
+		{"since is inclusive", LogQuery{Since: "09:00:00"}, []string{"写入 ORDERS 失败", "批次完成", "同步完成", "连接超时"}, 4, "all"},
+		{"until is inclusive", LogQuery{Until: "09:00:00"}, []string{"开始同步 Orders", "表 orders 慢查询", "写入 ORDERS 失败", "批次完成"}, 4, "all"},
+		{"since and until", LogQuery{Since: "08:30:00", Until: "10:15:00"}, []string{"表 orders 慢查询", "写入 ORDERS 失败", "批次完成", "同步完成"}, 4, "all"},
+		{"level, keyword and range", LogQuery{Levels: []string{"error"}, Keyword: "orders", Since: "08:00:00", Until: "12:00:00"}, []string{"写入 ORDERS 失败"}, 1, "all"},
+		{"limit and offset", LogQuery{Limit: 2, Offset: 1}, []string{"表 orders 慢查询", "写入 ORDERS 失败"}, 6, "all"},
+		{"filtered page", LogQuery{Levels: []string{"info", "error"}, Limit: 1, Offset: 2}, []string{"批次完成"}, 4, "all"},
+		{"offset past the end", LogQuery{Offset: 10}, nil, 6, "all"},
+		{"no match", LogQuery{Keyword: "不存在"}, nil, 0, "all"},
+		{"category file", LogQuery{Category: "initialize"}, []string{"开始同步 Orders"}, 1, "initialize"},
+	}
+	logService := NewTaskLogService()
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			result, err := logService.QueryTaskLogs("task-1", tt.query)
+			if err != nil {
+				t.Fatalf("QueryTaskLogs() error = %v", err)
+			}
+			var got []string
+			for _, item := range result.Items {
+				got = append(got, item.Message)
+				if item.Category != tt.category {
+					t.Errorf("item category = %q, want %q", item.Category, tt.category)
+				}
+			}
+			if !reflect.DeepEqual(got, tt.want) {
+				t.Errorf("items = %v, want %v", got, tt.want)
+			}
+			if result.Total != tt.total {
+				t.Errorf("Total = %d, want %d", result.Total, tt.total)
+			}
+		})
+	}
+}
+
+func TestQueryTaskLogsNormalize(t *testing.T) {
+	t.Chdir(t.TempDir())
+	logService := NewTaskLogService()
+
+	result, err := logService.QueryTaskLogs("missing", LogQuery{Limit: MaxLogQueryLimit + 1, Offset: -1})
+	if err != nil {
+		t.Fatalf("QueryTaskLogs() error = %v", err)
+	}
+	if result.Total != 0 || len(result.Items) != 0 || result.Limit != MaxLogQueryLimit || result.Offset != 0 {
+		t.Errorf("result = %+v, want empty page with limit %d and offset 0", result, MaxLogQueryLimit)
+	}
+	if result, _ := logService.QueryTaskLogs("missing", LogQuery{}); result.Limit != DefaultLogQueryLimit {
+		t.Errorf("default limit = %d, want %d", result.Limit, DefaultLogQueryLimit)
+	}
+
+	for _, query := range []LogQuery{{Category: "unknown"}, {Since: "9:00"}, {Until: "25:00:00"}} {
+		if _, err := logService.QueryTaskLogs("missing", query); err == nil {
+			t.Errorf("QueryTaskLogs(%+v) accepted an invalid query", query)
+		}
+	}
+}
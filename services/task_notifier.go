@@ -0,0 +1,229 @@
+package services
+
+import (
+	"bytes"
+	"crypto/tls"
+	"encoding/json"
+	"fmt"
+	"mime"
+	"net"
+	"net/http"
+	"net/mail"
+	"net/smtp"
+	"net/url"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// notifyTimeout 单个通知渠道的超时时间
+const notifyTimeout = 10 * time.Second
+
+// NotifyConfig 任务结束通知配置（任务级），webhook 和邮件两个渠道各自独立，配置了才发送
+type NotifyConfig struct {
+	OnSuccess  bool     `json:"on_success"`            // 同步成功时通知
+	OnFailure  bool     `json:"on_failure"`            // 同步失败时通知
+	WebhookURL string   `json:"webhook_url,omitempty"` // POST JSON 摘要
+	EmailTo    []string `json:"email_to,omitempty"`    // 收件人（需要在全局设置中配置 smtp）
+}
+
+// ValidateNotifyConfig 校验通知配置，nil 表示不通知
+func ValidateNotifyConfig(cfg *NotifyConfig) error {
+	if cfg == nil {
+		return nil
+	}
+	if !cfg.OnSuccess && !cfg.OnFailure {
+		return fmt.Errorf("on_success 和 on_failure 至少开启一个")
+	}
+	if cfg.WebhookURL == "" && len(cfg.EmailTo) == 0 {
+		return fmt.Errorf("webhook_url 和 email_to 至少配置一个")
+	}
+	if cfg.WebhookURL != "" {
+		u, err := url.Parse(cfg.WebhookURL)
+		if err != nil || (u.Scheme != "http" && u.Scheme != "https") || u.Host == "" {
+			return fmt.Errorf("webhook_url 必须是 http/https 地址")
+		}
+	}
+	for _, addr := range cfg.EmailTo {
+		if _, err := mail.ParseAddress(addr); err != nil {
+			return fmt.Errorf("收件人地址无效: %s", addr)
+		}
+	}
+	return nil
+}
+
+// TaskRunSummary 任务执行结束摘要（通知内容）
+type TaskRunSummary struct {
+	TaskID        string   `json:"task_id"`
+	TaskName      string   `json:"task_name"`
+	Status        string   `json:"status"` // success/failed
+	StartedAt     string   `json:"started_at"`
+	Duration      string   `json:"duration"`
+	TotalUnits    int      `json:"total_units"`
+	FailedUnits   int      `json:"failed_units"`
+	FailedSamples []string `json:"failed_samples,omitempty"` // 最多 10 个失败单元
+	Error         string   `json:"error,omitempty"`
+}
+
+// NotifyTaskFinished 按任务通知配置发送结束通知
+// 在独立 goroutine 中执行，发送失败只写入任务日志，不影响任务的同步结果
+func NotifyTaskFinished(cfg *NotifyConfig, summary TaskRunSummary) {
+	if cfg == nil {
+		return
+	}
+	enabled := cfg.OnFailure
+	if summary.Status == "success" {
+		enabled = cfg.OnSuccess
+	}
+	if !enabled {
+		return
+	}
+
+	go func() {
+		logService := NewTaskLogService()
+		if cfg.WebhookURL != "" {
+			if err := sendWebhook(cfg.WebhookURL, summary); err != nil {
+				logService.Warning(summary.TaskID, fmt.Sprintf("发送 webhook 通知失败: %v", err))
+			} else {
+				logService.Info(summary.TaskID, "已发送 webhook 通知")
+			}
+		}
+		if len(cfg.EmailTo) > 0 {
+			if err := sendNotifyEmail(NewSettingsService().Get().SMTP, cfg.EmailTo, summary); err != nil {
+				logService.Warning(summary.TaskID, fmt.Sprintf("发送邮件通知失败: %v", err))
+			} else {
+				logService.Info(summary.TaskID, fmt.Sprintf("已发送邮件通知: %s", strings.Join(cfg.EmailTo, ", ")))
+			}
+		}
+	}()
+}
+
+// notifyFullSyncFinished 全量同步结束后汇总单元结果并发送通知，runErr 为任务级错误（如初始化失败）
+func notifyFullSyncFinished(taskID, taskName string, config *TaskConfig, unitNames []string, startedAt time.Time, runErr string) {
+	if config == nil || config.SyncConfig.Notify == nil {
+		return
+	}
+
+	failed := GetProgressManager().GetFailedUnitNames(taskID, unitNames)
+	summary := TaskRunSummary{
+		TaskID:      taskID,
+		TaskName:    taskName,
+		Status:      "success",
+		StartedAt:   startedAt.Format("2006-01-02 15:04:05"),
+		Duration:    formatElapsed(time.Since(startedAt)),
+		TotalUnits:  len(unitNames),
+		FailedUnits: len(failed),
+		Error:       runErr,
+	}
+	if runErr != "" || len(failed) > 0 {
+		summary.Status = "failed"
+	}
+	if len(failed) > 10 {
+		failed = failed[:10]
+	}
+	summary.FailedSamples = failed
+
+	NotifyTaskFinished(config.SyncConfig.Notify, summary)
+}
+
+// sendWebhook POST JSON 摘要，非 2xx 视为失败
+func sendWebhook(webhookURL string, summary TaskRunSummary) error {
+	body, err := json.Marshal(summary)
+	if err != nil {
+		return err
+	}
+	client := &http.Client{Timeout: notifyTimeout}
+	resp, err := client.Post(webhookURL, "application/json", bytes.NewReader(body))
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode < 200 || resp.StatusCode >= 300 {
+		return fmt.Errorf("HTTP %d", resp.StatusCode)
+	}
+	return nil
+}
+
+// notifyEmailContent 通知邮件的标题和正文
+func notifyEmailContent(summary TaskRunSummary) (string, string) {
+	result := "同步成功"
+	if summary.Status != "success" {
+		result = "同步失败"
+	}
+	subject := fmt.Sprintf("[DataTrace] 任务 %s %s", summary.TaskName, result)
+
+	var body strings.Builder
+	fmt.Fprintf(&body, "任务: %s (%s)\n", summary.TaskName, summary.TaskID)
+	fmt.Fprintf(&body, "结果: %s\n", result)
+	fmt.Fprintf(&body, "开始时间: %s\n", summary.StartedAt)
+	fmt.Fprintf(&body, "耗时: %s\n", summary.Duration)
+	fmt.Fprintf(&body, "单元: 共 %d 个，失败 %d 个\n", summary.TotalUnits, summary.FailedUnits)
+	if len(summary.FailedSamples) > 0 {
+		fmt.Fprintf(&body, "失败单元: %s\n", strings.Join(summary.FailedSamples, ", "))
+	}
+	if summary.Error != "" {
+		fmt.Fprintf(&body, "错误: %s\n", summary.Error)
+	}
+	return subject, body.String()
+}
+
+// sendNotifyEmail 通过 SMTP 发送通知邮件（服务器支持时使用 STARTTLS）
+func sendNotifyEmail(cfg SMTPSettings, to []string, summary TaskRunSummary) error {
+	if cfg.Host == "" {
+		return fmt.Errorf("未配置 smtp 邮件服务器")
+	}
+
+	conn, err := net.DialTimeout("tcp", net.JoinHostPort(cfg.Host, strconv.Itoa(cfg.Port)), notifyTimeout)
+	if err != nil {
+		return fmt.Errorf("连接邮件服务器失败: %w", err)
+	}
+	conn.SetDeadline(time.Now().Add(notifyTimeout))
+
+	client, err := smtp.NewClient(conn, cfg.Host)
+	if err != nil {
+		conn.Close()
+		return fmt.Errorf("连接邮件服务器失败: %w", err)
+	}
+	defer client.Close()
+
+	if ok, _ := client.Extension("STARTTLS"); ok {
+		if err := client.StartTLS(&tls.Config{ServerName: cfg.Host}); err != nil {
+			return fmt.Errorf("STARTTLS 失败: %w", err)
+		}
+	}
+	if cfg.CredentialID != "" {
+		credService := NewCredentialService()
+		credential, err := credService.GetByID(cfg.CredentialID)
+		if err != nil {
+			return fmt.Errorf("邮件凭据不存在")
+		}
+		password, err := credService.GetDecryptedPassword(cfg.CredentialID)
+		if err != nil {
+			return fmt.Errorf("邮件凭据密码解密失败")
+		}
+		if err := client.Auth(smtp.PlainAuth("", credential.Username, password, cfg.Host)); err != nil {
+			return fmt.Errorf("邮件服务器认证失败: %w", err)
+		}
+	}
+
+	if err := client.Mail(cfg.From); err != nil {
+		return err
+	}
+	for _, addr := range to {
+		if err := client.Rcpt(addr); err != nil {
+			return fmt.Errorf("收件人 %s 被拒绝: %w", addr, err)
+		}
+	}
+
+	subject, body := notifyEmailContent(summary)
+	w, err := client.Data()
+	if err != nil {
+		return err
+	}
+	fmt.Fprintf(w, "From: %s\r\nTo: %s\r\nSubject: %s\r\nMIME-Version: 1.0\r\nContent-Type: text/plain; charset=UTF-8\r\n\r\n%s",
+		cfg.From, strings.Join(to, ", "), mime.BEncoding.Encode("UTF-8", subject), strings.ReplaceAll(body, "\n", "\r\n"))
+	if err := w.Close(); err != nil {
+		return err
+	}
+	return client.Quit()
+}
@@ -2,6 +2,7 @@ package services
 
 import (
 	"fmt"
+	"sort"
 	"sync"
 	"time"
 )
@@ -15,6 +16,8 @@ type ProgressMessage struct {
 	Status       string // "initialized" / "running" / "completed" / "failed"
 	TotalRecords int64  // 总记录数
 	Processed    int64  // 已处理记录数
+	Intended     int64  // 本次应写入的记录数（仅 completed 时上报）
+	Written      int64  // 实际写入目标的记录数（仅 completed 时上报）
 	IsNew        bool   // 是否是新发现的表（首次上报）
 }
 
@@ -26,7 +29,9 @@ type TaskUnit struct {
 	ProcessedRecords int64      `json:"processed_records"`
 	ApproxRows       int64      `json:"approx_rows"` // 初始化阶段预获取的近似行数（来自 SHOW TABLE STATUS）
 	ErrorMessage     string     `json:"error_message,omitempty"`
+	ErrorCode        ErrorCode  `json:"error_code,omitempty"` // 错误分类，见 ErrorCode
 	StartedAt        *time.Time `json:"started_at,omitempty"`
+	CompletedAt      *time.Time `json:"completed_at,omitempty"`
 	UpdatedAt        time.Time  `json:"updated_at"`
 }
 
@@ -38,6 +43,8 @@ type TargetUnit struct {
 	Status           string     `json:"status"` // pending/running/completed/failed/paused
 	TotalRecords     int64      `json:"total_records"`
 	ProcessedRecords int64      `json:"processed_records"`
+	IntendedRecords  int64      `json:"intended_records"` // 本次应写入的记录数（完成后记录，用于行数校验）
+	WrittenRecords   int64      `json:"written_records"`  // 实际写入目标的记录数（完成后记录）
 	BatchNum         int        `json:"batch_num"`
 	ErrorMessage     string     `json:"error_message,omitempty"`
 	StartedAt        *time.Time `json:"started_at,omitempty"`
@@ -50,6 +57,7 @@ type TaskProgressManager struct {
 	tasks        map[string]*TaskProgressData // taskID -> progress data
 	progressChan chan ProgressMessage         // 进度上报通道
 	ssePusher    SSEPusher                    // SSE 推送器
+	throttle     *progressThrottle            // 进度推送节流（间隔见全局设置 progress_interval_ms）
 }
 
 // SSEPusher SSE 推送接口
@@ -89,6 +97,7 @@ func GetProgressManager() *TaskProgressManager {
 			progressChan: make(chan ProgressMessage, 1000), // 带缓冲的 channel
 			ssePusher:    &SSEDirectPusher{},
 		}
+		progressManager.throttle = newProgressThrottle(progressManager.pushProgress)
 		// 启动进度处理线程
 		go progressManager.startProgressProcessor()
 	})
@@ -190,6 +199,10 @@ func (m *TaskProgressManager) processProgressMessage(msg ProgressMessage) {
 		unit.Status = msg.Status
 		unit.TotalRecords = msg.TotalRecords
 		unit.ProcessedRecords = msg.Processed
+		if msg.Status == "completed" {
+			unit.IntendedRecords = msg.Intended
+			unit.WrittenRecords = msg.Written
+		}
 		unit.UpdatedAt = now
 		if msg.Status == "running" && unit.StartedAt == nil {
 			unit.StartedAt = &now
@@ -203,6 +216,8 @@ func (m *TaskProgressManager) processProgressMessage(msg ProgressMessage) {
 			Status:           msg.Status,
 			TotalRecords:     msg.TotalRecords,
 			ProcessedRecords: msg.Processed,
+			IntendedRecords:  msg.Intended,
+			WrittenRecords:   msg.Written,
 			UpdatedAt:        now,
 		}
 		if msg.Status == "running" {
@@ -213,15 +228,21 @@ func (m *TaskProgressManager) processProgressMessage(msg ProgressMessage) {
 	// 计算并推送进度（需要释放锁，因为推送可能耗时）
 	m.mu.Unlock()
 
-	// 构建进度并推送
-	progress := m.buildProgress(msg.TaskID)
-	if progress != nil && m.ssePusher != nil {
-		m.ssePusher.BroadcastProgress(msg.TaskID, progress)
-	}
+	// 高频的 running 进度按间隔合并推送，终态立即推送
+	terminal := msg.Status == "completed" || msg.Status == "failed" || msg.Status == "paused"
+	m.throttle.Push(msg.TaskID, terminal)
 
 	m.mu.Lock()
 }
 
+// pushProgress 构建并推送任务进度
+func (m *TaskProgressManager) pushProgress(taskID string) {
+	progress := m.buildProgress(taskID)
+	if progress != nil && m.ssePusher != nil {
+		m.ssePusher.BroadcastProgress(taskID, progress)
+	}
+}
+
 // GetTask 获取任务进度
 func (m *TaskProgressManager) GetTask(taskID string) *TaskProgressData {
 	m.mu.RLock()
@@ -252,6 +273,11 @@ func (m *TaskProgressManager) UpdateUnitStatus(taskID, unitName, status string)
 		now := time.Now()
 		unit.StartedAt = &now
 	}
+
+	// 完成时记录结束时间并写入执行历史
+	if status == "completed" {
+		m.finishUnit(taskID, unit)
+	}
 }
 
 // UpdateUnitProgress 更新单元进度
@@ -279,8 +305,8 @@ func (m *TaskProgressManager) UpdateUnitProgress(taskID, unitName string, total,
 	unit.UpdatedAt = time.Now()
 }
 
-// UpdateUnitError 更新单元错误信息
-func (m *TaskProgressManager) UpdateUnitError(taskID, unitName, errorMsg string) {
+// UpdateUnitError 更新单元错误信息，错误分类见 ClassifyError
+func (m *TaskProgressManager) UpdateUnitError(taskID, unitName string, err error) {
 	m.mu.Lock()
 	defer m.mu.Unlock()
 
@@ -295,8 +321,52 @@ func (m *TaskProgressManager) UpdateUnitError(taskID, unitName, errorMsg string)
 	}
 
 	unit.Status = "failed"
-	unit.ErrorMessage = errorMsg
+	unit.ErrorMessage = err.Error()
+	unit.ErrorCode = ClassifyError(err)
 	unit.UpdatedAt = time.Now()
+	m.finishUnit(taskID, unit)
+}
+
+// ResetUnit 将失败单元重置为待处理（保留总记录数），用于重新同步
+func (m *TaskProgressManager) ResetUnit(taskID, unitName string) {
+	m.mu.Lock()
+	defer m.mu.Unlock()
+
+	task, ok := m.tasks[taskID]
+	if !ok {
+		return
+	}
+
+	unit, ok := task.Units[unitName]
+	if !ok {
+		return
+	}
+
+	unit.Status = "pending"
+	unit.ProcessedRecords = 0
+	unit.ErrorMessage = ""
+	unit.ErrorCode = ""
+	unit.StartedAt = nil
+	unit.CompletedAt = nil
+	unit.UpdatedAt = time.Now()
+
+	// 目标源级别进度在重新同步时重新创建
+	for _, targetUnits := range task.TargetUnits {
+		delete(targetUnits, unitName)
+	}
+}
+
+// finishUnit 记录单元结束时间并异步写入执行历史（调用方需持有写锁）
+func (m *TaskProgressManager) finishUnit(taskID string, unit *TaskUnit) {
+	if unit.CompletedAt != nil {
+		return
+	}
+	now := time.Now()
+	unit.CompletedAt = &now
+
+	// 拷贝快照，避免在锁内访问数据库
+	snapshot := *unit
+	go recordUnitHistory(taskID, snapshot)
 }
 
 // UpdateTaskStep 更新任务步骤
@@ -343,12 +413,32 @@ func (m *TaskProgressManager) GetUnit(taskID, unitName string) *TaskUnit {
 	return task.Units[unitName]
 }
 
+// GetFailedUnitNames 按给定顺序返回其中处于失败状态的单元
+func (m *TaskProgressManager) GetFailedUnitNames(taskID string, unitNames []string) []string {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+
+	task, ok := m.tasks[taskID]
+	if !ok {
+		return nil
+	}
+
+	var failed []string
+	for _, name := range unitNames {
+		if unit, ok := task.Units[name]; ok && unit.Status == "failed" {
+			failed = append(failed, name)
+		}
+	}
+	return failed
+}
+
 // ClearTask 清除任务进度
 func (m *TaskProgressManager) ClearTask(taskID string) {
 	m.mu.Lock()
 	defer m.mu.Unlock()
 
 	delete(m.tasks, taskID)
+	m.throttle.Forget(taskID)
 }
 
 // GetTaskStats 获取任务统计信息
@@ -427,6 +517,38 @@ func (m *TaskProgressManager) GetEarliestStartTime(taskID string) *time.Time {
 	return earliest
 }
 
+// ListTaskIDs 列出内存中有进度数据的任务ID
+func (m *TaskProgressManager) ListTaskIDs() []string {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+
+	ids := make([]string, 0, len(m.tasks))
+	for taskID := range m.tasks {
+		ids = append(ids, taskID)
+	}
+	return ids
+}
+
+// GetActiveUnits 获取任务当前步骤和正在运行的单元（只持有读锁，开销很小）
+func (m *TaskProgressManager) GetActiveUnits(taskID string) (currentStep string, runningUnits []string, ok bool) {
+	m.mu.RLock()
+	defer m.mu.RUnlock()
+
+	task, exists := m.tasks[taskID]
+	if !exists {
+		return "", nil, false
+	}
+
+	for name, unit := range task.Units {
+		if unit.Status == "running" {
+			runningUnits = append(runningUnits, name)
+		}
+	}
+	sort.Strings(runningUnits)
+
+	return task.CurrentStep, runningUnits, true
+}
+
 // ========== 目标源级别进度管理 ==========
 
 // InitTargetUnit 初始化目标源单元
@@ -0,0 +1,55 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+	"os"
+	"path/filepath"
+)
+
+// RestartTask 清除任务的运行时状态后从头启动：运行中的任务先停止，再清除内存进度、增量统计和日志，
+// 按存储的配置重新加载缓存并生成单元，最后以全新状态启动
+// 执行历史只有 clearHistory 为 true 时才清除，避免误删已完成单元的记录
+func (s *TaskControlService) RestartTask(taskID string, clearHistory bool) error {
+	var task models.SyncTask
+	if err := database.DB.First(&task, "id = ?", taskID).Error; err != nil {
+		return fmt.Errorf("任务不存在")
+	}
+	if task.Status != "configured" {
+		return fmt.Errorf("任务未配置，无法启动")
+	}
+
+	// 1. 停止运行中的任务（等待 Worker 退出）
+	if task.IsRunning {
+		if err := s.StopTask(taskID); err != nil {
+			return fmt.Errorf("停止任务失败: %w", err)
+		}
+	}
+
+	// 2. 清除运行时状态
+	GetProgressManager().ClearTask(taskID)
+	NewIncrementalStatsService().ClearTaskStats(taskID)
+	logDir := filepath.Join("logs", taskID)
+	if _, err := os.Stat(logDir); err == nil {
+		os.RemoveAll(logDir)
+	}
+	if clearHistory {
+		if _, err := NewTaskHistoryService().ClearAllHistories(taskID); err != nil {
+			return err
+		}
+	}
+
+	// 3. 重置运行状态（配置状态保持不变）
+	if err := database.DB.Model(&models.SyncTask{}).Where("id = ?", taskID).
+		Updates(map[string]interface{}{"is_running": false, "current_step": ""}).Error; err != nil {
+		return fmt.Errorf("重置任务状态失败: %w", err)
+	}
+
+	// 4. 按存储的配置重新加载缓存，启动时据此重新生成单元
+	if err := NewConfigCacheService().ReloadTaskConfig(taskID); err != nil && database.IsRedisEnabled() {
+		return fmt.Errorf("重新加载任务配置失败: %w", err)
+	}
+
+	return s.StartTask(taskID)
+}
@@ -54,15 +54,88 @@ type TableConfig struct {
 	TargetTable    string   `json:"target_table"`
 	IsModified     bool     `json:"is_modified"`
 	SelectedFields []string `json:"selected_fields"` // 选中的字段列表，为空表示同步所有字段
+	// 查询源：非空时同步该只读 SELECT 的结果集，source_table 只作为单元名称，目标表结构按结果集元数据推断（仅全量同步）
+	SourceQuery string `json:"source_query,omitempty"`
 }
 
 // SyncConfigParams 同步配置参数
 type SyncConfigParams struct {
-	SyncMode            string `json:"sync_mode"`             // full/incremental
+	SyncMode            string `json:"sync_mode"`             // full/incremental/bidirectional
 	ErrorStrategy       string `json:"error_strategy"`        // pause/skip
 	TableExistsStrategy string `json:"table_exists_strategy"` // skip/drop/truncate
 	SyncStructureOnly   bool   `json:"sync_structure_only"`   // 只同步表结构（不同步数据）
 
+	// 转换器（按顺序执行），格式 "name" 或 "name:arg"，如 ["mask:ssn", "lowercase_keys"]
+	Transformers []string `json:"transformers,omitempty"`
+	// 字段脱敏规则（先于 transformers 执行，全量和增量一致生效）
+	Mask []MaskRule `json:"mask,omitempty"`
+	// 字段值转换（在脱敏之后、transformers 之前执行），源字段名 -> 操作，如 {"country": "upper", "remark": "coalesce:无"}
+	ColumnTransforms map[string]string `json:"column_transforms,omitempty"`
+	// 目标库名正则改写（对未手动改名的库生效，不匹配时保留原库名），如 ^prod_(.*)$ -> staging_$1
+	DatabaseNameRule *NameRewriteRule `json:"database_name_rule,omitempty"`
+	// 批次大小自动调优：以自适应批次大小为起点，根据每批耗时在 [min, max] 之间动态调整
+	AutoTuneBatch        bool `json:"auto_tune_batch,omitempty"`
+	MinBatchSize         int  `json:"min_batch_size,omitempty"`          // 默认 100
+	MaxBatchSize         int  `json:"max_batch_size,omitempty"`          // 默认 100000
+	TargetBatchLatencyMs int  `json:"target_batch_latency_ms,omitempty"` // 目标单批耗时，默认 1000
+	// 源和目标为同一实例时使用服务端 INSERT ... SELECT 复制（需单列主键且未配置转换器），其余情况回退流式同步
+	UseNativeCopy bool `json:"use_native_copy,omitempty"`
+	// 单表并行分片数：按整数单列主键范围拆分并发同步，<=1 不分片，不超过自适应线程数
+	ParallelShards int `json:"parallel_shards,omitempty"`
+	// 深度校验：同步完成后对比源和目标的逐行校验和（会再扫描一遍数据），结果写入 verify 日志
+	ChecksumVerify        bool `json:"checksum_verify,omitempty"`
+	ChecksumSamplePercent int  `json:"checksum_sample_percent,omitempty"` // 按主键抽样的百分比 1~100，默认全量
+	// 失败单元自动重试：全量同步主流程结束后，对失败的表重新同步，最多重试 attempts 轮
+	AutoRetryFailed *AutoRetryConfig `json:"auto_retry_failed,omitempty"`
+	// 目标库/表默认字符集和排序规则覆盖（如 utf8mb4 / utf8mb4_bin），不填时沿用源库和源表
+	TargetCharset   string `json:"target_charset,omitempty"`
+	TargetCollation string `json:"target_collation,omitempty"`
+	// skip 策略下连续失败多少个批次后熔断并暂停任务，0 表示默认 10
+	MaxConsecutiveFailures int `json:"max_consecutive_failures,omitempty"`
+	// 失败单元比例阈值（0~1）：全量同步结束后（含自动重试）失败单元占比超过该值时任务标记为失败，0 表示不限制
+	MaxFailedUnitRatio float64 `json:"max_failed_unit_ratio,omitempty"`
+	// 自增列处理：preserve（默认）原样复制自增值并在完成后把计数器设为 MAX(id)+1；regenerate 不写入自增列，由目标重新生成
+	AutoIncrementMode string `json:"auto_increment_mode,omitempty"`
+	// 数据库连接数打满（Too many connections）时的退避重试次数和随机抖动（毫秒），0 表示默认 3 次 / 500ms
+	ConnectRetries       int `json:"connect_retries,omitempty"`
+	ConnectRetryJitterMs int `json:"connect_retry_jitter_ms,omitempty"`
+	// 单元执行顺序：name/size_asc（小表优先）/size_desc（大表优先），不填按加载顺序；unit_priority 中的单元（目标库.目标表）最先执行
+	UnitOrder    string   `json:"unit_order,omitempty"`
+	UnitPriority []string `json:"unit_priority,omitempty"`
+	// 预读缓冲批次数（0~16）：读取下一批的同时写入当前批，目标写入慢时提高吞吐；0 表示不预读
+	ReadAheadBatches int `json:"read_ahead_batches,omitempty"`
+	// 读写并发分开配置：read_threads 为同时同步的表数（每张表一个 Reader），不填使用自适应线程数；
+	// write_threads（0~16）为每张表每个目标并发写入的 goroutine 数，不填或 1 为单个写入
+	ReadThreads  int `json:"read_threads,omitempty"`
+	WriteThreads int `json:"write_threads,omitempty"`
+	// 任务结束通知（webhook / 邮件），暂停和停止不通知
+	Notify *NotifyConfig `json:"notify,omitempty"`
+	// 源表为空时：schema（默认）照常处理目标表结构、不同步数据；skip 完全跳过，不创建/清空/删除目标表
+	EmptyTableMode string `json:"empty_table_mode,omitempty"`
+	// 每个单元最多同步的行数（用于正式同步前抽样验证映射），0 表示不限制；按主键顺序取前 N 行，只支持全量同步
+	MaxRecordsPerUnit int64 `json:"max_records_per_unit,omitempty"`
+	// 字段 NULL 值处理（字段名 -> fail/default/nullable）：fail（默认）原样写入 NULL；default 写入 NOT NULL 字段时替换为默认值；nullable 建表时改为允许 NULL
+	NullPolicies map[string]string `json:"null_policies,omitempty"`
+	// 批次行数达到该值时使用 LOAD DATA LOCAL INFILE 写入（大批量初始导入更快），目标未开启 local_infile 时自动回退为 INSERT；0 表示只用 INSERT
+	LoadDataThreshold int `json:"load_data_threshold,omitempty"`
+	// 主键/唯一键冲突：strict（默认）整个批次失败；ignore 只忽略冲突的行（目标保留原有数据），其余行照常写入，并记录忽略的行数
+	DuplicateKeyMode string `json:"duplicate_key_mode,omitempty"`
+	// 批次内去重：按目标表主键去除同一批次中重复的源行，first 保留第一行、last 保留最后一行的值；不填不去重（目标表需要主键）
+	BatchDedup string `json:"batch_dedup,omitempty"`
+	// 同步前字段兼容性检查（目标表已存在且保留结构时）：warn 把类型收窄、缺少字段等问题写入 verify 日志；block 有问题时该表初始化失败；不填不检查
+	SchemaCheck string `json:"schema_check,omitempty"`
+	// 禁止运行时段（如工作日 09:00-18:00）：运行中进入时段时自动暂停，时段结束后自动继续；时段内不允许启动
+	Blackout *BlackoutConfig `json:"blackout,omitempty"`
+	// 目标端前置/后置 SQL：每个表同步前后在目标库同一连接上依次执行，{database}/{table} 替换为目标库名、表名
+	// 前置语句失败时该表同步失败；后置语句同步失败时也执行，失败只记录警告。语句以目标数据源账号执行，不做任何过滤
+	PreSQL  []string `json:"pre_sql,omitempty"`
+	PostSQL []string `json:"post_sql,omitempty"`
+	// TIMESTAMP 时区处理：utc（默认）源和目标会话时区统一为 +00:00，按绝对时间复制；server 使用各自服务器的默认时区，按源端显示的本地时间复制
+	TimestampMode string `json:"timestamp_mode,omitempty"`
+
+	// 双向同步配置（sync_mode=bidirectional 时必填）
+	Bidirectional *BidirectionalConfig `json:"bidirectional,omitempty"`
+
 	// 已废弃字段（保留向后兼容，但不再使用）
 	BatchSize   int `json:"batch_size,omitempty"`   // 已废弃：现在使用自适应批次大小
 	ThreadCount int `json:"thread_count,omitempty"` // 已废弃：现在使用自适应线程数
@@ -111,6 +184,49 @@ func (s *TaskService) Create(req *CreateTaskRequest) (*models.SyncTask, error) {
 	return task, nil
 }
 
+// DuplicateTaskRequest 复制任务请求
+type DuplicateTaskRequest struct {
+	Name string `json:"name" binding:"required"`
+}
+
+// Duplicate 以新名称复制任务：新 ID、空闲状态，配置（数据源、选中的库表、同步参数）原样复制并重新校验
+// 执行历史、进度和日志等运行时数据不复制，新任务从头开始
+func (s *TaskService) Duplicate(id string, req *DuplicateTaskRequest) (*models.SyncTask, error) {
+	var original models.SyncTask
+	if err := database.DB.First(&original, "id = ?", id).Error; err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+
+	task, err := s.Create(&CreateTaskRequest{
+		Name:       req.Name,
+		SourceType: original.SourceType,
+		TargetType: original.TargetType,
+	})
+	if err != nil {
+		return nil, err
+	}
+
+	// 原任务尚未配置时只复制基本信息
+	var config TaskConfig
+	if err := json.Unmarshal([]byte(original.Config), &config); err != nil || config.SourceID == "" {
+		return task, nil
+	}
+
+	configured, err := s.UpdateConfig(task.ID, &UpdateTaskConfigRequest{
+		SourceID:          config.SourceID,
+		TargetID:          config.TargetID,
+		TargetIDs:         config.TargetIDs,
+		SelectedDatabases: config.SelectedDatabases,
+		SyncConfig:        config.SyncConfig,
+	})
+	if err != nil {
+		s.Delete(task.ID)
+		return nil, fmt.Errorf("复制任务配置失败: %w", err)
+	}
+
+	return configured, nil
+}
+
 // List 获取任务列表
 func (s *TaskService) List() ([]models.SyncTask, error) {
 	var list []models.SyncTask
@@ -160,6 +276,104 @@ func (s *TaskService) UpdateConfig(id string, req *UpdateTaskConfigRequest) (*mo
 		}
 	}
 
+	// 验证转换器配置
+	if err := ValidateMaskRules(req.SyncConfig.Mask); err != nil {
+		return nil, fmt.Errorf("脱敏配置无效: %w", err)
+	}
+	if err := ValidateColumnTransforms(req.SyncConfig.ColumnTransforms); err != nil {
+		return nil, fmt.Errorf("字段转换配置无效: %w", err)
+	}
+	if err := ValidateAutoRetryConfig(req.SyncConfig.AutoRetryFailed); err != nil {
+		return nil, fmt.Errorf("自动重试配置无效: %w", err)
+	}
+	if err := ValidateTableCharset(req.SyncConfig.TargetCharset, req.SyncConfig.TargetCollation); err != nil {
+		return nil, err
+	}
+	if err := ValidateMaxConsecutiveFailures(req.SyncConfig.MaxConsecutiveFailures); err != nil {
+		return nil, err
+	}
+	if err := ValidateMaxFailedUnitRatio(req.SyncConfig.MaxFailedUnitRatio); err != nil {
+		return nil, err
+	}
+	if err := ValidateConnectRetry(req.SyncConfig.ConnectRetries, req.SyncConfig.ConnectRetryJitterMs); err != nil {
+		return nil, err
+	}
+	if err := ValidateUnitOrder(req.SyncConfig.UnitOrder, req.SyncConfig.UnitPriority); err != nil {
+		return nil, err
+	}
+	if err := ValidateReadAheadBatches(req.SyncConfig.ReadAheadBatches); err != nil {
+		return nil, err
+	}
+	if err := ValidateReadThreads(req.SyncConfig.ReadThreads); err != nil {
+		return nil, err
+	}
+	if err := ValidateWriteThreads(req.SyncConfig.WriteThreads); err != nil {
+		return nil, err
+	}
+	if err := ValidateNotifyConfig(req.SyncConfig.Notify); err != nil {
+		return nil, fmt.Errorf("通知配置无效: %w", err)
+	}
+	if err := ValidateEmptyTableMode(req.SyncConfig.EmptyTableMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateNameRewriteRule(req.SyncConfig.DatabaseNameRule); err != nil {
+		return nil, fmt.Errorf("库名改写规则无效: %w", err)
+	}
+	if err := applyDatabaseNameRule(req.SelectedDatabases, req.SyncConfig.DatabaseNameRule); err != nil {
+		return nil, err
+	}
+	if err := ValidateTableMappings(req.SelectedDatabases); err != nil {
+		return nil, err
+	}
+	syncMode := req.SyncConfig.SyncMode
+	if syncMode == "" {
+		syncMode = task.SyncMode
+	}
+	if err := ValidateSourceQueries(req.SelectedDatabases, syncMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateMaxRecordsPerUnit(req.SyncConfig.MaxRecordsPerUnit, syncMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateAutoIncrementMode(req.SyncConfig.AutoIncrementMode, syncMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateTransformers(req.SyncConfig.Transformers, syncMode); err != nil {
+		return nil, fmt.Errorf("转换器配置无效: %w", err)
+	}
+	if err := ValidateNullPolicies(req.SyncConfig.NullPolicies); err != nil {
+		return nil, err
+	}
+	if err := ValidateLoadDataThreshold(req.SyncConfig.LoadDataThreshold); err != nil {
+		return nil, err
+	}
+	if err := ValidateDuplicateKeyMode(req.SyncConfig.DuplicateKeyMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateBatchDedup(req.SyncConfig.BatchDedup); err != nil {
+		return nil, err
+	}
+	if err := ValidateSchemaCheck(req.SyncConfig.SchemaCheck); err != nil {
+		return nil, err
+	}
+	if err := ValidateBlackout(req.SyncConfig.Blackout, syncMode); err != nil {
+		return nil, err
+	}
+	if err := ValidateTargetSQLHooks(req.SyncConfig.PreSQL, req.SyncConfig.PostSQL); err != nil {
+		return nil, err
+	}
+	if err := ValidateTimestampMode(req.SyncConfig.TimestampMode); err != nil {
+		return nil, err
+	}
+	if req.SyncConfig.SyncMode == "bidirectional" {
+		if task.SourceType != "mysql" || task.TargetType != "mysql" {
+			return nil, fmt.Errorf("双向同步只支持 MySQL 到 MySQL")
+		}
+		if err := ValidateBidirectionalConfig(req.SyncConfig.Bidirectional, len(targetIDs)); err != nil {
+			return nil, err
+		}
+	}
+
 	// 构建配置
 	config := TaskConfig{
 		SourceID:          req.SourceID,
@@ -237,6 +451,12 @@ func (s *TaskService) Delete(id string) error {
 	statsService := NewIncrementalStatsService()
 	statsService.ClearTaskStats(id)
 
+	// 清理单元执行历史
+	NewTaskHistoryService().ClearAllHistories(id)
+
+	// 清理双向同步水位
+	clearWatermarks(id)
+
 	// 删除任务本身
 	if err := database.DB.Delete(&models.SyncTask{}, "id = ?", id).Error; err != nil {
 		return fmt.Errorf("删除失败: %w", err)
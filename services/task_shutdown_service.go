@@ -0,0 +1,110 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"log"
+	"sync"
+	"time"
+)
+
+// DefaultShutdownTimeout 退出时等待任务落盘的默认上限
+const DefaultShutdownTimeout = 10 * time.Second
+
+// ShutdownTasks 服务退出时中断运行中的任务并保存进度
+// 1. 取消全量/双向任务、停止增量任务，在 timeout 内等待其退出
+// 2. 将运行中和暂停中任务未完成的单元写入执行历史（状态 interrupted）
+// 3. 将运行中任务标记为 interrupted，下次启动后可重新开始
+// 没有活动任务时直接返回，返回值为被中断的任务数
+func (s *TaskControlService) ShutdownTasks(timeout time.Duration) int {
+	if database.DB == nil {
+		return 0
+	}
+	if timeout <= 0 {
+		timeout = DefaultShutdownTimeout
+	}
+
+	execManager := GetExecutionManager()
+	progressManager := GetProgressManager()
+
+	executions := execManager.ListExecutions()
+	incrementalSyncs := execManager.ListIncrementalSyncs()
+	pausedIDs := progressManager.ListTaskIDs()
+	if len(executions) == 0 && len(incrementalSyncs) == 0 && len(pausedIDs) == 0 {
+		return 0
+	}
+
+	// 1. 发出取消信号
+	runningIDs := make([]string, 0, len(executions)+len(incrementalSyncs))
+	var waitAll sync.WaitGroup
+	for _, exec := range executions {
+		runningIDs = append(runningIDs, exec.TaskID)
+		exec.Cancel()
+		if exec.WaitGroup != nil {
+			waitAll.Add(1)
+			go func(wg *sync.WaitGroup) {
+				defer waitAll.Done()
+				wg.Wait()
+			}(exec.WaitGroup)
+		}
+	}
+	for taskID := range incrementalSyncs {
+		runningIDs = append(runningIDs, taskID)
+		if incrementalSync, ok := execManager.GetIncrementalSync(taskID); ok {
+			if err := incrementalSync.Stop(); err != nil {
+				log.Printf("⚠️  停止增量同步 %s 失败: %v", taskID, err)
+			}
+		}
+	}
+
+	// 2. 有上限地等待任务退出，超时也继续落盘
+	done := make(chan struct{})
+	go func() {
+		waitAll.Wait()
+		close(done)
+	}()
+	select {
+	case <-done:
+	case <-time.After(timeout):
+		log.Printf("⚠️  等待任务退出超时（%s），直接保存当前进度", timeout)
+	}
+
+	// 3. 保存未完成单元的进度快照
+	now := time.Now()
+	for _, taskID := range pausedIDs {
+		for _, unit := range progressManager.GetUnits(taskID) {
+			snapshot := *unit
+			if snapshot.StartedAt == nil || snapshot.Status == "completed" || snapshot.Status == "failed" {
+				continue
+			}
+			snapshot.Status = "interrupted"
+			snapshot.CompletedAt = &now
+			recordUnitHistory(taskID, snapshot)
+		}
+	}
+
+	// 4. 标记运行中的任务为已中断
+	if len(runningIDs) > 0 {
+		database.DB.Model(&models.SyncTask{}).
+			Where("id IN ?", runningIDs).
+			Updates(map[string]interface{}{
+				"is_running":   false,
+				"current_step": "interrupted",
+				"finished_at":  now,
+			})
+	}
+
+	return len(runningIDs)
+}
+
+// RecoverInterruptedTasks 启动时修正上次异常退出遗留的运行状态
+// 进程刚启动时不可能有任务在运行，仍为 is_running 的任务一律标记为 interrupted
+func RecoverInterruptedTasks() (int64, error) {
+	result := database.DB.Model(&models.SyncTask{}).
+		Where("is_running = ?", true).
+		Updates(map[string]interface{}{
+			"is_running":   false,
+			"current_step": "interrupted",
+		})
+	return result.RowsAffected, result.Error
+}
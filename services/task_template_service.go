@@ -0,0 +1,145 @@
+package services
+
+import (
+	"datatrace/database"
+	"datatrace/models"
+	"encoding/json"
+	"fmt"
+
+	"github.com/google/uuid"
+)
+
+// TaskTemplateService 任务模板服务
+type TaskTemplateService struct {
+	taskService *TaskService
+}
+
+// NewTaskTemplateService 创建任务模板服务
+func NewTaskTemplateService() *TaskTemplateService {
+	return &TaskTemplateService{
+		taskService: NewTaskService(),
+	}
+}
+
+// TemplateConfig 模板配置（任务配置去掉源和目标）
+type TemplateConfig struct {
+	SelectedDatabases []DatabaseSelection `json:"selected_databases"`
+	SyncConfig        SyncConfigParams    `json:"sync_config"`
+}
+
+// CreateTemplateRequest 从任务创建模板请求
+type CreateTemplateRequest struct {
+	Name        string `json:"name" binding:"required"`
+	Description string `json:"description"`
+	TaskID      string `json:"task_id" binding:"required"` // 以该任务的当前配置作为模板
+}
+
+// CreateTaskFromTemplateRequest 从模板创建任务请求
+type CreateTaskFromTemplateRequest struct {
+	Name      string   `json:"name" binding:"required"`
+	Remark    string   `json:"remark"`
+	SourceID  string   `json:"source_id" binding:"required"`
+	TargetIDs []string `json:"target_ids" binding:"required"`
+}
+
+// Create 将任务的配置保存为模板
+func (s *TaskTemplateService) Create(req *CreateTemplateRequest) (*models.TaskTemplate, error) {
+	task, err := s.taskService.GetByID(req.TaskID)
+	if err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+	if task.Status != "configured" {
+		return nil, fmt.Errorf("任务未配置，无法保存为模板")
+	}
+
+	var count int64
+	database.DB.Model(&models.TaskTemplate{}).Where("name = ?", req.Name).Count(&count)
+	if count > 0 {
+		return nil, fmt.Errorf("模板名称已存在")
+	}
+
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(task.ID)
+	if err != nil {
+		return nil, fmt.Errorf("获取任务配置失败: %w", err)
+	}
+
+	configJSON, err := json.Marshal(TemplateConfig{
+		SelectedDatabases: config.SelectedDatabases,
+		SyncConfig:        config.SyncConfig,
+	})
+	if err != nil {
+		return nil, fmt.Errorf("配置序列化失败: %w", err)
+	}
+
+	template := &models.TaskTemplate{
+		ID:          uuid.New().String(),
+		Name:        req.Name,
+		Description: req.Description,
+		SourceType:  task.SourceType,
+		TargetType:  task.TargetType,
+		Config:      string(configJSON),
+	}
+	if err := database.DB.Create(template).Error; err != nil {
+		return nil, fmt.Errorf("创建失败: %w", err)
+	}
+
+	return template, nil
+}
+
+// List 获取模板列表
+func (s *TaskTemplateService) List() ([]models.TaskTemplate, error) {
+	var list []models.TaskTemplate
+	if err := database.DB.Order("created_at DESC").Find(&list).Error; err != nil {
+		return nil, err
+	}
+	return list, nil
+}
+
+// Delete 删除模板（不影响已从模板创建的任务）
+func (s *TaskTemplateService) Delete(id string) error {
+	result := database.DB.Delete(&models.TaskTemplate{}, "id = ?", id)
+	if result.Error != nil {
+		return fmt.Errorf("删除失败: %w", result.Error)
+	}
+	if result.RowsAffected == 0 {
+		return fmt.Errorf("模板不存在")
+	}
+	return nil
+}
+
+// CreateTask 从模板创建任务
+// 模板配置按普通配置更新流程重新校验（数据源、转换器、脱敏等），校验失败时删除已创建的任务
+func (s *TaskTemplateService) CreateTask(templateID string, req *CreateTaskFromTemplateRequest) (*models.SyncTask, error) {
+	var template models.TaskTemplate
+	if err := database.DB.First(&template, "id = ?", templateID).Error; err != nil {
+		return nil, fmt.Errorf("模板不存在")
+	}
+
+	var config TemplateConfig
+	if err := json.Unmarshal([]byte(template.Config), &config); err != nil {
+		return nil, fmt.Errorf("模板配置解析失败: %w", err)
+	}
+
+	task, err := s.taskService.Create(&CreateTaskRequest{
+		Name:       req.Name,
+		SourceType: template.SourceType,
+		TargetType: template.TargetType,
+		Remark:     req.Remark,
+	})
+	if err != nil {
+		return nil, err
+	}
+
+	configured, err := s.taskService.UpdateConfig(task.ID, &UpdateTaskConfigRequest{
+		SourceID:          req.SourceID,
+		TargetIDs:         req.TargetIDs,
+		SelectedDatabases: config.SelectedDatabases,
+		SyncConfig:        config.SyncConfig,
+	})
+	if err != nil {
+		s.taskService.Delete(task.ID)
+		return nil, fmt.Errorf("模板配置校验失败: %w", err)
+	}
+
+	return configured, nil
+}
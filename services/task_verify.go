@@ -0,0 +1,231 @@
+package services
+
+import (
+	"context"
+	"datatrace/database"
+	"datatrace/models"
+	"fmt"
+)
+
+// 单元校验结果
+const (
+	VerifyPassed = "passed" // 行数一致（开启校验和时校验和也一致）
+	VerifyFailed = "failed" // 行数或校验和不一致
+	VerifyError  = "error"  // 源表/目标表不存在或查询失败，无法校验
+)
+
+// UnitVerifyResult 单元在一个目标上的校验结果
+type UnitVerifyResult struct {
+	UnitName   string `json:"unit_name"`
+	TargetName string `json:"target_name"`
+	Status     string `json:"status"` // passed/failed/error
+	SourceRows int64  `json:"source_rows"`
+	TargetRows int64  `json:"target_rows"`
+	Checksum   string `json:"checksum,omitempty"` // match/mismatch/skipped/error，未校验校验和时为空
+	Message    string `json:"message,omitempty"`
+}
+
+// TaskVerifyReport 任务校验报告
+type TaskVerifyReport struct {
+	TaskID   string             `json:"task_id"`
+	Checksum bool               `json:"checksum"` // 是否对比了校验和
+	Units    int                `json:"units"`    // 校验的单元数
+	Skipped  int                `json:"skipped"`  // 未完成、跳过的单元数
+	Passed   int                `json:"passed"`
+	Failed   int                `json:"failed"`
+	Errors   int                `json:"errors"`
+	Results  []UnitVerifyResult `json:"results"`
+}
+
+// VerifyTask 重新校验已完成的全量同步单元，不读写数据：对比源和各目标的行数，checksum 或任务开启了 checksum_verify 时同时对比校验和
+// 内存中有进度时只校验已完成的单元，否则（如服务重启后）校验配置中的全部单元；结果写入 verify 日志并返回
+// 源端行数按同步时的口径统计（查询源为结果集行数，受 max_records_per_unit 限制），目标端为 COUNT(*)，目标表有原有数据时会不一致
+func (s *TaskControlService) VerifyTask(ctx context.Context, taskID string, checksum bool) (*TaskVerifyReport, error) {
+	var task models.SyncTask
+	if err := database.DB.Preload("SourceConn").First(&task, "id = ?", taskID).Error; err != nil {
+		return nil, fmt.Errorf("任务不存在")
+	}
+	if task.IsRunning {
+		return nil, fmt.Errorf("任务运行中，请在结束后再校验")
+	}
+	if task.SyncMode != "full" {
+		return nil, fmt.Errorf("只支持校验全量同步任务")
+	}
+	if task.SourceConn == nil {
+		return nil, fmt.Errorf("任务的源数据源不存在")
+	}
+
+	config, err := NewConfigCacheService().GetTaskConfigWithFallback(taskID)
+	if err != nil {
+		return nil, fmt.Errorf("获取任务配置失败: %w", err)
+	}
+	checksum = checksum || config.SyncConfig.ChecksumVerify
+
+	engine := NewSyncEngine()
+	sourcePassword, err := engine.dsService.ResolveConnection(task.SourceConn)
+	if err != nil {
+		return nil, fmt.Errorf("解密源数据库密码失败: %w", err)
+	}
+	targetIDs := config.TargetIDs
+	if len(targetIDs) == 0 {
+		targetIDs = []string{config.TargetID}
+	}
+	targetConns, err := engine.loadTargetConns(targetIDs)
+	if err != nil {
+		return nil, err
+	}
+
+	// 内存中有进度时只校验已完成的单元
+	unitNames := configUnitNames(config)
+	report := &TaskVerifyReport{TaskID: taskID, Checksum: checksum, Results: []UnitVerifyResult{}}
+	if units := GetProgressManager().GetUnits(taskID); units != nil {
+		completed := make(map[string]bool, len(units))
+		for _, unit := range units {
+			completed[unit.UnitName] = unit.Status == "completed"
+		}
+		var filtered []string
+		for _, name := range unitNames {
+			if completed[name] {
+				filtered = append(filtered, name)
+			}
+		}
+		report.Skipped = len(unitNames) - len(filtered)
+		unitNames = filtered
+	}
+	report.Units = len(unitNames)
+
+	scope := "行数"
+	if checksum {
+		scope = "行数和校验和"
+	}
+	engine.logService.AddLog(taskID, "info", fmt.Sprintf("开始校验 %d 个单元的%s（不同步数据）", len(unitNames), scope), "verify")
+
+	for _, unitName := range unitNames {
+		if ctx.Err() != nil {
+			return nil, ctx.Err()
+		}
+		for _, result := range engine.verifyUnit(ctx, taskID, unitName, &task, sourcePassword, targetConns, config, checksum) {
+			switch result.Status {
+			case VerifyPassed:
+				report.Passed++
+			case VerifyFailed:
+				report.Failed++
+			default:
+				report.Errors++
+			}
+			report.Results = append(report.Results, result)
+		}
+	}
+
+	level := "success"
+	if report.Failed > 0 || report.Errors > 0 {
+		level = "warning"
+	}
+	engine.logService.AddLog(taskID, level, fmt.Sprintf("校验完成: 通过 %d，不一致 %d，无法校验 %d（跳过未完成单元 %d 个）",
+		report.Passed, report.Failed, report.Errors, report.Skipped), "verify")
+	return report, nil
+}
+
+// verifyUnit 校验单元在每个目标上的行数（和校验和），源表或目标表不存在时返回 error 结果而不中断整个校验
+func (e *SyncEngine) verifyUnit(ctx context.Context, taskID, unitName string, task *models.SyncTask, sourcePassword string,
+	targetConns []TargetConnWithPassword, config *TaskConfig, checksum bool) []UnitVerifyResult {
+	results := make([]UnitVerifyResult, 0, len(targetConns))
+	fail := func(message string) []UnitVerifyResult {
+		for _, targetConn := range targetConns {
+			results = append(results, UnitVerifyResult{UnitName: unitName, TargetName: targetConn.Conn.Name, Status: VerifyError, Message: message})
+		}
+		e.logService.AddLog(taskID, "error", fmt.Sprintf("表 %s 无法校验: %s", unitName, message), "verify")
+		return results
+	}
+
+	sourceDB, sourceTable, targetDB, targetTable, err := e.parseUnitName(unitName, config)
+	if err != nil {
+		return fail(err.Error())
+	}
+	selectedFields := e.getSelectedFields(config, sourceDB, sourceTable)
+	sourceQuery := e.getSourceQuery(config, sourceDB, sourceTable)
+	timeZone := sessionTimeZone(config.SyncConfig.TimestampMode)
+	maxRecords := config.SyncConfig.MaxRecordsPerUnit
+
+	// 源端行数与同步时 Reader 的统计口径一致
+	reader, err := newUnitReader(task.SourceConn, sourcePassword, sourceDB, sourceTable, sourceQuery,
+		NewAdaptiveConfigCalculator().GetDefaultConfig().BatchSize, selectedFields, timeZone)
+	if err != nil {
+		return fail(fmt.Sprintf("读取源表失败（源表可能已不存在）: %v", err))
+	}
+	defer reader.Close()
+	applyRecordLimit(reader, maxRecords, sourceDB, sourceTable)
+	sourceRows := reader.GetTotalCount()
+
+	hasTransformers := len(config.SyncConfig.Transformers) > 0 || len(config.SyncConfig.ColumnTransforms) > 0 ||
+		len(config.SyncConfig.Mask) > 0
+
+	for _, targetConn := range targetConns {
+		targetName := targetConn.Conn.Name
+		result := UnitVerifyResult{UnitName: unitName, TargetName: targetName, SourceRows: sourceRows}
+
+		writer, err := NewMySQLWriter(targetConn.Conn.MySQLHost(), targetConn.Conn.Port, targetConn.Conn.Username,
+			targetConn.Password, targetDB, targetTable, timeZone)
+		if err != nil {
+			result.Status = VerifyError
+			result.Message = fmt.Sprintf("连接目标失败（目标库可能已不存在）: %v", err)
+			e.logService.AddLog(taskID, "error", fmt.Sprintf("目标 %s 表 %s 无法校验: %s", targetName, unitName, result.Message), "verify")
+			results = append(results, result)
+			continue
+		}
+
+		err = writer.GetDB().QueryRowContext(ctx,
+			fmt.Sprintf("SELECT COUNT(*) FROM `%s`.`%s`", targetDB, targetTable)).Scan(&result.TargetRows)
+		if err != nil {
+			writer.Close()
+			result.Status = VerifyError
+			result.Message = fmt.Sprintf("统计目标行数失败（目标表可能已不存在）: %v", err)
+			e.logService.AddLog(taskID, "error", fmt.Sprintf("目标 %s 表 %s 无法校验: %s", targetName, unitName, result.Message), "verify")
+			results = append(results, result)
+			continue
+		}
+
+		result.Status = VerifyPassed
+		if result.TargetRows != sourceRows {
+			result.Status = VerifyFailed
+			result.Message = fmt.Sprintf("行数不一致，相差 %d 行", sourceRows-result.TargetRows)
+			e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 行数不一致: 源 %d 行，目标 %d 行",
+				targetName, unitName, sourceRows, result.TargetRows), "verify")
+		} else {
+			e.logService.AddLog(taskID, "success", fmt.Sprintf("目标 %s 表 %s 行数一致: %d 行", targetName, unitName, sourceRows), "verify")
+		}
+
+		if checksum {
+			if sourceQuery != "" {
+				result.Checksum = ChecksumSkipped
+				e.logService.AddLog(taskID, "warning", fmt.Sprintf("目标 %s 表 %s 为查询源，跳过校验和校验", targetName, unitName), "verify")
+			} else {
+				// regenerate 模式下目标的自增列由目标重新生成，NULL 值 default 策略的字段可能被替换为默认值，都不参与校验
+				excludeColumns := nullPolicyColumns(config.SyncConfig.NullPolicies, NullPolicyDefault)
+				if config.SyncConfig.AutoIncrementMode == AutoIncrementRegenerate {
+					if autoIncColumn, err := getAutoIncrementColumn(writer.GetDB(), targetDB, targetTable); err == nil && autoIncColumn != "" {
+						excludeColumns = append(excludeColumns, autoIncColumn)
+					}
+				}
+				result.Checksum = e.verifyTableChecksum(ctx, taskID, unitName, targetName, reader.GetDB(), writer.GetDB(),
+					sourceDB, sourceTable, targetDB, targetTable, selectedFields, excludeColumns, config.SyncConfig.ChecksumSamplePercent,
+					hasTransformers, maxRecords)
+			}
+			switch result.Checksum {
+			case ChecksumMismatch:
+				result.Status = VerifyFailed
+				if result.Message == "" {
+					result.Message = "校验和不一致"
+				}
+			case ChecksumError:
+				if result.Status == VerifyPassed {
+					result.Status = VerifyError
+					result.Message = "计算校验和失败（详见 verify 日志）"
+				}
+			}
+		}
+		writer.Close()
+		results = append(results, result)
+	}
+	return results
+}
@@ -0,0 +1,167 @@
+package services
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+	"sync"
+)
+
+// TransformerFactory 转换器工厂
+// arg 为任务配置中冒号后的参数，如 "mask:ssn" 中的 "ssn"，无参数时为空字符串
+type TransformerFactory func(arg string) (Transformer, error)
+
+// TransformerFunc 函数式转换器
+type TransformerFunc func(record DataRecord) (DataRecord, error)
+
+// Transform 实现 Transformer 接口
+func (f TransformerFunc) Transform(record DataRecord) (DataRecord, error) {
+	return f(record)
+}
+
+var (
+	transformerRegistry   = make(map[string]TransformerFactory)
+	transformerRegistryMu sync.RWMutex
+)
+
+func init() {
+	RegisterTransformer("mask", newMaskFieldTransformer)
+	RegisterTransformer("lowercase_keys", newLowercaseKeysTransformer)
+}
+
+// RegisterTransformer 注册自定义转换器
+// 注册后可在任务配置 sync_config.transformers 中按名称引用，例如 ["mask:ssn", "lowercase_keys"]
+// 同名注册会覆盖已有工厂
+//
+// Transformer 约定：
+//   - 输入一条 DataRecord，返回转换后的 DataRecord（可原地修改后返回同一个 map）
+//   - 返回 nil 记录表示丢弃该条记录
+//   - 返回 error 时整个批次按任务的错误策略处理（pause/skip）
+//   - 同一个实例会被多个目标源的 goroutine 并发调用，必须是无状态或并发安全的
+func RegisterTransformer(name string, factory TransformerFactory) error {
+	name = strings.TrimSpace(name)
+	if name == "" {
+		return fmt.Errorf("转换器名称不能为空")
+	}
+	if strings.Contains(name, ":") {
+		return fmt.Errorf("转换器名称不能包含冒号: %s", name)
+	}
+	if factory == nil {
+		return fmt.Errorf("转换器 %s 的工厂函数不能为空", name)
+	}
+
+	transformerRegistryMu.Lock()
+	defer transformerRegistryMu.Unlock()
+	transformerRegistry[name] = factory
+	return nil
+}
+
+// ListTransformers 获取已注册的转换器名称
+func ListTransformers() []string {
+	transformerRegistryMu.RLock()
+	defer transformerRegistryMu.RUnlock()
+
+	names := make([]string, 0, len(transformerRegistry))
+	for name := range transformerRegistry {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+// primaryKeyAware 需要知道表主键的转换器（如脱敏不能修改主键）
+// BuildTransformers 实例化后传入主键，返回实际使用的转换器和因是主键而忽略的字段
+type primaryKeyAware interface {
+	withPrimaryKeys(pkColumns []string) (Transformer, []string)
+}
+
+// ValidateTransformers 校验转换器配置
+// 转换器只在全量同步的管道中执行，增量事件不经过转换器，非全量模式配置后两端数据会不一致
+func ValidateTransformers(specs []string, syncMode string) error {
+	if len(specs) > 0 && syncMode != "full" {
+		return fmt.Errorf("transformers 只支持全量同步（增量同步可使用 mask/column_transforms）")
+	}
+	_, _, err := BuildTransformers(specs, nil)
+	return err
+}
+
+// BuildTransformers 按配置顺序实例化转换器
+// 每一项格式为 "name" 或 "name:arg"；pkColumns 为源表主键，返回值中的 skipped 为因是主键而忽略的字段
+func BuildTransformers(specs []string, pkColumns []string) (transformers []Transformer, skipped []string, err error) {
+	if len(specs) == 0 {
+		return nil, nil, nil
+	}
+
+	transformerRegistryMu.RLock()
+	defer transformerRegistryMu.RUnlock()
+
+	transformers = make([]Transformer, 0, len(specs))
+	for _, spec := range specs {
+		name, arg := spec, ""
+		if idx := strings.Index(spec, ":"); idx >= 0 {
+			name, arg = spec[:idx], spec[idx+1:]
+		}
+		name = strings.TrimSpace(name)
+
+		factory, ok := transformerRegistry[name]
+		if !ok {
+			return nil, nil, fmt.Errorf("未知的转换器: %s", name)
+		}
+
+		t, err := factory(strings.TrimSpace(arg))
+		if err != nil {
+			return nil, nil, fmt.Errorf("创建转换器 %s 失败: %w", spec, err)
+		}
+		if aware, ok := t.(primaryKeyAware); ok {
+			var fields []string
+			t, fields = aware.withPrimaryKeys(pkColumns)
+			skipped = append(skipped, fields...)
+		}
+		transformers = append(transformers, t)
+	}
+
+	return transformers, skipped, nil
+}
+
+// newMaskFieldTransformer 内置转换器：字段脱敏（"mask:字段名" 或 "mask:字段名:策略"，默认 full）
+func newMaskFieldTransformer(arg string) (Transformer, error) {
+	if arg == "" {
+		return nil, fmt.Errorf("mask 需要指定字段名，例如 mask:ssn")
+	}
+
+	rule := MaskRule{Field: arg, Strategy: MaskStrategyFull}
+	if idx := strings.Index(arg, ":"); idx >= 0 {
+		rule.Field, rule.Strategy = arg[:idx], arg[idx+1:]
+	}
+	if err := ValidateMaskRules([]MaskRule{rule}); err != nil {
+		return nil, err
+	}
+	return maskFieldTransformer{rule: rule}, nil
+}
+
+// maskFieldTransformer mask 转换器，BuildTransformers 传入主键后替换为不修改主键的脱敏转换器
+type maskFieldTransformer struct {
+	rule MaskRule
+}
+
+// Transform 未传入主键时按规则脱敏
+func (t maskFieldTransformer) Transform(record DataRecord) (DataRecord, error) {
+	masker, _ := NewMaskTransformer([]MaskRule{t.rule}, nil)
+	return masker.Transform(record)
+}
+
+// withPrimaryKeys 创建不修改主键字段的脱敏转换器
+func (t maskFieldTransformer) withPrimaryKeys(pkColumns []string) (Transformer, []string) {
+	return NewMaskTransformer([]MaskRule{t.rule}, pkColumns)
+}
+
+// newLowercaseKeysTransformer 内置转换器：字段名全部转为小写（"lowercase_keys"）
+func newLowercaseKeysTransformer(arg string) (Transformer, error) {
+	return TransformerFunc(func(record DataRecord) (DataRecord, error) {
+		out := make(DataRecord, len(record))
+		for k, v := range record {
+			out[strings.ToLower(k)] = v
+		}
+		return out, nil
+	}), nil
+}
@@ -0,0 +1,44 @@
+package services
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestValidateTransformersRequiresFullSync(t *testing.T) {
+	if err := ValidateTransformers([]string{"lowercase_keys"}, "full"); err != nil {
+		t.Errorf("full sync: unexpected error %v", err)
+	}
+	if err := ValidateTransformers([]string{"lowercase_keys"}, "incremental"); err == nil {
+		t.Errorf("incremental sync: expected an error")
+	}
+	if err := ValidateTransformers(nil, "incremental"); err != nil {
+		t.Errorf("no transformers: unexpected error %v", err)
+	}
+	if err := ValidateTransformers([]string{"unknown"}, "full"); err == nil {
+		t.Errorf("unknown transformer: expected an error")
+	}
+}
+
+func TestMaskTransformerSkipsPrimaryKey(t *testing.T) {
+	transformers, skipped, err := BuildTransformers([]string{"mask:id", "mask:ssn"}, []string{"id"})
+	if err != nil {
+		t.Fatalf("BuildTransformers() error = %v", err)
+	}
+	if !reflect.DeepEqual(skipped, []string{"id"}) {
+		t.Errorf("skipped = %v, want [id]", skipped)
+	}
+
+	record := DataRecord{"id": int64(7), "ssn": "123-45-6789"}
+	for _, tr := range transformers {
+		if record, err = tr.Transform(record); err != nil {
+			t.Fatalf("Transform() error = %v", err)
+		}
+	}
+	if record["id"] != int64(7) {
+		t.Errorf("primary key was masked: %v", record["id"])
+	}
+	if record["ssn"] == "123-45-6789" {
+		t.Errorf("ssn was not masked")
+	}
+}
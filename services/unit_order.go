@@ -0,0 +1,85 @@
+package services
+
+import (
+	"fmt"
+	"sort"
+)
+
+// 单元执行顺序
+const (
+	UnitOrderName     = "name"      // 按单元名（目标库.目标表）排序
+	UnitOrderSizeAsc  = "size_asc"  // 小表优先
+	UnitOrderSizeDesc = "size_desc" // 大表优先
+)
+
+// ValidateUnitOrder 校验单元执行顺序配置，order 为空表示按配置加载顺序
+func ValidateUnitOrder(order string, priority []string) error {
+	switch order {
+	case "", UnitOrderName, UnitOrderSizeAsc, UnitOrderSizeDesc:
+	default:
+		return fmt.Errorf("unit_order 只支持 %s/%s/%s", UnitOrderName, UnitOrderSizeAsc, UnitOrderSizeDesc)
+	}
+	seen := make(map[string]bool, len(priority))
+	for _, unitName := range priority {
+		if unitName == "" {
+			return fmt.Errorf("unit_priority 不能包含空单元名")
+		}
+		if seen[unitName] {
+			return fmt.Errorf("unit_priority 中单元 %s 重复", unitName)
+		}
+		seen[unitName] = true
+	}
+	return nil
+}
+
+// orderUnits 按配置调整单元调度顺序：unit_priority 中的单元按列表顺序排在最前，其余按 unit_order 排序
+// 未配置时保持原顺序；sizeOf 返回单元的行数（用于按大小排序）
+func orderUnits(unitNames []string, order string, priority []string, sizeOf func(unitName string) int64) []string {
+	if order == "" && len(priority) == 0 {
+		return unitNames
+	}
+
+	rank := make(map[string]int, len(priority))
+	for i, unitName := range priority {
+		rank[unitName] = i
+	}
+
+	sizes := make(map[string]int64, len(unitNames))
+	if order == UnitOrderSizeAsc || order == UnitOrderSizeDesc {
+		for _, unitName := range unitNames {
+			sizes[unitName] = sizeOf(unitName)
+		}
+	}
+
+	ordered := append([]string(nil), unitNames...)
+	sort.SliceStable(ordered, func(i, j int) bool {
+		a, b := ordered[i], ordered[j]
+		rankA, prioA := rank[a]
+		rankB, prioB := rank[b]
+		if prioA || prioB {
+			if prioA && prioB {
+				return rankA < rankB
+			}
+			return prioA
+		}
+		switch order {
+		case UnitOrderName:
+			return a < b
+		case UnitOrderSizeAsc:
+			return sizes[a] < sizes[b]
+		case UnitOrderSizeDesc:
+			return sizes[a] > sizes[b]
+		}
+		return false
+	})
+	return ordered
+}
+
+// unitSize 单元行数：优先使用精确总数，初始化阶段只拿到 SHOW TABLE STATUS 近似行数时使用近似值
+func unitSize(taskID, unitName string) int64 {
+	progressManager := GetProgressManager()
+	if unit := progressManager.GetUnit(taskID, unitName); unit != nil && unit.TotalRecords > 0 {
+		return unit.TotalRecords
+	}
+	return progressManager.GetApproxRows(taskID, unitName)
+}
@@ -0,0 +1,34 @@
+package utils
+
+import (
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+)
+
+// envPlaceholder ${VAR} 形式的环境变量占位符
+var envPlaceholder = regexp.MustCompile(`\$\{([A-Za-z_][A-Za-z0-9_]*)\}`)
+
+// InterpolateEnv 将字符串中的 ${VAR} 替换为进程环境变量的值，不含占位符的字符串原样返回
+// 引用的变量未设置时返回错误；错误信息只包含变量名，不包含任何值
+func InterpolateEnv(value string) (string, error) {
+	if !strings.Contains(value, "${") {
+		return value, nil
+	}
+
+	var missing []string
+	result := envPlaceholder.ReplaceAllStringFunc(value, func(match string) string {
+		name := envPlaceholder.FindStringSubmatch(match)[1]
+		resolved, ok := os.LookupEnv(name)
+		if !ok {
+			missing = append(missing, name)
+			return match
+		}
+		return resolved
+	})
+	if len(missing) > 0 {
+		return "", fmt.Errorf("环境变量未设置: %s", strings.Join(missing, ", "))
+	}
+	return result, nil
+}